@@ -0,0 +1,170 @@
+#!/usr/bin/env python3
+#
+# Generates `motoko-rts/src/char_props/tables.rs`, the Unicode property tables used by
+# `motoko-rts/src/char_props.rs`.
+#
+# General category, numeric values and East Asian width are taken from Python's `unicodedata`
+# module, scripts from the UCD files `Scripts.txt` and `PropertyValueAliases.txt`. The UCD files
+# should be of the same (or a later) Unicode version as `unicodedata.unidata_version`; code points
+# unassigned in `unicodedata` are always reported as script `Zzzz` (Unknown).
+#
+# Usage:
+#
+#     python3 gen-char-props.py Scripts.txt PropertyValueAliases.txt \
+#         > motoko-rts/src/char_props/tables.rs
+
+import sys
+import unicodedata
+
+MAX_CHAR = 0x10FFFF
+
+# Keep in sync with `GC_*` constants in `char_props.rs`
+GENERAL_CATEGORIES = [
+    "Lu", "Ll", "Lt", "Lm", "Lo",
+    "Mn", "Mc", "Me",
+    "Nd", "Nl", "No",
+    "Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po",
+    "Sm", "Sc", "Sk", "So",
+    "Zs", "Zl", "Zp",
+    "Cc", "Cf", "Cs", "Co", "Cn",
+]
+
+# Keep in sync with `EAW_*` constants in `char_props.rs`
+EAST_ASIAN_WIDTHS = ["N", "A", "H", "F", "Na", "W"]
+
+# Numeric runs longer than this are split, see `NUMERIC_RANGES` in `char_props.rs`
+MAX_NUMERIC_RUN = 0x7F
+
+
+def ranges(prop):
+    """Returns `(start, value)` pairs for maximal runs of code points with the same value"""
+    out = []
+    for c in range(MAX_CHAR + 1):
+        v = prop(c)
+        if not out or out[-1][1] != v:
+            out.append((c, v))
+    return out
+
+
+def parse_ucd(path):
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if line:
+                yield [field.strip() for field in line.split(";")]
+
+
+def script_table(scripts_path, aliases_path):
+    long_to_short = {"Unknown": "Zzzz"}
+    for fields in parse_ucd(aliases_path):
+        if fields[0] == "sc":
+            long_to_short[fields[2]] = fields[1]
+
+    scripts = ["Zzzz"] * (MAX_CHAR + 1)
+    for rng, name in parse_ucd(scripts_path):
+        lo, _, hi = rng.partition("..")
+        lo = int(lo, 16)
+        hi = int(hi, 16) if hi else lo
+        for c in range(lo, hi + 1):
+            if unicodedata.category(chr(c)) != "Cn":
+                scripts[c] = long_to_short[name]
+
+    codes = sorted(set(scripts))
+    index = {code: i for i, code in enumerate(codes)}
+    return codes, ranges(lambda c: index[scripts[c]])
+
+
+def numeric_table():
+    """Runs of code points whose numeric values are either all the same or increase by one"""
+    values = []
+    runs = []  # [start, len, step, value]
+    for c in range(MAX_CHAR + 1):
+        v = unicodedata.numeric(chr(c), None)
+        if v is None:
+            continue
+        if v not in values:
+            values.append(v)
+        if runs:
+            start, n, step, first = runs[-1]
+            if start + n == c and n < MAX_NUMERIC_RUN:
+                if n == 1 and v in (first, first + 1):
+                    runs[-1] = [start, 2, int(v - first), first]
+                    continue
+                if n > 1 and v == first + step * n:
+                    runs[-1][1] += 1
+                    continue
+        runs.append([c, 1, 0, v])
+    values.sort()
+    return values, runs
+
+
+def print_packed(name, entries, shift, comment):
+    print("/// %s" % comment)
+    print("pub(super) static %s: [u32; %d] = [" % (name, len(entries)))
+    line = "   "
+    for start, value in entries:
+        item = " 0x%x," % ((start << shift) | value)
+        if len(line) + len(item) > 100:
+            print(line)
+            line = "   "
+        line += item
+    print(line)
+    print("];")
+    print()
+
+
+def main():
+    scripts_path, aliases_path = sys.argv[1:3]
+
+    print("// Generated by `rts/gen-char-props.py` from Unicode %s data. Do not edit."
+          % unicodedata.unidata_version)
+    print()
+
+    gc_index = {gc: i for i, gc in enumerate(GENERAL_CATEGORIES)}
+    print_packed(
+        "GENERAL_CATEGORY",
+        ranges(lambda c: gc_index[unicodedata.category(chr(c))]),
+        5,
+        "Start of each range (shifted by 5) and its general category",
+    )
+
+    eaw_index = {eaw: i for i, eaw in enumerate(EAST_ASIAN_WIDTHS)}
+    print_packed(
+        "EAST_ASIAN_WIDTH",
+        ranges(lambda c: eaw_index[unicodedata.east_asian_width(chr(c))]),
+        3,
+        "Start of each range (shifted by 3) and its East Asian width",
+    )
+
+    codes, script_ranges = script_table(scripts_path, aliases_path)
+    print_packed(
+        "SCRIPT",
+        script_ranges,
+        8,
+        "Start of each range (shifted by 8) and its index into `SCRIPT_CODES`",
+    )
+    print("/// ISO 15924 codes of scripts, as big-endian packed ASCII")
+    print("pub(super) static SCRIPT_CODES: [u32; %d] = [" % len(codes))
+    for i in range(0, len(codes), 8):
+        print("   " + "".join(" 0x%s," % code.encode("ascii").hex() for code in codes[i:i + 8]))
+    print("];")
+    print()
+
+    values, runs = numeric_table()
+    value_index = {v: i for i, v in enumerate(values)}
+    print("/// Numeric values, indexed by `NUMERIC_RANGES`")
+    print("pub(super) static NUMERIC_VALUES: [f64; %d] = [" % len(values))
+    for i in range(0, len(values), 6):
+        print("   " + "".join(" %r," % v for v in values[i:i + 6]))
+    print("];")
+    print()
+    print("/// Start of each range, its length (bit 7 set if the values increase by one) and the")
+    print("/// index of the numeric value of its first code point")
+    print("pub(super) static NUMERIC_RANGES: [(u32, u8, u8); %d] = [" % len(runs))
+    for start, n, step, first in runs:
+        print("    (0x%x, 0x%x, %d)," % (start, n | (step << 7), value_index[first]))
+    print("];")
+
+
+if __name__ == "__main__":
+    main()
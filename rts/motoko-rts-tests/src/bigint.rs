@@ -2,7 +2,9 @@ use crate::memory::TestMemory;
 
 use motoko_rts::bigint::{self, *};
 use motoko_rts::buf::Buf;
-use motoko_rts::types::{Bytes, Value, Words};
+use motoko_rts::memory::Memory;
+use motoko_rts::text::{blob_of_text, text_concat, text_of_str};
+use motoko_rts::types::{Bytes, Value, Words, TAG_BIGINT, TAG_BITS64};
 
 // mp functions below are implemented separately for tests as we can't modify mp_int source code to
 // pass a generic heap argument (then monomorphise it for IC).
@@ -28,46 +30,411 @@ pub unsafe fn test() {
     println!("Testing BigInt ...");
 
     // Not sure how much we will need in these tests but 1G should be enough
-    let mut heap = TestMemory::new(Words(1024 * 1024));
-    HEAP = &mut heap;
+    let mut test_heap = TestMemory::new(Words(1024 * 1024));
+    HEAP = &mut test_heap;
+    // Most bigint functions take the heap as an argument, so arguments that are results of other
+    // bigint functions are bound to locals first
+    let heap = &mut *HEAP;
 
-    assert!(bigint_eq(
-        bigint_pow(bigint_of_word32(70), bigint_of_word32(32)),
-        bigint_mul(
-            bigint_pow(bigint_of_word32(70), bigint_of_word32(31)),
-            bigint_of_word32(70)
-        )
-    ));
+    let seventy = bigint_of_word32(heap, 70);
+    let thirty_two = bigint_of_word32(heap, 32);
+    let pow32 = bigint_pow(heap, seventy, thirty_two);
+    let thirty_one = bigint_of_word32(heap, 31);
+    let pow31 = bigint_pow(heap, seventy, thirty_one);
+    assert!(bigint_eq(pow32, bigint_mul(heap, pow31, seventy)));
 
     //
     // (s)leb128 encoding
     //
 
-    let one = bigint_of_word32(1);
-    let two = bigint_of_word32(2);
+    let one = bigint_of_word32(heap, 1);
+    let two = bigint_of_word32(heap, 2);
     for i in 0..100 {
-        let two_pow_i = bigint_pow(two, bigint_of_word32(i));
-        let minus_one = bigint_sub(two_pow_i, one);
-        let plus_one = bigint_add(two_pow_i, one);
-
-        test_bigint_leb128(minus_one);
-        test_bigint_leb128(two_pow_i);
-        test_bigint_leb128(plus_one);
-
-        test_bigint_sleb128(minus_one);
-        test_bigint_sleb128(two_pow_i);
-        test_bigint_sleb128(plus_one);
-        test_bigint_sleb128(bigint_neg(minus_one));
-        test_bigint_sleb128(bigint_neg(two_pow_i));
-        test_bigint_sleb128(bigint_neg(plus_one));
+        let two_pow_i = bigint_pow2(heap, i);
+        let minus_one = bigint_sub(heap, two_pow_i, one);
+        let plus_one = bigint_add(heap, two_pow_i, one);
+
+        for n in [minus_one, two_pow_i, plus_one].iter() {
+            test_bigint_leb128(heap, *n);
+            test_bigint_sleb128(heap, *n);
+            let minus_n = bigint_neg(heap, *n);
+            test_bigint_sleb128(heap, minus_n);
+        }
+    }
+
+    //
+    // Conversion to and from text
+    //
+
+    let n = bigint_pow2(heap, 100);
+    let n = bigint_sub(heap, n, one);
+    assert_eq!(
+        to_text_string(heap, n, 10),
+        "1267650600228229401496703205375"
+    );
+    let minus_n = bigint_neg(heap, n);
+    assert_eq!(
+        to_text_string(heap, minus_n, 16),
+        "-FFFFFFFFFFFFFFFFFFFFFFFFF"
+    );
+    let zero = bigint_of_word32(heap, 0);
+    assert_eq!(to_text_string(heap, zero, 2), "0");
+
+    let three = bigint_of_word32(heap, 3);
+    for radix in 2..=36 {
+        for i in 0..130 {
+            let exponent = bigint_of_word32(heap, i);
+            let n = bigint_pow(heap, three, exponent);
+            let n = bigint_sub(heap, n, two);
+            test_bigint_text(heap, n, radix);
+            let minus_n = bigint_neg(heap, n);
+            test_bigint_text(heap, minus_n, radix);
+        }
+        let n = u64::MAX - 12345;
+        let big_n = bigint_of_u64(heap, n);
+        assert_eq!(to_text_string(heap, big_n, radix), u64_to_radix(n, radix));
+    }
+
+    // Parsing is case insensitive, accepts `+`, and works on ropes
+    let parsed = [("fF", 16, 255), ("+42", 10, 42), ("-0", 10, 0)];
+    for (str, radix, n) in parsed.iter() {
+        let big_n = parse(heap, str, *radix).unwrap();
+        assert!(bigint_eq(big_n, bigint_of_word32(heap, *n)), "{:?}", str);
+    }
+    let left = text_of_str(heap, "1234567890");
+    let right = text_of_str(heap, "1234567890");
+    let rope = text_concat(heap, left, right);
+    let big_n = bigint_parse(heap, rope, 10).unwrap();
+    assert!(bigint_eq(big_n, bigint_of_u64(heap, 12345678901234567890)));
+
+    let errors = [
+        ("", 10, BigIntParseError::NoDigits),
+        ("-", 10, BigIntParseError::NoDigits),
+        ("+-1", 10, BigIntParseError::InvalidDigit),
+        ("12a", 10, BigIntParseError::InvalidDigit),
+        ("102", 2, BigIntParseError::InvalidDigit),
+        ("z", 35, BigIntParseError::InvalidDigit),
+        (" 1", 10, BigIntParseError::InvalidDigit),
+        ("1\n", 10, BigIntParseError::InvalidDigit),
+        ("1", 1, BigIntParseError::InvalidRadix),
+        ("1", 37, BigIntParseError::InvalidRadix),
+    ];
+    for (str, radix, error) in errors.iter() {
+        assert_eq!(parse(heap, str, *radix).err(), Some(*error), "{:?}", str);
+    }
+
+    //
+    // Bitwise operations, compared against two's complement i128
+    //
+
+    let mut values: Vec<i128> = vec![0];
+    for k in [0, 1, 7, 31, 32, 33, 63, 64, 100, 125].iter() {
+        for delta in [-1, 0, 1].iter() {
+            let v = (1i128 << k) + delta;
+            values.push(v);
+            values.push(-v);
+        }
+    }
+    values.push(0x5555_5555_5555_5555_5555_5555_5555);
+
+    for a in values.iter() {
+        let big_a = bigint_of_i128(heap, *a);
+        for b in values.iter() {
+            let big_b = bigint_of_i128(heap, *b);
+            let and = bigint_and(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, and), a & b);
+            let or = bigint_or(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, or), a | b);
+            let xor = bigint_xor(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, xor), a ^ b);
+        }
+
+        let not = bigint_not(heap, big_a);
+        assert_eq!(i128_of_bigint(heap, not), !a);
+
+        let popcount = if *a >= 0 {
+            a.count_ones()
+        } else {
+            let width = 1 + 128 - (!a).leading_zeros();
+            width - (!a).count_ones()
+        };
+        assert_eq!(bigint_popcount(big_a), popcount, "{}", a);
+
+        for i in [0, 1, 5, 31, 32, 64, 100, 126].iter() {
+            assert_eq!(bigint_test_bit(big_a, *i), (a >> i) & 1 != 0);
+            let set = bigint_set_bit(heap, big_a, *i);
+            assert_eq!(i128_of_bigint(heap, set), a | (1 << i));
+            let cleared = bigint_clear_bit(heap, big_a, *i);
+            assert_eq!(i128_of_bigint(heap, cleared), a & !(1 << i));
+        }
+        // Bits beyond the representation are the sign
+        assert_eq!(bigint_test_bit(big_a, 1000), *a < 0);
+        assert_eq!(bigint_test_bit(big_a, u32::MAX), *a < 0);
+        // Setting the sign bit of a negative number, or clearing it of a positive one, does not
+        // need a mask
+        let unchanged = if *a < 0 {
+            bigint_set_bit(heap, big_a, u32::MAX)
+        } else {
+            bigint_clear_bit(heap, big_a, u32::MAX)
+        };
+        assert_eq!(i128_of_bigint(heap, unchanged), *a);
+    }
+
+    //
+    // Number theory
+    //
+
+    let big = |heap: &mut TestMemory, str: &str| parse(heap, str, 10).unwrap();
+
+    // RSA with p = 61, q = 53, e = 17, d = 2753
+    let rsa_n = bigint_of_word32(heap, 3233);
+    let rsa_e = bigint_of_word32(heap, 17);
+    let phi = bigint_of_word32(heap, 3120);
+    let rsa_d = bigint_modinv(heap, rsa_e, phi);
+    assert!(bigint_eq(rsa_d, bigint_of_word32(heap, 2753)));
+    let m = bigint_of_word32(heap, 65);
+    let c = bigint_modpow(heap, m, rsa_e, rsa_n);
+    assert!(bigint_eq(c, bigint_of_word32(heap, 2790)));
+    assert!(bigint_eq(bigint_modpow(heap, c, rsa_d, rsa_n), m));
+
+    let m127 = bigint_pow2(heap, 127);
+    let m127 = bigint_sub(heap, m127, one);
+    let p25519 = bigint_pow2(heap, 255);
+    let nineteen = bigint_of_word32(heap, 19);
+    let p25519 = bigint_sub(heap, p25519, nineteen);
+    let exponent = bigint_of_word32(heap, 1024);
+    assert!(bigint_eq(
+        bigint_modpow(heap, two, exponent, m127),
+        bigint_of_word32(heap, 256)
+    ));
+    let base = bigint_of_word32(heap, 123456789);
+    let exponent = bigint_pow2(heap, 64);
+    let exponent = bigint_add(heap, exponent, one);
+    let expected = big(
+        heap,
+        "28948796549014264681621037850938127988720400128005167833759054698246508789167",
+    );
+    assert!(bigint_eq(
+        bigint_modpow(heap, base, exponent, p25519),
+        expected
+    ));
+    // Negative exponents use the inverse
+    let minus_one = bigint_neg(heap, one);
+    let seven = bigint_of_word32(heap, 7);
+    assert!(bigint_eq(
+        bigint_modpow(heap, three, minus_one, seven),
+        bigint_of_word32(heap, 5)
+    ));
+
+    // gcd(F(m), F(n)) = F(gcd(m, n)) for Fibonacci numbers
+    let mut fib = vec![zero, one];
+    for i in 2..=100 {
+        fib.push(bigint_add(heap, fib[i - 1], fib[i - 2]));
+    }
+    assert!(bigint_eq(bigint_gcd(heap, fib[100], fib[99]), one));
+    assert!(bigint_eq(bigint_gcd(heap, fib[100], fib[75]), fib[25]));
+    let minus_fib100 = bigint_neg(heap, fib[100]);
+    assert!(bigint_eq(bigint_gcd(heap, minus_fib100, fib[50]), fib[50]));
+    assert!(bigint_eq(bigint_gcd(heap, zero, fib[50]), fib[50]));
+    let four = bigint_of_word32(heap, 4);
+    let six = bigint_of_word32(heap, 6);
+    assert!(bigint_eq(
+        bigint_lcm(heap, four, six),
+        bigint_of_word32(heap, 12)
+    ));
+    let gcd = bigint_gcd(heap, fib[100], fib[80]);
+    let lcm = bigint_lcm(heap, fib[100], fib[80]);
+    assert!(bigint_eq(
+        bigint_mul(heap, gcd, lcm),
+        bigint_mul(heap, fib[100], fib[80])
+    ));
+
+    let ninety_nine = bigint_of_word32(heap, 99);
+    assert!(bigint_eq(
+        bigint_sqrt(heap, ninety_nine),
+        bigint_of_word32(heap, 9)
+    ));
+    let ten = bigint_of_word32(heap, 10);
+    let forty = bigint_of_word32(heap, 40);
+    let ten_pow_40 = bigint_pow(heap, ten, forty);
+    let twenty = bigint_of_word32(heap, 20);
+    let ten_pow_20 = bigint_pow(heap, ten, twenty);
+    assert!(bigint_eq(bigint_sqrt(heap, ten_pow_40), ten_pow_20));
+    let n = bigint_pow2(heap, 100);
+    let n = bigint_sub(heap, n, one);
+    let root = bigint_pow2(heap, 50);
+    let root = bigint_sub(heap, root, one);
+    assert!(bigint_eq(bigint_sqrt(heap, n), root));
+
+    let jacobi = [
+        (1001, 9907, -1),
+        (19, 45, 1),
+        (8, 21, -1),
+        (5, 21, 1),
+        (30, 59, -1),
+        (3, 9, 0),
+    ];
+    for (a, n, symbol) in jacobi.iter() {
+        let big_a = bigint_of_word32(heap, *a);
+        let big_n = bigint_of_word32(heap, *n);
+        assert_eq!(bigint_jacobi(big_a, big_n), *symbol);
+    }
+    assert_eq!(bigint_jacobi(bigint_sub(heap, m127, one), m127), -1);
+
+    let limit = 5000;
+    let mut sieve = vec![true; limit];
+    sieve[0] = false;
+    sieve[1] = false;
+    for i in 2..limit {
+        if sieve[i] {
+            for j in (i * i..limit).step_by(i) {
+                sieve[j] = false;
+            }
+        }
+    }
+    for i in 0..limit {
+        assert_eq!(
+            bigint_is_prime(bigint_of_word32(heap, i as u32)),
+            sieve[i],
+            "{}",
+            i
+        );
+    }
+
+    let primes = [
+        "2305843009213693951",                     // 2^61 - 1
+        "170141183460469231731687303715884105727", // 2^127 - 1
+        "57896044618658097711785492504343953926634992332820282019728792003956564819949", // 2^255 - 19
+    ];
+    for p in primes.iter() {
+        assert!(bigint_is_prime(big(heap, p)), "{}", p);
+    }
+    let m521 = bigint_pow2(heap, 521);
+    assert!(bigint_is_prime(bigint_sub(heap, m521, one)));
+
+    let composites = [
+        "-7",
+        "147573952589676412927",                          // 2^67 - 1
+        "3215031751",          // strong pseudoprime to bases 2, 3, 5 and 7
+        "3825123056546413051", // strong pseudoprime to bases 2 to 23
+        "5316911983139663487003542222693990401", // (2^61 - 1)^2
+        "1427247692705959880439315947500961989719490561", // (2^61 - 1) * (2^89 - 1)
+    ];
+    for c in composites.iter() {
+        assert!(!bigint_is_prime(big(heap, c)), "{}", c);
     }
 
+    test_compact(heap);
+
     HEAP = std::ptr::null_mut();
-    drop(heap);
+    drop(test_heap);
+}
+
+// Check the fast paths for compact numbers, and the switch between representations on overflow
+unsafe fn test_compact(heap: &mut TestMemory) {
+    let values: [i128; 16] = [
+        0,
+        1,
+        -1,
+        2,
+        -3,
+        i32::MAX as i128,
+        i32::MIN as i128,
+        u32::MAX as i128,
+        3037000499, // floor(sqrt(i64::MAX))
+        -3037000500,
+        1 << 62,
+        -(1 << 62),
+        i64::MAX as i128 - 1,
+        i64::MAX as i128,
+        i64::MIN as i128 + 1,
+        i64::MIN as i128,
+    ];
+
+    for a in values.iter() {
+        let big_a = bigint_of_i128(heap, *a);
+        let neg = bigint_neg(heap, big_a);
+        assert_eq!(i128_of_bigint(heap, neg), -a);
+        let abs = bigint_abs(heap, big_a);
+        assert_eq!(i128_of_bigint(heap, abs), a.abs());
+        let not = bigint_not(heap, big_a);
+        assert_eq!(i128_of_bigint(heap, not), !a);
+        assert_eq!(bigint_isneg(big_a), *a < 0);
+        assert_eq!(
+            bigint_count_bits(big_a) as u32,
+            128 - a.unsigned_abs().leading_zeros()
+        );
+
+        for b in values.iter() {
+            let big_b = bigint_of_i128(heap, *b);
+            let sum = bigint_add(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, sum), a + b);
+            let difference = bigint_sub(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, difference), a - b);
+            let product = bigint_mul(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, product), a * b);
+            let and = bigint_and(heap, big_a, big_b);
+            assert_eq!(i128_of_bigint(heap, and), a & b);
+            if *b != 0 {
+                let quotient = bigint_div(heap, big_a, big_b);
+                assert_eq!(i128_of_bigint(heap, quotient), a / b);
+                let remainder = bigint_rem(heap, big_a, big_b);
+                assert_eq!(i128_of_bigint(heap, remainder), a % b);
+            }
+            assert_eq!(bigint_eq(big_a, big_b), a == b);
+        }
+
+        for shift in [0, 1, 31, 62, 63, 64, 100].iter() {
+            let lsh = bigint_lsh(heap, big_a, *shift);
+            let factor = bigint_pow2(heap, *shift as u32);
+            assert!(bigint_eq(lsh, bigint_mul(heap, big_a, factor)));
+            let rsh = bigint_rsh(heap, big_a, *shift);
+            let magnitude = a.unsigned_abs().checked_shr(*shift as u32).unwrap_or(0) as i128;
+            assert_eq!(
+                i128_of_bigint(heap, rsh),
+                if *a < 0 { -magnitude } else { magnitude }
+            );
+        }
+    }
+
+    // Results are compact exactly when they fit in 64 bits
+    let max = bigint_of_i128(heap, i64::MAX as i128);
+    let min = bigint_of_i128(heap, i64::MIN as i128);
+    let one = bigint_of_word32(heap, 1);
+    let minus_one = bigint_of_i128(heap, -1);
+    assert_eq!(max.tag(), TAG_BITS64);
+    assert_eq!(min.tag(), TAG_BITS64);
+    let overflows = [
+        bigint_add(heap, max, one),
+        bigint_sub(heap, min, one),
+        bigint_mul(heap, min, minus_one),
+        bigint_div(heap, min, minus_one),
+        bigint_neg(heap, min),
+        bigint_lsh(heap, one, 63),
+    ];
+    for n in overflows.iter() {
+        assert_eq!(n.tag(), TAG_BIGINT);
+    }
+    assert!(bigint_eq(overflows[0], bigint_pow2(heap, 63)));
+    let back = bigint_sub(heap, overflows[0], one);
+    assert_eq!(back.tag(), TAG_BITS64);
+    assert!(bigint_eq(back, max));
+    assert!(bigint_eq(bigint_add(heap, overflows[1], one), min));
+    let dividend = bigint_pow2(heap, 100);
+    let divisor = bigint_pow2(heap, 40);
+    let quotient = bigint_div(heap, dividend, divisor);
+    assert_eq!(quotient.tag(), TAG_BITS64);
+    assert!(bigint_eq(quotient, bigint_pow2(heap, 60)));
+}
+
+unsafe fn bigint_pow2(heap: &mut TestMemory, i: u32) -> Value {
+    let two = bigint_of_word32(heap, 2);
+    let exponent = bigint_of_word32(heap, i);
+    bigint_pow(heap, two, exponent)
 }
 
 // Check leb128 encode/decode roundtrip
-unsafe fn test_bigint_leb128(n: Value) {
+unsafe fn test_bigint_leb128(heap: &mut TestMemory, n: Value) {
     let mut buf = [0u8; 100];
     let s = bigint_leb128_size(n);
     let mut buf_ = Buf {
@@ -75,13 +442,13 @@ unsafe fn test_bigint_leb128(n: Value) {
         end: buf.as_mut_ptr().add(100),
     };
     bigint_leb128_encode(n, buf.as_mut_ptr());
-    let n2 = bigint_leb128_decode(&mut buf_ as *mut _);
+    let n2 = bigint_leb128_decode(heap, &mut buf_ as *mut _);
     assert!(bigint_eq(n, n2));
     assert_eq!(buf_.ptr.offset_from(buf.as_ptr()), s as isize);
 }
 
 // Check sleb128 encode/decode roundtrip
-unsafe fn test_bigint_sleb128(n: Value) {
+unsafe fn test_bigint_sleb128(heap: &mut TestMemory, n: Value) {
     let mut buf = [0u8; 100];
     let s = bigint_sleb128_size(n);
     bigint_sleb128_encode(n, buf.as_mut_ptr());
@@ -89,7 +456,54 @@ unsafe fn test_bigint_sleb128(n: Value) {
         ptr: buf.as_mut_ptr(),
         end: buf.as_mut_ptr().add(100),
     };
-    let n2 = bigint_sleb128_decode(&mut buf_ as *mut _);
+    let n2 = bigint_sleb128_decode(heap, &mut buf_ as *mut _);
     assert!(bigint_eq(n, n2));
     assert_eq!(buf_.ptr.offset_from(buf.as_ptr()), s as isize);
 }
+
+unsafe fn to_text_string<M: Memory>(mem: &mut M, n: Value, radix: u32) -> String {
+    let text = bigint_to_text(mem, n, radix);
+    let blob = blob_of_text(mem, text).as_blob();
+    let bytes = std::slice::from_raw_parts(blob.payload_const(), blob.len().as_usize());
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+unsafe fn parse<M: Memory>(mem: &mut M, str: &str, radix: u32) -> Result<Value, BigIntParseError> {
+    let text = text_of_str(mem, str);
+    bigint_parse(mem, text, radix)
+}
+
+fn u64_to_radix(mut n: u64, radix: u32) -> String {
+    let mut digits = vec![];
+    loop {
+        digits.push(std::char::from_digit((n % radix as u64) as u32, radix).unwrap());
+        n /= radix as u64;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect::<String>().to_uppercase()
+}
+
+// Check text conversion roundtrip
+unsafe fn test_bigint_text<M: Memory>(mem: &mut M, n: Value, radix: u32) {
+    let text = bigint_to_text(mem, n, radix);
+    let n2 = bigint_parse(mem, text, radix).unwrap();
+    assert!(bigint_eq(n, n2));
+}
+
+unsafe fn bigint_of_u64(heap: &mut TestMemory, n: u64) -> Value {
+    let hi = bigint_of_word32(heap, (n >> 32) as u32);
+    let factor = bigint_pow2(heap, 32);
+    let hi = bigint_mul(heap, hi, factor);
+    let lo = bigint_of_word32(heap, n as u32);
+    bigint_add(heap, hi, lo)
+}
+
+unsafe fn bigint_of_i128<M: Memory>(mem: &mut M, n: i128) -> Value {
+    parse(mem, &n.to_string(), 10).unwrap()
+}
+
+unsafe fn i128_of_bigint<M: Memory>(mem: &mut M, n: Value) -> i128 {
+    to_text_string(mem, n, 10).parse().unwrap()
+}
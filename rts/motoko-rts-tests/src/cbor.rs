@@ -0,0 +1,498 @@
+//! CBOR decoding tests, with the examples of RFC 8949 (appendices A and F)
+
+use crate::memory::TestMemory;
+
+use motoko_rts::buf::Buf;
+use motoko_rts::cbor::{
+    cbor_decode_checked, cbor_read_array, cbor_read_break, cbor_read_bytes, cbor_read_head,
+    cbor_read_map, cbor_read_nat, cbor_read_tag, cbor_read_text, cbor_skip_checked, CborError,
+    CborHead, CborResult, CBOR_MAX_DEPTH, CBOR_NEGATIVE, CBOR_SIMPLE, CBOR_TEXT, CBOR_UNSIGNED,
+};
+use motoko_rts::types::{Value, Words, TAG_ARRAY, TAG_BITS64, TAG_BLOB};
+
+/// Well-formed data items of appendix A
+const WELL_FORMED: &[&str] = &[
+    "00",
+    "01",
+    "0a",
+    "17",
+    "1818",
+    "1819",
+    "1864",
+    "1903e8",
+    "1a000f4240",
+    "1b000000e8d4a51000",
+    "1bffffffffffffffff",
+    "c249010000000000000000",
+    "3bffffffffffffffff",
+    "c349010000000000000000",
+    "20",
+    "29",
+    "3863",
+    "3903e7",
+    "f90000",
+    "f98000",
+    "f93c00",
+    "fb3ff199999999999a",
+    "f93e00",
+    "f97bff",
+    "fa47c35000",
+    "fa7f7fffff",
+    "fb7e37e43c8800759c",
+    "f90001",
+    "f90400",
+    "f9c400",
+    "fbc010666666666666",
+    "f97c00",
+    "f97e00",
+    "f9fc00",
+    "fa7f800000",
+    "fa7fc00000",
+    "faff800000",
+    "fb7ff0000000000000",
+    "fb7ff8000000000000",
+    "fbfff0000000000000",
+    "f4",
+    "f5",
+    "f6",
+    "f7",
+    "f0",
+    "f8ff",
+    "c074323031332d30332d32315432303a30343a30305a",
+    "c11a514b67b0",
+    "c1fb41d452d9ec200000",
+    "d74401020304",
+    "d818456449455446",
+    "d82076687474703a2f2f7777772e6578616d706c652e636f6d",
+    "40",
+    "4401020304",
+    "60",
+    "6161",
+    "6449455446",
+    "62225c",
+    "62c3bc",
+    "63e6b0b4",
+    "64f0908591",
+    "80",
+    "83010203",
+    "8301820203820405",
+    "98190102030405060708090a0b0c0d0e0f101112131415161718181819",
+    "a0",
+    "a201020304",
+    "a26161016162820203",
+    "826161a161626163",
+    "a56161614161626142616361436164614461656145",
+    "5f42010243030405ff",
+    "7f657374726561646d696e67ff",
+    "9fff",
+    "9f018202039f0405ffff",
+    "9f01820203820405ff",
+    "83018202039f0405ff",
+    "83019f0203ff820405",
+    "9f0102030405060708090a0b0c0d0e0f101112131415161718181819ff",
+    "bf61610161629f0203ffff",
+    "826161bf61626163ff",
+    "bf6346756ef563416d7421ff",
+];
+
+/// Malformed data items of appendix F, with their errors
+const MALFORMED: &[(&str, CborError)] = &[
+    // End of input in a head
+    ("18", CborError::EndOfInput),
+    ("19", CborError::EndOfInput),
+    ("1a", CborError::EndOfInput),
+    ("1b", CborError::EndOfInput),
+    ("1901", CborError::EndOfInput),
+    ("1a0102", CborError::EndOfInput),
+    ("1b01020304050607", CborError::EndOfInput),
+    ("38", CborError::EndOfInput),
+    ("58", CborError::EndOfInput),
+    ("78", CborError::EndOfInput),
+    ("98", CborError::EndOfInput),
+    ("9a01ff00", CborError::EndOfInput),
+    ("b8", CborError::EndOfInput),
+    ("d8", CborError::EndOfInput),
+    ("f8", CborError::EndOfInput),
+    ("f900", CborError::EndOfInput),
+    ("fa0000", CborError::EndOfInput),
+    ("fb000000", CborError::EndOfInput),
+    // Definite-length strings with short data
+    ("41", CborError::EndOfInput),
+    ("61", CborError::EndOfInput),
+    ("5affffffff00", CborError::EndOfInput),
+    ("5bffffffffffffffff010203", CborError::EndOfInput),
+    ("7affffffff00", CborError::EndOfInput),
+    ("7b7fffffffffffffff010203", CborError::EndOfInput),
+    // Definite-length maps and arrays not closed with enough items
+    ("81", CborError::EndOfInput),
+    ("818181818181818181", CborError::EndOfInput),
+    ("8200", CborError::EndOfInput),
+    ("a1", CborError::EndOfInput),
+    ("a20102", CborError::EndOfInput),
+    ("a100", CborError::EndOfInput),
+    ("a2000000", CborError::EndOfInput),
+    // Tag number not followed by tag content
+    ("c0", CborError::EndOfInput),
+    // Indefinite-length strings not closed by a break
+    ("5f4100", CborError::EndOfInput),
+    ("7f6100", CborError::EndOfInput),
+    // Indefinite-length maps and arrays not closed by a break
+    ("9f", CborError::EndOfInput),
+    ("9f0102", CborError::EndOfInput),
+    ("bf", CborError::EndOfInput),
+    ("bf01020102", CborError::EndOfInput),
+    ("819f", CborError::EndOfInput),
+    ("9f8000", CborError::EndOfInput),
+    ("9f9f9f9f9fffffffff", CborError::EndOfInput),
+    ("9f819f819f9fffffff", CborError::EndOfInput),
+    // Reserved additional information values
+    ("1c", CborError::ReservedInfo),
+    ("1d", CborError::ReservedInfo),
+    ("1e", CborError::ReservedInfo),
+    ("3c", CborError::ReservedInfo),
+    ("3d", CborError::ReservedInfo),
+    ("3e", CborError::ReservedInfo),
+    ("5c", CborError::ReservedInfo),
+    ("5d", CborError::ReservedInfo),
+    ("5e", CborError::ReservedInfo),
+    ("7c", CborError::ReservedInfo),
+    ("7d", CborError::ReservedInfo),
+    ("7e", CborError::ReservedInfo),
+    ("9c", CborError::ReservedInfo),
+    ("9d", CborError::ReservedInfo),
+    ("9e", CborError::ReservedInfo),
+    ("bc", CborError::ReservedInfo),
+    ("bd", CborError::ReservedInfo),
+    ("be", CborError::ReservedInfo),
+    ("dc", CborError::ReservedInfo),
+    ("dd", CborError::ReservedInfo),
+    ("de", CborError::ReservedInfo),
+    ("fc", CborError::ReservedInfo),
+    ("fd", CborError::ReservedInfo),
+    ("fe", CborError::ReservedInfo),
+    // Reserved two-byte encodings of simple values
+    ("f800", CborError::InvalidSimple),
+    ("f801", CborError::InvalidSimple),
+    ("f818", CborError::InvalidSimple),
+    ("f81f", CborError::InvalidSimple),
+    // Indefinite-length string chunks not of the correct type
+    ("5f00ff", CborError::InvalidIndefinite),
+    ("5f21ff", CborError::InvalidIndefinite),
+    ("5f6100ff", CborError::InvalidIndefinite),
+    ("5f80ff", CborError::InvalidIndefinite),
+    ("5fa0ff", CborError::InvalidIndefinite),
+    ("5fc000ff", CborError::InvalidIndefinite),
+    ("5fe0ff", CborError::InvalidIndefinite),
+    ("7f4100ff", CborError::InvalidIndefinite),
+    // Indefinite-length string chunks not definite length
+    ("5f5f4100ffff", CborError::InvalidIndefinite),
+    ("7f7f6100ffff", CborError::InvalidIndefinite),
+    // Break occurring on its own outside of an indefinite-length item
+    ("ff", CborError::UnexpectedBreak),
+    // Break occurring in a definite-length array or map or a tag
+    ("81ff", CborError::UnexpectedBreak),
+    ("8200ff", CborError::UnexpectedBreak),
+    ("a1ff", CborError::EndOfInput),
+    ("a1ff00", CborError::UnexpectedBreak),
+    ("a100ff", CborError::UnexpectedBreak),
+    ("a20000ff", CborError::EndOfInput),
+    ("9f81ff", CborError::UnexpectedBreak),
+    ("9f829f819f9fffffffff", CborError::UnexpectedBreak),
+    ("9fc0ff", CborError::UnexpectedBreak),
+    // Break in an indefinite-length map that would lead to an odd number of items
+    ("bf00ff", CborError::UnexpectedBreak),
+    ("bf000000ff", CborError::UnexpectedBreak),
+    // Major type 0, 1 and 6 with additional information 31
+    ("1f", CborError::InvalidIndefinite),
+    ("3f", CborError::InvalidIndefinite),
+    ("df", CborError::InvalidIndefinite),
+    // Invalid UTF-8 in text strings
+    ("62c328", CborError::NotUtf8),
+    ("7f61ff62c3bcff", CborError::NotUtf8),
+];
+
+/// A decoded Motoko value
+#[derive(Debug, PartialEq)]
+enum Decoded {
+    Nat(u64),
+    Bytes(Vec<u8>),
+    Array(Vec<Decoded>),
+}
+
+use Decoded::*;
+
+pub unsafe fn test() {
+    println!("Testing CBOR ...");
+
+    println!("  Testing cbor_skip with well-formed data items");
+    for item in WELL_FORMED {
+        let bytes = hex(item);
+        let mut buf = buf_of(&bytes);
+        assert_eq!(cbor_skip_checked(&mut buf), Ok(()), "{}", item);
+        assert_eq!({ buf.ptr }, { buf.end }, "{}", item);
+    }
+
+    println!("  Testing cbor_skip with malformed data items");
+    for (item, error) in MALFORMED {
+        let bytes = hex(item);
+        let mut buf = buf_of(&bytes);
+        assert_eq!(cbor_skip_checked(&mut buf), Err(*error), "{}", item);
+    }
+
+    println!("  Testing cbor_skip with nested arrays");
+    let nested = [vec![0x81; CBOR_MAX_DEPTH], vec![0]].concat();
+    assert_eq!(skip(&nested), Ok(()));
+    let nested = [vec![0x81; CBOR_MAX_DEPTH + 1], vec![0]].concat();
+    assert_eq!(skip(&nested), Err(CborError::NestedTooDeeply));
+    let nested = [vec![0x9f; CBOR_MAX_DEPTH], vec![0xff; CBOR_MAX_DEPTH]].concat();
+    assert_eq!(skip(&nested), Ok(()));
+    // Tags do not nest
+    let tagged = [vec![0xc0; 1000], vec![0]].concat();
+    assert_eq!(skip(&tagged), Ok(()));
+
+    println!("  Testing the CBOR readers");
+    let bytes = hex("1bffffffffffffffff");
+    assert_eq!(cbor_read_nat(&mut buf_of(&bytes)), Ok(u64::MAX));
+    let bytes = hex("3903e7");
+    assert_eq!(
+        cbor_read_head(&mut buf_of(&bytes)),
+        Ok(CborHead {
+            major: CBOR_NEGATIVE,
+            info: 25,
+            arg: 999
+        })
+    );
+    assert_eq!(
+        cbor_read_nat(&mut buf_of(&bytes)),
+        Err(CborError::UnexpectedType {
+            expected: CBOR_UNSIGNED,
+            found: CBOR_NEGATIVE
+        })
+    );
+    let bytes = hex("fb3ff199999999999a");
+    let head = cbor_read_head(&mut buf_of(&bytes)).unwrap();
+    assert_eq!(head.major, CBOR_SIMPLE);
+    assert_eq!(f64::from_bits(head.arg), 1.1);
+    let bytes = hex("f8ff");
+    assert_eq!(cbor_read_head(&mut buf_of(&bytes)).unwrap().arg, 255);
+    let bytes = hex("d82076687474703a2f2f7777772e6578616d706c652e636f6d");
+    let mut buf = buf_of(&bytes);
+    assert_eq!(cbor_read_tag(&mut buf), Ok(32));
+    assert_eq!(cbor_read_text(&mut buf), Ok("http://www.example.com"));
+    let bytes = hex("4401020304");
+    assert_eq!(
+        cbor_read_bytes(&mut buf_of(&bytes)),
+        Ok(&[1u8, 2, 3, 4][..])
+    );
+    let bytes = hex("5f42010243030405ff");
+    assert_eq!(
+        cbor_read_bytes(&mut buf_of(&bytes)),
+        Err(CborError::Unsupported)
+    );
+    assert_eq!(
+        cbor_read_text(&mut buf_of(&bytes)),
+        Err(CborError::UnexpectedType {
+            expected: CBOR_TEXT,
+            found: 2
+        })
+    );
+    // [_ 1, [2, 3], [_ 4, 5]]
+    let bytes = hex("9f018202039f0405ffff");
+    let mut buf = buf_of(&bytes);
+    assert_eq!(cbor_read_array(&mut buf), Ok(None));
+    assert_eq!(cbor_read_break(&mut buf), Ok(false));
+    assert_eq!(cbor_read_nat(&mut buf), Ok(1));
+    assert_eq!(cbor_read_array(&mut buf), Ok(Some(2)));
+    assert_eq!(cbor_skip_checked(&mut buf), Ok(()));
+    assert_eq!(cbor_skip_checked(&mut buf), Ok(()));
+    assert_eq!(cbor_skip_checked(&mut buf), Ok(()));
+    assert_eq!(cbor_read_break(&mut buf), Ok(true));
+    assert_eq!(cbor_read_break(&mut buf), Err(CborError::EndOfInput));
+    let bytes = hex("a26161016162820203");
+    let mut buf = buf_of(&bytes);
+    assert_eq!(cbor_read_map(&mut buf), Ok(Some(2)));
+    assert_eq!(cbor_read_text(&mut buf), Ok("a"));
+    let bytes = hex("9bffffffffffffffff");
+    assert_eq!(
+        cbor_read_array(&mut buf_of(&bytes)),
+        Err(CborError::TooLarge)
+    );
+
+    println!("  Testing cbor_decode");
+    let mut mem = TestMemory::new(Words(1024 * 1024));
+    assert_eq!(decode(&mut mem, "00"), Ok(Nat(0)));
+    assert_eq!(decode(&mut mem, "1864"), Ok(Nat(100)));
+    assert_eq!(
+        decode(&mut mem, "1b000000e8d4a51000"),
+        Ok(Nat(1000000000000))
+    );
+    assert_eq!(decode(&mut mem, "40"), Ok(Bytes(vec![])));
+    assert_eq!(decode(&mut mem, "4401020304"), Ok(Bytes(vec![1, 2, 3, 4])));
+    assert_eq!(decode(&mut mem, "6449455446"), Ok(Bytes(b"IETF".to_vec())));
+    assert_eq!(
+        decode(&mut mem, "64f0908591"),
+        Ok(Bytes("𐅑".as_bytes().to_vec()))
+    );
+    assert_eq!(decode(&mut mem, "80"), Ok(Array(vec![])));
+    assert_eq!(
+        decode(&mut mem, "8301820203820405"),
+        Ok(Array(vec![
+            Nat(1),
+            Array(vec![Nat(2), Nat(3)]),
+            Array(vec![Nat(4), Nat(5)])
+        ]))
+    );
+    assert_eq!(
+        decode(
+            &mut mem,
+            "98190102030405060708090a0b0c0d0e0f101112131415161718181819"
+        ),
+        Ok(Array((1..=25).map(Nat).collect()))
+    );
+    assert_eq!(decode(&mut mem, "a0"), Ok(Array(vec![])));
+    assert_eq!(
+        decode(&mut mem, "a201020304"),
+        Ok(Array(vec![
+            Array(vec![Nat(1), Nat(2)]),
+            Array(vec![Nat(3), Nat(4)])
+        ]))
+    );
+    assert_eq!(
+        decode(&mut mem, "826161a161626163"),
+        Ok(Array(vec![
+            Bytes(b"a".to_vec()),
+            Array(vec![Array(vec![
+                Bytes(b"b".to_vec()),
+                Bytes(b"c".to_vec())
+            ])])
+        ]))
+    );
+
+    println!("  Testing cbor_decode with indefinite lengths and tags");
+    assert_eq!(
+        decode(&mut mem, "5f42010243030405ff"),
+        Ok(Bytes(vec![1, 2, 3, 4, 5]))
+    );
+    assert_eq!(
+        decode(&mut mem, "7f657374726561646d696e67ff"),
+        Ok(Bytes(b"streaming".to_vec()))
+    );
+    assert_eq!(decode(&mut mem, "5fff"), Ok(Bytes(vec![])));
+    assert_eq!(decode(&mut mem, "9fff"), Ok(Array(vec![])));
+    let expected = Array(vec![
+        Nat(1),
+        Array(vec![Nat(2), Nat(3)]),
+        Array(vec![Nat(4), Nat(5)]),
+    ]);
+    for item in [
+        "9f018202039f0405ffff",
+        "9f01820203820405ff",
+        "83018202039f0405ff",
+        "83019f0203ff820405",
+    ] {
+        assert_eq!(decode(&mut mem, item).as_ref(), Ok(&expected), "{}", item);
+    }
+    assert_eq!(
+        decode(&mut mem, "bf61610161629f0203ffff"),
+        Ok(Array(vec![
+            Array(vec![Bytes(b"a".to_vec()), Nat(1)]),
+            Array(vec![Bytes(b"b".to_vec()), Array(vec![Nat(2), Nat(3)])])
+        ]))
+    );
+    assert_eq!(decode(&mut mem, "c11a514b67b0"), Ok(Nat(1363896240)));
+    // A certificate starts with the self-described CBOR tag
+    assert_eq!(
+        decode(&mut mem, "d9d9f7a1647472656583018301"),
+        Err(CborError::EndOfInput)
+    );
+    assert_eq!(
+        decode(&mut mem, "d9d9f7a164747265658201820203"),
+        Ok(Array(vec![Array(vec![
+            Bytes(b"tree".to_vec()),
+            Array(vec![Nat(1), Array(vec![Nat(2), Nat(3)])])
+        ])]))
+    );
+
+    println!("  Testing cbor_decode with unsupported and malformed data items");
+    for item in [
+        "20",
+        "f4",
+        "f6",
+        "fb3ff199999999999a",
+        "8120",
+        "bf6346756ef563416d7421ff",
+    ] {
+        assert_eq!(
+            decode(&mut mem, item),
+            Err(CborError::Unsupported),
+            "{}",
+            item
+        );
+    }
+    for (item, error) in MALFORMED {
+        // Unsupported data items may come before the malformation
+        let result = decode(&mut mem, item);
+        assert!(
+            result == Err(*error) || result == Err(CborError::Unsupported),
+            "{}: {:?}",
+            item,
+            result
+        );
+    }
+    assert_eq!(decode(&mut mem, "0000"), Err(CborError::TrailingBytes));
+    let nested = [vec![0x81; CBOR_MAX_DEPTH], vec![0]].concat();
+    assert!(decode_bytes(&mut mem, &nested).is_ok());
+    let nested = [vec![0x81; CBOR_MAX_DEPTH + 1], vec![0]].concat();
+    assert_eq!(
+        decode_bytes(&mut mem, &nested),
+        Err(CborError::NestedTooDeeply)
+    );
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn buf_of(bytes: &[u8]) -> Buf {
+    Buf {
+        ptr: bytes.as_ptr() as *mut u8,
+        end: unsafe { bytes.as_ptr().add(bytes.len()) as *mut u8 },
+    }
+}
+
+unsafe fn skip(bytes: &[u8]) -> CborResult<()> {
+    cbor_skip_checked(&mut buf_of(bytes))
+}
+
+/// Like `cbor_decode`, returning an error instead of trapping
+unsafe fn decode(mem: &mut TestMemory, item: &str) -> CborResult<Decoded> {
+    decode_bytes(mem, &hex(item))
+}
+
+unsafe fn decode_bytes(mem: &mut TestMemory, bytes: &[u8]) -> CborResult<Decoded> {
+    let mut buf = buf_of(bytes);
+    let value = cbor_decode_checked(mem, &mut buf)?;
+    if buf.ptr != buf.end {
+        return Err(CborError::TrailingBytes);
+    }
+    Ok(decoded(value))
+}
+
+unsafe fn decoded(value: Value) -> Decoded {
+    match value.tag() {
+        TAG_BITS64 => Nat((*value.as_bits64()).bits()),
+        TAG_BLOB => {
+            let blob = value.as_blob();
+            let bytes = std::slice::from_raw_parts(blob.payload_const(), blob.len().as_usize());
+            Bytes(bytes.to_vec())
+        }
+        TAG_ARRAY => {
+            let array = value.as_array();
+            Array((0..array.len()).map(|i| decoded(array.get(i))).collect())
+        }
+        tag => panic!("unexpected tag {}", tag),
+    }
+}
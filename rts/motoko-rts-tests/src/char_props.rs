@@ -0,0 +1,98 @@
+//! Unicode character property tests
+
+use motoko_rts::char_props::*;
+
+pub unsafe fn test() {
+    println!("Testing Unicode character properties ...");
+
+    println!("  Testing general category");
+    let categories = [
+        ('A', GC_UPPERCASE_LETTER),
+        ('a', GC_LOWERCASE_LETTER),
+        ('\u{1C5}', GC_TITLECASE_LETTER), // Dž
+        ('\u{2B0}', GC_MODIFIER_LETTER),  // ʰ
+        ('中', GC_OTHER_LETTER),
+        ('\u{301}', GC_NONSPACING_MARK),
+        ('\u{903}', GC_SPACING_MARK),
+        ('\u{20DD}', GC_ENCLOSING_MARK),
+        ('7', GC_DECIMAL_NUMBER),
+        ('Ⅻ', GC_LETTER_NUMBER),
+        ('½', GC_OTHER_NUMBER),
+        ('_', GC_CONNECTOR_PUNCTUATION),
+        ('-', GC_DASH_PUNCTUATION),
+        ('(', GC_OPEN_PUNCTUATION),
+        (')', GC_CLOSE_PUNCTUATION),
+        ('«', GC_INITIAL_PUNCTUATION),
+        ('»', GC_FINAL_PUNCTUATION),
+        ('!', GC_OTHER_PUNCTUATION),
+        ('+', GC_MATH_SYMBOL),
+        ('$', GC_CURRENCY_SYMBOL),
+        ('^', GC_MODIFIER_SYMBOL),
+        ('©', GC_OTHER_SYMBOL),
+        (' ', GC_SPACE_SEPARATOR),
+        ('\u{2028}', GC_LINE_SEPARATOR),
+        ('\u{2029}', GC_PARAGRAPH_SEPARATOR),
+        ('\u{0}', GC_CONTROL),
+        ('\u{200B}', GC_FORMAT),
+        ('\u{E000}', GC_PRIVATE_USE),
+        ('\u{10FFFF}', GC_UNASSIGNED),
+    ];
+    for (c, category) in categories.iter() {
+        assert_eq!(general_category(*c as u32), *category, "{:?}", c);
+    }
+    assert_eq!(general_category(0xD800), GC_SURROGATE);
+
+    println!("  Testing numeric value");
+    let values = [
+        ('0', 0.0),
+        ('7', 7.0),
+        ('\u{663}', 3.0), // Arabic-Indic digit three
+        ('½', 0.5),
+        ('⅓', 1.0 / 3.0),
+        ('Ⅻ', 12.0),
+        ('\u{F33}', -0.5),  // Tibetan digit half zero
+        ('\u{5146}', 1e12), // 兆
+        ('\u{1D7FF}', 9.0), // Mathematical monospace digit nine
+    ];
+    for (c, value) in values.iter() {
+        assert_eq!(numeric_value(*c as u32), Some(*value), "{:?}", c);
+    }
+    for c in ['a', ' ', '\u{0}', '\u{10FFFF}'].iter() {
+        assert_eq!(numeric_value(*c as u32), None, "{:?}", c);
+    }
+    for c in 0..=0x10FFFF {
+        if general_category(c) == GC_DECIMAL_NUMBER {
+            let value = numeric_value(c).unwrap();
+            assert!(value >= 0.0 && value <= 9.0 && value.fract() == 0.0);
+        }
+    }
+
+    println!("  Testing script");
+    let scripts = [
+        ('A', b"Latn"),
+        ('α', b"Grek"),
+        ('я', b"Cyrl"),
+        ('中', b"Hani"),
+        ('あ', b"Hira"),
+        ('ا', b"Arab"),
+        ('1', b"Zyyy"),
+        ('\u{301}', b"Zinh"),
+        ('\u{10FFFF}', b"Zzzz"),
+    ];
+    for (c, code) in scripts.iter() {
+        assert_eq!(script(*c as u32), u32::from_be_bytes(**code), "{:?}", c);
+    }
+
+    println!("  Testing East Asian width");
+    let widths = [
+        ('\u{0}', EAW_NEUTRAL),
+        ('±', EAW_AMBIGUOUS),
+        ('ｱ', EAW_HALFWIDTH),
+        ('Ａ', EAW_FULLWIDTH),
+        ('A', EAW_NARROW),
+        ('中', EAW_WIDE),
+    ];
+    for (c, width) in widths.iter() {
+        assert_eq!(east_asian_width(*c as u32), *width, "{:?}", c);
+    }
+}
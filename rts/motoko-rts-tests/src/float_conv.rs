@@ -0,0 +1,142 @@
+//! Float formatting and parsing tests
+
+use motoko_rts::float_conv::{format_shortest, parse_float, ParseFloatError, MAX_SHORTEST_LEN};
+
+use proptest::test_runner::{Config, TestCaseError, TestCaseResult, TestRunner};
+
+pub unsafe fn test() {
+    println!("Testing float formatting and parsing ...");
+
+    println!("  Testing special values");
+    let values = [
+        0.0,
+        -0.0,
+        1.0,
+        0.1,
+        0.3,
+        1e-7,
+        1e21,
+        1e23,
+        123456.789,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+        -5e-324,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ];
+    for value in values.iter() {
+        assert_eq!(format(*value), format!("{}", value));
+    }
+    assert_eq!(format(-5e-324).len(), MAX_SHORTEST_LEN);
+
+    println!("  Testing parse errors");
+    let errors = [
+        ("", ParseFloatError::Empty),
+        ("-", ParseFloatError::MissingDigits),
+        (".", ParseFloatError::MissingDigits),
+        ("e5", ParseFloatError::MissingDigits),
+        ("1e", ParseFloatError::MissingExponent),
+        ("1e+", ParseFloatError::MissingExponent),
+        ("1.2.3", ParseFloatError::InvalidCharacter),
+        (" 1", ParseFloatError::InvalidCharacter),
+        ("1_000", ParseFloatError::InvalidCharacter),
+        ("1e5x", ParseFloatError::InvalidCharacter),
+        ("infinit", ParseFloatError::InvalidCharacter),
+    ];
+    for (s, error) in errors.iter() {
+        assert_eq!(parse_float(s.as_bytes()), Err(*error), "{:?}", s);
+        assert!(s.parse::<f64>().is_err());
+    }
+
+    println!("  Testing parse edge cases");
+    let inputs = [
+        "+.5",
+        "1.",
+        "-inf",
+        "InFiNiTy",
+        "NaN",
+        "1e400",
+        "1e-400",
+        "1e99999999999999999999",
+        "0e99999999999999999999",
+        "0.000000000000000000000000000001e30",
+        "2.4703282292062327e-324", // just below half of the smallest subnormal
+        "2.4703282292062328e-324", // just above
+        "9007199254740993",        // halfway between 2^53 and 2^53 + 2
+        "9007199254740993.000000000000000000000000000000000000000000000000000000000001",
+        "1.7976931348623158e308",
+        "1.7976931348623159e308",
+        "2.2250738585072011e-308",
+    ];
+    for s in inputs.iter() {
+        assert_parse_agrees(s).unwrap();
+    }
+
+    let mut proptest_runner = TestRunner::new(Config {
+        cases: 10_000,
+        failure_persistence: None,
+        ..Default::default()
+    });
+
+    println!("  Testing formatting against Display");
+    proptest_runner
+        .run(&proptest::num::u64::ANY, |bits| {
+            format_roundtrip(f64::from_bits(bits))
+        })
+        .unwrap();
+
+    println!("  Testing parsing against parse");
+    proptest_runner
+        .run(&proptest::num::u64::ANY, |bits| {
+            assert_parse_agrees(&format!("{:e}", f64::from_bits(bits)))
+        })
+        .unwrap();
+    proptest_runner
+        .run(
+            &proptest::string::string_regex("[0-9]{1,40}(\\.[0-9]{0,40})?(e-?[0-9]{1,3})?")
+                .unwrap(),
+            |s| assert_parse_agrees(&s),
+        )
+        .unwrap();
+    proptest_runner
+        .run(
+            &proptest::string::string_regex("[-+0-9.eE]{0,10}").unwrap(),
+            |s| assert_parse_agrees(&s),
+        )
+        .unwrap();
+}
+
+fn format(x: f64) -> String {
+    let mut buf = [0u8; MAX_SHORTEST_LEN];
+    let len = format_shortest(x, &mut buf);
+    String::from_utf8(buf[..len].to_vec()).unwrap()
+}
+
+fn format_roundtrip(x: f64) -> TestCaseResult {
+    let s = format(x);
+    if s != format!("{}", x) {
+        return Err(TestCaseError::Fail(
+            format!("format_shortest({:e}) = {}", x, s).into(),
+        ));
+    }
+
+    let y = parse_float(s.as_bytes()).unwrap();
+    if y.to_bits() != x.to_bits() && !x.is_nan() {
+        return Err(TestCaseError::Fail(format!("parse_float({})", s).into()));
+    }
+
+    Ok(())
+}
+
+/// Checks that `parse_float` agrees with Rust's `parse` on the value or on failing
+fn assert_parse_agrees(s: &str) -> TestCaseResult {
+    match (parse_float(s.as_bytes()), s.parse::<f64>()) {
+        (Ok(x), Ok(y)) if x.to_bits() == y.to_bits() || (x.is_nan() && y.is_nan()) => Ok(()),
+        (Err(_), Err(_)) => Ok(()),
+        (x, y) => Err(TestCaseError::Fail(
+            format!("parse_float({:?}) = {:?}, expected {:?}", s, x, y).into(),
+        )),
+    }
+}
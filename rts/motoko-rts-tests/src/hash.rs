@@ -0,0 +1,115 @@
+//! Text and blob hashing tests
+
+use crate::memory::TestMemory;
+
+use motoko_rts::hash::{blob_hash, text_hash, SipHasher};
+use motoko_rts::memory::Memory;
+use motoko_rts::text::{blob_of_text, text_concat, text_of_str};
+use motoko_rts::types::{Value, Words};
+
+use proptest::test_runner::{Config, TestCaseError, TestCaseResult, TestRunner};
+
+pub unsafe fn test() {
+    println!("Testing text and blob hashing ...");
+
+    println!("  Testing SipHash-2-4 reference vectors");
+    // From the reference implementation: key 00 01 .. 0f, messages 00 01 .. (n - 1)
+    let key0 = u64::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7]);
+    let key1 = u64::from_le_bytes([8, 9, 10, 11, 12, 13, 14, 15]);
+    let vectors: [(usize, u64); 3] = [
+        (0, 0x726f_db47_dd0e_0e31),
+        (1, 0x74f8_39c5_93dc_67fd),
+        (15, 0xa129_ca61_49be_45e5),
+    ];
+    for (len, expected) in vectors.iter() {
+        let message: Vec<u8> = (0..*len as u8).collect();
+        let mut hasher = SipHasher::new_with_keys(key0, key1);
+        hasher.write(&message);
+        assert_eq!(hasher.finish(), *expected);
+    }
+
+    let mut proptest_runner = TestRunner::new(Config {
+        cases: 1_000,
+        failure_persistence: None,
+        ..Default::default()
+    });
+
+    println!("  Testing incremental hashing");
+    proptest_runner
+        .run(
+            &proptest::collection::vec(proptest::collection::vec(0u8.., 0..20), 0..20),
+            |chunks| incremental_hashing(chunks),
+        )
+        .unwrap();
+
+    println!("  Testing rope shape independence");
+    proptest_runner
+        .run(
+            &proptest::collection::vec(proptest::string::string_regex(".{0, 20}").unwrap(), 1..20),
+            |strs| {
+                let mut mem = TestMemory::new(Words(1024 * 1024));
+                rope_shape_independence(&mut mem, strs)
+            },
+        )
+        .unwrap();
+}
+
+fn incremental_hashing(chunks: Vec<Vec<u8>>) -> TestCaseResult {
+    let mut hasher = SipHasher::new_with_keys(1, 2);
+    for chunk in &chunks {
+        hasher.write(chunk);
+    }
+
+    #[allow(deprecated)]
+    let mut reference = std::hash::SipHasher::new_with_keys(1, 2);
+    std::hash::Hasher::write(&mut reference, &chunks.concat());
+
+    if hasher.finish() != std::hash::Hasher::finish(&reference) {
+        return Err(TestCaseError::Fail("SipHasher::write".into()));
+    }
+
+    Ok(())
+}
+
+/// Builds a text rope from the given strings, concatenating from the left
+unsafe fn text_of_strs_left<M: Memory>(mem: &mut M, strs: &[String]) -> Value {
+    let mut text = text_of_str(mem, "");
+    for str in strs {
+        let str_text = text_of_str(mem, str);
+        text = text_concat(mem, text, str_text);
+    }
+    text
+}
+
+/// Builds a text rope from the given strings, concatenating from the right
+unsafe fn text_of_strs_right<M: Memory>(mem: &mut M, strs: &[String]) -> Value {
+    let mut text = text_of_str(mem, "");
+    for str in strs.iter().rev() {
+        let str_text = text_of_str(mem, str);
+        text = text_concat(mem, str_text, text);
+    }
+    text
+}
+
+fn rope_shape_independence<M: Memory>(mem: &mut M, strs: Vec<String>) -> TestCaseResult {
+    unsafe {
+        let left = text_of_strs_left(mem, &strs);
+        let right = text_of_strs_right(mem, &strs);
+        let flat = text_of_str(mem, &strs.concat());
+
+        let hash = text_hash(flat);
+        if text_hash(left) != hash || text_hash(right) != hash {
+            return Err(TestCaseError::Fail(
+                "text_hash depends on rope shape".into(),
+            ));
+        }
+
+        if blob_hash(blob_of_text(mem, left)) != hash {
+            return Err(TestCaseError::Fail(
+                "text_hash differs from blob_hash".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
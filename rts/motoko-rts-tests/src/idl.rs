@@ -0,0 +1,1229 @@
+mod fuzz;
+mod json;
+mod print;
+
+use crate::memory::TestMemory;
+
+use motoko_rts::buf::Buf;
+use motoko_rts::idl::{
+    find_field_checked, idl_set_arg, idl_set_decoding_quota, idl_sub_buf_init, idl_sub_buf_words,
+    idl_sub_cache_clear, idl_sub_cached_checked, idl_sub_checked, idl_sub_failure,
+    idl_sub_set_explain, parse_idl_header, parse_idl_header_checked, skip_any_checked,
+    skip_any_set_budget, skip_fields_checked, IDL_CON_alias, IDL_CON_func, IDL_CON_opt,
+    IDL_CON_record, IDL_CON_service, IDL_CON_variant, IDL_CON_vec, IDL_FUNC_ANNOT_composite_query,
+    IDL_FUNC_ANNOT_oneway, IDL_FUNC_ANNOT_query, IDL_PRIM_bool, IDL_PRIM_empty, IDL_PRIM_float32,
+    IDL_PRIM_float64, IDL_PRIM_int, IDL_PRIM_int16, IDL_PRIM_int32, IDL_PRIM_int64, IDL_PRIM_int8,
+    IDL_PRIM_nat, IDL_PRIM_nat16, IDL_PRIM_nat32, IDL_PRIM_nat64, IDL_PRIM_nat8, IDL_PRIM_null,
+    IDL_PRIM_reserved, IDL_PRIM_text, IDL_REF_principal, IdlError, IdlErrorAt, IdlPath,
+    IdlPathSegment, IdlResult, IdlSubContext, IdlSubFailure, IdlSubReason,
+};
+use motoko_rts::leb128::{leb128_decode, sleb128_decode};
+use motoko_rts::types::Words;
+
+pub unsafe fn test() {
+    println!("Testing IDL ...");
+
+    let mut mem = TestMemory::new(Words(1024 * 1024));
+
+    test_parse_idl_header(&mut mem);
+    test_skip_any(&mut mem);
+    test_find_field(&mut mem);
+    test_error_locations(&mut mem);
+    test_idl_sub(&mut mem);
+    test_idl_sub_explain(&mut mem);
+    test_idl_sub_cache(&mut mem);
+    print::test(&mut mem);
+    json::test(&mut mem);
+
+    fuzz::test();
+}
+
+unsafe fn test_parse_idl_header(mem: &mut TestMemory) {
+    println!("  Testing parse_idl_header");
+
+    // All type constructors
+    let types = vec![
+        opt(IDL_PRIM_nat),
+        vec(0),
+        record(&[(0, IDL_PRIM_text), (1, 1)]),
+        variant(&[(3, IDL_PRIM_null), (0xFFFFFFFF, 2)]),
+        func(&[IDL_PRIM_int], &[], &[1]),
+        service(&[("bar", 4), ("foo", 4)]),
+        future(&[1, 2, 3]),
+    ];
+    let msg = message(&types, &[5, IDL_REF_principal], &[]);
+    let header = parse(mem, &msg, false).unwrap();
+    assert_eq!(header.n_types, 7);
+    for i in 0..7 {
+        assert_eq!(
+            *header.typtbl.add(i),
+            msg.as_ptr().add(5 + types[..i].concat().len()) as *mut u8
+        );
+    }
+    assert_eq!({ header.data.ptr }, msg.as_ptr().add(msg.len()) as *mut u8);
+
+    // The trapping version, as called by generated code
+    let mut buf = buf_of(&msg);
+    let mut typtbl = std::ptr::null_mut();
+    let mut n_types = 0;
+    let mut main_types = std::ptr::null_mut();
+    parse_idl_header(
+        mem,
+        false,
+        &mut buf,
+        &mut typtbl,
+        &mut n_types,
+        &mut main_types,
+    );
+    assert_eq!(n_types, 7);
+    assert_eq!(main_types, header.main_types);
+
+    // Aliases are only allowed in the extended format
+    let msg = message(&[alias(IDL_PRIM_nat)], &[0], &[]);
+    assert!(parse(mem, &msg, true).is_ok());
+    assert_eq!(parse_error(mem, &msg), IdlError::IllegalTypeTable);
+
+    println!("  Testing parse_idl_header with malformed input");
+    assert_eq!(parse_error(mem, &[]), IdlError::EmptyInput);
+    assert_eq!(parse_error(mem, b"DID"), IdlError::WordReadOutOfBuffer);
+    assert_eq!(parse_error(mem, b"DIDX\x00\x00"), IdlError::MissingMagic);
+    assert_eq!(
+        parse_error(mem, b"DIDL\x80\x80\x80\x80\x10"),
+        IdlError::Leb128Overflow
+    );
+    assert_eq!(parse_error(mem, b"DIDL\x05\x00"), IdlError::TooManyTypes);
+    assert_eq!(
+        parse_error(mem, b"DIDL\x00\x01"),
+        IdlError::ByteReadOutOfBuffer
+    );
+    assert_eq!(
+        parse_error(mem, &message(&[sleb(0)], &[], &[])),
+        IdlError::IllegalTypeTable
+    );
+    assert_eq!(
+        parse_error(mem, &message(&[sleb(IDL_PRIM_nat)], &[], &[])),
+        IdlError::PrimitiveInTypeTable
+    );
+    assert_eq!(
+        parse_error(mem, &message(&[opt(1)], &[], &[])),
+        IdlError::InvalidTypeArgument
+    );
+    assert_eq!(
+        parse_error(mem, &message(&[opt(-25)], &[], &[])),
+        IdlError::InvalidTypeArgument
+    );
+    assert_eq!(
+        parse_error(mem, &message(&[opt(IDL_PRIM_nat)], &[1], &[])),
+        IdlError::InvalidTypeArgument
+    );
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(&[record(&[(1, IDL_PRIM_nat), (1, IDL_PRIM_nat)])], &[], &[])
+        ),
+        IdlError::TagsOutOfOrder
+    );
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(
+                &[variant(&[(0xFFFFFFFF, IDL_PRIM_nat), (0, IDL_PRIM_nat)])],
+                &[],
+                &[]
+            )
+        ),
+        IdlError::TagsOutOfOrder
+    );
+    // All annotations, in any order and repeated
+    let annotations = [
+        IDL_FUNC_ANNOT_composite_query,
+        IDL_FUNC_ANNOT_oneway,
+        IDL_FUNC_ANNOT_query,
+        IDL_FUNC_ANNOT_query,
+    ];
+    let msg = message(&[func(&[], &[], &annotations)], &[], &[]);
+    assert!(parse(mem, &msg, false).is_ok());
+    for a in [0, 4, 0xFF] {
+        assert_eq!(
+            parse_error(mem, &message(&[func(&[], &[], &[a])], &[], &[])),
+            IdlError::InvalidFuncAnnotation
+        );
+    }
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(
+                &[func(&[], &[], &[]), service(&[("foo", 0), ("bar", 0)])],
+                &[],
+                &[]
+            )
+        ),
+        IdlError::MethodNamesOutOfOrder
+    );
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(
+                &[func(&[], &[], &[]), service(&[("foo", 0), ("foo", 0)])],
+                &[],
+                &[]
+            )
+        ),
+        IdlError::MethodNamesOutOfOrder
+    );
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(&[service(&[("foo", IDL_PRIM_nat)])], &[], &[])
+        ),
+        IdlError::MethodNotConstructor
+    );
+    assert_eq!(
+        parse_error(
+            mem,
+            &message(&[opt(IDL_PRIM_nat), service(&[("foo", 0)])], &[], &[])
+        ),
+        IdlError::MethodNotFunc
+    );
+    let mut bad_name = service(&[("foo", 0)]);
+    bad_name[3] = 0xFF;
+    assert_eq!(
+        parse_error(mem, &message(&[func(&[], &[], &[]), bad_name], &[], &[])),
+        IdlError::NotUtf8
+    );
+    let mut truncated = message(&[future(&[1, 2, 3])], &[], &[]);
+    truncated.truncate(truncated.len() - 2);
+    assert_eq!(parse_error(mem, &truncated), IdlError::AdvanceOutOfBuffer);
+
+    println!("  Testing the decoding quota of parse_idl_header");
+    let types: Vec<Vec<u8>> = (0..100).map(|i| opt((i + 1) % 100)).collect();
+    let msg = message(&types, &[0], &[]);
+    idl_set_decoding_quota(201); // two units per type, one for the argument
+    assert!(parse(mem, &msg, false).is_ok());
+    idl_set_decoding_quota(200);
+    assert_eq!(parse_error(mem, &msg), IdlError::QuotaExceeded);
+    assert!(parse(mem, &msg, true).is_ok()); // the extended format has no quota
+    idl_set_decoding_quota(1 << 22); // the default
+}
+
+unsafe fn test_skip_any(mem: &mut TestMemory) {
+    println!("  Testing skip_any with primitive types");
+    assert_eq!(skip(mem, &[], IDL_PRIM_null, &[]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_reserved, &[]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_bool, &[1]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_nat, &[0xAC, 0x02]), Ok(()));
+    assert_eq!(
+        skip(
+            mem,
+            &[],
+            IDL_PRIM_nat,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+        ),
+        Ok(())
+    );
+    assert_eq!(skip(mem, &[], IDL_PRIM_int, &[0xFF, 0x7E]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_nat8, &[1]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_int8, &[1]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_nat16, &[1, 2]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_int16, &[1, 2]), Ok(()));
+    for t in [IDL_PRIM_nat32, IDL_PRIM_int32, IDL_PRIM_float32] {
+        assert_eq!(skip(mem, &[], t, &[1, 2, 3, 4]), Ok(()));
+    }
+    for t in [IDL_PRIM_nat64, IDL_PRIM_int64, IDL_PRIM_float64] {
+        assert_eq!(skip(mem, &[], t, &[1, 2, 3, 4, 5, 6, 7, 8]), Ok(()));
+    }
+    assert_eq!(skip(mem, &[], IDL_PRIM_text, b"\x05hello"), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_PRIM_text, b"\x02\xC3\xA9"), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_REF_principal, &[1, 3, 1, 2, 3]), Ok(()));
+    assert_eq!(skip(mem, &[], IDL_REF_principal, &[0]), Ok(()));
+
+    println!("  Testing skip_any with primitive types and malformed input");
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_bool, &[2]),
+        Err(IdlError::InvalidByteTag)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_bool, &[]),
+        Err(IdlError::ByteReadOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_nat, &[0xFF, 0xFF]),
+        Err(IdlError::ByteReadOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_nat16, &[1]),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_float64, &[1, 2, 3, 4]),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_text, b"\x06hello"),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_text, b"\x02\xC3\x28"),
+        Err(IdlError::NotUtf8)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_text, b"\x80\x80\x80\x80\x80\x00"),
+        Err(IdlError::Leb128Overflow)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_PRIM_empty, &[]),
+        Err(IdlError::SkipEmpty)
+    );
+    assert_eq!(
+        skip(mem, &[], IDL_REF_principal, &[1, 4, 1, 2, 3]),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+    // Constructors are not primitive types, header parsing rejects such type arguments
+    let mut buf = buf_of(&[]);
+    assert_eq!(
+        skip_any_checked(mem, &mut buf, std::ptr::null_mut(), IDL_CON_opt).map_err(|err| err.error),
+        Err(IdlError::UnknownPrim)
+    );
+
+    println!("  Testing skip_any with type constructors");
+    let opt_nat = [opt(IDL_PRIM_nat)];
+    assert_eq!(skip(mem, &opt_nat, 0, &[0]), Ok(()));
+    assert_eq!(skip(mem, &opt_nat, 0, &[1, 42]), Ok(()));
+    assert_eq!(skip(mem, &opt_nat, 0, &[2]), Err(IdlError::InvalidByteTag));
+    assert_eq!(
+        skip(mem, &opt_nat, 0, &[1]),
+        Err(IdlError::ByteReadOutOfBuffer)
+    );
+
+    let vec_text = [vec(IDL_PRIM_text)];
+    assert_eq!(skip(mem, &vec_text, 0, &[0]), Ok(()));
+    assert_eq!(skip(mem, &vec_text, 0, b"\x02\x01a\x02bc"), Ok(()));
+    assert_eq!(
+        skip(mem, &vec_text, 0, b"\x03\x01a\x02bc"),
+        Err(IdlError::ByteReadOutOfBuffer)
+    );
+
+    let rec = [record(&[
+        (0, IDL_PRIM_nat8),
+        (1, IDL_PRIM_text),
+        (7, IDL_PRIM_bool),
+    ])];
+    assert_eq!(skip(mem, &rec, 0, b"\x01\x02hi\x01"), Ok(()));
+    assert_eq!(
+        skip(mem, &rec, 0, b"\x01\x02hi\x05"),
+        Err(IdlError::InvalidByteTag)
+    );
+    assert_eq!(skip(mem, &[record(&[])], 0, &[]), Ok(()));
+
+    let var = [variant(&[(0, IDL_PRIM_null), (5, IDL_PRIM_nat16)])];
+    assert_eq!(skip(mem, &var, 0, &[0]), Ok(()));
+    assert_eq!(skip(mem, &var, 0, &[1, 1, 2]), Ok(()));
+    assert_eq!(
+        skip(mem, &var, 0, &[1, 1]),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+    assert_eq!(
+        skip(mem, &var, 0, &[2]),
+        Err(IdlError::VariantTagTooLarge {
+            tag: 2,
+            t: 0,
+            cases: 2
+        })
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            IdlError::VariantTagTooLarge {
+                tag: 2,
+                t: 0,
+                cases: 2
+            }
+        ),
+        "skip_any: variant tag 2 too large for type 0 with 2 cases"
+    );
+
+    let fun = [func(&[IDL_PRIM_nat], &[], &[1])];
+    assert_eq!(skip(mem, &fun, 0, b"\x01\x01\x02ab\x03foo"), Ok(()));
+    assert_eq!(skip(mem, &fun, 0, &[0]), Err(IdlError::SkipReferences));
+    assert_eq!(skip(mem, &fun, 0, &[1, 0]), Err(IdlError::SkipReferences));
+
+    let serv = [func(&[], &[], &[]), service(&[("foo", 0)])];
+    assert_eq!(skip(mem, &serv, 1, &[1, 2, 1, 2]), Ok(()));
+    assert_eq!(skip(mem, &serv, 1, &[0]), Err(IdlError::SkipReferences));
+
+    let fut = [future(&[9, 9])];
+    assert_eq!(skip(mem, &fut, 0, &[3, 0, 1, 2, 3]), Ok(()));
+    assert_eq!(
+        skip(mem, &fut, 0, &[3, 1, 1, 2, 3]),
+        Err(IdlError::FutureReferences)
+    );
+    assert_eq!(
+        skip(mem, &fut, 0, &[4, 0, 1, 2, 3]),
+        Err(IdlError::AdvanceOutOfBuffer)
+    );
+
+    // Mutable stable values, in the extended format
+    let msg = message(
+        &[alias(IDL_PRIM_nat16)],
+        &[0, 0],
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 1, 1, 2, 3, 4],
+    );
+    let mut header = parse(mem, &msg, true).unwrap();
+    assert_eq!(
+        skip_any_checked(mem, &mut header.data, header.typtbl, 0),
+        Ok(())
+    );
+    assert_eq!(
+        skip_any_checked(mem, &mut header.data, header.typtbl, 0),
+        Ok(())
+    );
+    assert_eq!({ header.data.ptr }, { header.data.end });
+
+    println!("  Testing skip_any with recursive and nested types");
+    // type list = opt record { 0 : nat8; 1 : list }
+    let list = [opt(1), record(&[(0, IDL_PRIM_nat8), (1, 0)])];
+    assert_eq!(skip(mem, &list, 0, &[1, 1, 1, 2, 1, 3, 0]), Ok(()));
+    assert_eq!(
+        skip(mem, &list, 0, &[1, 1, 1, 2, 1, 3]),
+        Err(IdlError::ByteReadOutOfBuffer)
+    );
+
+    // type t = record { 0 : t } has no values
+    assert_eq!(
+        skip(mem, &[record(&[(0, 0)])], 0, &[1, 2, 3]),
+        Err(IdlError::RecordCycle { t: 0 })
+    );
+
+    // type t = vec t, nested deeply
+    let depth = 10_000;
+    let mut data = vec![1; depth];
+    data.push(0);
+    assert_eq!(skip(mem, &[vec(0)], 0, &data), Ok(()));
+    skip_any_set_budget(1024);
+    assert!(matches!(
+        skip(mem, &[vec(0)], 0, &data),
+        Err(IdlError::NestedTooDeeply { .. })
+    ));
+    skip_any_set_budget(1 << 20);
+
+    println!("  Testing the decoding quota of skip_any");
+    // `vec null` with a huge count takes no space, but is charged for every element
+    assert_eq!(skip(mem, &[vec(IDL_PRIM_null)], 0, &leb(1 << 20)), Ok(()));
+    assert_eq!(
+        skip(mem, &[vec(IDL_PRIM_null)], 0, &leb(1 << 30)),
+        Err(IdlError::QuotaExceeded)
+    );
+
+    // A message of the maximal size (2MiB) with nested values is within the default quota
+    let types = [vec(1), record(&[(0, 2)]), record(&[(0, IDL_PRIM_nat8)])];
+    let count = 2 * 1024 * 1024 - 32;
+    let data = [leb(count), vec![7; count as usize]].concat();
+    assert!(message(&types, &[0], &data).len() <= 2 * 1024 * 1024);
+    assert_eq!(skip(mem, &types, 0, &data), Ok(()));
+
+    // Values nested more deeply than they take space are still charged
+    let chain: Vec<Vec<u8>> = (0..100)
+        .map(|i| {
+            if i == 0 {
+                vec(1)
+            } else {
+                record(&[(0, if i == 99 { IDL_PRIM_nat8 } else { i + 1 })])
+            }
+        })
+        .collect();
+    let count = 1 << 16;
+    let data = [leb(count), vec![7; count as usize]].concat();
+    assert_eq!(skip(mem, &chain, 0, &data), Err(IdlError::QuotaExceeded));
+}
+
+unsafe fn test_find_field(mem: &mut TestMemory) {
+    println!("  Testing find_field and skip_fields");
+
+    let types = [record(&[
+        (1, IDL_PRIM_nat8),
+        (3, IDL_PRIM_text),
+        (5, IDL_PRIM_bool),
+        (6, IDL_PRIM_nat16),
+    ])];
+    let msg = message(&types, &[0], b"\x07\x02hi\x01\x01\x02");
+    let mut header = parse(mem, &msg, false).unwrap();
+
+    // The fields of the record type
+    let mut tb = Buf {
+        ptr: *header.typtbl,
+        end: header.data.end,
+    };
+    assert_eq!(sleb128_decode(&mut tb), IDL_CON_record);
+    let mut n = leb128_decode(&mut tb) as u8;
+    assert_eq!(n, 4);
+
+    let data = &mut header.data;
+    assert_eq!(
+        find_field_checked(mem, &mut tb, data, header.typtbl, 3, &mut n),
+        Ok(1)
+    );
+    assert_eq!(n, 2);
+    assert_eq!({ data.ptr }, msg.as_ptr().add(msg.len() - 6) as *mut u8); // at the text
+    assert_eq!(sleb128_decode(&mut tb), IDL_PRIM_text);
+    assert_eq!(
+        skip_any_checked(mem, data, header.typtbl, IDL_PRIM_text),
+        Ok(())
+    );
+
+    // Missing field, before field 5
+    assert_eq!(
+        find_field_checked(mem, &mut tb, data, header.typtbl, 4, &mut n),
+        Ok(0)
+    );
+    assert_eq!(n, 2);
+    assert_eq!(leb128_decode(&mut tb), 5);
+    assert_eq!(sleb128_decode(&mut tb), IDL_PRIM_bool);
+    assert_eq!(
+        skip_any_checked(mem, data, header.typtbl, IDL_PRIM_bool),
+        Ok(())
+    );
+    n -= 1;
+
+    // Skip the remaining fields
+    assert_eq!(
+        skip_fields_checked(mem, &mut tb, data, header.typtbl, &mut n),
+        Ok(())
+    );
+    assert_eq!(n, 0);
+    assert_eq!({ data.ptr }, { data.end });
+
+    // Malformed data of a skipped field
+    let msg = message(&types, &[0], b"\x07\x03hi");
+    let mut header = parse(mem, &msg, false).unwrap();
+    let mut tb = Buf {
+        ptr: *header.typtbl,
+        end: header.data.end,
+    };
+    sleb128_decode(&mut tb);
+    let mut n = leb128_decode(&mut tb) as u8;
+    assert_eq!(
+        find_field_checked(mem, &mut tb, &mut header.data, header.typtbl, 5, &mut n),
+        Err(IdlErrorAt {
+            error: IdlError::AdvanceOutOfBuffer,
+            offset: 19, // after the length of the text
+            type_index: None,
+            path: path(&[IdlPathSegment::Field(3)]),
+        })
+    );
+}
+
+unsafe fn test_error_locations(mem: &mut TestMemory) {
+    println!("  Testing error locations");
+
+    // In the type table
+    let msg = message(&[opt(IDL_PRIM_nat), vec(7)], &[0], &[]);
+    assert_eq!(
+        parse(mem, &msg, false).err(),
+        Some(IdlErrorAt {
+            error: IdlError::InvalidTypeArgument,
+            offset: 9, // after the type argument
+            type_index: Some(1),
+            path: IdlPath::EMPTY,
+        })
+    );
+
+    // In the check of service methods, which happens after the table was read
+    let msg = message(&[opt(IDL_PRIM_nat), service(&[("foo", 0)])], &[1], &[]);
+    assert_eq!(
+        parse(mem, &msg, false).err(),
+        Some(IdlErrorAt {
+            error: IdlError::MethodNotFunc,
+            offset: 14, // after the type of the method
+            type_index: Some(1),
+            path: IdlPath::EMPTY,
+        })
+    );
+
+    // In the argument types
+    let msg = message(&[opt(IDL_PRIM_nat)], &[0, 5], &[]);
+    assert_eq!(
+        parse(mem, &msg, false).err(),
+        Some(IdlErrorAt {
+            error: IdlError::InvalidTypeArgument,
+            offset: 10,
+            type_index: None,
+            path: path(&[IdlPathSegment::Arg(1)]),
+        })
+    );
+
+    // In a value: vec record { 0x1234 : vec bool }
+    let types = [vec(1), record(&[(0x1234, 2)]), vec(IDL_PRIM_bool)];
+    let msg = message(&types, &[0], &[2, 1, 1, 2, 1, 5]);
+    let mut header = parse(mem, &msg, false).unwrap();
+    idl_set_arg(1);
+    let err = skip_any_checked(mem, &mut header.data, header.typtbl, 0).unwrap_err();
+    assert_eq!(
+        err,
+        IdlErrorAt {
+            error: IdlError::InvalidByteTag,
+            offset: 22, // after the tag
+            type_index: Some(2),
+            path: path(&[
+                IdlPathSegment::Arg(1),
+                IdlPathSegment::Element(1),
+                IdlPathSegment::Field(0x1234),
+                IdlPathSegment::Element(1),
+            ]),
+        }
+    );
+    assert_eq!(
+        format!("{}", err),
+        "skip_any: byte tag not 0 or 1 (byte 22, type 2, path arg 1 / vec[1] / field 0x1234 / vec[1])"
+    );
+
+    // Parsing the next header forgets the argument
+    let msg = message(&[vec(IDL_PRIM_bool)], &[0], &[1, 2]);
+    let mut header = parse(mem, &msg, false).unwrap();
+    assert_eq!(
+        skip_any_checked(mem, &mut header.data, header.typtbl, 0)
+            .unwrap_err()
+            .path,
+        path(&[IdlPathSegment::Element(0)])
+    );
+
+    // Deep paths keep their ends
+    let msg = message(&[vec(1), record(&[(7, 0)])], &[0], &[1; 6]);
+    let mut header = parse(mem, &msg, false).unwrap();
+    let err = skip_any_checked(mem, &mut header.data, header.typtbl, 0).unwrap_err();
+    assert_eq!(err.error, IdlError::ByteReadOutOfBuffer);
+    assert_eq!(err.path.depth(), 12);
+    assert_eq!(
+        format!("{}", err.path),
+        "vec[0] / field 0x7 / vec[0] / field 0x7 / (4 more) / \
+         vec[0] / field 0x7 / vec[0] / field 0x7"
+    );
+
+    // The longest message still fits into the trap message buffer
+    let mut long_path = IdlPath::EMPTY;
+    for _ in 0..100 {
+        long_path.push(IdlPathSegment::Field(u32::MAX));
+    }
+    let err = IdlErrorAt {
+        error: IdlError::EmptyInput,
+        offset: u32::MAX,
+        type_index: Some(u32::MAX),
+        path: long_path,
+    };
+    assert!(format!("{}", err).len() < 480);
+}
+
+unsafe fn test_idl_sub(mem: &mut TestMemory) {
+    println!("  Testing idl_sub with primitive types");
+    let prims = [
+        IDL_PRIM_null,
+        IDL_PRIM_bool,
+        IDL_PRIM_nat,
+        IDL_PRIM_int,
+        IDL_PRIM_nat8,
+        IDL_PRIM_nat16,
+        IDL_PRIM_nat32,
+        IDL_PRIM_nat64,
+        IDL_PRIM_int8,
+        IDL_PRIM_int16,
+        IDL_PRIM_int32,
+        IDL_PRIM_int64,
+        IDL_PRIM_float32,
+        IDL_PRIM_float64,
+        IDL_PRIM_text,
+        IDL_PRIM_reserved,
+        IDL_PRIM_empty,
+        IDL_REF_principal,
+    ];
+    for &t1 in prims.iter() {
+        for &t2 in prims.iter() {
+            let expected = t1 == t2
+                || t2 == IDL_PRIM_reserved
+                || t1 == IDL_PRIM_empty
+                || (t1, t2) == (IDL_PRIM_nat, IDL_PRIM_int);
+            assert_eq!(sub(mem, &[], t1, &[], t2), Ok(expected));
+        }
+    }
+
+    println!("  Testing idl_sub with type constructors");
+    // Everything is a subtype of an option (for decoding, it becomes `null`)
+    assert_eq!(
+        sub(mem, &[], IDL_PRIM_nat, &[opt(IDL_PRIM_text)], 0),
+        Ok(true)
+    );
+    assert_eq!(
+        sub(mem, &[opt(IDL_PRIM_text)], 0, &[], IDL_PRIM_text),
+        Ok(false)
+    );
+
+    let vec_nat = [vec(IDL_PRIM_nat)];
+    let vec_int = [vec(IDL_PRIM_int)];
+    assert_eq!(sub(mem, &vec_nat, 0, &vec_int, 0), Ok(true));
+    assert_eq!(sub(mem, &vec_int, 0, &vec_nat, 0), Ok(false));
+
+    let rec1 = [record(&[(1, IDL_PRIM_nat), (2, IDL_PRIM_text)])];
+    let rec2 = [record(&[(1, IDL_PRIM_int)])];
+    let rec3 = [
+        record(&[(1, IDL_PRIM_nat), (2, IDL_PRIM_text), (3, 1)]),
+        opt(IDL_PRIM_nat),
+    ];
+    let rec4 = [record(&[(1, IDL_PRIM_nat), (3, IDL_PRIM_text)])];
+    assert_eq!(sub(mem, &rec1, 0, &rec2, 0), Ok(true));
+    assert_eq!(sub(mem, &rec2, 0, &rec1, 0), Ok(false));
+    assert_eq!(sub(mem, &rec1, 0, &rec3, 0), Ok(true)); // missing optional field
+    assert_eq!(sub(mem, &rec1, 0, &rec4, 0), Ok(false)); // missing field, after the last one
+                                                         // The last field of t1 comes after an optional field of t2
+    let rec5 = [record(&[(5, IDL_PRIM_nat)])];
+    let rec6 = [record(&[(2, IDL_PRIM_nat), (5, IDL_PRIM_nat)])];
+    let rec7 = [record(&[(3, 1), (5, IDL_PRIM_nat)]), opt(IDL_PRIM_nat)];
+    assert_eq!(sub(mem, &rec5, 0, &rec7, 0), Ok(true));
+    assert_eq!(sub(mem, &rec6, 0, &rec7, 0), Ok(true));
+    assert_eq!(sub(mem, &rec7, 0, &rec5, 0), Ok(true));
+    assert_eq!(sub(mem, &rec5, 0, &rec6, 0), Ok(false));
+
+    let var1 = [variant(&[(1, IDL_PRIM_nat)])];
+    let var2 = [variant(&[(1, IDL_PRIM_int), (2, IDL_PRIM_text)])];
+    assert_eq!(sub(mem, &var1, 0, &var2, 0), Ok(true));
+    assert_eq!(sub(mem, &var2, 0, &var1, 0), Ok(false));
+
+    let func1 = [func(&[IDL_PRIM_int], &[IDL_PRIM_nat], &[])];
+    let func2 = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[])];
+    let query = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[1])];
+    let func3 = [
+        func(&[IDL_PRIM_nat, 1], &[IDL_PRIM_int, IDL_PRIM_reserved], &[]),
+        opt(IDL_PRIM_text),
+    ];
+    assert_eq!(sub(mem, &func1, 0, &func2, 0), Ok(true));
+    assert_eq!(sub(mem, &func2, 0, &func1, 0), Ok(false));
+    assert_eq!(sub(mem, &func2, 0, &query, 0), Ok(false));
+    assert_eq!(sub(mem, &query, 0, &query, 0), Ok(true));
+    let composite_query = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[3])];
+    assert_eq!(sub(mem, &composite_query, 0, &composite_query, 0), Ok(true));
+    assert_eq!(sub(mem, &query, 0, &composite_query, 0), Ok(false));
+    assert_eq!(sub(mem, &composite_query, 0, &query, 0), Ok(false));
+    assert_eq!(sub(mem, &func2, 0, &composite_query, 0), Ok(false));
+    // Annotations are a set
+    let query_twice = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[1, 1])];
+    assert_eq!(sub(mem, &query, 0, &query_twice, 0), Ok(true));
+    let both1 = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[1, 3])];
+    let both2 = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[3, 1])];
+    assert_eq!(sub(mem, &both1, 0, &both2, 0), Ok(true));
+    assert_eq!(sub(mem, &both1, 0, &query, 0), Ok(false));
+    assert_eq!(sub(mem, &func2, 0, &func3, 0), Ok(true)); // optional extra args and results
+    assert_eq!(sub(mem, &func3, 0, &func2, 0), Ok(true));
+
+    let serv1 = [func(&[], &[], &[]), service(&[("bar", 0), ("foo", 0)])];
+    let serv2 = [func(&[], &[], &[]), service(&[("foo", 0)])];
+    let serv3 = [func(&[], &[], &[1]), service(&[("foo", 0)])];
+    assert_eq!(sub(mem, &serv1, 1, &serv2, 1), Ok(true));
+    assert_eq!(sub(mem, &serv2, 1, &serv1, 1), Ok(false));
+    assert_eq!(sub(mem, &serv1, 1, &serv3, 1), Ok(false));
+
+    assert_eq!(sub(mem, &vec_nat, 0, &rec1, 0), Ok(false));
+
+    println!("  Testing idl_sub with recursive types");
+    // type list = opt record { 0 : nat; 1 : list }
+    let list_nat = [opt(1), record(&[(0, IDL_PRIM_nat), (1, 0)])];
+    let list_int = [opt(1), record(&[(0, IDL_PRIM_int), (1, 0)])];
+    // type tree = variant { 0 : nat; 1 : record { 0 : tree; 1 : tree } }
+    let tree_nat = [
+        variant(&[(0, IDL_PRIM_nat), (1, 1)]),
+        record(&[(0, 0), (1, 0)]),
+    ];
+    let tree_int = [
+        variant(&[(0, IDL_PRIM_int), (1, 1)]),
+        record(&[(0, 0), (1, 0)]),
+    ];
+    assert_eq!(sub(mem, &list_nat, 1, &list_int, 1), Ok(true));
+    assert_eq!(sub(mem, &list_int, 1, &list_nat, 1), Ok(false));
+    assert_eq!(sub(mem, &tree_nat, 0, &tree_int, 0), Ok(true));
+    assert_eq!(sub(mem, &tree_int, 0, &tree_nat, 0), Ok(false));
+
+    println!("  Testing idl_sub with malformed input");
+    let msg = message(&[alias(IDL_PRIM_nat)], &[], &[]);
+    let header = parse(mem, &msg, true).unwrap();
+    assert_eq!(
+        sub_parsed(&header, 0, &header, 0),
+        Err(IdlError::UnexpectedAlias)
+    );
+
+    println!("  Testing the decoding quota of idl_sub");
+    // Long chains of records
+    let chain1: Vec<Vec<u8>> = (0..100)
+        .map(|i| record(&[(0, if i == 99 { IDL_PRIM_nat } else { i + 1 })]))
+        .collect();
+    let chain2: Vec<Vec<u8>> = (0..100)
+        .map(|i| record(&[(0, if i == 99 { IDL_PRIM_int } else { i + 1 })]))
+        .collect();
+    let msg1 = message(&chain1, &[], &[]);
+    let msg2 = message(&chain2, &[], &[]);
+    idl_set_decoding_quota(500);
+    let header1 = parse(mem, &msg1, false).unwrap();
+    let header2 = parse(mem, &msg2, false).unwrap();
+    assert_eq!(sub_parsed(&header1, 0, &header2, 0), Ok(true));
+    idl_set_decoding_quota(200);
+    let header1 = parse(mem, &msg1, false).unwrap();
+    assert_eq!(
+        sub_parsed(&header1, 0, &header2, 0),
+        Err(IdlError::QuotaExceeded)
+    );
+    idl_set_decoding_quota(1 << 22);
+}
+
+unsafe fn test_idl_sub_explain(mem: &mut TestMemory) {
+    println!("  Testing explanations of idl_sub");
+
+    idl_set_decoding_quota(1 << 22);
+    idl_sub_set_explain(true);
+
+    let vec_nat = [vec(IDL_PRIM_nat)];
+    let vec_int = [vec(IDL_PRIM_int)];
+    assert_eq!(sub(mem, &vec_int, 0, &vec_nat, 0), Ok(false));
+    assert_eq!(
+        idl_sub_failure(),
+        Some(IdlSubFailure {
+            context: IdlSubContext::Element,
+            t1: IDL_PRIM_int,
+            con1: IDL_PRIM_int,
+            t2: IDL_PRIM_nat,
+            con2: IDL_PRIM_nat,
+            reason: IdlSubReason::Incompatible,
+        })
+    );
+    assert_eq!(
+        explain(mem, &vec_int, 0, &vec_nat, 0),
+        "vec element : int is not a subtype of nat"
+    );
+    // Succeeding checks forget the failure
+    assert_eq!(sub(mem, &vec_nat, 0, &vec_int, 0), Ok(true));
+    assert_eq!(idl_sub_failure(), None);
+    assert_eq!(
+        explain(mem, &vec_nat, 0, &[], IDL_PRIM_text),
+        "vec (type 0) is not a subtype of text"
+    );
+
+    let rec1 = [record(&[(1, IDL_PRIM_nat), (2, IDL_PRIM_text)])];
+    let rec2 = [record(&[(1, IDL_PRIM_int)])];
+    let rec3 = [record(&[(1, IDL_PRIM_nat), (3, IDL_PRIM_text)])];
+    assert_eq!(
+        explain(mem, &rec2, 0, &rec1, 0),
+        "field 0x1 : int is not a subtype of nat"
+    );
+    assert_eq!(
+        explain(mem, &rec1, 0, &rec3, 0),
+        "record (type 0) is not a subtype of record (type 0): field 0x3 is missing and not optional"
+    );
+
+    let var1 = [variant(&[(1, IDL_PRIM_nat)])];
+    let var2 = [variant(&[(1, IDL_PRIM_nat), (2, IDL_PRIM_text)])];
+    let var3 = [variant(&[(1, IDL_PRIM_int)])];
+    assert_eq!(
+        explain(mem, &var2, 0, &var1, 0),
+        "variant (type 0) is not a subtype of variant (type 0): case 0x2 is missing"
+    );
+    assert_eq!(
+        explain(mem, &var3, 0, &var1, 0),
+        "case 0x1 : int is not a subtype of nat"
+    );
+
+    let func1 = [func(&[IDL_PRIM_int], &[IDL_PRIM_nat], &[])];
+    let func2 = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[])];
+    let query = [func(&[IDL_PRIM_nat], &[IDL_PRIM_int], &[1])];
+    let more_args = [func(&[IDL_PRIM_int, IDL_PRIM_nat], &[IDL_PRIM_nat], &[])];
+    let more_results = [func(&[IDL_PRIM_int], &[IDL_PRIM_nat, IDL_PRIM_nat], &[])];
+    // Arguments are contravariant
+    assert_eq!(
+        explain(mem, &func2, 0, &func1, 0),
+        "argument 0 : int is not a subtype of nat"
+    );
+    assert_eq!(
+        explain(mem, &more_args, 0, &func1, 0),
+        "func (type 0) is not a subtype of func (type 0): argument 1 is missing and not optional"
+    );
+    assert_eq!(
+        explain(mem, &func1, 0, &more_results, 0),
+        "func (type 0) is not a subtype of func (type 0): result 1 is missing and not optional"
+    );
+    assert_eq!(
+        explain(mem, &func2, 0, &query, 0),
+        "func (type 0) is not a subtype of func (type 0): the annotations differ"
+    );
+
+    let serv1 = [func(&[], &[], &[]), service(&[("bar", 0), ("foo", 0)])];
+    let serv2 = [func(&[], &[], &[]), service(&[("foo", 0)])];
+    let serv3 = [func(&[], &[], &[1]), service(&[("foo", 0)])];
+    let serv4 = [
+        func(&[], &[], &[]),
+        service(&[("a_very_long_method_name_indeed", 0)]),
+    ];
+    assert_eq!(
+        explain(mem, &serv2, 1, &serv1, 1),
+        "service (type 1) is not a subtype of service (type 1): method `bar` is missing"
+    );
+    assert_eq!(
+        explain(mem, &serv1, 1, &serv3, 1),
+        "method `foo` : func (type 0) is not a subtype of func (type 0): the annotations differ"
+    );
+    assert_eq!(
+        explain(mem, &serv1, 1, &serv4, 1),
+        "service (type 1) is not a subtype of service (type 1): method `a_very_long_method_name_...` is missing"
+    );
+
+    // type tree = variant { 0 : nat; 1 : record { 0 : tree; 1 : tree } }
+    let tree_nat = [
+        variant(&[(0, IDL_PRIM_nat), (1, 1)]),
+        record(&[(0, 0), (1, 0)]),
+    ];
+    let tree_int = [
+        variant(&[(0, IDL_PRIM_int), (1, 1)]),
+        record(&[(0, 0), (1, 0)]),
+    ];
+    assert_eq!(
+        explain(mem, &tree_int, 1, &tree_nat, 1),
+        "case 0x0 : int is not a subtype of nat"
+    );
+
+    // With the memo of an earlier check, as in generated code
+    let msg1 = message(&vec_int, &[], &[]);
+    let msg2 = message(&vec_nat, &[], &[]);
+    let header1 = parse(mem, &msg1, false).unwrap();
+    let header2 = parse(mem, &msg2, false).unwrap();
+    let mut rel_buf = vec![0u32; idl_sub_buf_words(1, 1) as usize];
+    idl_sub_buf_init(rel_buf.as_mut_ptr(), 1, 1);
+    let check = |rel_buf: &mut Vec<u32>| {
+        idl_sub_checked(
+            rel_buf.as_mut_ptr(),
+            header1.typtbl,
+            header2.typtbl,
+            header1.data.end,
+            header2.data.end,
+            1,
+            1,
+            0,
+            0,
+        )
+    };
+    assert_eq!(check(&mut rel_buf), Ok(false));
+    assert_eq!(idl_sub_failure().unwrap().context, IdlSubContext::Element);
+    assert_eq!(check(&mut rel_buf), Ok(false));
+    assert_eq!(
+        format!("{}", idl_sub_failure().unwrap()),
+        "vec (type 0) is not a subtype of vec (type 0) (as checked before)"
+    );
+
+    // Nothing is recorded without explaining
+    idl_sub_set_explain(false);
+    assert_eq!(sub(mem, &vec_int, 0, &vec_nat, 0), Ok(false));
+    assert_eq!(idl_sub_failure(), None);
+}
+
+unsafe fn test_idl_sub_cache(mem: &mut TestMemory) {
+    println!("  Testing the cache of idl_sub");
+
+    // Long chains of records, as in the quota tests of idl_sub: with a quota of 200, parsing them
+    // leaves nothing for checking them, unless the verdict is cached
+    let chain = |n: i32, last: i32| -> Vec<Vec<u8>> {
+        (0..n)
+            .map(|i| record(&[(0, if i == n - 1 { last } else { i + 1 })]))
+            .collect()
+    };
+    let msg_nat = message(&chain(100, IDL_PRIM_nat), &[], &[]);
+    let msg_int = message(&chain(100, IDL_PRIM_int), &[], &[]);
+    let msg_text = message(&chain(100, IDL_PRIM_text), &[], &[]);
+    let expected = parse(mem, &msg_int, false).unwrap();
+    let check = |mem: &mut TestMemory, msg: &[u8], quota: u64| {
+        idl_set_decoding_quota(quota);
+        let header = parse(mem, msg, false).unwrap();
+        sub_cached(&header, 0, &expected, 0)
+    };
+
+    idl_sub_cache_clear();
+    // Equal type tables at other addresses hit the cache, with both verdicts. Each table is
+    // checked again right away, as the entries of the two tables may evict each other.
+    assert_eq!(check(mem, &msg_nat, 500), Ok(true));
+    assert_eq!(check(mem, &msg_nat.clone(), 200), Ok(true));
+    assert_eq!(check(mem, &msg_text, 500), Ok(false));
+    assert_eq!(check(mem, &msg_text.clone(), 200), Ok(false));
+    // Other type tables and types do not
+    let msg_bool = message(&chain(100, IDL_PRIM_bool), &[], &[]);
+    assert_eq!(check(mem, &msg_bool, 200), Err(IdlError::QuotaExceeded));
+    let header = parse(mem, &msg_nat, false).unwrap();
+    assert_eq!(
+        sub_cached(&header, 1, &expected, 1),
+        Err(IdlError::QuotaExceeded)
+    );
+    // Nor the expected type tables at other addresses
+    let expected2 = parse(mem, &msg_int.clone(), false).unwrap();
+    idl_set_decoding_quota(200);
+    let header = parse(mem, &msg_nat, false).unwrap();
+    assert_eq!(
+        sub_cached(&header, 0, &expected2, 0),
+        Err(IdlError::QuotaExceeded)
+    );
+
+    // Explaining bypasses the cache
+    idl_sub_set_explain(true);
+    assert_eq!(check(mem, &msg_nat, 200), Err(IdlError::QuotaExceeded));
+    idl_sub_set_explain(false);
+
+    // Long type tables are not cached
+    let msg_long = message(&chain(300, IDL_PRIM_nat), &[], &[]);
+    assert!(msg_long.len() > 1024);
+    assert_eq!(check(mem, &msg_long, 1200), Ok(false));
+    assert_eq!(check(mem, &msg_long, 600), Err(IdlError::QuotaExceeded));
+
+    idl_sub_cache_clear();
+    assert_eq!(check(mem, &msg_nat, 200), Err(IdlError::QuotaExceeded));
+    idl_set_decoding_quota(1 << 22);
+}
+
+/// The explanation of a failing subtype check
+unsafe fn explain(
+    mem: &mut TestMemory,
+    types1: &[Vec<u8>],
+    t1: i32,
+    types2: &[Vec<u8>],
+    t2: i32,
+) -> String {
+    assert_eq!(sub(mem, types1, t1, types2, t2), Ok(false));
+    format!("{}", idl_sub_failure().unwrap())
+}
+
+//
+// Building Candid messages
+//
+
+fn leb(mut n: u32) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if n == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn sleb(mut n: i32) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if (n == 0 && byte & 0x40 == 0) || (n == -1 && byte & 0x40 != 0) {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn leb_u128(mut n: u128) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if n == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn sleb_i128(mut n: i128) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if (n == 0 && byte & 0x40 == 0) || (n == -1 && byte & 0x40 != 0) {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn opt(t: i32) -> Vec<u8> {
+    [sleb(IDL_CON_opt), sleb(t)].concat()
+}
+
+fn vec(t: i32) -> Vec<u8> {
+    [sleb(IDL_CON_vec), sleb(t)].concat()
+}
+
+fn fields(con: i32, fields: &[(u32, i32)]) -> Vec<u8> {
+    let mut bytes = [sleb(con), leb(fields.len() as u32)].concat();
+    for &(tag, t) in fields {
+        bytes.extend(leb(tag));
+        bytes.extend(sleb(t));
+    }
+    bytes
+}
+
+fn record(fs: &[(u32, i32)]) -> Vec<u8> {
+    fields(IDL_CON_record, fs)
+}
+
+fn variant(fs: &[(u32, i32)]) -> Vec<u8> {
+    fields(IDL_CON_variant, fs)
+}
+
+fn func(args: &[i32], rets: &[i32], annotations: &[u8]) -> Vec<u8> {
+    let mut bytes = sleb(IDL_CON_func);
+    for ts in [args, rets] {
+        bytes.extend(leb(ts.len() as u32));
+        for &t in ts {
+            bytes.extend(sleb(t));
+        }
+    }
+    bytes.extend(leb(annotations.len() as u32));
+    bytes.extend(annotations);
+    bytes
+}
+
+fn service(methods: &[(&str, i32)]) -> Vec<u8> {
+    let mut bytes = [sleb(IDL_CON_service), leb(methods.len() as u32)].concat();
+    for &(name, t) in methods {
+        bytes.extend(leb(name.len() as u32));
+        bytes.extend(name.as_bytes());
+        bytes.extend(sleb(t));
+    }
+    bytes
+}
+
+/// A type added to Candid in the future, with its (opaque) type arguments
+fn future(args: &[u8]) -> Vec<u8> {
+    [sleb(-30), leb(args.len() as u32), args.to_vec()].concat()
+}
+
+/// A mutable stable variable, in the extended format
+fn alias(t: i32) -> Vec<u8> {
+    [sleb(IDL_CON_alias), sleb(t)].concat()
+}
+
+fn message(types: &[Vec<u8>], args: &[i32], data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"DIDL".to_vec();
+    bytes.extend(leb(types.len() as u32));
+    for t in types {
+        bytes.extend(t);
+    }
+    bytes.extend(leb(args.len() as u32));
+    for &t in args {
+        bytes.extend(sleb(t));
+    }
+    bytes.extend(data);
+    bytes
+}
+
+//
+// Decoding Candid messages
+//
+
+fn path(segments: &[IdlPathSegment]) -> IdlPath {
+    let mut path = IdlPath::EMPTY;
+    for &segment in segments {
+        path.push(segment);
+    }
+    path
+}
+
+fn buf_of(bytes: &[u8]) -> Buf {
+    Buf {
+        ptr: bytes.as_ptr() as *mut u8,
+        end: unsafe { bytes.as_ptr().add(bytes.len()) as *mut u8 },
+    }
+}
+
+struct Header {
+    typtbl: *mut *mut u8,
+    n_types: u32,
+    main_types: *mut u8,
+    /// The values after the header
+    data: Buf,
+}
+
+unsafe fn parse(mem: &mut TestMemory, msg: &[u8], extended: bool) -> Result<Header, IdlErrorAt> {
+    let mut buf = buf_of(msg);
+    let mut typtbl = std::ptr::null_mut();
+    let mut n_types = 0;
+    let mut main_types = std::ptr::null_mut();
+    parse_idl_header_checked(
+        mem,
+        extended,
+        &mut buf,
+        &mut typtbl,
+        &mut n_types,
+        &mut main_types,
+    )?;
+    Ok(Header {
+        typtbl,
+        n_types,
+        main_types,
+        data: buf,
+    })
+}
+
+unsafe fn parse_error(mem: &mut TestMemory, msg: &[u8]) -> IdlError {
+    match parse(mem, msg, false) {
+        Ok(_) => panic!("parse_idl_header accepted {:?}", msg),
+        Err(err) => err.error,
+    }
+}
+
+/// Skips a value of type `t`, checking that it takes all of `data`
+unsafe fn skip(mem: &mut TestMemory, types: &[Vec<u8>], t: i32, data: &[u8]) -> IdlResult<()> {
+    let msg = message(types, &[t], data);
+    let mut header = parse(mem, &msg, false).unwrap();
+    skip_any_checked(mem, &mut header.data, header.typtbl, t).map_err(|err| err.error)?;
+    assert_eq!({ header.data.ptr }, { header.data.end });
+    Ok(())
+}
+
+unsafe fn sub(
+    mem: &mut TestMemory,
+    types1: &[Vec<u8>],
+    t1: i32,
+    types2: &[Vec<u8>],
+    t2: i32,
+) -> IdlResult<bool> {
+    let msg1 = message(types1, &[], &[]);
+    let msg2 = message(types2, &[], &[]);
+    let header1 = parse(mem, &msg1, false).unwrap();
+    let header2 = parse(mem, &msg2, false).unwrap();
+    sub_parsed(&header1, t1, &header2, t2)
+}
+
+unsafe fn sub_cached(header1: &Header, t1: i32, header2: &Header, t2: i32) -> IdlResult<bool> {
+    let (n1, n2) = (header1.n_types, header2.n_types);
+    let mut rel_buf = vec![0u32; idl_sub_buf_words(n1, n2) as usize];
+    idl_sub_buf_init(rel_buf.as_mut_ptr(), n1, n2);
+    idl_sub_cached_checked(
+        rel_buf.as_mut_ptr(),
+        header1.typtbl,
+        header2.typtbl,
+        header1.main_types,
+        header2.main_types,
+        n1,
+        n2,
+        t1,
+        t2,
+    )
+}
+
+unsafe fn sub_parsed(header1: &Header, t1: i32, header2: &Header, t2: i32) -> IdlResult<bool> {
+    let (n1, n2) = (header1.n_types, header2.n_types);
+    let mut rel_buf = vec![0u32; idl_sub_buf_words(n1, n2) as usize];
+    idl_sub_buf_init(rel_buf.as_mut_ptr(), n1, n2);
+    idl_sub_checked(
+        rel_buf.as_mut_ptr(),
+        header1.typtbl,
+        header2.typtbl,
+        header1.data.end,
+        header2.data.end,
+        n1,
+        n2,
+        t1,
+        t2,
+    )
+}
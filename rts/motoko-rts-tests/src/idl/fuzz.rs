@@ -0,0 +1,485 @@
+//! Property-based fuzzing of the IDL decoder with random and mutated messages. Decoding must
+//! either succeed or fail with an `IdlError`, which the functions called by generated code trap
+//! with through `idl_trap_with`. It must never read outside of the message: messages are decoded
+//! in a "guarded" buffer, followed by guard bytes that must not change the outcome, including
+//! where errors are located.
+
+#![allow(non_upper_case_globals)]
+
+use super::{
+    alias, func, future, message, opt, parse, record, service, sub_parsed, variant, vec, Header,
+};
+use crate::memory::TestMemory;
+
+use motoko_rts::buf::Buf;
+use motoko_rts::idl::{
+    idl_set_arg, skip_any_checked, IDL_PRIM_bool, IDL_PRIM_float32, IDL_PRIM_float64, IDL_PRIM_int,
+    IDL_PRIM_int16, IDL_PRIM_int32, IDL_PRIM_int64, IDL_PRIM_int8, IDL_PRIM_nat, IDL_PRIM_nat16,
+    IDL_PRIM_nat32, IDL_PRIM_nat64, IDL_PRIM_nat8, IDL_PRIM_null, IDL_PRIM_reserved, IDL_PRIM_text,
+    IDL_REF_principal, IdlError, IdlErrorAt, IdlResult,
+};
+use motoko_rts::leb128::{leb128_decode, sleb128_decode};
+use motoko_rts::types::Words;
+
+use oorandom::Rand32;
+use proptest::arbitrary::any;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest::test_runner::{Config, TestCaseError, TestCaseResult, TestRunner};
+
+pub unsafe fn test() {
+    let mut proptest_runner = TestRunner::new(Config {
+        cases: 1_000,
+        failure_persistence: None,
+        ..Default::default()
+    });
+
+    println!("  Fuzzing with generated messages");
+    proptest_runner
+        .run(&message_strategy(), |input| check_generated(&input))
+        .unwrap();
+
+    println!("  Fuzzing with mutated messages");
+    proptest_runner
+        .run(
+            &(
+                message_strategy(),
+                proptest::collection::vec(mutation_strategy(), 1..4),
+            ),
+            |(input, mutations)| check_mutated(&input, &mutations),
+        )
+        .unwrap();
+
+    println!("  Fuzzing with random messages");
+    proptest_runner
+        .run(
+            &proptest::collection::vec(byte_strategy(), 0..100),
+            |bytes| {
+                let msg = [b"DIDL".to_vec(), bytes].concat();
+                check_guarded(&msg, false).map(|_| ())
+            },
+        )
+        .unwrap();
+}
+
+//
+// Generating messages
+//
+
+/// Primitive types that have values (all but `empty`)
+const PRIMS: [i32; 17] = [
+    IDL_PRIM_null,
+    IDL_PRIM_bool,
+    IDL_PRIM_nat,
+    IDL_PRIM_int,
+    IDL_PRIM_nat8,
+    IDL_PRIM_nat16,
+    IDL_PRIM_nat32,
+    IDL_PRIM_nat64,
+    IDL_PRIM_int8,
+    IDL_PRIM_int16,
+    IDL_PRIM_int32,
+    IDL_PRIM_int64,
+    IDL_PRIM_float32,
+    IDL_PRIM_float64,
+    IDL_PRIM_text,
+    IDL_PRIM_reserved,
+    IDL_REF_principal,
+];
+
+/// An entry of a type table
+#[derive(Clone, Debug)]
+enum Entry {
+    Opt(i32),
+    Vec(i32),
+    Record(Vec<(u32, i32)>),
+    Variant(Vec<(u32, i32)>),
+    Func(Vec<i32>, Vec<i32>, Vec<u8>),
+    Service(Vec<(&'static str, i32)>),
+    Future(Vec<u8>),
+    Alias(i32),
+}
+
+/// A type table, the argument types and a seed for generating the values
+#[derive(Clone, Debug)]
+struct Input {
+    table: Vec<Entry>,
+    args: Vec<i32>,
+    seed: u64,
+}
+
+fn message_strategy() -> impl Strategy<Value = Input> {
+    (1u32..6).prop_flat_map(|n_types| {
+        (
+            proptest::collection::vec(entry_strategy(n_types), n_types as usize),
+            proptest::collection::vec(type_strategy(n_types), 0..4),
+            any::<u64>(),
+        )
+            .prop_map(|(table, args, seed)| Input { table, args, seed })
+    })
+}
+
+/// Primitive types and indices into a table of `n_types` entries
+fn type_strategy(n_types: u32) -> BoxedStrategy<i32> {
+    proptest::prop_oneof![proptest::sample::select(PRIMS.to_vec()), 0..n_types as i32].boxed()
+}
+
+fn fields_strategy(n_types: u32) -> BoxedStrategy<Vec<(u32, i32)>> {
+    let tag = proptest::prop_oneof![0u32..4, any::<u32>()];
+    proptest::collection::btree_map(tag, type_strategy(n_types), 0..4)
+        .prop_map(|fields| fields.into_iter().collect::<Vec<_>>())
+        .boxed()
+}
+
+fn entry_strategy(n_types: u32) -> BoxedStrategy<Entry> {
+    let types = || proptest::collection::vec(type_strategy(n_types), 0..3);
+    // Method names in order, with method types that are table indices (not necessarily of
+    // function types)
+    let methods = (
+        proptest::sample::subsequence(vec!["bar", "baz", "foo"], 0..=3),
+        proptest::collection::vec(0..n_types as i32, 3),
+    )
+        .prop_map(|(names, types)| names.into_iter().zip(types).collect::<Vec<_>>());
+    proptest::prop_oneof![
+        type_strategy(n_types).prop_map(Entry::Opt),
+        type_strategy(n_types).prop_map(Entry::Vec),
+        fields_strategy(n_types).prop_map(Entry::Record),
+        fields_strategy(n_types).prop_map(Entry::Variant),
+        (types(), types(), proptest::collection::vec(1u8..=3, 0..2))
+            .prop_map(|(args, rets, annotations)| Entry::Func(args, rets, annotations)),
+        methods.prop_map(Entry::Service),
+        proptest::collection::vec(any::<u8>(), 0..4).prop_map(Entry::Future),
+        type_strategy(n_types).prop_map(Entry::Alias),
+    ]
+    .boxed()
+}
+
+/// Bytes that are likely to be meaningful: tags, small counts and type codes
+fn byte_strategy() -> BoxedStrategy<u8> {
+    proptest::prop_oneof![0u8..4, 0x60u8..0x80, any::<u8>()].boxed()
+}
+
+/// Encodes the message, with values if all arguments have (small enough) values. Returns whether
+/// it has values.
+fn encode(input: &Input) -> (Vec<u8>, bool) {
+    let types: Vec<Vec<u8>> = input
+        .table
+        .iter()
+        .map(|entry| match entry {
+            Entry::Opt(t) => opt(*t),
+            Entry::Vec(t) => vec(*t),
+            Entry::Record(fields) => record(fields),
+            Entry::Variant(fields) => variant(fields),
+            Entry::Func(args, rets, annotations) => func(args, rets, annotations),
+            Entry::Service(methods) => service(methods),
+            Entry::Future(args) => future(args),
+            Entry::Alias(t) => alias(*t),
+        })
+        .collect();
+
+    let mut rng = Rand32::new(input.seed);
+    let mut data = vec![];
+    let has_values = input
+        .args
+        .iter()
+        .all(|&t| encode_value(&input.table, t, &mut rng, 0, &mut data));
+    if !has_values {
+        data.clear();
+    }
+    (message(&types, &input.args, &data), has_values)
+}
+
+/// Encodes a random value of type `t`. Returns false if the value gets too deep, e.g. for types
+/// without finite values.
+fn encode_value(table: &[Entry], t: i32, rng: &mut Rand32, depth: u32, out: &mut Vec<u8>) -> bool {
+    if depth > 10 {
+        return false;
+    }
+    if t < 0 {
+        match t {
+            IDL_PRIM_null | IDL_PRIM_reserved => {}
+            IDL_PRIM_bool => out.push(rng.rand_range(0..2) as u8),
+            IDL_PRIM_nat | IDL_PRIM_int => out.extend(super::leb(rng.rand_u32() >> 1)),
+            IDL_PRIM_nat8 | IDL_PRIM_int8 => random_bytes(rng, 1, out),
+            IDL_PRIM_nat16 | IDL_PRIM_int16 => random_bytes(rng, 2, out),
+            IDL_PRIM_nat32 | IDL_PRIM_int32 | IDL_PRIM_float32 => random_bytes(rng, 4, out),
+            IDL_PRIM_nat64 | IDL_PRIM_int64 | IDL_PRIM_float64 => random_bytes(rng, 8, out),
+            IDL_PRIM_text => {
+                let len = rng.rand_range(0..5);
+                out.extend(super::leb(len));
+                for _ in 0..len {
+                    out.push(b'a' + rng.rand_range(0..26) as u8);
+                }
+            }
+            IDL_REF_principal => {
+                out.push(1);
+                let len = rng.rand_range(0..5);
+                out.extend(super::leb(len));
+                random_bytes(rng, len, out);
+            }
+            _ => return false, // `empty`
+        }
+        return true;
+    }
+
+    match &table[t as usize] {
+        Entry::Opt(it) => {
+            let len = out.len();
+            if rng.rand_range(0..2) == 1 {
+                out.push(1);
+                if encode_value(table, *it, rng, depth + 1, out) {
+                    return true;
+                }
+                out.truncate(len);
+            }
+            out.push(0);
+            true
+        }
+        Entry::Vec(it) => {
+            let count = rng.rand_range(0..3);
+            out.extend(super::leb(count));
+            (0..count).all(|_| encode_value(table, *it, rng, depth + 1, out))
+        }
+        Entry::Record(fields) => fields
+            .iter()
+            .all(|&(_, it)| encode_value(table, it, rng, depth + 1, out)),
+        Entry::Variant(fields) => {
+            if fields.is_empty() {
+                return false;
+            }
+            let i = rng.rand_range(0..fields.len() as u32);
+            out.extend(super::leb(i));
+            encode_value(table, fields[i as usize].1, rng, depth + 1, out)
+        }
+        Entry::Func(..) => {
+            out.extend([1, 1, 1, 42, 3]);
+            out.extend(b"foo");
+            true
+        }
+        Entry::Service(_) => {
+            out.extend([1, 1, 42]);
+            true
+        }
+        Entry::Future(_) => {
+            let len = rng.rand_range(0..5);
+            out.extend(super::leb(len));
+            out.push(0); // no references
+            random_bytes(rng, len, out);
+            true
+        }
+        Entry::Alias(it) => {
+            out.push(0); // the contents follow
+            random_bytes(rng, 8, out);
+            encode_value(table, *it, rng, depth + 1, out)
+        }
+    }
+}
+
+fn random_bytes(rng: &mut Rand32, n: u32, out: &mut Vec<u8>) {
+    for _ in 0..n {
+        out.push(rng.rand_u32() as u8);
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Mutation {
+    Set(usize, u8),
+    Insert(usize, u8),
+    Remove(usize),
+    Truncate(usize),
+}
+
+fn mutation_strategy() -> impl Strategy<Value = Mutation> {
+    proptest::prop_oneof![
+        (any::<usize>(), byte_strategy()).prop_map(|(i, b)| Mutation::Set(i, b)),
+        (any::<usize>(), byte_strategy()).prop_map(|(i, b)| Mutation::Insert(i, b)),
+        any::<usize>().prop_map(Mutation::Remove),
+        any::<usize>().prop_map(Mutation::Truncate),
+    ]
+}
+
+fn mutate(msg: &mut Vec<u8>, mutation: &Mutation) {
+    if msg.is_empty() {
+        return;
+    }
+    match *mutation {
+        Mutation::Set(i, b) => {
+            let i = i % msg.len();
+            msg[i] = b;
+        }
+        Mutation::Insert(i, b) => msg.insert(i % (msg.len() + 1), b),
+        Mutation::Remove(i) => {
+            msg.remove(i % msg.len());
+        }
+        Mutation::Truncate(len) => msg.truncate(len % msg.len()),
+    }
+}
+
+//
+// Checking the decoder
+//
+
+/// Guard bytes after the message. `0x80` continues (s)leb128 numbers.
+const GUARDS: [u8; 3] = [0x00, 0x80, 0xFF];
+
+const GUARD_SIZE: usize = 16;
+
+/// What decoding a message observed, with positions as offsets into the message
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    /// The argument types
+    header: Result<Vec<i32>, IdlErrorAt>,
+    /// Where the skipped arguments end, until the first error
+    args: Vec<Result<usize, IdlErrorAt>>,
+    /// Subtype checks between the first arguments
+    subs: Vec<(usize, usize, IdlResult<bool>)>,
+}
+
+/// Decodes the message in a guarded buffer: it is followed by `guard` bytes, and positions are
+/// checked to stay in the message
+unsafe fn decode_guarded(msg: &[u8], extended: bool, guard: u8) -> Outcome {
+    let guarded = [msg, &[guard; GUARD_SIZE]].concat();
+    let msg = &guarded[..msg.len()];
+    let start = msg.as_ptr() as usize;
+    let end = start + msg.len();
+    let in_bounds = |p: *mut u8| start <= p as usize && p as usize <= end;
+
+    let mut mem = TestMemory::new(Words(16 * 1024));
+    let mut header = match parse(&mut mem, msg, extended) {
+        Ok(header) => header,
+        Err(err) => {
+            assert!(err.offset as usize <= msg.len());
+            return Outcome {
+                header: Err(err),
+                args: vec![],
+                subs: vec![],
+            };
+        }
+    };
+
+    for i in 0..header.n_types as usize {
+        let entry = *header.typtbl.add(i);
+        assert!(in_bounds(entry) && (entry as usize) < end);
+    }
+    assert!(in_bounds(header.main_types));
+    assert!(in_bounds(header.data.ptr));
+
+    let arg_types = main_types(&header);
+    let mut args = vec![];
+    for (i, &t) in arg_types.iter().enumerate() {
+        idl_set_arg(i as u32);
+        let result = skip_any_checked(&mut mem, &mut header.data, header.typtbl, t);
+        assert!(in_bounds(header.data.ptr));
+        if let Err(err) = result {
+            assert!(err.offset as usize <= msg.len());
+        }
+        args.push(result.map(|()| header.data.ptr as usize - start));
+        if result.is_err() {
+            break;
+        }
+    }
+
+    let mut subs = vec![];
+    for (i, &t1) in arg_types.iter().enumerate().take(3) {
+        for (j, &t2) in arg_types.iter().enumerate().take(3) {
+            subs.push((i, j, sub_parsed(&header, t1, &header, t2)));
+        }
+    }
+
+    Outcome {
+        header: Ok(arg_types),
+        args,
+        subs,
+    }
+}
+
+/// The argument types of a parsed message
+unsafe fn main_types(header: &Header) -> Vec<i32> {
+    let mut buf = Buf {
+        ptr: header.main_types,
+        end: header.data.end,
+    };
+    (0..leb128_decode(&mut buf))
+        .map(|_| sleb128_decode(&mut buf))
+        .collect()
+}
+
+/// Decodes the message with all guards, checking that the outcome is the same
+fn check_guarded(msg: &[u8], extended: bool) -> Result<Outcome, TestCaseError> {
+    let mut outcomes: Vec<Outcome> = GUARDS
+        .iter()
+        .map(|&guard| unsafe { decode_guarded(msg, extended, guard) })
+        .collect();
+    let outcome = outcomes.pop().unwrap();
+    for other in outcomes {
+        if other != outcome {
+            return Err(TestCaseError::Fail(
+                format!(
+                    "decoding {:?} depends on the bytes after it: {:?} vs. {:?}",
+                    msg, other, outcome
+                )
+                .into(),
+            ));
+        }
+    }
+    Ok(outcome)
+}
+
+fn has_aliases(input: &Input) -> bool {
+    input
+        .table
+        .iter()
+        .any(|entry| matches!(entry, Entry::Alias(_)))
+}
+
+fn check_generated(input: &Input) -> TestCaseResult {
+    let (msg, has_values) = encode(input);
+    let extended = has_aliases(input);
+    let outcome = check_guarded(&msg, extended)?;
+
+    let fail = |what: &str| {
+        Err(TestCaseError::Fail(
+            format!("{} {:?}: {:?}", what, msg, outcome).into(),
+        ))
+    };
+
+    match outcome.header {
+        // Method types are not necessarily function types
+        Err(IdlErrorAt {
+            error: IdlError::MethodNotFunc,
+            ..
+        }) => return Ok(()),
+        Err(_) => return fail("rejected the header of"),
+        Ok(_) => {}
+    }
+
+    if has_values && outcome.args.last().unwrap_or(&Ok(msg.len())) != &Ok(msg.len()) {
+        return fail("could not skip the values of");
+    }
+
+    // Subtyping is reflexive, except for future types (which are not understood) and aliases
+    // (which are not compared)
+    let reflexive = !extended
+        && !input
+            .table
+            .iter()
+            .any(|entry| matches!(entry, Entry::Future(_)));
+    if reflexive
+        && outcome
+            .subs
+            .iter()
+            .any(|(i, j, result)| i == j && *result != Ok(true))
+    {
+        return fail("subtyping is not reflexive for");
+    }
+
+    Ok(())
+}
+
+fn check_mutated(input: &Input, mutations: &[Mutation]) -> TestCaseResult {
+    let (mut msg, _) = encode(input);
+    for mutation in mutations {
+        mutate(&mut msg, mutation);
+    }
+    check_guarded(&msg, has_aliases(input))?;
+    Ok(())
+}
@@ -0,0 +1,538 @@
+//! Converting Candid messages to JSON and back
+
+use super::{
+    buf_of, func, future, leb, leb_u128, message, opt, path, record, service, sleb_i128, variant,
+    vec,
+};
+use crate::memory::TestMemory;
+
+use motoko_rts::idl::json::{
+    idl_from_json_checked, idl_to_json_checked, Encoder, JsonError, JsonErrorAt,
+};
+use motoko_rts::idl::print::{idl_hash, TextWriter};
+use motoko_rts::idl::{
+    IDL_PRIM_bool, IDL_PRIM_float32, IDL_PRIM_float64, IDL_PRIM_int, IDL_PRIM_int16,
+    IDL_PRIM_int64, IDL_PRIM_int8, IDL_PRIM_nat, IDL_PRIM_nat16, IDL_PRIM_nat64, IDL_PRIM_nat8,
+    IDL_PRIM_null, IDL_PRIM_reserved, IDL_PRIM_text, IDL_REF_principal, IdlError, IdlErrorAt,
+    IdlPathSegment,
+};
+
+pub unsafe fn test(mem: &mut TestMemory) {
+    println!("  Testing idl_to_json");
+
+    let msg = message(&[], &[], &[]);
+    assert_eq!(to_json(mem, &msg, &[]), Ok("[]".to_string()));
+    let msg = message(
+        &[],
+        &[
+            IDL_PRIM_null,
+            IDL_PRIM_reserved,
+            IDL_PRIM_bool,
+            IDL_PRIM_nat8,
+            IDL_PRIM_int8,
+            IDL_PRIM_int16,
+            IDL_PRIM_nat64,
+            IDL_PRIM_int64,
+            IDL_PRIM_float32,
+            IDL_PRIM_float64,
+            IDL_PRIM_float64,
+            IDL_PRIM_nat,
+            IDL_PRIM_int,
+        ],
+        &[
+            &[1, 255, 0x80, 0x00, 0x80][..],
+            &u64::MAX.to_le_bytes(),
+            &i64::MIN.to_le_bytes(),
+            &1.5f32.to_le_bytes(),
+            &(-0.25f64).to_le_bytes(),
+            &f64::NAN.to_le_bytes(),
+            &[0xE5, 0x8E, 0x26, 0x7F],
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        to_json(mem, &msg, &[]),
+        Ok("[null,null,true,255,-128,-32768,\"18446744073709551615\",\
+            \"-9223372036854775808\",1.5,-0.25,null,\"624485\",\"-1\"]"
+            .to_string())
+    );
+
+    println!("  Testing idl_to_json with text and blobs");
+    let text = "a\"b\\\n\u{1}é";
+    let msg = message(
+        &[vec(IDL_PRIM_nat8)],
+        &[IDL_PRIM_text, 0, 0, 0],
+        &[
+            &leb(text.len() as u32),
+            text.as_bytes(),
+            &[3, b'A', 0, b'"'],
+            &[1, 0xFF],
+            &[0],
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        to_json(mem, &msg, &[]),
+        Ok("[\"a\\\"b\\\\\\n\\u0001é\",\"QQAi\",\"/w==\",\"\"]".to_string())
+    );
+
+    println!("  Testing idl_to_json with constructed types");
+    let types = [
+        opt(IDL_PRIM_nat16),
+        opt(2),
+        opt(IDL_PRIM_nat16),
+        vec(IDL_PRIM_bool),
+        record(&[(1, IDL_PRIM_bool), (2, 3)]),
+        variant(&[(3, IDL_PRIM_null), (5, 0)]),
+    ];
+    let msg = message(
+        &types,
+        &[0, 0, 1, 1, 1, 3, 4, 5, 5],
+        &[
+            0, 1, 5, 0, // opt nat16
+            0, 1, 0, 1, 1, 7, 0, // opt opt nat16
+            2, 1, 0, // vec bool
+            1, 0, // record
+            0, 1, 1, 3, 0, // variant
+        ],
+    );
+    assert_eq!(
+        to_json(mem, &msg, &[]),
+        Ok("[null,5,[],[null],[7],[true,false],{\"1\":true,\"2\":[]},\
+            {\"3\":null},{\"5\":3}]"
+            .to_string())
+    );
+
+    println!("  Testing idl_to_json with names");
+    let names = ["name", "some", "a\"b"];
+    let mut cases = [(idl_hash(b"some"), 0), (idl_hash(b"a\"b"), IDL_PRIM_null)];
+    cases.sort();
+    let types = [
+        record(&[(idl_hash(b"name"), IDL_PRIM_text)]),
+        variant(&cases),
+    ];
+    let some = (idl_hash(b"some") > idl_hash(b"a\"b")) as u8;
+    let msg = message(&types, &[1], &[some, 3, b'B', b'o', b'b']);
+    assert_eq!(
+        to_json(mem, &msg, &names),
+        Ok("[{\"some\":{\"name\":\"Bob\"}}]".to_string())
+    );
+    let msg = message(&types, &[1], &[1 - some]);
+    assert_eq!(
+        to_json(mem, &msg, &names),
+        Ok("[{\"a\\\"b\":null}]".to_string())
+    );
+
+    println!("  Testing idl_to_json with references");
+    let msg = message(
+        &[func(&[], &[], &[]), service(&[])],
+        &[IDL_REF_principal, 0, 1],
+        &[
+            1, 0, // the management canister
+            1, 1, 1, 0x04, 3, b'f', b'o', b'o', // a method
+            1, 1, 0x04, // a service
+        ],
+    );
+    assert_eq!(
+        to_json(mem, &msg, &[]),
+        Ok("[\"aaaaa-aa\",[\"2vxsx-fae\",\"foo\"],\"2vxsx-fae\"]".to_string())
+    );
+
+    println!("  Testing idl_to_json with future types");
+    let msg = message(&[future(&[])], &[0, IDL_PRIM_bool], &[2, 0, 0xAB, 0xCD, 1]);
+    assert_eq!(to_json(mem, &msg, &[]), Ok("[null,true]".to_string()));
+
+    println!("  Testing idl_to_json with malformed input");
+    let msg = message(&[record(&[(1, IDL_PRIM_int16)])], &[0], &[1]);
+    let err = to_json(mem, &msg, &[]).unwrap_err();
+    assert_eq!(err.error, IdlError::AdvanceOutOfBuffer);
+    assert_eq!(err.offset, msg.len() as u32 - 1);
+    assert_eq!(err.type_index, Some(0));
+    assert_eq!(
+        err.path,
+        path(&[IdlPathSegment::Arg(0), IdlPathSegment::Field(1)])
+    );
+    let msg = message(&[vec(IDL_PRIM_bool)], &[0], &[2, 1, 2]);
+    let err = to_json(mem, &msg, &[]).unwrap_err();
+    assert_eq!(err.error, IdlError::InvalidByteTag);
+    assert_eq!(
+        err.path,
+        path(&[IdlPathSegment::Arg(0), IdlPathSegment::Element(1)])
+    );
+    let mut bytes = vec![0x80; 74];
+    bytes.push(0x01);
+    let msg = message(&[], &[IDL_PRIM_nat], &bytes);
+    assert_eq!(
+        to_json(mem, &msg, &[]).map_err(|err| err.error),
+        Err(IdlError::NumberTooLarge)
+    );
+    // A full buffer is not an error
+    let msg = message(&[], &[IDL_PRIM_text], b"\x05hello");
+    let mut bytes = [0; 4];
+    let mut out = TextWriter::new(&mut bytes);
+    let mut buf = buf_of(&msg);
+    assert_eq!(
+        idl_to_json_checked(mem, &mut buf, &[][..], &mut out),
+        Ok(())
+    );
+    assert_eq!(out.as_str(), "[\"he");
+
+    println!("  Testing idl_from_json");
+    let types = message(&[], &[], &[]);
+    assert_eq!(from_json(mem, &types, " [ ] "), Ok(types.clone()));
+    let args = [
+        IDL_PRIM_null,
+        IDL_PRIM_reserved,
+        IDL_PRIM_bool,
+        IDL_PRIM_nat8,
+        IDL_PRIM_int8,
+        IDL_PRIM_int16,
+        IDL_PRIM_nat64,
+        IDL_PRIM_int64,
+        IDL_PRIM_float32,
+        IDL_PRIM_float64,
+        IDL_PRIM_nat,
+        IDL_PRIM_int,
+        IDL_PRIM_int,
+    ];
+    let types = message(&[], &args, &[]);
+    assert_eq!(
+        from_json(
+            mem,
+            &types,
+            "[null, {\"x\": [1, 2e3]}, true, \"255\", -128, -32768, \"18446744073709551615\", \
+             -9223372036854775808, 1.5, -2.5e-1, 624485, \"-1\", 64]"
+        ),
+        Ok(message(
+            &[],
+            &args,
+            &[
+                &[1, 255, 0x80, 0x00, 0x80][..],
+                &u64::MAX.to_le_bytes(),
+                &i64::MIN.to_le_bytes(),
+                &1.5f32.to_le_bytes(),
+                &(-0.25f64).to_le_bytes(),
+                &[0xE5, 0x8E, 0x26, 0x7F, 0xC0, 0x00],
+            ]
+            .concat(),
+        ))
+    );
+
+    println!("  Testing idl_from_json with numbers");
+    assert_eq!(from_nat(mem, "0"), Ok(vec![0]));
+    assert_eq!(from_nat(mem, "\"-0\""), Ok(vec![0]));
+    assert_eq!(
+        from_nat(mem, "\"1267650600228229401496703205376\""),
+        Ok(leb_u128(1 << 100))
+    );
+    assert_eq!(
+        from_int(mem, &format!("\"{}\"", i128::MIN)),
+        Ok(sleb_i128(i128::MIN))
+    );
+    assert_eq!(
+        from_int(mem, &i128::MAX.to_string()),
+        Ok(sleb_i128(i128::MAX))
+    );
+    assert_eq!(
+        from_nat(mem, &format!("\"{}\"", "9".repeat(200))).map_err(|err| err.error),
+        Err(JsonError::OutOfRange { expected: "nat" })
+    );
+    assert_eq!(
+        from_nat(mem, "-1"),
+        Err(JsonErrorAt {
+            error: JsonError::OutOfRange { expected: "nat" },
+            offset: 1
+        })
+    );
+    assert_eq!(
+        from_nat(mem, "1.5").map_err(|err| err.error),
+        Err(JsonError::Mismatch { expected: "nat" })
+    );
+    assert_eq!(
+        from_nat(mem, "\"1e3\"").map_err(|err| err.error),
+        Err(JsonError::Mismatch { expected: "nat" })
+    );
+    assert_eq!(
+        from_nat(mem, "true").map_err(|err| err.error),
+        Err(JsonError::Mismatch { expected: "nat" })
+    );
+    let types = message(&[], &[IDL_PRIM_nat8, IDL_PRIM_nat16], &[]);
+    assert_eq!(
+        from_json(mem, &types, "[255, 65536]"),
+        Err(JsonErrorAt {
+            error: JsonError::OutOfRange { expected: "nat16" },
+            offset: 6
+        })
+    );
+
+    println!("  Testing idl_from_json with text and blobs");
+    let types = message(&[vec(IDL_PRIM_nat8)], &[IDL_PRIM_text, 0, 0, 0], &[]);
+    let text = "a\"b\\\n\u{1}é/😀";
+    assert_eq!(
+        from_json(
+            mem,
+            &types,
+            "[\"a\\\"b\\\\\\n\\u0001\\u00e9\\/\\ud83d\\ude00\", \"QQAi\", \"/w==\", [1, 2]]"
+        ),
+        Ok(message(
+            &[vec(IDL_PRIM_nat8)],
+            &[IDL_PRIM_text, 0, 0, 0],
+            &[
+                &leb(text.len() as u32),
+                text.as_bytes(),
+                &[3, b'A', 0, b'"'],
+                &[1, 0xFF],
+                &[2, 1, 2],
+            ]
+            .concat(),
+        ))
+    );
+    let types = message(&[], &[IDL_PRIM_text], &[]);
+    for json in ["[\"\\x\"]", "[\"\\ud83d\"]", "[\"\n\"]", "[\"a]"] {
+        assert_eq!(
+            from_json(mem, &types, json).map_err(|err| err.error),
+            Err(JsonError::Syntax)
+        );
+    }
+    let types = message(&[vec(IDL_PRIM_nat8)], &[0], &[]);
+    assert_eq!(
+        from_json(mem, &types, "[\"QQA\"]"),
+        Ok(message(&[vec(IDL_PRIM_nat8)], &[0], &[2, b'A', 0]))
+    );
+    for json in ["[\"Q\"]", "[\"QQA=A\"]", "[\"QQ!=\"]"] {
+        assert_eq!(
+            from_json(mem, &types, json).map_err(|err| err.error),
+            Err(JsonError::Mismatch { expected: "blob" })
+        );
+    }
+    assert_eq!(
+        from_json(mem, &types, "[[256]]").map_err(|err| err.error),
+        Err(JsonError::OutOfRange { expected: "nat8" })
+    );
+
+    println!("  Testing idl_from_json with constructed types");
+    let types = [
+        opt(IDL_PRIM_nat16),
+        opt(2),
+        opt(IDL_PRIM_nat16),
+        vec(IDL_PRIM_bool),
+        record(&[(1, IDL_PRIM_bool), (2, 3), (3, 0), (4, IDL_PRIM_null)]),
+        variant(&[(3, IDL_PRIM_null), (5, 0)]),
+    ];
+    let args = [0, 0, 1, 1, 1, 3, 4, 5, 5];
+    assert_eq!(
+        from_json(
+            mem,
+            &message(&types, &args, &[]),
+            "[null, 5, [], [null], [7], [true, false], {\"2\": [], \"9\": {}, \"1\": true}, \
+             {\"3\": null}, {\"5\": 3}]"
+        ),
+        Ok(message(
+            &types,
+            &args,
+            &[
+                0, 1, 5, 0, // opt nat16
+                0, 1, 0, 1, 1, 7, 0, // opt opt nat16
+                2, 1, 0, // vec bool
+                1, 0, 0, // record, without fields 3 and 4
+                0, 1, 1, 3, 0, // variant
+            ],
+        ))
+    );
+    let types = message(&types, &[4], &[]);
+    assert_eq!(
+        from_json(mem, &types, "[ {\"1\": true}]"),
+        Err(JsonErrorAt {
+            error: JsonError::MissingField { tag: 2 },
+            offset: 2
+        })
+    );
+    assert_eq!(
+        from_json(mem, &types, "[{\"1\": true, \"2\": [], \"2\": null}]"),
+        Ok([&types[..], &[1, 0, 0]].concat())
+    );
+    assert_eq!(
+        from_json(mem, &types, "[{\"1\": true, \"2\": []]").map_err(|err| err.error),
+        Err(JsonError::Syntax)
+    );
+    let types = message(&[variant(&[(3, IDL_PRIM_null)])], &[0], &[]);
+    assert_eq!(
+        from_json(mem, &types, "[{\"4\": null}]"),
+        Err(JsonErrorAt {
+            error: JsonError::UnknownCase { tag: 4 },
+            offset: 2
+        })
+    );
+    assert_eq!(
+        from_json(mem, &types, "[{\"3\": null, \"4\": null}]").map_err(|err| err.error),
+        Err(JsonError::Syntax)
+    );
+
+    println!("  Testing idl_from_json with names");
+    let types = message(
+        &[
+            record(&[(idl_hash(b"name"), IDL_PRIM_text)]),
+            variant(&[(idl_hash(b"some"), 0)]),
+        ],
+        &[1],
+        &[],
+    );
+    assert_eq!(
+        from_json(mem, &types, "[{\"some\": {\"n\\u0061me\": \"Bob\"}}]"),
+        Ok([&types[..], &[0, 3, b'B', b'o', b'b']].concat())
+    );
+    assert_eq!(
+        from_json(
+            mem,
+            &types,
+            &format!("[{{\"{}\": {{\"name\": \"Bob\"}}}}]", idl_hash(b"some"))
+        ),
+        Ok([&types[..], &[0, 3, b'B', b'o', b'b']].concat())
+    );
+
+    println!("  Testing idl_from_json with references");
+    let types = [func(&[], &[], &[]), service(&[])];
+    let args = [IDL_REF_principal, 0, 1];
+    assert_eq!(
+        from_json(
+            mem,
+            &message(&types, &args, &[]),
+            "[\"aaaaa-aa\", [\"2vxsx-fae\", \"foo\"], \"2vxsx-fae\"]"
+        ),
+        Ok(message(
+            &types,
+            &args,
+            &[1, 0, 1, 1, 1, 0x04, 3, b'f', b'o', b'o', 1, 1, 0x04],
+        ))
+    );
+    let types = message(&[], &[IDL_REF_principal], &[]);
+    for json in [
+        "[\"2vxsx-faf\"]",
+        "[\"2vxsxfae\"]",
+        "[\"2VXSX-FAE\"]",
+        "[\"aaa\"]",
+        "[1]",
+    ] {
+        assert_eq!(
+            from_json(mem, &types, json),
+            Err(JsonErrorAt {
+                error: JsonError::Mismatch {
+                    expected: "principal"
+                },
+                offset: 1
+            })
+        );
+    }
+
+    println!("  Testing idl_from_json with malformed input");
+    let types = message(&[], &[IDL_PRIM_bool], &[]);
+    assert_eq!(
+        from_json(mem, &types, "[true] x"),
+        Err(JsonErrorAt {
+            error: JsonError::Syntax,
+            offset: 7
+        })
+    );
+    assert_eq!(
+        from_json(mem, &types, "[true,]"),
+        Err(JsonErrorAt {
+            error: JsonError::ArgumentCount { expected: 1 },
+            offset: 5
+        })
+    );
+    assert_eq!(
+        from_json(mem, &types, "[]").map_err(|err| err.error),
+        Err(JsonError::ArgumentCount { expected: 1 })
+    );
+    assert_eq!(
+        from_json(mem, &types, "[1]"),
+        Err(JsonErrorAt {
+            error: JsonError::Mismatch { expected: "bool" },
+            offset: 1
+        })
+    );
+    let types = message(&[], &[IDL_PRIM_reserved], &[]);
+    for json in ["[01]", "[-]", "[1.]", "[{1: 2}]", "[[1 2]]", "[nul]", "["] {
+        assert_eq!(
+            from_json(mem, &types, json).map_err(|err| err.error),
+            Err(JsonError::Syntax)
+        );
+    }
+    assert!(matches!(
+        from_json(mem, b"DIDX", "[]").map_err(|err| err.error),
+        Err(JsonError::Types(IdlErrorAt {
+            error: IdlError::MissingMagic,
+            ..
+        }))
+    ));
+
+    println!("  Testing JSON round trips");
+    let types = [
+        record(&[
+            (1, IDL_PRIM_nat),
+            (2, IDL_PRIM_int),
+            (3, 1),
+            (4, 2),
+            (5, IDL_PRIM_float64),
+        ]),
+        vec(3),
+        opt(IDL_PRIM_text),
+        vec(IDL_PRIM_nat8),
+    ];
+    let msg = message(
+        &types,
+        &[0, 0],
+        &[
+            &[0x80, 0x01, 0x7F, 2, 0, 1, 0xAA, 1, 2, b'h', b'i'][..],
+            &0.1f64.to_le_bytes(),
+            &[0, 0x40, 0, 0],
+            &f64::MAX.to_le_bytes(),
+        ]
+        .concat(),
+    );
+    let json = to_json(mem, &msg, &[]).unwrap();
+    assert_eq!(
+        from_json(mem, &message(&types, &[0, 0], &[]), &json),
+        Ok(msg)
+    );
+}
+
+unsafe fn to_json(mem: &mut TestMemory, msg: &[u8], names: &[&str]) -> Result<String, IdlErrorAt> {
+    let mut buf = buf_of(msg);
+    let mut bytes = vec![0; 1000];
+    let mut out = TextWriter::new(&mut bytes);
+    idl_to_json_checked(mem, &mut buf, names, &mut out)?;
+    Ok(out.as_str().to_string())
+}
+
+/// Like `idl_from_json`, with the types of the Candid message `types`
+unsafe fn from_json(
+    mem: &mut TestMemory,
+    types: &[u8],
+    json: &str,
+) -> Result<Vec<u8>, JsonErrorAt> {
+    let mut size = Encoder::measure();
+    let mut buf = buf_of(types);
+    idl_from_json_checked(mem, json, &mut buf, &mut size)?;
+    let mut bytes = vec![0; size.size()];
+    let mut out = Encoder::new(&mut bytes);
+    let mut buf = buf_of(types);
+    idl_from_json_checked(mem, json, &mut buf, &mut out)?;
+    assert_eq!(out.size(), bytes.len());
+    Ok(bytes)
+}
+
+unsafe fn from_nat(mem: &mut TestMemory, json: &str) -> Result<Vec<u8>, JsonErrorAt> {
+    from_number(mem, IDL_PRIM_nat, json)
+}
+
+unsafe fn from_int(mem: &mut TestMemory, json: &str) -> Result<Vec<u8>, JsonErrorAt> {
+    from_number(mem, IDL_PRIM_int, json)
+}
+
+/// The value of `json` as a number of type `t`
+unsafe fn from_number(mem: &mut TestMemory, t: i32, json: &str) -> Result<Vec<u8>, JsonErrorAt> {
+    let types = message(&[], &[t], &[]);
+    let msg = from_json(mem, &types, &format!("[{}]", json))?;
+    Ok(msg[types.len()..].to_vec())
+}
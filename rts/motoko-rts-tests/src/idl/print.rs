@@ -0,0 +1,319 @@
+//! Printing Candid messages in the Candid text format
+
+use super::{
+    buf_of, func, future, leb, leb_u128, message, opt, path, record, service, sleb_i128, variant,
+    vec,
+};
+use crate::memory::TestMemory;
+
+use motoko_rts::idl::print::{idl_hash, idl_print_checked, TextWriter};
+use motoko_rts::idl::{
+    IDL_PRIM_bool, IDL_PRIM_float32, IDL_PRIM_float64, IDL_PRIM_int, IDL_PRIM_int16, IDL_PRIM_int8,
+    IDL_PRIM_nat, IDL_PRIM_nat64, IDL_PRIM_nat8, IDL_PRIM_null, IDL_PRIM_reserved, IDL_PRIM_text,
+    IDL_REF_principal, IdlError, IdlErrorAt, IdlPathSegment,
+};
+
+pub unsafe fn test(mem: &mut TestMemory) {
+    println!("  Testing idl_print");
+
+    // The example of the format
+    let msg = message(
+        &[vec(IDL_PRIM_nat), record(&[(1, IDL_PRIM_text), (2, 0)])],
+        &[1],
+        &[1, b'a', 2, 1, 2],
+    );
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok("(record { 1 = \"a\"; 2 = vec { 1; 2 } })".to_string())
+    );
+
+    println!("  Testing idl_print with primitive types");
+    assert_eq!(
+        print(mem, &message(&[], &[], &[]), &[]),
+        Ok("()".to_string())
+    );
+    let msg = message(
+        &[],
+        &[
+            IDL_PRIM_null,
+            IDL_PRIM_reserved,
+            IDL_PRIM_bool,
+            IDL_PRIM_nat8,
+            IDL_PRIM_int8,
+            IDL_PRIM_int16,
+            IDL_PRIM_nat64,
+            IDL_PRIM_float32,
+            IDL_PRIM_float64,
+        ],
+        &[
+            &[1, 255, 0x80, 0x00, 0x80][..],
+            &u64::MAX.to_le_bytes(),
+            &1.5f32.to_le_bytes(),
+            &(-0.25f64).to_le_bytes(),
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok("(null, null, true, 255, -128, -32768, 18446744073709551615, 1.5, -0.25)".to_string())
+    );
+
+    println!("  Testing idl_print with numbers");
+    assert_eq!(print_nat(mem, &[0]), "0");
+    assert_eq!(print_nat(mem, &[0xE5, 0x8E, 0x26]), "624485");
+    assert_eq!(
+        print_nat(mem, &leb_u128(1 << 100)),
+        "1267650600228229401496703205376"
+    );
+    assert_eq!(print_nat(mem, &leb_u128(u128::MAX)), u128::MAX.to_string());
+    assert_eq!(print_int(mem, &[0x7F]), "-1");
+    assert_eq!(print_int(mem, &[0xC0, 0xBB, 0x78]), "-123456");
+    assert_eq!(print_int(mem, &sleb_i128(i128::MIN)), i128::MIN.to_string());
+    assert_eq!(print_int(mem, &sleb_i128(i128::MAX)), i128::MAX.to_string());
+    // 2^511, the largest power of two printed
+    let mut bytes = vec![0x80; 73];
+    bytes.push(0x01);
+    assert_eq!(
+        print_nat(mem, &bytes),
+        "6703903964971298549787012499102923063739682910296196688861780721860882015036773488400937\
+         149083451713845015929093243025426876941405973284973216824503042048"
+    );
+    bytes.insert(0, 0x80);
+    assert_eq!(print_nat(mem, &bytes), "<large number>");
+    // Leading zeros do not count
+    let mut bytes = vec![0x81];
+    bytes.extend([0x80; 100]);
+    bytes.push(0x00);
+    assert_eq!(print_nat(mem, &bytes), "1");
+
+    println!("  Testing idl_print with text and blobs");
+    let text = "a\"b\\\n\u{1}é";
+    let msg = message(
+        &[vec(IDL_PRIM_nat8)],
+        &[IDL_PRIM_text, 0],
+        &[
+            &leb(text.len() as u32),
+            text.as_bytes(),
+            &[3, b'A', 0, b'"'],
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok("(\"a\\\"b\\\\\\n\\u{1}é\", blob \"A\\00\\22\")".to_string())
+    );
+
+    println!("  Testing idl_print with constructed types");
+    let types = [
+        opt(IDL_PRIM_nat),
+        vec(IDL_PRIM_bool),
+        record(&[]),
+        variant(&[(3, IDL_PRIM_null), (5, 0)]),
+    ];
+    let msg = message(
+        &types,
+        &[0, 0, 1, 1, 2, 3, 3],
+        &[0, 1, 5, 0, 2, 1, 0, 0, 1, 1, 3],
+    );
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok(
+            "(null, opt 5, vec {}, vec { true; false }, record {}, variant { 3 }, \
+             variant { 5 = opt 3 })"
+                .to_string()
+        )
+    );
+
+    println!("  Testing idl_print with names");
+    let names = ["name", "age", "none", "some"];
+    let mut fields = [
+        (idl_hash(b"name"), IDL_PRIM_text),
+        (idl_hash(b"age"), IDL_PRIM_nat),
+    ];
+    fields.sort();
+    let mut cases = [(idl_hash(b"none"), IDL_PRIM_null), (idl_hash(b"some"), 0)];
+    cases.sort();
+    let none = cases.iter().position(|&(_, t)| t == IDL_PRIM_null).unwrap() as u8;
+    let data: &[u8] = if fields[0].1 == IDL_PRIM_text {
+        &[3, b'B', b'o', b'b', 42]
+    } else {
+        &[42, 3, b'B', b'o', b'b']
+    };
+    let msg = message(
+        &[record(&fields), variant(&cases)],
+        &[0, 1, 1],
+        &[data, &[1 - none], data, &[none]].concat(),
+    );
+    assert_eq!(
+        print(mem, &msg, &names),
+        Ok(format!(
+            "({0}, variant {{ some = {0} }}, variant {{ none }})",
+            if fields[0].1 == IDL_PRIM_text {
+                "record { name = \"Bob\"; age = 42 }"
+            } else {
+                "record { age = 42; name = \"Bob\" }"
+            }
+        ))
+    );
+    // Unknown names are printed as hashes
+    let msg = message(&[record(&[(1, IDL_PRIM_nat)])], &[0], &[0]);
+    assert_eq!(
+        print(mem, &msg, &names),
+        Ok("(record { 1 = 0 })".to_string())
+    );
+
+    println!("  Testing idl_print with references");
+    let msg = message(
+        &[func(&[], &[], &[]), service(&[])],
+        &[IDL_REF_principal, IDL_REF_principal, 0, 0, 1],
+        &[
+            1, 0, // the management canister
+            1, 1, 0x04, // the anonymous principal
+            1, 1, 1, 0x04, 3, b'f', b'o', b'o', // a method
+            1, 1, 1, 0x04, 1, b'-', // a method that is not an identifier
+            1, 1, 0x04, // a service
+        ],
+    );
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok(
+            "(principal \"aaaaa-aa\", principal \"2vxsx-fae\", func \"2vxsx-fae\".foo, \
+             func \"2vxsx-fae\".\"-\", service \"2vxsx-fae\")"
+                .to_string()
+        )
+    );
+
+    println!("  Testing idl_print with future types");
+    let msg = message(&[future(&[])], &[0, IDL_PRIM_bool], &[2, 0, 0xAB, 0xCD, 1]);
+    assert_eq!(
+        print(mem, &msg, &[]),
+        Ok("(<future type>, true)".to_string())
+    );
+
+    println!("  Testing idl_print with limits");
+    let msg = message(
+        &[opt(1), opt(2), opt(IDL_PRIM_nat)],
+        &[0, IDL_PRIM_nat],
+        &[1, 1, 1, 7, 8],
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 32, 1000),
+        Ok("(opt opt opt 7, 8)".to_string())
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 2, 1000),
+        Ok("(opt opt ..., 8)".to_string())
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 0, 1000),
+        Ok("(..., 8)".to_string())
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 32, 10),
+        Ok("(opt op...".to_string())
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 32, 18),
+        Ok("(opt opt opt 7, 8)".to_string())
+    );
+    assert_eq!(
+        print_limited(mem, &msg, 32, 17),
+        Ok("(opt opt opt 7...".to_string())
+    );
+    // Characters are not split
+    let msg = message(
+        &[],
+        &[IDL_PRIM_text],
+        &[&[6][..], "ééé".as_bytes()].concat(),
+    );
+    assert_eq!(print_limited(mem, &msg, 32, 7), Ok("(\"é...".to_string()));
+    // Deep values are skipped, and still checked
+    let msg = message(&[opt(0)], &[0, IDL_PRIM_nat], &[1, 1, 1, 1, 0, 9]);
+    assert_eq!(
+        print_limited(mem, &msg, 2, 1000),
+        Ok("(opt opt ..., 9)".to_string())
+    );
+    let msg = message(&[opt(0)], &[0, IDL_PRIM_nat], &[1, 1, 1, 1, 2, 9]);
+    assert_eq!(
+        print_limited(mem, &msg, 2, 1000).map_err(|err| err.error),
+        Err(IdlError::InvalidByteTag)
+    );
+
+    println!("  Testing idl_print with malformed input");
+    let msg = message(&[record(&[(1, IDL_PRIM_int16)])], &[0], &[1]);
+    let err = print(mem, &msg, &[]).unwrap_err();
+    assert_eq!(err.error, IdlError::AdvanceOutOfBuffer);
+    assert_eq!(err.offset, msg.len() as u32 - 1);
+    assert_eq!(err.type_index, Some(0));
+    assert_eq!(
+        err.path,
+        path(&[IdlPathSegment::Arg(0), IdlPathSegment::Field(1)])
+    );
+    let msg = message(&[], &[IDL_PRIM_nat, IDL_PRIM_bool], &[1, 2]);
+    let err = print(mem, &msg, &[]).unwrap_err();
+    assert_eq!(err.error, IdlError::InvalidByteTag);
+    assert_eq!(err.path, path(&[IdlPathSegment::Arg(1)]));
+    let msg = message(&[], &[IDL_REF_principal], &[0]);
+    assert_eq!(
+        print(mem, &msg, &[]).map_err(|err| err.error),
+        Err(IdlError::SkipReferences)
+    );
+    let msg = message(&[variant(&[(1, IDL_PRIM_null)])], &[0], &[1]);
+    assert_eq!(
+        print(mem, &msg, &[]).map_err(|err| err.error),
+        Err(IdlError::VariantTagTooLarge {
+            tag: 1,
+            t: 0,
+            cases: 1
+        })
+    );
+    assert_eq!(
+        print(mem, b"DIDL\x00\x01\x7e\x02", &[]).map_err(|err| err.error),
+        Err(IdlError::InvalidByteTag)
+    );
+    assert_eq!(
+        print(mem, b"DIDX", &[]).map_err(|err| err.error),
+        Err(IdlError::MissingMagic)
+    );
+}
+
+unsafe fn print(mem: &mut TestMemory, msg: &[u8], names: &[&str]) -> Result<String, IdlErrorAt> {
+    print_names(mem, msg, names, 32, 1000)
+}
+
+unsafe fn print_limited(
+    mem: &mut TestMemory,
+    msg: &[u8],
+    max_depth: u32,
+    max_len: usize,
+) -> Result<String, IdlErrorAt> {
+    print_names(mem, msg, &[], max_depth, max_len)
+}
+
+/// Like `idl_print`, printing at most `max_len` bytes
+unsafe fn print_names(
+    mem: &mut TestMemory,
+    msg: &[u8],
+    names: &[&str],
+    max_depth: u32,
+    max_len: usize,
+) -> Result<String, IdlErrorAt> {
+    let mut buf = buf_of(msg);
+    let mut bytes = vec![0; max_len];
+    let mut out = TextWriter::new(&mut bytes);
+    idl_print_checked(mem, &mut buf, names, max_depth, &mut out)?;
+    out.mark_truncated();
+    Ok(out.as_str().to_string())
+}
+
+unsafe fn print_nat(mem: &mut TestMemory, bytes: &[u8]) -> String {
+    let msg = message(&[], &[IDL_PRIM_nat], bytes);
+    let text = print(mem, &msg, &[]).unwrap();
+    text[1..text.len() - 1].to_string()
+}
+
+unsafe fn print_int(mem: &mut TestMemory, bytes: &[u8]) -> String {
+    let msg = message(&[], &[IDL_PRIM_int], bytes);
+    let text = print(mem, &msg, &[]).unwrap();
+    text[1..text.len() - 1].to_string()
+}
@@ -0,0 +1,83 @@
+use motoko_rts::lz4::{compress, compress_bound, decompress, DecompressError, MAX_INPUT_SIZE};
+
+use proptest::strategy::{Just, Strategy};
+use proptest::test_runner::{Config, TestCaseError, TestCaseResult, TestRunner};
+
+pub unsafe fn test() {
+    println!("Testing lz4 ...");
+
+    println!("  Testing decompression of reference output");
+    // Output of the reference implementation (`lz4 -l`, block only)
+    let block = [
+        0x7f, 0x4d, 0x6f, 0x74, 0x6f, 0x6b, 0x6f, 0x20, 0x07, 0x00, 0x09, 0xd0, 0x73, 0x74, 0x61,
+        0x62, 0x6c, 0x65, 0x20, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+    ];
+    let expected = b"Motoko Motoko Motoko Motoko Motoko stable memory";
+    let mut out = [0u8; 100];
+    assert_eq!(decompress(&block, &mut out), Ok(expected.len()));
+    assert_eq!(&out[..expected.len()], &expected[..]);
+
+    println!("  Testing invalid input");
+    assert_eq!(
+        decompress(&block[..20], &mut out),
+        Err(DecompressError::InputTruncated)
+    );
+    assert_eq!(
+        decompress(&block, &mut out[..40]),
+        Err(DecompressError::OutputOverflow)
+    );
+    let mut bad_offset = block;
+    bad_offset[8] = 0x20;
+    assert_eq!(
+        decompress(&bad_offset, &mut out),
+        Err(DecompressError::InvalidOffset)
+    );
+    assert_eq!(
+        decompress(&[], &mut out),
+        Err(DecompressError::InputTruncated)
+    );
+
+    println!("  Testing compression ratio");
+    let repetitive: Vec<u8> = (0..MAX_INPUT_SIZE).map(|i| (i % 17) as u8).collect();
+    let mut compressed = vec![0u8; compress_bound(repetitive.len())];
+    let size = compress(&repetitive, &mut compressed);
+    assert!(size < repetitive.len() / 100);
+    let mut decompressed = vec![0u8; repetitive.len()];
+    assert_eq!(
+        decompress(&compressed[..size], &mut decompressed),
+        Ok(repetitive.len())
+    );
+    assert_eq!(decompressed, repetitive);
+
+    println!("  Testing round trips");
+    let mut proptest_runner = TestRunner::new(Config {
+        cases: 500,
+        failure_persistence: None,
+        ..Default::default()
+    });
+
+    proptest_runner
+        .run(&input_strategy(), |input| round_trip(&input))
+        .unwrap();
+}
+
+/// Generates inputs with (runs of) repeated bytes, so that there are matches to find
+fn input_strategy() -> impl Strategy<Value = Vec<u8>> {
+    let run = (0u8..4, 1usize..300).prop_map(|(byte, len)| vec![byte; len]);
+    let random = proptest::collection::vec(0u8..=255, 0..50);
+    let part = proptest::prop_oneof![run, random, Just(b"Motoko".to_vec())];
+    proptest::collection::vec(part, 0..50).prop_map(|parts| parts.concat())
+}
+
+fn round_trip(input: &[u8]) -> TestCaseResult {
+    let mut compressed = vec![0u8; compress_bound(input.len())];
+    let size = compress(input, &mut compressed);
+
+    let mut output = vec![0u8; input.len()];
+    match decompress(&compressed[..size], &mut output) {
+        Ok(n) if n == input.len() && output == input => Ok(()),
+        result => Err(TestCaseError::Fail(
+            format!("round trip failed: {:?}", result).into(),
+        )),
+    }
+}
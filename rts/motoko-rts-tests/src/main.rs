@@ -1,15 +1,24 @@
 mod bigint;
 mod bitmap;
 mod bitrel;
+mod cbor;
+mod char_props;
 mod continuation_table;
 mod crc32;
+mod float_conv;
 mod gc;
+mod hash;
+mod idl;
 mod leb128;
+mod lz4;
 mod memory;
 mod principal_id;
+mod region;
 mod remembered_set;
+mod stable_memory;
 mod stream;
 mod text;
+mod transcode;
 mod utf8;
 
 use motoko_rts::types::Bytes;
@@ -24,13 +33,21 @@ fn main() {
         bigint::test();
         bitmap::test();
         bitrel::test();
+        cbor::test();
+        char_props::test();
         continuation_table::test();
         crc32::test();
+        float_conv::test();
         gc::test();
+        hash::test();
+        idl::test();
         leb128::test();
+        lz4::test();
         principal_id::test();
+        region::test();
         stream::test();
         text::test();
+        transcode::test();
         utf8::test();
         remembered_set::test();
     }
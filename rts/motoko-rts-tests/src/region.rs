@@ -0,0 +1,76 @@
+use crate::stable_memory;
+
+use motoko_rts::region::{
+    region_grow, region_init, region_load, region_new, region_size, region_store, BLOCK_PAGES,
+    PAGE_SIZE, REGIONS_MAGIC,
+};
+
+pub unsafe fn test() {
+    println!("Testing regions ...");
+    stable_memory::reset();
+
+    println!("  Testing region creation");
+    let r0 = region_new(); // sets up the metadata on first use
+    assert_eq!(stable_memory::size(), BLOCK_PAGES);
+    assert_eq!(stable_memory::read(0, 4), REGIONS_MAGIC.to_le_bytes());
+    let r1 = region_new();
+    assert_eq!((r0, r1), (0, 1));
+    assert_eq!(region_size(r0), 0);
+
+    println!("  Testing region growth");
+    assert_eq!(region_grow(r0, 1), 0);
+    assert_eq!(region_grow(r1, 1), 0);
+    assert_eq!(region_grow(r0, BLOCK_PAGES), 1); // needs a second block, after the one of `r1`
+    assert_eq!(region_size(r0), BLOCK_PAGES + 1);
+    assert_eq!(stable_memory::size(), 4 * BLOCK_PAGES);
+
+    println!("  Testing region accesses");
+    let block_size = BLOCK_PAGES * PAGE_SIZE;
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+    let offset = block_size - 500; // across the block boundary
+    region_store(r0, offset, data.as_ptr(), data.len() as u32);
+    region_store(r1, 0, data.as_ptr(), data.len() as u32);
+    assert_eq!(load(r0, offset, data.len()), data);
+    assert_eq!(load(r1, 0, data.len()), data);
+    assert_eq!(load(r0, 0, 10), vec![0; 10]);
+    assert_eq!(stable_memory::read(block_size, 1000), vec![0; 1000]); // the start of `r0`
+    assert_eq!(stable_memory::read(2 * block_size, 1000), data); // in the block of `r1`
+    assert_eq!(
+        stable_memory::read(2 * block_size - 500, 500),
+        data[..500].to_vec()
+    );
+    assert_eq!(
+        stable_memory::read(3 * block_size, 500),
+        data[500..].to_vec()
+    );
+
+    println!("  Testing region growth after direct growth");
+    stable_memory::grow(3);
+    assert_eq!(region_grow(r1, BLOCK_PAGES), 1);
+    assert_eq!(stable_memory::size(), 6 * BLOCK_PAGES); // the directly grown pages are skipped
+    region_store(r1, block_size, data.as_ptr(), data.len() as u32);
+    assert_eq!(stable_memory::read(5 * block_size, 1000), data);
+
+    println!("  Testing failing region growth");
+    stable_memory::set_max_pages(7 * BLOCK_PAGES);
+    assert_eq!(region_grow(r0, 2 * BLOCK_PAGES), u64::MAX);
+    assert_eq!(region_size(r0), BLOCK_PAGES + 1);
+    assert_eq!(region_grow(r0, u64::MAX), u64::MAX);
+    assert_eq!(region_grow(r0, 10), BLOCK_PAGES + 1); // fits in the last block of `r0`
+
+    println!("  Testing region recovery");
+    region_init();
+    assert_eq!(region_size(r0), BLOCK_PAGES + 11);
+    assert_eq!(region_size(r1), BLOCK_PAGES + 1);
+    assert_eq!(load(r0, offset, data.len()), data);
+    assert_eq!(load(r1, block_size, data.len()), data);
+    assert_eq!(region_new(), 2);
+
+    stable_memory::reset();
+}
+
+unsafe fn load(region: u32, offset: u64, n: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; n];
+    region_load(region, offset, bytes.as_mut_ptr(), n as u32);
+    bytes
+}
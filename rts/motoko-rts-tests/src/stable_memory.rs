@@ -0,0 +1,90 @@
+//! Simulated stable memory, linked in place of the functions generated by `moc`. Like the IC's
+//! stable memory, it grows in pages, and accesses out of bounds trap (here: panic).
+
+pub const PAGE_SIZE: u64 = 64 * 1024;
+
+static mut STABLE_MEMORY: Vec<u8> = Vec::new();
+
+/// Highest address written so far, plus one
+static mut HIGH_WATER_MARK: u64 = 0;
+
+/// Growing beyond this number of pages fails
+static mut MAX_PAGES: u64 = u64::MAX;
+
+/// Drop all pages
+pub unsafe fn reset() {
+    STABLE_MEMORY = Vec::new();
+    HIGH_WATER_MARK = 0;
+    MAX_PAGES = u64::MAX;
+}
+
+pub unsafe fn set_max_pages(pages: u64) {
+    MAX_PAGES = pages;
+}
+
+/// Size in pages, like `ic0.stable64_size`
+pub unsafe fn size() -> u64 {
+    STABLE_MEMORY.len() as u64 / PAGE_SIZE
+}
+
+/// Add zeroed pages, returning the old size in pages, or `u64::MAX` if the memory cannot grow,
+/// like `ic0.stable64_grow`
+pub unsafe fn grow(pages: u64) -> u64 {
+    let old_size = size();
+    if MAX_PAGES.saturating_sub(old_size) < pages {
+        return u64::MAX;
+    }
+    STABLE_MEMORY.resize(((old_size + pages) * PAGE_SIZE) as usize, 0);
+    old_size
+}
+
+pub unsafe fn high_water_mark() -> u64 {
+    HIGH_WATER_MARK
+}
+
+pub unsafe fn read(from: u64, n: u64) -> Vec<u8> {
+    STABLE_MEMORY[range(from, n)].to_vec()
+}
+
+pub unsafe fn write(to: u64, bytes: &[u8]) {
+    STABLE_MEMORY[range(to, bytes.len() as u64)].copy_from_slice(bytes);
+    HIGH_WATER_MARK = std::cmp::max(HIGH_WATER_MARK, to + bytes.len() as u64);
+}
+
+unsafe fn range(start: u64, n: u64) -> std::ops::Range<usize> {
+    match start.checked_add(n) {
+        Some(end) if end <= STABLE_MEMORY.len() as u64 => start as usize..end as usize,
+        _ => panic!(
+            "stable memory access out of bounds: {:#x}+{:#x} (size {:#x})",
+            start,
+            n,
+            STABLE_MEMORY.len()
+        ),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn stable64_write_moc(to: u64, ptr: u64, n: u64) {
+    write(
+        to,
+        std::slice::from_raw_parts(ptr as usize as *const u8, n as usize),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn stable64_read_moc(ptr: u64, from: u64, n: u64) {
+    let bytes = read(from, n);
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr as usize as *mut u8, bytes.len());
+}
+
+// The logical size of stable memory is the physical size here
+
+#[no_mangle]
+unsafe extern "C" fn stable64_size_moc() -> u64 {
+    size()
+}
+
+#[no_mangle]
+unsafe extern "C" fn stable64_grow_moc(pages: u64) -> u64 {
+    grow(pages)
+}
@@ -1,9 +1,16 @@
 //! Stream tests
 
 use crate::memory::TestMemory;
+use crate::stable_memory;
 
-use motoko_rts::stream::alloc_stream;
-use motoko_rts::types::{Bytes, Stream, Value, Words};
+use motoko_rts::leb128::{leb128_encode, sleb128_encode};
+use motoko_rts::principal_id::update_crc32;
+use motoko_rts::stream::{
+    alloc_read_stream, alloc_stream, stream_stable_take_blob, StreamHeader, StreamHeaderError,
+    COMPRESSION_CHUNK_SIZE, STREAM_COMPRESSED, STREAM_FORMAT_VERSION, STREAM_FRAMED,
+    STREAM_HEADER_SIZE,
+};
+use motoko_rts::types::{Bytes, ReadStream, Stream, Value, Words};
 
 pub unsafe fn test() {
     println!("Testing streaming ...");
@@ -17,13 +24,13 @@ pub unsafe fn test() {
     for b in 32..92u8 {
         stream.as_stream().cache_byte(b);
     }
-    assert_eq!(stream.as_blob().get(32), 32);
-    assert_eq!(stream.as_blob().get(91), 91);
+    assert_eq!(stream.as_blob().get(48), 32);
+    assert_eq!(stream.as_blob().get(107), 91);
 
     println!("  Testing stream decay");
     let blob = stream.as_stream().split();
     assert_eq!(blob.as_blob().len(), Bytes(60));
-    assert_eq!(stream.as_blob().len(), Bytes(24));
+    assert_eq!(stream.as_blob().len(), Bytes(40));
 
     println!("  Testing stream filling (blocks)");
     let stream = Value::from_ptr(alloc_stream(&mut mem, Bytes(6000)) as usize);
@@ -33,11 +40,11 @@ pub unsafe fn test() {
             .as_stream()
             .cache_bytes(&chunk[0], Bytes(chunk.len() as u32));
     }
-    assert_eq!(stream.as_blob().get(32), 10);
-    assert_eq!(stream.as_blob().get(33), 1);
-    assert_eq!(stream.as_blob().get(41), 9);
-    assert_eq!(stream.as_blob().get(42), 10);
-    assert_eq!(stream.as_blob().get(6031), 9);
+    assert_eq!(stream.as_blob().get(48), 10);
+    assert_eq!(stream.as_blob().get(49), 1);
+    assert_eq!(stream.as_blob().get(57), 9);
+    assert_eq!(stream.as_blob().get(58), 10);
+    assert_eq!(stream.as_blob().get(6047), 9);
     let blob = stream.as_stream().split();
     assert_eq!(blob.as_blob().len(), Bytes(6000));
 
@@ -68,4 +75,361 @@ pub unsafe fn test() {
     assert_eq!(WRITTEN, Bytes(6020)); // all at once
     stream.shutdown();
     assert_eq!(WRITTEN, Bytes(6021)); // u8 too
+
+    test_read_stream(&mut mem);
+    test_framing(&mut mem);
+    test_compression(&mut mem);
+    test_stable_memory(&mut mem);
+}
+
+// Source of the read streams below, the stream's `ptr64` is the offset into it
+static mut SOURCE: Vec<u8> = Vec::new();
+static mut FETCHES: u32 = 0;
+
+fn from_source(stream: *mut ReadStream, ptr: *mut u8, n: Bytes<u32>) {
+    unsafe {
+        let from = (*stream).ptr64 as usize;
+        std::ptr::copy_nonoverlapping(SOURCE[from..].as_ptr(), ptr, n.as_usize());
+        (*stream).ptr64 += n.as_u32() as u64;
+        FETCHES += 1;
+    }
+}
+
+unsafe fn read_stream_of_source(mem: &mut TestMemory, cache_size: u32) -> *mut ReadStream {
+    let stream = alloc_read_stream(mem, Bytes(cache_size));
+    (*stream).inputter = from_source;
+    (*stream).limit64 = SOURCE.len() as u64;
+    stream
+}
+
+unsafe fn test_read_stream(mem: &mut TestMemory) {
+    println!("  Testing read stream (single bytes)");
+    SOURCE = (0..1000u32).map(|i| i as u8).collect();
+    let stream = read_stream_of_source(mem, 16);
+    for i in 0..1000u32 {
+        assert_eq!(stream.read_byte(), i as u8);
+    }
+    assert_eq!(stream.remaining(), 0);
+    assert_eq!(FETCHES, 63); // 1000 / 16, rounded up
+
+    println!("  Testing read stream (words and blocks)");
+    FETCHES = 0;
+    let stream = read_stream_of_source(mem, 16);
+    assert_eq!(stream.read_byte(), 0);
+    assert_eq!(stream.read_word(), u32::from_le_bytes([1, 2, 3, 4]));
+    let mut block = [0u8; 20];
+    stream.read_bytes(block.as_mut_ptr(), Bytes(20)); // across the end of the cache
+    assert_eq!(block.to_vec(), (5..25).collect::<Vec<u8>>());
+    assert_eq!(FETCHES, 2);
+    let mut block = [0u8; 500];
+    stream.read_bytes(block.as_mut_ptr(), Bytes(500)); // bypasses the cache
+    assert_eq!(block.to_vec(), SOURCE[25..525].to_vec());
+    assert_eq!(FETCHES, 3);
+    stream.advance(Bytes(100));
+    assert_eq!(stream.read_byte(), SOURCE[625]);
+    assert_eq!(stream.remaining(), 1000 - 626);
+
+    println!("  Testing read stream (blocks larger than the cache)");
+    FETCHES = 0;
+    let stream = read_stream_of_source(mem, 16);
+    assert_eq!(stream.read_byte(), 0);
+    let mut block = [0u8; 100];
+    stream.read_bytes(block.as_mut_ptr(), Bytes(100)); // 15 cached, 85 from the source
+    assert_eq!(block.to_vec(), SOURCE[1..101].to_vec());
+    assert_eq!(FETCHES, 2);
+    assert_eq!(stream.read_byte(), SOURCE[101]);
+    assert_eq!(stream.remaining(), 1000 - 102);
+
+    println!("  Testing read stream (LEB128)");
+    let values: [i32; 9] = [0, 1, 63, 64, 127, 128, 624485, i32::MAX, i32::MIN];
+    SOURCE.clear();
+    for value in values.iter() {
+        let mut buf = [0u8; 5];
+        leb128_encode(*value as u32, buf.as_mut_ptr());
+        let len = buf.iter().position(|byte| byte & 0b1000_0000 == 0).unwrap() + 1;
+        SOURCE.extend_from_slice(&buf[..len]);
+        sleb128_encode(*value, buf.as_mut_ptr());
+        let len = buf.iter().position(|byte| byte & 0b1000_0000 == 0).unwrap() + 1;
+        SOURCE.extend_from_slice(&buf[..len]);
+    }
+    let stream = read_stream_of_source(mem, 7);
+    for value in values.iter() {
+        assert_eq!(stream.read_leb128(), *value as u32);
+        assert_eq!(stream.read_sleb128(), *value);
+    }
+    assert_eq!(stream.remaining(), 0);
+}
+
+// Destination of the framed streams below, the stream's `ptr64` is the offset into it
+static mut DEST: Vec<u8> = Vec::new();
+
+fn to_dest(stream: *mut Stream, ptr: *const u8, n: Bytes<u32>) {
+    unsafe {
+        let to = (*stream).ptr64 as usize;
+        if DEST.len() < to + n.as_usize() {
+            DEST.resize(to + n.as_usize(), 0);
+        }
+        std::ptr::copy_nonoverlapping(ptr, DEST[to..].as_mut_ptr(), n.as_usize());
+        (*stream).ptr64 += n.as_u32() as u64;
+    }
+}
+
+unsafe fn test_framing(mem: &mut TestMemory) {
+    println!("  Testing stream framing");
+    DEST.clear();
+    let stream = alloc_stream(mem, Bytes(100));
+    (*stream).outputter = to_dest;
+    stream.start_framing();
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+    for chunk in data.chunks(250) {
+        stream.cache_bytes(chunk.as_ptr(), Bytes(chunk.len() as u32)); // bypasses the cache
+        stream.cache_byte(chunk[0]);
+    }
+    stream.shutdown();
+
+    let mut expected_data = vec![];
+    for chunk in data.chunks(250) {
+        expected_data.extend_from_slice(chunk);
+        expected_data.push(chunk[0]);
+    }
+    let header_size = STREAM_HEADER_SIZE.as_usize();
+    assert_eq!(DEST[header_size..].to_vec(), expected_data);
+    let mut header_bytes = [0u8; 24];
+    header_bytes.copy_from_slice(&DEST[..header_size]);
+    assert_eq!(
+        StreamHeader::from_bytes(&header_bytes),
+        Some(StreamHeader {
+            version: STREAM_FORMAT_VERSION,
+            flags: STREAM_FRAMED,
+            crc32: !update_crc32(!0, &expected_data),
+            length: expected_data.len() as u64,
+        })
+    );
+
+    println!("  Testing stream header verification");
+    SOURCE = DEST.clone();
+    let reader = read_stream_of_source(mem, 64);
+    assert!(reader.read_header().is_ok());
+    assert_eq!(reader.remaining(), expected_data.len() as u64);
+    let mut read_data = vec![0u8; expected_data.len()];
+    reader.read_bytes(read_data.as_mut_ptr(), Bytes(read_data.len() as u32));
+    assert_eq!(read_data, expected_data);
+
+    let read_header_of = |mem: &mut TestMemory, source: Vec<u8>| {
+        SOURCE = source;
+        read_stream_of_source(mem, 64).read_header().err()
+    };
+
+    let mut corrupted = DEST.clone();
+    corrupted[header_size + 500] ^= 1;
+    assert_eq!(
+        read_header_of(mem, corrupted),
+        Some(StreamHeaderError::ChecksumMismatch)
+    );
+
+    let truncated = DEST[..DEST.len() - 1].to_vec();
+    assert_eq!(
+        read_header_of(mem, truncated),
+        Some(StreamHeaderError::Truncated)
+    );
+
+    let mut newer = DEST.clone();
+    newer[4] += 1;
+    assert_eq!(
+        read_header_of(mem, newer),
+        Some(StreamHeaderError::UnsupportedVersion)
+    );
+
+    assert_eq!(
+        read_header_of(mem, expected_data.clone()),
+        Some(StreamHeaderError::NoHeader)
+    );
+    assert_eq!(
+        read_header_of(mem, vec![]),
+        Some(StreamHeaderError::NoHeader)
+    );
+}
+
+unsafe fn test_compression(mem: &mut TestMemory) {
+    println!("  Testing stream compression");
+    DEST.clear();
+    let stream = alloc_stream(mem, Bytes(100));
+    (*stream).outputter = to_dest;
+    stream.start_framing();
+    stream.start_compression();
+    let data: Vec<u8> = (0..20000u32).map(|i| (i % 300 / 3) as u8).collect();
+    for chunk in data.chunks(5000) {
+        stream.cache_bytes(chunk.as_ptr(), Bytes(chunk.len() as u32)); // bypasses the cache
+        for byte in &chunk[..200] {
+            stream.cache_byte(*byte);
+        }
+    }
+    stream.shutdown();
+    // Offsets for alias back-references count bytes before compression
+    assert_eq!((*stream).written64, (data.len() + 4 * 200) as u64);
+
+    let mut expected_data = vec![];
+    for chunk in data.chunks(5000) {
+        expected_data.extend_from_slice(chunk);
+        expected_data.extend_from_slice(&chunk[..200]);
+    }
+    let header_size = STREAM_HEADER_SIZE.as_usize();
+    assert!(DEST.len() < expected_data.len() / 2);
+    let mut header_bytes = [0u8; 24];
+    header_bytes.copy_from_slice(&DEST[..header_size]);
+    assert_eq!(
+        StreamHeader::from_bytes(&header_bytes),
+        Some(StreamHeader {
+            version: STREAM_FORMAT_VERSION,
+            flags: STREAM_FRAMED | STREAM_COMPRESSED,
+            crc32: !update_crc32(!0, &expected_data),
+            length: (DEST.len() - header_size) as u64,
+        })
+    );
+
+    println!("  Testing stream decompression");
+    SOURCE = DEST.clone();
+    let reader = read_stream_of_source(mem, COMPRESSION_CHUNK_SIZE.as_u32());
+    assert!(reader.read_header().is_ok());
+    let mut read_data = vec![0u8; 6000];
+    reader.read_bytes(read_data.as_mut_ptr(), Bytes(6000));
+    assert_eq!(read_data, expected_data[..6000]);
+    reader.advance(Bytes(10000));
+    for byte in &expected_data[16000..16100] {
+        assert_eq!(reader.read_byte(), *byte);
+    }
+    let rest = expected_data.len() - 16100;
+    let mut read_data = vec![0u8; rest];
+    reader.read_bytes(read_data.as_mut_ptr(), Bytes(rest as u32));
+    assert_eq!(read_data, expected_data[16100..]);
+    assert_eq!(reader.remaining(), 0);
+
+    let mut corrupted = DEST.clone();
+    corrupted[header_size + 100] ^= 1;
+    SOURCE = corrupted;
+    let reader = read_stream_of_source(mem, COMPRESSION_CHUNK_SIZE.as_u32());
+    assert_eq!(
+        reader.read_header().err(),
+        Some(StreamHeaderError::ChecksumMismatch)
+    );
+}
+
+unsafe fn test_stable_memory(mem: &mut TestMemory) {
+    println!("  Testing stream to stable memory");
+    stable_memory::reset();
+    stable_memory::grow(2);
+
+    let data: Vec<u8> = (0..3000u32).map(|i| (i * 13 % 251) as u8).collect();
+    let start = 100;
+    let limit = start + data.len() as u64;
+    let stream = alloc_stream(mem, Bytes(100));
+    stream.setup_stable_dest(start, limit);
+    stream.cache_bytes(data[..500].as_ptr(), Bytes(500)); // bypasses the cache
+    assert_eq!(stable_memory::high_water_mark(), start + 500);
+    for byte in &data[500..1000] {
+        stream.cache_byte(*byte);
+    }
+    assert!(stable_memory::high_water_mark() < start + 1000); // the last bytes are cached
+    for chunk in data[1000..2900].chunks(50) {
+        stream.cache_bytes(chunk.as_ptr(), Bytes(chunk.len() as u32));
+    }
+    let place = stream.reserve(Bytes(100));
+    std::ptr::copy_nonoverlapping(data[2900..].as_ptr(), place, 100);
+    stream.shutdown();
+    assert_eq!(stable_memory::high_water_mark(), limit); // not past `limit64`
+    assert_eq!(stable_memory::read(start, data.len() as u64), data);
+    assert_eq!(stable_memory::read(0, start), vec![0; start as usize]);
+
+    println!("  Testing read stream from stable memory");
+    let reader = alloc_read_stream(mem, Bytes(64));
+    reader.setup_stable_source(start, limit);
+    let mut read_data = vec![0u8; data.len()];
+    reader.read_bytes(read_data.as_mut_ptr(), Bytes(1000));
+    for byte in read_data[1000..1100].iter_mut() {
+        *byte = reader.read_byte();
+    }
+    reader.advance(Bytes(900));
+    read_data[1100..2000].copy_from_slice(&data[1100..2000]);
+    reader.read_bytes(read_data[2000..].as_mut_ptr(), Bytes(1000));
+    assert_eq!(read_data, data);
+    assert_eq!(reader.remaining(), 0);
+
+    println!("  Testing stable variable images");
+    let limit = start + STREAM_HEADER_SIZE.as_u32() as u64 + data.len() as u64;
+    let stream = alloc_stream(mem, Bytes(100));
+    stream.setup_framed_stable_dest(start, limit);
+    stream.cache_bytes(data.as_ptr(), Bytes(data.len() as u32));
+    stream.shutdown();
+    assert_eq!((*stream).ptr64, limit);
+    let blob = stream_stable_take_blob(mem, start, limit);
+    let blob_data = std::slice::from_raw_parts(
+        blob.as_blob().payload_const(),
+        blob.as_blob().len().as_usize(),
+    );
+    assert_eq!(blob_data, &data[..]);
+    assert_eq!(
+        stable_memory::read(start, limit - start),
+        vec![0; (limit - start) as usize]
+    );
+
+    println!("  Testing compressed stable variable images");
+    // Incompressible data, written in pieces of varying sizes, stays within the bound reserved
+    // in `compile.ml`
+    let mut seed = 1u32;
+    let noise: Vec<u8> = (0..50_000)
+        .map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) as u8
+        })
+        .collect();
+    let size = STREAM_HEADER_SIZE.as_u32() as u64 + noise.len() as u64;
+    let limit = start + size + size / 64 + 64;
+    let stream = alloc_stream(mem, Bytes(0x8000));
+    stream.setup_compressed_stable_dest(start, limit);
+    let mut pieces = noise.as_slice();
+    for piece_size in [1, 129, 5000, 40_000].iter().cycle() {
+        let n = std::cmp::min(*piece_size, pieces.len());
+        if n == 1 {
+            stream.cache_byte(pieces[0]);
+        } else {
+            stream.cache_bytes(pieces.as_ptr(), Bytes(n as u32));
+        }
+        pieces = &pieces[n..];
+        if pieces.is_empty() {
+            break;
+        }
+    }
+    stream.shutdown();
+    assert!((*stream).ptr64 <= limit);
+    assert_eq!((*stream).written64, noise.len() as u64);
+    let blob = stream_stable_take_blob(mem, start, limit);
+    let blob_data = std::slice::from_raw_parts(
+        blob.as_blob().payload_const(),
+        blob.as_blob().len().as_usize(),
+    );
+    assert_eq!(blob_data, &noise[..]);
+    assert_eq!(
+        stable_memory::read(start, limit - start),
+        vec![0; (limit - start) as usize]
+    );
+
+    println!("  Testing compressed stream to stable memory");
+    let start = 64;
+    let limit = stable_memory::size() * stable_memory::PAGE_SIZE;
+    let stream = alloc_stream(mem, Bytes(100));
+    stream.setup_compressed_stable_dest(start, limit);
+    for slice in data.chunks(1000) {
+        stream.cache_bytes(slice.as_ptr(), Bytes(slice.len() as u32));
+    }
+    stream.shutdown();
+    let end = (*stream).ptr64;
+
+    let reader = alloc_read_stream(mem, COMPRESSION_CHUNK_SIZE);
+    reader.setup_framed_stable_source(start, end);
+    let mut read_data = vec![0u8; data.len()];
+    reader.read_bytes(read_data.as_mut_ptr(), Bytes(data.len() as u32));
+    assert_eq!(read_data, data);
+    assert_eq!(reader.remaining(), 0);
+
+    stable_memory::reset();
 }
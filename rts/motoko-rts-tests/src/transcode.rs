@@ -0,0 +1,160 @@
+//! UTF-16 and Latin-1 transcoding tests
+
+use crate::memory::TestMemory;
+
+use motoko_rts::memory::{alloc_blob, Memory};
+use motoko_rts::text::{blob_of_text, text_concat, text_of_str};
+use motoko_rts::transcode::{
+    text_of_latin1, text_of_utf16, text_to_latin1, text_to_utf16, TRANSCODE_REPLACE, TRANSCODE_TRAP,
+};
+use motoko_rts::types::{Bytes, Value, Words};
+
+use proptest::test_runner::{Config, TestCaseError, TestCaseResult, TestRunner};
+
+pub unsafe fn test() {
+    println!("Testing UTF-16 and Latin-1 transcoding ...");
+
+    let mut mem = TestMemory::new(Words(1024 * 1024));
+
+    println!("  Testing UTF-16 encoding");
+    let text = text_of_str(&mut mem, "a€😀");
+    assert_eq!(
+        blob_bytes(text_to_utf16(&mut mem, text, false)),
+        [0x61, 0x00, 0xAC, 0x20, 0x3D, 0xD8, 0x00, 0xDE]
+    );
+    assert_eq!(
+        blob_bytes(text_to_utf16(&mut mem, text, true)),
+        [0x00, 0x61, 0x20, 0xAC, 0xD8, 0x3D, 0xDE, 0x00]
+    );
+
+    println!("  Testing invalid UTF-16 replacement");
+    let invalid: [(&[u8], &str); 4] = [
+        (&[0x3D, 0xD8], "\u{FFFD}"),              // lone high surrogate
+        (&[0x00, 0xDE, 0x61, 0x00], "\u{FFFD}a"), // lone low surrogate
+        (&[0x3D, 0xD8, 0x61, 0x00], "\u{FFFD}a"), // high surrogate without low surrogate
+        (&[0x61, 0x00, 0x62], "a\u{FFFD}"),       // odd number of bytes
+    ];
+    for (bytes, expected) in invalid.iter() {
+        let blob = blob_of_bytes(&mut mem, bytes);
+        let text = text_of_utf16(&mut mem, blob, false, TRANSCODE_REPLACE);
+        assert_eq!(text_string(&mut mem, text), *expected);
+    }
+
+    println!("  Testing Latin-1 replacement");
+    let text = text_of_str(&mut mem, "é€ü");
+    assert_eq!(
+        blob_bytes(text_to_latin1(&mut mem, text, TRANSCODE_REPLACE)),
+        [0xE9, b'?', 0xFC]
+    );
+
+    drop(mem);
+
+    let mut proptest_runner = TestRunner::new(Config {
+        cases: 1_000,
+        failure_persistence: None,
+        ..Default::default()
+    });
+
+    println!("  Testing UTF-16 roundtrip");
+    proptest_runner
+        .run(
+            &proptest::collection::vec(proptest::string::string_regex(".{0, 20}").unwrap(), 1..20),
+            |strs| {
+                let mut mem = TestMemory::new(Words(1024 * 1024));
+                utf16_roundtrip(&mut mem, strs)
+            },
+        )
+        .unwrap();
+
+    println!("  Testing Latin-1 roundtrip");
+    proptest_runner
+        .run(
+            &proptest::collection::vec(
+                proptest::string::string_regex("[\\x00-\\xFF]{0, 20}").unwrap(),
+                1..20,
+            ),
+            |strs| {
+                let mut mem = TestMemory::new(Words(1024 * 1024));
+                latin1_roundtrip(&mut mem, strs)
+            },
+        )
+        .unwrap();
+}
+
+unsafe fn blob_bytes(blob: Value) -> Vec<u8> {
+    let blob = blob.as_blob();
+    std::slice::from_raw_parts(blob.payload_const(), blob.len().as_usize()).to_vec()
+}
+
+unsafe fn blob_of_bytes<M: Memory>(mem: &mut M, bytes: &[u8]) -> Value {
+    let blob = alloc_blob(mem, Bytes(bytes.len() as u32));
+    let payload = blob.as_blob_mut().payload_addr();
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), payload, bytes.len());
+    blob
+}
+
+unsafe fn text_string<M: Memory>(mem: &mut M, text: Value) -> String {
+    String::from_utf8(blob_bytes(blob_of_text(mem, text))).unwrap()
+}
+
+/// Builds a text rope from the given strings
+unsafe fn text_of_strs<M: Memory>(mem: &mut M, strs: &[String]) -> Value {
+    let mut text = text_of_str(mem, "");
+    for str in strs {
+        let str_text = text_of_str(mem, str);
+        text = text_concat(mem, text, str_text);
+    }
+    text
+}
+
+fn utf16_roundtrip<M: Memory>(mem: &mut M, strs: Vec<String>) -> TestCaseResult {
+    unsafe {
+        let text = text_of_strs(mem, &strs);
+        let expected = strs.concat();
+
+        for big_endian in [false, true] {
+            let blob = text_to_utf16(mem, text, big_endian);
+
+            let expected_bytes: Vec<u8> = expected
+                .encode_utf16()
+                .flat_map(|unit| {
+                    if big_endian {
+                        unit.to_be_bytes()
+                    } else {
+                        unit.to_le_bytes()
+                    }
+                })
+                .collect();
+            if blob_bytes(blob) != expected_bytes {
+                return Err(TestCaseError::Fail("text_to_utf16".into()));
+            }
+
+            let decoded = text_of_utf16(mem, blob, big_endian, TRANSCODE_TRAP);
+            if text_string(mem, decoded) != expected {
+                return Err(TestCaseError::Fail("text_of_utf16".into()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn latin1_roundtrip<M: Memory>(mem: &mut M, strs: Vec<String>) -> TestCaseResult {
+    unsafe {
+        let text = text_of_strs(mem, &strs);
+        let expected = strs.concat();
+
+        let blob = text_to_latin1(mem, text, TRANSCODE_TRAP);
+        let expected_bytes: Vec<u8> = expected.chars().map(|c| c as u8).collect();
+        if blob_bytes(blob) != expected_bytes {
+            return Err(TestCaseError::Fail("text_to_latin1".into()));
+        }
+
+        let decoded = text_of_latin1(mem, blob);
+        if text_string(mem, decoded) != expected {
+            return Err(TestCaseError::Fail("text_of_latin1".into()));
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! - libtommath memory management
 //! - libtommath wrappers
+//! - compact representation of numbers up to 64 bits
 //! - (s)leb128 encoding/decoding for bigints
 
 /*
@@ -33,9 +34,14 @@ This scheme makes the following assumptions:
 
 use crate::buf::{read_byte, Buf};
 use crate::mem_utils::memcpy_bytes;
-use crate::memory::Memory;
+use crate::memory::{alloc_blob, Memory};
+use crate::rts_trap_with;
+use crate::text::{alloc_text_blob, for_each_leaf, text_size};
 use crate::tommath_bindings::*;
-use crate::types::{size_of, BigInt, Bytes, Stream, Value, TAG_BIGINT};
+use crate::types::{size_of, BigInt, Bits64, Bytes, Stream, Value, TAG_BIGINT, TAG_BITS64};
+
+use core::cmp::Ordering;
+use core::slice;
 
 use motoko_rts_macros::ic_mem_fn;
 
@@ -158,8 +164,11 @@ unsafe fn tmp_bigint() -> mp_int {
     i
 }
 
-// Persists an mp_int from the stack on the heap
-unsafe fn persist_bigint(i: mp_int) -> Value {
+// Persists an mp_int from the stack on the heap, in the compact representation if it fits
+unsafe fn persist_bigint<M: Memory>(mem: &mut M, i: mp_int) -> Value {
+    if let Some(n) = mp_to_compact(&i) {
+        return alloc_compact(mem, n);
+    }
     if i.dp == core::ptr::null_mut() {
         panic!("persist_bigint: dp == NULL?");
     }
@@ -171,29 +180,169 @@ unsafe fn persist_bigint(i: mp_int) -> Value {
     Value::from_ptr(r as usize)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_of_word32(w: u32) -> Value {
-    let mut i = tmp_bigint();
-    mp_set_u32(&mut i, w);
-    persist_bigint(i)
+/*
+Compact representation
+----------------------
+
+Most numbers in programs fit in 64 bits, and for those an `mp_int` with a separately allocated
+digit array is a lot of overhead. Numbers in the `i64` range are therefore stored in a `TAG_BITS64`
+object (two words of payload) instead of a `TAG_BIGINT`. All functions below accept numbers in
+both representations (and scalars, the compact numbers of the generated code), and return
+compact numbers when the result fits in `i64`.
+
+When all arguments are compact, the common operations work on `i64`s directly and only fall back
+to libtommath when the result overflows. For the fallback, and for operations without a fast path,
+compact arguments are passed to libtommath as an `mp_int` with the digits on the stack (`MpArg`).
+Results of libtommath are converted to the compact representation by `persist_bigint` when they
+fit, so that further operations on them take the fast path again.
+
+Static numbers in the generated code are always `TAG_BIGINT`s, so the representation of a number
+is not unique and a compact number can be compared to a `TAG_BIGINT` of the same value.
+*/
+
+const MP_DIGIT_BIT: u32 = 28; // -DMP_32BIT
+
+/// Number of digits of the magnitude of an `i64`
+const COMPACT_DIGITS: usize = 3;
+
+/// The value of a number in the compact representation, or `None` for a `TAG_BIGINT`
+pub(crate) unsafe fn as_compact(n: Value) -> Option<i64> {
+    if n.is_scalar() {
+        Some(i64::from(n.get_signed_scalar()))
+    } else if n.tag() == TAG_BITS64 {
+        Some((*n.as_bits64()).bits() as i64)
+    } else {
+        None
+    }
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_of_int32(j: i32) -> Value {
-    let mut i = tmp_bigint();
-    mp_set_i32(&mut i, j);
-    persist_bigint(i)
+unsafe fn alloc_compact<M: Memory>(mem: &mut M, n: i64) -> Value {
+    let ptr = mem.alloc_words(size_of::<Bits64>());
+    let bits64 = ptr.get_ptr() as *mut Bits64;
+    (*bits64).header.tag = TAG_BITS64;
+    (*bits64).set_bits(n as u64);
+    ptr
+}
+
+// The value of an mp_int, if it fits in an `i64`
+unsafe fn mp_to_compact(p: *const mp_int) -> Option<i64> {
+    if mp_count_bits(p) > 64 {
+        return None;
+    }
+
+    let mut magnitude: u64 = 0;
+    for digit in slice::from_raw_parts((*p).dp, (*p).used as usize)
+        .iter()
+        .rev()
+    {
+        magnitude = (magnitude << MP_DIGIT_BIT) | u64::from(*digit);
+    }
+
+    if mp_isneg(p) {
+        if magnitude <= 1 << 63 {
+            Some((magnitude as i64).wrapping_neg())
+        } else {
+            None
+        }
+    } else if magnitude <= i64::MAX as u64 {
+        Some(magnitude as i64)
+    } else {
+        None
+    }
+}
+
+/// A number in either representation as libtommath input. Compact numbers are converted to an
+/// mp_int with the digits stored in the `MpArg`.
+enum MpArg {
+    BigInt(*mut BigInt),
+    Compact(mp_int, [mp_digit; COMPACT_DIGITS]),
+}
+
+impl MpArg {
+    unsafe fn new(n: Value) -> MpArg {
+        match as_compact(n) {
+            None => MpArg::BigInt(n.as_bigint()),
+            Some(c) => {
+                let mut digits = [0; COMPACT_DIGITS];
+                let mut magnitude = c.unsigned_abs();
+                let mut used = 0;
+                while magnitude != 0 {
+                    digits[used] = (magnitude & ((1 << MP_DIGIT_BIT) - 1)) as mp_digit;
+                    magnitude >>= MP_DIGIT_BIT;
+                    used += 1;
+                }
+                let mut i: mp_int = core::mem::zeroed();
+                i.used = used as i32;
+                i.alloc = COMPACT_DIGITS as i32;
+                i.sign = (c < 0) as mp_sign; // MP_NEG or MP_ZPOS
+                MpArg::Compact(i, digits)
+            }
+        }
+    }
+
+    /// Pointer to the mp_int, valid as long as the `MpArg` is not moved. Like
+    /// `BigInt::mp_int_ptr`, this returns a `*const` as libtommath must not modify the number.
+    unsafe fn ptr(&mut self) -> *const mp_int {
+        match self {
+            MpArg::BigInt(n) => n.mp_int_ptr(),
+            MpArg::Compact(i, digits) => {
+                i.dp = digits.as_mut_ptr();
+                i
+            }
+        }
+    }
+}
+
+// Fast path for unary operations on compact numbers. `None` when the argument is not compact or
+// `op` overflows.
+unsafe fn compact_op1<M: Memory, F: FnOnce(i64) -> Option<i64>>(
+    mem: &mut M,
+    a: Value,
+    op: F,
+) -> Option<Value> {
+    let r = op(as_compact(a)?)?;
+    Some(alloc_compact(mem, r))
+}
+
+// Fast path for binary operations on compact numbers, see `compact_op1`
+unsafe fn compact_op2<M: Memory, F: FnOnce(i64, i64) -> Option<i64>>(
+    mem: &mut M,
+    a: Value,
+    b: Value,
+    op: F,
+) -> Option<Value> {
+    let r = op(as_compact(a)?, as_compact(b)?)?;
+    Some(alloc_compact(mem, r))
+}
+
+#[ic_mem_fn]
+pub unsafe fn bigint_of_word32<M: Memory>(mem: &mut M, w: u32) -> Value {
+    alloc_compact(mem, i64::from(w))
+}
+
+#[ic_mem_fn(ic_only)]
+unsafe fn bigint_of_int32<M: Memory>(mem: &mut M, j: i32) -> Value {
+    alloc_compact(mem, i64::from(j))
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_to_word32_wrap(p: Value) -> u32 {
-    mp_get_u32(p.as_bigint().mp_int_ptr())
+    match as_compact(p) {
+        Some(n) => n as u32,
+        None => mp_get_u32(p.as_bigint().mp_int_ptr()),
+    }
 }
 
 #[no_mangle]
 unsafe extern "C" fn bigint_to_word32_trap(p: Value) -> u32 {
+    if let Some(n) = as_compact(p) {
+        if !(0..=i64::from(u32::MAX)).contains(&n) {
+            bigint_trap();
+        }
+        return n as u32;
+    }
+
     let mp_int = p.as_bigint().mp_int_ptr();
 
     if mp_isneg(mp_int) || mp_count_bits(mp_int) > 32 {
@@ -207,24 +356,40 @@ unsafe extern "C" fn bigint_to_word32_trap(p: Value) -> u32 {
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_to_word32_trap_with(p: Value, msg: Value) -> u32 {
-    let mp_int = p.as_bigint().mp_int_ptr();
+    let fits = match as_compact(p) {
+        Some(n) => (0..=i64::from(u32::MAX)).contains(&n),
+        None => {
+            let mp_int = p.as_bigint().mp_int_ptr();
+            !mp_isneg(mp_int) && mp_count_bits(mp_int) <= 32
+        }
+    };
 
-    if mp_isneg(mp_int) || mp_count_bits(mp_int) > 32 {
+    if !fits {
         crate::rts_trap(msg.as_blob().payload_const(), msg.as_blob().len());
     }
 
-    mp_get_u32(mp_int)
+    bigint_to_word32_wrap(p)
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_to_word64_wrap(p: Value) -> u64 {
-    mp_get_u64(p.as_bigint().mp_int_ptr())
+    match as_compact(p) {
+        Some(n) => n as u64,
+        None => mp_get_u64(p.as_bigint().mp_int_ptr()),
+    }
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_to_word64_trap(p: Value) -> u64 {
+    if let Some(n) = as_compact(p) {
+        if n < 0 {
+            bigint_trap();
+        }
+        return n as u64;
+    }
+
     let mp_int = p.as_bigint().mp_int_ptr();
 
     if mp_isneg(mp_int) || mp_count_bits(mp_int) > 64 {
@@ -234,193 +399,639 @@ unsafe extern "C" fn bigint_to_word64_trap(p: Value) -> u64 {
     mp_get_u64(mp_int)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_of_word64(w: u64) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_of_word64<M: Memory>(mem: &mut M, w: u64) -> Value {
+    if w <= i64::MAX as u64 {
+        return alloc_compact(mem, w as i64);
+    }
     let mut i = tmp_bigint();
     mp_set_u64(&mut i, w);
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_of_int64(j: i64) -> Value {
-    let mut i = tmp_bigint();
-    mp_set_i64(&mut i, j);
-    persist_bigint(i)
+#[ic_mem_fn(ic_only)]
+unsafe fn bigint_of_int64<M: Memory>(mem: &mut M, j: i64) -> Value {
+    alloc_compact(mem, j)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_of_float64(j: f64) -> Value {
+#[ic_mem_fn(ic_only)]
+unsafe fn bigint_of_float64<M: Memory>(mem: &mut M, j: f64) -> Value {
     // handle fast path: some numbers (when rounded towards zero by `j as i32`)
     // can be represented as `Int` without resorting to heap allocation, i.e.
     // in the range `-1073741824 == 0xc0000000 <= j as i32 <= 0x3fffffff == 1073741823`
     if j < 1073741824.0 && j > -1073741825.0 {
         return Value::from_signed_scalar(j as i32);
     }
+    // -2^63 <= j < 2^63 (false for NaN)
+    if j < 9223372036854775808.0 && j >= -9223372036854775808.0 {
+        return alloc_compact(mem, j as i64);
+    }
     let mut i = tmp_bigint();
     check(mp_set_double(&mut i, j));
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_to_float64(p: Value) -> f64 {
-    if p.is_scalar() {
-        p.get_signed_scalar() as f64
-    } else {
-        let mp_int = p.as_bigint().mp_int_ptr();
-        mp_get_double(mp_int)
+    // Rounding of `as` agrees with mp_get_double for numbers of up to 64 bits
+    match as_compact(p) {
+        Some(n) => n as f64,
+        None => mp_get_double(p.as_bigint().mp_int_ptr()),
+    }
+}
+
+// Compares two numbers, like mp_cmp
+unsafe fn bigint_cmp(a: Value, b: Value) -> mp_ord {
+    match (as_compact(a), as_compact(b)) {
+        (Some(a), Some(b)) => a.cmp(&b) as mp_ord,
+        _ => mp_cmp(MpArg::new(a).ptr(), MpArg::new(b).ptr()),
     }
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn bigint_eq(a: Value, b: Value) -> bool {
-    mp_cmp(a.as_bigint().mp_int_ptr(), b.as_bigint().mp_int_ptr()) == 0
+    bigint_cmp(a, b) == 0
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_lt(a: Value, b: Value) -> bool {
-    mp_cmp(a.as_bigint().mp_int_ptr(), b.as_bigint().mp_int_ptr()) < 0
+    bigint_cmp(a, b) < 0
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_gt(a: Value, b: Value) -> bool {
-    mp_cmp(a.as_bigint().mp_int_ptr(), b.as_bigint().mp_int_ptr()) > 0
+    bigint_cmp(a, b) > 0
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_le(a: Value, b: Value) -> bool {
-    mp_cmp(a.as_bigint().mp_int_ptr(), b.as_bigint().mp_int_ptr()) <= 0
+    bigint_cmp(a, b) <= 0
 }
 
 #[cfg(feature = "ic")]
 #[no_mangle]
 unsafe extern "C" fn bigint_ge(a: Value, b: Value) -> bool {
-    mp_cmp(a.as_bigint().mp_int_ptr(), b.as_bigint().mp_int_ptr()) >= 0
+    bigint_cmp(a, b) >= 0
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_add(a: Value, b: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_add<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, i64::checked_add) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_add(
-        a.as_bigint().mp_int_ptr(),
-        b.as_bigint().mp_int_ptr(),
-        &mut i,
-    ));
-    persist_bigint(i)
+    check(mp_add(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_sub(a: Value, b: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_sub<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, i64::checked_sub) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_sub(
-        a.as_bigint().mp_int_ptr(),
-        b.as_bigint().mp_int_ptr(),
-        &mut i,
-    ));
-    persist_bigint(i)
+    check(mp_sub(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_mul(a: Value, b: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_mul<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, i64::checked_mul) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_mul(
-        a.as_bigint().mp_int_ptr(),
-        b.as_bigint().mp_int_ptr(),
-        &mut i,
-    ));
-    persist_bigint(i)
+    check(mp_mul(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_pow(a: Value, b: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_pow<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
     let exp = bigint_to_word32_trap(b);
+    if let Some(r) = compact_op1(mem, a, |a| a.checked_pow(exp)) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_expt_u32(a.as_bigint().mp_int_ptr(), exp, &mut i));
-    persist_bigint(i)
+    check(mp_expt_u32(MpArg::new(a).ptr(), exp, &mut i));
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_div(a: Value, b: Value) -> Value {
+// Division rounds towards zero, and the remainder has the sign of the dividend, in both Rust and
+// libtommath. Division by zero (and the overflowing `i64::MIN / -1`) is left to libtommath.
+
+#[ic_mem_fn]
+pub unsafe fn bigint_div<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, i64::checked_div) {
+        return r;
+    }
     let mut i = tmp_bigint();
     check(mp_div(
-        a.as_bigint().mp_int_ptr(),
-        b.as_bigint().mp_int_ptr(),
+        MpArg::new(a).ptr(),
+        MpArg::new(b).ptr(),
         &mut i,
         core::ptr::null_mut(),
     ));
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_rem(a: Value, b: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_rem<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, i64::checked_rem) {
+        return r;
+    }
     let mut i = tmp_bigint();
     check(mp_div(
-        a.as_bigint().mp_int_ptr(),
-        b.as_bigint().mp_int_ptr(),
+        MpArg::new(a).ptr(),
+        MpArg::new(b).ptr(),
         core::ptr::null_mut(),
         &mut i,
     ));
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_neg(a: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_neg<M: Memory>(mem: &mut M, a: Value) -> Value {
+    if let Some(r) = compact_op1(mem, a, i64::checked_neg) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_neg(a.as_bigint().mp_int_ptr(), &mut i));
-    persist_bigint(i)
+    check(mp_neg(MpArg::new(a).ptr(), &mut i));
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_abs(a: Value) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_abs<M: Memory>(mem: &mut M, a: Value) -> Value {
+    if let Some(r) = compact_op1(mem, a, i64::checked_abs) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_abs(a.as_bigint().mp_int_ptr(), &mut i));
-    persist_bigint(i)
+    check(mp_abs(MpArg::new(a).ptr(), &mut i));
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
 #[no_mangle]
-unsafe extern "C" fn bigint_isneg(a: Value) -> bool {
-    mp_isneg(a.as_bigint().mp_int_ptr())
+pub unsafe extern "C" fn bigint_isneg(a: Value) -> bool {
+    match as_compact(a) {
+        Some(n) => n < 0,
+        None => mp_isneg(a.as_bigint().mp_int_ptr()),
+    }
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_lsh(a: Value, b: i32) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_lsh<M: Memory>(mem: &mut M, a: Value, b: i32) -> Value {
+    let lsh = |a: i64| {
+        if (0..64).contains(&b) && (a << b) >> b == a {
+            Some(a << b)
+        } else {
+            None
+        }
+    };
+    if let Some(r) = compact_op1(mem, a, lsh) {
+        return r;
+    }
     let mut i = tmp_bigint();
-    check(mp_mul_2d(a.as_bigint().mp_int_ptr(), b, &mut i));
-    persist_bigint(i)
+    check(mp_mul_2d(MpArg::new(a).ptr(), b, &mut i));
+    persist_bigint(mem, i)
 }
 
-#[cfg(feature = "ic")]
-#[no_mangle]
-unsafe extern "C" fn bigint_rsh(a: Value, b: i32) -> Value {
+/// Shifts the magnitude, so negative numbers are rounded towards zero
+#[ic_mem_fn]
+pub unsafe fn bigint_rsh<M: Memory>(mem: &mut M, a: Value, b: i32) -> Value {
+    let rsh = |a: i64| {
+        if b < 0 {
+            // Invalid, libtommath traps
+            None
+        } else {
+            let magnitude = a.unsigned_abs().checked_shr(b as u32).unwrap_or(0) as i64;
+            // `magnitude` is `i64::MIN` only for `a == i64::MIN` and `b == 0`
+            Some(if a < 0 {
+                magnitude.wrapping_neg()
+            } else {
+                magnitude
+            })
+        }
+    };
+    if let Some(r) = compact_op1(mem, a, rsh) {
+        return r;
+    }
     let mut i = tmp_bigint();
     check(mp_div_2d(
-        a.as_bigint().mp_int_ptr(),
+        MpArg::new(a).ptr(),
         b,
         &mut i,
         core::ptr::null_mut(),
     ));
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
 #[no_mangle]
-unsafe extern "C" fn bigint_count_bits(a: Value) -> i32 {
-    mp_count_bits(a.as_bigint().mp_int_ptr())
+pub unsafe extern "C" fn bigint_count_bits(a: Value) -> i32 {
+    match as_compact(a) {
+        Some(n) => 64 - n.unsigned_abs().leading_zeros() as i32,
+        None => mp_count_bits(a.as_bigint().mp_int_ptr()),
+    }
+}
+
+/*
+Bitwise operations. Negative numbers behave as in two's complement with infinitely many leading
+ones, e.g. `-1 & n == n` and `-2 & n` clears the lowest bit (libtommath's bitwise operations
+implement these semantics, as do the operators on `i64`).
+*/
+
+#[ic_mem_fn]
+pub unsafe fn bigint_and<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, |a, b| Some(a & b)) {
+        return r;
+    }
+    let mut i = tmp_bigint();
+    check(mp_and(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+#[ic_mem_fn]
+pub unsafe fn bigint_or<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, |a, b| Some(a | b)) {
+        return r;
+    }
+    let mut i = tmp_bigint();
+    check(mp_or(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+#[ic_mem_fn]
+pub unsafe fn bigint_xor<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    if let Some(r) = compact_op2(mem, a, b, |a, b| Some(a ^ b)) {
+        return r;
+    }
+    let mut i = tmp_bigint();
+    check(mp_xor(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// `-a - 1`
+#[ic_mem_fn]
+pub unsafe fn bigint_not<M: Memory>(mem: &mut M, a: Value) -> Value {
+    if let Some(r) = compact_op1(mem, a, |a| Some(!a)) {
+        return r;
+    }
+    let mut i = tmp_bigint();
+    check(mp_complement(MpArg::new(a).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// Number of one bits. For negative numbers this counts the one bits in the
+/// `bigint_2complement_bits` bits wide representation, e.g. 1 for `-1` (`1`) and `-2` (`10`).
+#[no_mangle]
+pub unsafe extern "C" fn bigint_popcount(a: Value) -> u32 {
+    let mut a_arg = MpArg::new(a);
+    let a = a_arg.ptr();
+    if mp_isneg(a) {
+        // The bits of `a` are the inverted bits of `-a - 1`
+        let mut c = tmp_bigint();
+        check(mp_complement(a, &mut c));
+        1 + mp_count_bits(&c) as u32 - mp_popcount(&c)
+    } else {
+        mp_popcount(a)
+    }
+}
+
+// Number of one bits in the magnitude
+unsafe fn mp_popcount(p: *const mp_int) -> u32 {
+    // Unused bits of the digits are always zero
+    slice::from_raw_parts((*p).dp, (*p).used as usize)
+        .iter()
+        .map(|digit| digit.count_ones())
+        .sum()
+}
+
+/// Whether bit `i` (counting from the least significant bit) is set
+#[no_mangle]
+pub unsafe extern "C" fn bigint_test_bit(a: Value, i: u32) -> bool {
+    if let Some(n) = as_compact(a) {
+        return (n >> i.min(63)) & 1 != 0;
+    }
+    let a = a.as_bigint().mp_int_ptr();
+    let digits = slice::from_raw_parts((*a).dp, (*a).used as usize);
+    if !mp_isneg(a) {
+        return digit_bit(digits, i);
+    }
+    // The bits of `-m` are the inverted bits of `m - 1`. Subtracting 1 flips the bits of `m` up to
+    // and including its lowest one bit, so below that bit `-m` has zeros, at that bit a one, and
+    // above it the inverted bits of `m`.
+    let lowest = digits
+        .iter()
+        .position(|digit| *digit != 0)
+        .map(|d| d as u32 * MP_DIGIT_BIT + digits[d].trailing_zeros())
+        .unwrap(); // not zero, as it is negative
+    match i.cmp(&lowest) {
+        Ordering::Less => false,
+        Ordering::Equal => true,
+        Ordering::Greater => !digit_bit(digits, i),
+    }
+}
+
+// Bit `i` of a magnitude
+fn digit_bit(digits: &[mp_digit], i: u32) -> bool {
+    match digits.get((i / MP_DIGIT_BIT) as usize) {
+        Some(digit) => (digit >> (i % MP_DIGIT_BIT)) & 1 != 0,
+        None => false,
+    }
+}
+
+// `2^i`
+unsafe fn bit_mask(i: u32) -> mp_int {
+    if i > i32::MAX as u32 {
+        rts_trap_with("bigint: bit index too large");
+    }
+    let mut mask = tmp_bigint();
+    check(mp_2expt(&mut mask, i as i32));
+    mask
+}
+
+/// `a` with bit `i` set
+#[ic_mem_fn]
+pub unsafe fn bigint_set_bit<M: Memory>(mem: &mut M, a: Value, i: u32) -> Value {
+    if bigint_test_bit(a, i) {
+        return a;
+    }
+    let mask = bit_mask(i);
+    let mut r = tmp_bigint();
+    check(mp_or(MpArg::new(a).ptr(), &mask, &mut r));
+    persist_bigint(mem, r)
+}
+
+/// `a` with bit `i` cleared
+#[ic_mem_fn]
+pub unsafe fn bigint_clear_bit<M: Memory>(mem: &mut M, a: Value, i: u32) -> Value {
+    if !bigint_test_bit(a, i) {
+        return a;
+    }
+    let mask = bit_mask(i);
+    let mut inverted_mask = tmp_bigint();
+    check(mp_complement(&mask, &mut inverted_mask));
+    let mut r = tmp_bigint();
+    check(mp_and(MpArg::new(a).ptr(), &inverted_mask, &mut r));
+    persist_bigint(mem, r)
+}
+
+/*
+Conversion to and from text in radix 2 to 36, using libtommath's radix routines. Digits above 9
+are upper case letters when converting to text, and are accepted in either case when parsing.
+Negative numbers have a leading `-`; parsing also accepts a leading `+`.
+*/
+
+/// Why `bigint_parse` rejected its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BigIntParseError {
+    /// The radix is not between 2 and 36
+    InvalidRadix,
+    /// There are no digits after the optional sign
+    NoDigits,
+    /// A character is not a digit in the radix
+    InvalidDigit,
+}
+
+fn valid_radix(radix: u32) -> bool {
+    (2..=36).contains(&radix)
+}
+
+#[ic_mem_fn]
+pub unsafe fn bigint_to_text<M: Memory>(mem: &mut M, n: Value, radix: u32) -> Value {
+    if !valid_radix(radix) {
+        rts_trap_with("bigint_to_text: radix must be between 2 and 36");
+    }
+
+    // Upper bound on the number of digits, plus sign and the NUL terminator that mp_to_radix
+    // writes. The blob is shrunk to the actual size afterwards.
+    let mut n_arg = MpArg::new(n);
+    let n = n_arg.ptr();
+    let max_digits = mp_count_bits(n) as u32 / (31 - radix.leading_zeros()) + 1;
+    let max_len = Bytes(max_digits + 2);
+
+    let text = alloc_text_blob(mem, max_len);
+    let mut written: usize = 0;
+    check(mp_to_radix(
+        n,
+        text.as_blob_mut().payload_addr() as *mut libc::c_char,
+        max_len.as_usize(),
+        &mut written,
+        radix as i32,
+    ));
+
+    // `written` includes the NUL terminator
+    text.as_blob_mut().shrink(Bytes(written as u32 - 1));
+    text
+}
+
+/// Parses a text as a number in the given radix
+pub unsafe fn bigint_parse<M: Memory>(
+    mem: &mut M,
+    text: Value,
+    radix: u32,
+) -> Result<Value, BigIntParseError> {
+    if !valid_radix(radix) {
+        return Err(BigIntParseError::InvalidRadix);
+    }
+
+    // mp_read_radix needs a NUL-terminated string
+    let len = text_size(text);
+    let buf = alloc_blob(mem, len + Bytes(1)).as_blob_mut();
+    let mut dest = buf.payload_addr();
+    for_each_leaf(text, &mut |leaf| {
+        memcpy_bytes(
+            dest as usize,
+            leaf.as_ptr() as usize,
+            Bytes(leaf.len() as u32),
+        );
+        dest = dest.add(leaf.len());
+    });
+    *dest = 0;
+
+    let mut str = slice::from_raw_parts(buf.payload_const(), len.as_usize());
+    let digits = match str.first() {
+        Some(b'+') => {
+            // libtommath only knows about `-`
+            str = &str[1..];
+            str
+        }
+        Some(b'-') => &str[1..],
+        _ => str,
+    };
+
+    if digits.is_empty() {
+        return Err(BigIntParseError::NoDigits);
+    }
+
+    if !digits
+        .iter()
+        .all(|c| char::from(*c).to_digit(radix).is_some())
+    {
+        return Err(BigIntParseError::InvalidDigit);
+    }
+
+    let mut i = tmp_bigint();
+    check(mp_read_radix(
+        &mut i,
+        str.as_ptr() as *const libc::c_char,
+        radix as i32,
+    ));
+    Ok(persist_bigint(mem, i))
+}
+
+#[ic_mem_fn(ic_only)]
+unsafe fn bigint_of_text<M: Memory>(mem: &mut M, text: Value, radix: u32) -> Value {
+    match bigint_parse(mem, text, radix) {
+        Ok(n) => n,
+        Err(BigIntParseError::InvalidRadix) => {
+            rts_trap_with("bigint_of_text: radix must be between 2 and 36")
+        }
+        Err(BigIntParseError::NoDigits) => rts_trap_with("bigint_of_text: no digits"),
+        Err(BigIntParseError::InvalidDigit) => rts_trap_with("bigint_of_text: invalid digit"),
+    }
+}
+
+/*
+Number-theoretic operations. Errors from libtommath (a modulus that is not positive, a number
+without inverse, the square root of a negative number) trap, like division by zero.
+*/
+
+/// `b^e mod m`, in `[0, m)`. A negative exponent uses the inverse of `b` modulo `m`.
+#[ic_mem_fn]
+pub unsafe fn bigint_modpow<M: Memory>(mem: &mut M, b: Value, e: Value, m: Value) -> Value {
+    let mut i = tmp_bigint();
+    check(mp_exptmod(
+        MpArg::new(b).ptr(),
+        MpArg::new(e).ptr(),
+        MpArg::new(m).ptr(),
+        &mut i,
+    ));
+    persist_bigint(mem, i)
+}
+
+/// Inverse of `a` modulo `m`, in `[0, m)`. Traps if `a` and `m` are not coprime.
+#[ic_mem_fn]
+pub unsafe fn bigint_modinv<M: Memory>(mem: &mut M, a: Value, m: Value) -> Value {
+    let mut i = tmp_bigint();
+    check(mp_invmod(MpArg::new(a).ptr(), MpArg::new(m).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// Greatest common divisor, non-negative
+#[ic_mem_fn]
+pub unsafe fn bigint_gcd<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    let mut i = tmp_bigint();
+    check(mp_gcd(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// Least common multiple, non-negative
+#[ic_mem_fn]
+pub unsafe fn bigint_lcm<M: Memory>(mem: &mut M, a: Value, b: Value) -> Value {
+    let mut i = tmp_bigint();
+    check(mp_lcm(MpArg::new(a).ptr(), MpArg::new(b).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// Integer square root (rounded down)
+#[ic_mem_fn]
+pub unsafe fn bigint_sqrt<M: Memory>(mem: &mut M, a: Value) -> Value {
+    let mut i = tmp_bigint();
+    check(mp_sqrt(MpArg::new(a).ptr(), &mut i));
+    persist_bigint(mem, i)
+}
+
+/// Jacobi symbol `(a/n)` for odd positive `n`. Other `n` give the Kronecker symbol, which extends
+/// the Jacobi symbol to all integers.
+#[no_mangle]
+pub unsafe extern "C" fn bigint_jacobi(a: Value, n: Value) -> i32 {
+    let mut symbol: libc::c_int = 0;
+    check(mp_kronecker(
+        MpArg::new(a).ptr(),
+        MpArg::new(n).ptr(),
+        &mut symbol,
+    ));
+    symbol
+}
+
+/// Primes used for trial division and as Miller-Rabin bases in `bigint_is_prime`
+const SMALL_PRIMES: [u32; 46] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199,
+];
+
+/// Probabilistic primality test: a Baillie-PSW test (Miller-Rabin with base 2 and a strong Lucas
+/// test, no composite passing it is known), followed by Miller-Rabin tests with the number of
+/// rounds recommended for the size of `a`.
+///
+/// The Miller-Rabin bases are the first primes rather than random numbers, so the result is
+/// deterministic. This function does not use `mp_prime_is_prime`, as that needs a source of
+/// randomness.
+#[no_mangle]
+pub unsafe extern "C" fn bigint_is_prime(a: Value) -> bool {
+    let mut a_arg = MpArg::new(a);
+    let a = a_arg.ptr();
+
+    if mp_isneg(a) || mp_count_bits(a) <= 1 {
+        // 0 and 1
+        return false;
+    }
+
+    let mut rem: mp_digit = 0;
+    for p in SMALL_PRIMES.iter() {
+        check(mp_div_d(a, *p as mp_digit, core::ptr::null_mut(), &mut rem));
+        if rem == 0 {
+            return mp_count_bits(a) <= 8 && mp_get_u32(a) == *p;
+        }
+    }
+    if mp_count_bits(a) <= 15 {
+        // Smaller than 199^2
+        return true;
+    }
+
+    // The strong Lucas test does not terminate in reasonable time for perfect squares
+    let mut root = tmp_bigint();
+    check(mp_sqrt(a, &mut root));
+    let mut square = tmp_bigint();
+    check(mp_mul(&root, &root, &mut square));
+    if mp_cmp(&square, a) == 0 {
+        return false;
+    }
+
+    let mut result: mp_bool = 0;
+    let mut base = tmp_bigint();
+    mp_set_u32(&mut base, 2);
+    check(mp_prime_miller_rabin(a, &base, &mut result));
+    if result == 0 {
+        return false;
+    }
+
+    check(mp_prime_strong_lucas_selfridge(a, &mut result));
+    if result == 0 {
+        return false;
+    }
+
+    // Up to 80 bits, libtommath suggests a deterministic test: the first 13 primes as bases give
+    // correct results for all numbers below 3.3 * 10^24
+    let trials = mp_prime_rabin_miller_trials(mp_count_bits(a));
+    let rounds = if trials < 0 { 12 } else { trials as usize };
+    for p in SMALL_PRIMES[1..].iter().take(rounds) {
+        mp_set_u32(&mut base, *p);
+        check(mp_prime_miller_rabin(a, &base, &mut result));
+        if result == 0 {
+            return false;
+        }
+    }
+
+    true
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn bigint_leb128_size(a: Value) -> u32 {
-    if mp_iszero(a.as_bigint().mp_int_ptr()) {
+    if mp_iszero(MpArg::new(a).ptr()) {
         1
     } else {
         (bigint_count_bits(a) as u32 + 6) / 7 // divide by 7, round up
@@ -449,20 +1060,27 @@ unsafe fn bigint_leb128_encode_go(tmp: *mut mp_int, mut buf: *mut u8, add_bit: b
 #[no_mangle]
 pub unsafe extern "C" fn bigint_leb128_encode(n: Value, buf: *mut u8) {
     let mut tmp: mp_int = core::mem::zeroed(); // or core::mem::uninitialized?
-    check(mp_init_copy(&mut tmp, n.as_bigint().mp_int_ptr()));
+    check(mp_init_copy(&mut tmp, MpArg::new(n).ptr()));
     bigint_leb128_encode_go(&mut tmp, buf, false)
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn bigint_leb128_stream_encode(stream: *mut Stream, n: Value) {
     let mut tmp: mp_int = core::mem::zeroed(); // or core::mem::uninitialized?
-    check(mp_init_copy(&mut tmp, n.as_bigint().mp_int_ptr()));
+    check(mp_init_copy(&mut tmp, MpArg::new(n).ptr()));
     stream.write_leb128(&mut tmp, false)
 }
 
 #[no_mangle]
 unsafe extern "C" fn bigint_2complement_bits(n: Value) -> u32 {
-    let mp_int = n.as_bigint().mp_int_ptr();
+    if let Some(n) = as_compact(n) {
+        // Bits of `n` or `-n - 1`, plus the sign bit
+        let magnitude = if n < 0 { !n } else { n };
+        return 65 - magnitude.leading_zeros();
+    }
+
+    let mut n_arg = MpArg::new(n);
+    let mp_int = n_arg.ptr();
     if mp_isneg(mp_int) {
         let mut tmp: mp_int = core::mem::zeroed(); // or core::mem::uninitialized?
         check(mp_init_copy(&mut tmp, mp_int));
@@ -481,7 +1099,7 @@ pub unsafe extern "C" fn bigint_sleb128_size(n: Value) -> u32 {
 #[no_mangle]
 pub unsafe extern "C" fn bigint_sleb128_encode(n: Value, buf: *mut u8) {
     let mut tmp: mp_int = core::mem::zeroed(); // or core::mem::uninitialized?
-    check(mp_init_copy(&mut tmp, n.as_bigint().mp_int_ptr()));
+    check(mp_init_copy(&mut tmp, MpArg::new(n).ptr()));
 
     if mp_isneg(&tmp) {
         // Turn negative numbers into the two's complement of the right size
@@ -499,7 +1117,7 @@ pub unsafe extern "C" fn bigint_sleb128_encode(n: Value, buf: *mut u8) {
 #[no_mangle]
 pub unsafe extern "C" fn bigint_sleb128_stream_encode(stream: *mut Stream, n: Value) {
     let mut tmp: mp_int = core::mem::zeroed(); // or core::mem::uninitialized?
-    check(mp_init_copy(&mut tmp, n.as_bigint().mp_int_ptr()));
+    check(mp_init_copy(&mut tmp, MpArg::new(n).ptr()));
 
     if mp_isneg(&tmp) {
         // Turn negative numbers into the two's complement of the right size
@@ -514,8 +1132,8 @@ pub unsafe extern "C" fn bigint_sleb128_stream_encode(stream: *mut Stream, n: Va
     }
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_leb128_decode(buf: *mut Buf) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_leb128_decode<M: Memory>(mem: &mut M, buf: *mut Buf) -> Value {
     let mut i = tmp_bigint();
     let mut tmp = tmp_bigint();
 
@@ -532,16 +1150,16 @@ pub unsafe extern "C" fn bigint_leb128_decode(buf: *mut Buf) -> Value {
         }
     }
 
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
 /// Decode at most 5 bytes of LEB128 data to a compact bignum `Value`.
 /// The number of 7-bit chunks are located in the lower portion of `leb`
 /// as indicated by `bits`.
 ///
-#[cfg(feature = "ic")]
-#[no_mangle]
-pub unsafe extern "C" fn bigint_leb128_decode_word64(
+#[ic_mem_fn(ic_only)]
+pub unsafe fn bigint_leb128_decode_word64<M: Memory>(
+    mem: &mut M,
     mut leb: u64,
     mut bits: u64,
     buf: *mut Buf,
@@ -570,11 +1188,11 @@ pub unsafe extern "C" fn bigint_leb128_decode_word64(
         return Value::from_signed_scalar(tentative);
     }
 
-    bigint_of_word64(acc)
+    bigint_of_word64(mem, acc)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn bigint_sleb128_decode(buf: *mut Buf) -> Value {
+#[ic_mem_fn]
+pub unsafe fn bigint_sleb128_decode<M: Memory>(mem: &mut M, buf: *mut Buf) -> Value {
     let mut i = tmp_bigint();
     let mut tmp = tmp_bigint();
 
@@ -600,16 +1218,16 @@ pub unsafe extern "C" fn bigint_sleb128_decode(buf: *mut Buf) -> Value {
         check(mp_sub(&mut i, &big, &mut i));
     }
 
-    persist_bigint(i)
+    persist_bigint(mem, i)
 }
 
 /// Decode at most 5 bytes of SLEB128 data to a compact bignum `Value`.
 /// The number of 7-bit chunks are located in the lower portion of `sleb`
 /// as indicated by `bits`.
 ///
-#[cfg(feature = "ic")]
-#[no_mangle]
-pub unsafe extern "C" fn bigint_sleb128_decode_word64(
+#[ic_mem_fn(ic_only)]
+pub unsafe fn bigint_sleb128_decode_word64<M: Memory>(
+    mem: &mut M,
     mut sleb: u64,
     mut bits: u64,
     buf: *mut Buf,
@@ -640,5 +1258,5 @@ pub unsafe extern "C" fn bigint_sleb128_decode_word64(
         return Value::from_signed_scalar(tentative);
     }
 
-    bigint_of_int64(signed)
+    bigint_of_int64(mem, signed)
 }
@@ -29,21 +29,6 @@ pub(crate) unsafe fn read_byte(buf: *mut Buf) -> u8 {
     byte
 }
 
-#[cfg(feature = "ic")]
-/// Read a little-endian word
-pub(crate) unsafe fn read_word(buf: *mut Buf) -> u32 {
-    if (*buf).ptr.add(3) >= (*buf).end {
-        idl_trap_with("word read out of buffer");
-    }
-
-    let p = (*buf).ptr;
-    let word = u32::from_le_bytes([*p, *p.add(1), *p.add(2), *p.add(3)]);
-
-    (*buf).ptr = (*buf).ptr.add(4);
-
-    word
-}
-
 #[cfg(feature = "ic")]
 unsafe fn advance(buf: *mut Buf, n: u32) {
     if (*buf).ptr.add(n as usize) > (*buf).end {
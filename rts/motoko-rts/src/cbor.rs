@@ -0,0 +1,574 @@
+//! CBOR decoding, for IC certificates and request payloads. Reference: RFC 8949
+//!
+//! A data item starts with a head: the major type in the top 3 bits of the first byte, and the
+//! "additional information" in the low 5 bits. The additional information is the argument itself
+//! (0 to 23), or says that the argument follows in 1, 2, 4 or 8 bytes (24 to 27, big-endian), or
+//! that the length is indefinite (31). Indefinite-length strings, arrays and maps end with a
+//! "break" byte (0xFF).
+//!
+//! There are two APIs:
+//!
+//! * Streaming: `cbor_read_head` and the typed readers below read one head (and the contents of
+//!   strings) from a `Buf`, and `cbor_skip_checked` skips a complete data item.
+//!
+//! * `cbor_decode` decodes a complete data item to a Motoko value: unsigned integers are `Nat`,
+//!   byte strings `Blob`, text strings `Text`, arrays arrays, and maps arrays of pairs. Tags are
+//!   dropped, e.g. the "self-described CBOR" tag at the start of certificates. Other data items
+//!   (negative integers, floats and simple values) are rejected.
+//!
+//! All functions check their input, so malformed input results in a `CborError` (or a trap),
+//! instead of reading out of the buffer.
+
+use crate::bigint::bigint_of_word64;
+use crate::buf::{read_byte, Buf};
+use crate::memory::{alloc_array, alloc_blob, Memory};
+use crate::print::WriteBuf;
+use crate::rts_trap_with;
+use crate::text::{alloc_text_blob, text_of_ptr_size};
+use crate::types::{Array, Bytes, Value};
+use crate::utf8::utf8_valid;
+
+use core::fmt;
+use core::slice;
+
+use motoko_rts_macros::ic_mem_fn;
+
+// Major types
+pub const CBOR_UNSIGNED: u8 = 0;
+pub const CBOR_NEGATIVE: u8 = 1;
+pub const CBOR_BYTES: u8 = 2;
+pub const CBOR_TEXT: u8 = 3;
+pub const CBOR_ARRAY: u8 = 4;
+pub const CBOR_MAP: u8 = 5;
+pub const CBOR_TAG: u8 = 6;
+pub const CBOR_SIMPLE: u8 = 7;
+
+/// Additional information of indefinite lengths, and of the break
+pub const CBOR_INDEFINITE: u8 = 31;
+
+/// Maximum nesting of arrays and maps
+pub const CBOR_MAX_DEPTH: usize = 128;
+
+/// Why a data item cannot be decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CborError {
+    /// The input ends within the data item
+    EndOfInput,
+    /// Additional information 28 to 30, which is reserved
+    ReservedInfo,
+    /// An indefinite length for an integer or tag, or a chunk of an indefinite-length string that
+    /// is not a definite-length string of the same major type
+    InvalidIndefinite,
+    /// A break that does not end an indefinite-length string, array or map (with an even number
+    /// of items)
+    UnexpectedBreak,
+    /// A simple value below 32 in two bytes
+    InvalidSimple,
+    NotUtf8,
+    /// A length that does not fit in 32 bits
+    TooLarge,
+    NestedTooDeeply,
+    /// A data item of another major type than expected
+    UnexpectedType {
+        expected: u8,
+        found: u8,
+    },
+    /// A data item that is not supported: an indefinite-length string where a slice of the input
+    /// is returned, or a data item without a Motoko value in `cbor_decode`
+    Unsupported,
+    /// Bytes after the data item
+    TrailingBytes,
+}
+
+pub type CborResult<T> = Result<T, CborError>;
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CborError::EndOfInput => f.write_str("cbor: unexpected end of input"),
+            CborError::ReservedInfo => f.write_str("cbor: reserved additional information"),
+            CborError::InvalidIndefinite => f.write_str("cbor: invalid indefinite length"),
+            CborError::UnexpectedBreak => f.write_str("cbor: unexpected break"),
+            CborError::InvalidSimple => f.write_str("cbor: invalid simple value"),
+            CborError::NotUtf8 => f.write_str("cbor: text string not UTF-8"),
+            CborError::TooLarge => f.write_str("cbor: length too large"),
+            CborError::NestedTooDeeply => write!(
+                f,
+                "cbor: arrays and maps nested more than {} levels",
+                CBOR_MAX_DEPTH
+            ),
+            CborError::UnexpectedType { expected, found } => {
+                write!(f, "cbor: expected major type {}, found {}", expected, found)
+            }
+            CborError::Unsupported => f.write_str("cbor: unsupported data item"),
+            CborError::TrailingBytes => f.write_str("cbor: bytes after the data item"),
+        }
+    }
+}
+
+unsafe fn or_trap<T>(result: CborResult<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            let mut buf = [0u8; 100];
+            let mut fmt = WriteBuf::new(&mut buf);
+            let _ = fmt::write(&mut fmt, format_args!("{}", err));
+            rts_trap_with(fmt.as_str())
+        }
+    }
+}
+
+//
+// Streaming
+//
+
+/// The head of a data item
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CborHead {
+    pub major: u8,
+    pub info: u8,
+    /// The argument: the value of an integer or simple value, the length of a string or array,
+    /// the number of entries of a map, the tag number, or the bits of a float. 0 for indefinite
+    /// lengths.
+    pub arg: u64,
+}
+
+impl CborHead {
+    pub fn is_indefinite(&self) -> bool {
+        self.info == CBOR_INDEFINITE
+    }
+
+    pub fn is_break(&self) -> bool {
+        self.major == CBOR_SIMPLE && self.info == CBOR_INDEFINITE
+    }
+}
+
+unsafe fn remaining(buf: *mut Buf) -> usize {
+    ((*buf).end as usize).saturating_sub((*buf).ptr as usize)
+}
+
+unsafe fn read_u8(buf: *mut Buf) -> CborResult<u8> {
+    if remaining(buf) == 0 {
+        return Err(CborError::EndOfInput);
+    }
+    Ok(read_byte(buf))
+}
+
+/// Reads `n` bytes, without copying them
+unsafe fn read_slice<'a>(buf: *mut Buf, n: u64) -> CborResult<&'a [u8]> {
+    if n > remaining(buf) as u64 {
+        return Err(CborError::EndOfInput);
+    }
+    let p = (*buf).ptr;
+    (*buf).ptr = p.add(n as usize);
+    Ok(slice::from_raw_parts(p, n as usize))
+}
+
+/// Reads the head of a data item, checking that it is well-formed
+pub unsafe fn cbor_read_head(buf: *mut Buf) -> CborResult<CborHead> {
+    let byte = read_u8(buf)?;
+    let major = byte >> 5;
+    let info = byte & 0x1F;
+    let arg = match info {
+        0..=23 => info as u64,
+        24..=27 => {
+            let bytes = read_slice(buf, 1 << (info - 24))?;
+            bytes.iter().fold(0, |arg, &b| arg << 8 | b as u64)
+        }
+        28..=30 => return Err(CborError::ReservedInfo),
+        _ => {
+            if let CBOR_UNSIGNED | CBOR_NEGATIVE | CBOR_TAG = major {
+                return Err(CborError::InvalidIndefinite);
+            }
+            0
+        }
+    };
+    if major == CBOR_SIMPLE && info == 24 && arg < 32 {
+        return Err(CborError::InvalidSimple);
+    }
+    Ok(CborHead { major, info, arg })
+}
+
+/// Reads the head of a data item of major type `expected`
+unsafe fn read_head_of(buf: *mut Buf, expected: u8) -> CborResult<CborHead> {
+    let head = cbor_read_head(buf)?;
+    if head.major != expected {
+        return Err(CborError::UnexpectedType {
+            expected,
+            found: head.major,
+        });
+    }
+    Ok(head)
+}
+
+/// Reads an unsigned integer
+pub unsafe fn cbor_read_nat(buf: *mut Buf) -> CborResult<u64> {
+    Ok(read_head_of(buf, CBOR_UNSIGNED)?.arg)
+}
+
+/// Reads a tag, which is followed by the tagged data item
+pub unsafe fn cbor_read_tag(buf: *mut Buf) -> CborResult<u64> {
+    Ok(read_head_of(buf, CBOR_TAG)?.arg)
+}
+
+/// Reads a definite-length byte string
+pub unsafe fn cbor_read_bytes<'a>(buf: *mut Buf) -> CborResult<&'a [u8]> {
+    let head = read_head_of(buf, CBOR_BYTES)?;
+    if head.is_indefinite() {
+        return Err(CborError::Unsupported);
+    }
+    read_slice(buf, head.arg)
+}
+
+/// Reads a definite-length text string
+pub unsafe fn cbor_read_text<'a>(buf: *mut Buf) -> CborResult<&'a str> {
+    let head = read_head_of(buf, CBOR_TEXT)?;
+    if head.is_indefinite() {
+        return Err(CborError::Unsupported);
+    }
+    let bytes = read_slice(buf, head.arg)?;
+    if !utf8_valid(bytes.as_ptr() as *const _, bytes.len() as u32) {
+        return Err(CborError::NotUtf8);
+    }
+    Ok(core::str::from_utf8_unchecked(bytes))
+}
+
+/// Reads the head of an array, and returns its length, or `None` if it is indefinite. The items
+/// of an indefinite-length array are followed by a break, see `cbor_read_break`.
+pub unsafe fn cbor_read_array(buf: *mut Buf) -> CborResult<Option<u32>> {
+    length(read_head_of(buf, CBOR_ARRAY)?)
+}
+
+/// Reads the head of a map, and returns its number of entries, or `None` if it is indefinite
+pub unsafe fn cbor_read_map(buf: *mut Buf) -> CborResult<Option<u32>> {
+    length(read_head_of(buf, CBOR_MAP)?)
+}
+
+fn length(head: CborHead) -> CborResult<Option<u32>> {
+    if head.is_indefinite() {
+        return Ok(None);
+    }
+    if head.arg > u32::MAX as u64 {
+        return Err(CborError::TooLarge);
+    }
+    Ok(Some(head.arg as u32))
+}
+
+/// Reads a break, if it is next
+pub unsafe fn cbor_read_break(buf: *mut Buf) -> CborResult<bool> {
+    if remaining(buf) == 0 {
+        return Err(CborError::EndOfInput);
+    }
+    if *(*buf).ptr == 0xFF {
+        (*buf).ptr = (*buf).ptr.add(1);
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// Reads the chunks of an indefinite-length string of major type `major`, up to the break, and
+/// calls `f` with each
+unsafe fn read_chunks<F: FnMut(&[u8])>(buf: *mut Buf, major: u8, mut f: F) -> CborResult<()> {
+    loop {
+        let head = cbor_read_head(buf)?;
+        if head.is_break() {
+            return Ok(());
+        }
+        if head.major != major || head.is_indefinite() {
+            return Err(CborError::InvalidIndefinite);
+        }
+        let chunk = read_slice(buf, head.arg)?;
+        if major == CBOR_TEXT && !utf8_valid(chunk.as_ptr() as *const _, chunk.len() as u32) {
+            return Err(CborError::NotUtf8);
+        }
+        f(chunk);
+    }
+}
+
+/// Items left in an array or map being skipped
+#[derive(Clone, Copy)]
+enum Left {
+    Items(u64),
+    /// An indefinite-length array, or map with `odd` tells whether it has a key without value
+    Indefinite {
+        odd: Option<bool>,
+    },
+}
+
+/// Skips a complete data item, checking that it is well-formed
+pub unsafe fn cbor_skip_checked(buf: *mut Buf) -> CborResult<()> {
+    let mut stack = [Left::Items(0); CBOR_MAX_DEPTH];
+    let mut depth = 0;
+    // Whether a tag is waiting for its data item
+    let mut tagged = false;
+    loop {
+        let head = cbor_read_head(buf)?;
+        if head.is_break() {
+            match stack[..depth].last() {
+                Some(Left::Indefinite { odd }) if !tagged && *odd != Some(true) => depth -= 1,
+                _ => return Err(CborError::UnexpectedBreak),
+            }
+        } else {
+            tagged = false;
+            match head.major {
+                CBOR_BYTES | CBOR_TEXT if head.is_indefinite() => {
+                    read_chunks(buf, head.major, |_| {})?
+                }
+                CBOR_BYTES => {
+                    read_slice(buf, head.arg)?;
+                }
+                CBOR_TEXT => {
+                    let bytes = read_slice(buf, head.arg)?;
+                    if !utf8_valid(bytes.as_ptr() as *const _, bytes.len() as u32) {
+                        return Err(CborError::NotUtf8);
+                    }
+                }
+                CBOR_ARRAY | CBOR_MAP => {
+                    let left = if head.is_indefinite() {
+                        let odd = if head.major == CBOR_MAP {
+                            Some(false)
+                        } else {
+                            None
+                        };
+                        Left::Indefinite { odd }
+                    } else {
+                        let items = if head.major == CBOR_MAP {
+                            head.arg.saturating_mul(2)
+                        } else {
+                            head.arg
+                        };
+                        // Every item takes at least one byte
+                        if items > remaining(buf) as u64 {
+                            return Err(CborError::EndOfInput);
+                        }
+                        Left::Items(items)
+                    };
+                    if let Left::Indefinite { .. } | Left::Items(1..) = left {
+                        if depth == CBOR_MAX_DEPTH {
+                            return Err(CborError::NestedTooDeeply);
+                        }
+                        stack[depth] = left;
+                        depth += 1;
+                        continue;
+                    }
+                }
+                CBOR_TAG => {
+                    tagged = true;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        // A data item is complete, which may complete the enclosing arrays and maps
+        loop {
+            if depth == 0 {
+                return Ok(());
+            }
+            match &mut stack[depth - 1] {
+                Left::Indefinite { odd } => {
+                    if let Some(odd) = odd {
+                        *odd = !*odd;
+                    }
+                    break;
+                }
+                Left::Items(items) => {
+                    *items -= 1;
+                    if *items > 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Skips a complete data item, trapping if it is malformed
+#[no_mangle]
+pub unsafe extern "C" fn cbor_skip(buf: *mut Buf) {
+    or_trap(cbor_skip_checked(buf))
+}
+
+//
+// Motoko values
+//
+
+/// Decodes the data item in `blob`, see the module documentation. Traps if it is malformed or
+/// unsupported, or followed by other bytes.
+#[ic_mem_fn]
+pub unsafe fn cbor_decode<M: Memory>(mem: &mut M, blob: Value) -> Value {
+    let blob = blob.as_blob();
+    let start = blob.payload_const() as *mut u8;
+    let mut buf = Buf {
+        ptr: start,
+        end: start.add(blob.len().as_usize()),
+    };
+    let result = cbor_decode_checked(mem, &mut buf);
+    or_trap(result.and_then(|value| match remaining(&mut buf) {
+        0 => Ok(value),
+        _ => Err(CborError::TrailingBytes),
+    }))
+}
+
+/// An array or map being decoded
+#[derive(Clone, Copy)]
+struct Frame {
+    array: *mut Array,
+    /// Number of items decoded, both keys and values in maps
+    index: u32,
+    /// Number of items
+    items: u32,
+    map: bool,
+    indefinite: bool,
+}
+
+/// Decodes a data item from `buf`, like `cbor_decode`, and leaves `buf` after it
+pub unsafe fn cbor_decode_checked<M: Memory>(mem: &mut M, buf: *mut Buf) -> CborResult<Value> {
+    let empty = Frame {
+        array: core::ptr::null_mut(),
+        index: 0,
+        items: 0,
+        map: false,
+        indefinite: false,
+    };
+    let mut stack = [empty; CBOR_MAX_DEPTH];
+    let mut depth = 0;
+    loop {
+        let mut head = cbor_read_head(buf)?;
+        while head.major == CBOR_TAG {
+            head = cbor_read_head(buf)?;
+        }
+
+        let mut value = match head.major {
+            CBOR_UNSIGNED => bigint_of_word64(mem, head.arg),
+            CBOR_BYTES | CBOR_TEXT => decode_string(mem, buf, head)?,
+            CBOR_ARRAY | CBOR_MAP => {
+                let map = head.major == CBOR_MAP;
+                let (entries, indefinite) = match length(head)? {
+                    Some(entries) => (entries, false),
+                    None => (count_entries(buf, map)?, true),
+                };
+                // Every item takes at least one byte
+                let items = if map {
+                    2 * entries as u64
+                } else {
+                    entries as u64
+                };
+                if items > remaining(buf) as u64 {
+                    return Err(CborError::EndOfInput);
+                }
+                let value = alloc_array(mem, entries);
+                let array = value.as_array();
+                for i in 0..entries {
+                    array.set_scalar(i, Value::from_scalar(0));
+                }
+                if entries > 0 {
+                    if depth == CBOR_MAX_DEPTH {
+                        return Err(CborError::NestedTooDeeply);
+                    }
+                    stack[depth] = Frame {
+                        array,
+                        index: 0,
+                        items: items as u32,
+                        map,
+                        indefinite,
+                    };
+                    depth += 1;
+                    continue;
+                }
+                if indefinite {
+                    cbor_read_head(buf)?; // the break
+                }
+                value
+            }
+            CBOR_SIMPLE if head.is_break() => return Err(CborError::UnexpectedBreak),
+            _ => return Err(CborError::Unsupported),
+        };
+
+        // A data item is complete, which may complete the enclosing arrays and maps
+        loop {
+            if depth == 0 {
+                return Ok(value);
+            }
+            let frame = &mut stack[depth - 1];
+            if !frame.map {
+                frame.array.set_pointer(frame.index, value, mem);
+            } else if frame.index % 2 == 0 {
+                let pair = alloc_array(mem, 2);
+                pair.as_array().set_pointer(0, value, mem);
+                pair.as_array().set_scalar(1, Value::from_scalar(0));
+                frame.array.set_pointer(frame.index / 2, pair, mem);
+            } else {
+                let pair = frame.array.get(frame.index / 2).as_array();
+                pair.set_pointer(1, value, mem);
+            }
+            frame.index += 1;
+            if frame.index < frame.items {
+                break;
+            }
+            if frame.indefinite && !cbor_read_break(buf)? {
+                return Err(CborError::UnexpectedBreak);
+            }
+            value = Value::from_ptr(frame.array as usize);
+            depth -= 1;
+        }
+    }
+}
+
+/// Counts the entries of an indefinite-length array or map, without moving `buf`
+unsafe fn count_entries(buf: *mut Buf, map: bool) -> CborResult<u32> {
+    let mut items = Buf {
+        ptr: (*buf).ptr,
+        end: (*buf).end,
+    };
+    let mut count = 0;
+    while !cbor_read_break(&mut items)? {
+        cbor_skip_checked(&mut items)?;
+        count += 1;
+    }
+    if map && count % 2 != 0 {
+        return Err(CborError::UnexpectedBreak);
+    }
+    Ok(if map { count / 2 } else { count })
+}
+
+/// Decodes a byte or text string to a `Blob` or `Text`
+unsafe fn decode_string<M: Memory>(
+    mem: &mut M,
+    buf: *mut Buf,
+    head: CborHead,
+) -> CborResult<Value> {
+    if !head.is_indefinite() {
+        let bytes = read_slice(buf, head.arg)?;
+        let size = Bytes(bytes.len() as u32);
+        if head.major == CBOR_TEXT {
+            if !utf8_valid(bytes.as_ptr() as *const _, size.0) {
+                return Err(CborError::NotUtf8);
+            }
+            return Ok(text_of_ptr_size(mem, bytes.as_ptr(), size));
+        }
+        let blob = alloc_blob(mem, size);
+        let dest = blob.as_blob_mut().payload_addr();
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), dest, bytes.len());
+        return Ok(blob);
+    }
+
+    // The chunks are checked and measured first, and then copied
+    let start = (*buf).ptr;
+    let mut size = 0;
+    read_chunks(buf, head.major, |chunk| size += chunk.len() as u32)?;
+    let end = (*buf).ptr;
+    let value = if head.major == CBOR_TEXT {
+        alloc_text_blob(mem, Bytes(size))
+    } else {
+        alloc_blob(mem, Bytes(size))
+    };
+    let mut dest = value.as_blob_mut().payload_addr();
+    let mut chunks = Buf { ptr: start, end };
+    read_chunks(&mut chunks, head.major, |chunk| {
+        core::ptr::copy_nonoverlapping(chunk.as_ptr(), dest, chunk.len());
+        dest = dest.add(chunk.len());
+    })?;
+    Ok(value)
+}
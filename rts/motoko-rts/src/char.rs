@@ -43,3 +43,43 @@ unsafe extern "C" fn char_is_lowercase(c: u32) -> u32 {
 unsafe extern "C" fn char_is_alphabetic(c: u32) -> u32 {
     core::char::from_u32_unchecked(c).is_alphabetic().into()
 }
+
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_is_alphanumeric(c: u32) -> u32 {
+    core::char::from_u32_unchecked(c).is_alphanumeric().into()
+}
+
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_is_control(c: u32) -> u32 {
+    core::char::from_u32_unchecked(c).is_control().into()
+}
+
+/// Returns one of the `char_props::GC_*` constants
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_general_category(c: u32) -> u32 {
+    crate::char_props::general_category(c)
+}
+
+/// Returns NaN if the character has no numeric value
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_numeric_value(c: u32) -> f64 {
+    crate::char_props::numeric_value(c).unwrap_or(f64::NAN)
+}
+
+/// Returns the ISO 15924 script code as big-endian packed ASCII
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_script(c: u32) -> u32 {
+    crate::char_props::script(c)
+}
+
+/// Returns one of the `char_props::EAW_*` constants
+#[cfg(feature = "ic")]
+#[no_mangle]
+unsafe extern "C" fn char_east_asian_width(c: u32) -> u32 {
+    crate::char_props::east_asian_width(c)
+}
@@ -0,0 +1,103 @@
+//! Unicode character properties beyond what `core::char` provides: general category, numeric
+//! value, script and East Asian width.
+//!
+//! Properties are looked up in compact range tables (`char_props/tables.rs`), generated by
+//! `rts/gen-char-props.py`. Each table entry marks the first code point of a range of code points
+//! sharing the same property value, so a lookup is a binary search for the last range starting at
+//! or before the code point.
+//!
+//! The numbering of general categories and East Asian widths below is part of the RTS interface
+//! and must stay in sync with the generator script.
+
+#[rustfmt::skip]
+mod tables;
+
+use tables::*;
+
+// General categories
+pub const GC_UPPERCASE_LETTER: u32 = 0; // Lu
+pub const GC_LOWERCASE_LETTER: u32 = 1; // Ll
+pub const GC_TITLECASE_LETTER: u32 = 2; // Lt
+pub const GC_MODIFIER_LETTER: u32 = 3; // Lm
+pub const GC_OTHER_LETTER: u32 = 4; // Lo
+pub const GC_NONSPACING_MARK: u32 = 5; // Mn
+pub const GC_SPACING_MARK: u32 = 6; // Mc
+pub const GC_ENCLOSING_MARK: u32 = 7; // Me
+pub const GC_DECIMAL_NUMBER: u32 = 8; // Nd
+pub const GC_LETTER_NUMBER: u32 = 9; // Nl
+pub const GC_OTHER_NUMBER: u32 = 10; // No
+pub const GC_CONNECTOR_PUNCTUATION: u32 = 11; // Pc
+pub const GC_DASH_PUNCTUATION: u32 = 12; // Pd
+pub const GC_OPEN_PUNCTUATION: u32 = 13; // Ps
+pub const GC_CLOSE_PUNCTUATION: u32 = 14; // Pe
+pub const GC_INITIAL_PUNCTUATION: u32 = 15; // Pi
+pub const GC_FINAL_PUNCTUATION: u32 = 16; // Pf
+pub const GC_OTHER_PUNCTUATION: u32 = 17; // Po
+pub const GC_MATH_SYMBOL: u32 = 18; // Sm
+pub const GC_CURRENCY_SYMBOL: u32 = 19; // Sc
+pub const GC_MODIFIER_SYMBOL: u32 = 20; // Sk
+pub const GC_OTHER_SYMBOL: u32 = 21; // So
+pub const GC_SPACE_SEPARATOR: u32 = 22; // Zs
+pub const GC_LINE_SEPARATOR: u32 = 23; // Zl
+pub const GC_PARAGRAPH_SEPARATOR: u32 = 24; // Zp
+pub const GC_CONTROL: u32 = 25; // Cc
+pub const GC_FORMAT: u32 = 26; // Cf
+pub const GC_SURROGATE: u32 = 27; // Cs
+pub const GC_PRIVATE_USE: u32 = 28; // Co
+pub const GC_UNASSIGNED: u32 = 29; // Cn
+
+// East Asian widths
+pub const EAW_NEUTRAL: u32 = 0; // N
+pub const EAW_AMBIGUOUS: u32 = 1; // A
+pub const EAW_HALFWIDTH: u32 = 2; // H
+pub const EAW_FULLWIDTH: u32 = 3; // F
+pub const EAW_NARROW: u32 = 4; // Na
+pub const EAW_WIDE: u32 = 5; // W
+
+/// Finds the range containing `c` in a table of range starts shifted by `shift` bits, with the
+/// range's property value in the low `shift` bits. Tables start at code point 0.
+fn lookup_packed(table: &[u32], shift: u32, c: u32) -> u32 {
+    let idx = table.partition_point(|entry| entry >> shift <= c);
+    debug_assert!(idx > 0);
+    table[idx - 1] & ((1 << shift) - 1)
+}
+
+/// General category of a character, one of the `GC_*` constants
+pub fn general_category(c: u32) -> u32 {
+    lookup_packed(&GENERAL_CATEGORY, 5, c)
+}
+
+/// East Asian width of a character, one of the `EAW_*` constants
+pub fn east_asian_width(c: u32) -> u32 {
+    lookup_packed(&EAST_ASIAN_WIDTH, 3, c)
+}
+
+/// ISO 15924 code of the script of a character, as big-endian packed ASCII (e.g. `0x4c61746e`
+/// for "Latn"). Characters shared by scripts are "Zyyy" (Common) or "Zinh" (Inherited),
+/// unassigned characters are "Zzzz" (Unknown).
+pub fn script(c: u32) -> u32 {
+    SCRIPT_CODES[lookup_packed(&SCRIPT, 8, c) as usize]
+}
+
+/// Numeric value of a character (Unicode property `Numeric_Value`), e.g. 7 for '7', 0.5 for '½'
+/// and 12 for 'Ⅻ'
+pub fn numeric_value(c: u32) -> Option<f64> {
+    let idx = NUMERIC_RANGES.partition_point(|(start, _, _)| *start <= c);
+    if idx == 0 {
+        return None;
+    }
+
+    let (start, len_step, value_idx) = NUMERIC_RANGES[idx - 1];
+    let offset = c - start;
+    if offset >= u32::from(len_step & 0x7F) {
+        return None;
+    }
+
+    let value = NUMERIC_VALUES[value_idx as usize];
+    if len_step & 0x80 != 0 {
+        // Values increase by one within the range
+        Some(value + f64::from(offset))
+    } else {
+        Some(value)
+    }
+}
@@ -0,0 +1,1580 @@
+// Generated by `rts/gen-char-props.py` from Unicode 14.0.0 data. Do not edit.
+
+/// Start of each range (shifted by 5) and its general category
+pub(super) static GENERAL_CATEGORY: [u32; 3968] = [
+    0x19, 0x416, 0x431, 0x493, 0x4b1, 0x50d, 0x52e, 0x551, 0x572, 0x591, 0x5ac, 0x5d1, 0x608, 0x751,
+    0x792, 0x7f1, 0x820, 0xb6d, 0xb91, 0xbae, 0xbd4, 0xbeb, 0xc14, 0xc21, 0xf6d, 0xf92, 0xfae,
+    0xfd2, 0xff9, 0x1416, 0x1431, 0x1453, 0x14d5, 0x14f1, 0x1514, 0x1535, 0x1544, 0x156f, 0x1592,
+    0x15ba, 0x15d5, 0x15f4, 0x1615, 0x1632, 0x164a, 0x1694, 0x16a1, 0x16d1, 0x1714, 0x172a, 0x1744,
+    0x1770, 0x178a, 0x17f1, 0x1800, 0x1af2, 0x1b00, 0x1be1, 0x1ef2, 0x1f01, 0x2000, 0x2021, 0x2040,
+    0x2061, 0x2080, 0x20a1, 0x20c0, 0x20e1, 0x2100, 0x2121, 0x2140, 0x2161, 0x2180, 0x21a1, 0x21c0,
+    0x21e1, 0x2200, 0x2221, 0x2240, 0x2261, 0x2280, 0x22a1, 0x22c0, 0x22e1, 0x2300, 0x2321, 0x2340,
+    0x2361, 0x2380, 0x23a1, 0x23c0, 0x23e1, 0x2400, 0x2421, 0x2440, 0x2461, 0x2480, 0x24a1, 0x24c0,
+    0x24e1, 0x2500, 0x2521, 0x2540, 0x2561, 0x2580, 0x25a1, 0x25c0, 0x25e1, 0x2600, 0x2621, 0x2640,
+    0x2661, 0x2680, 0x26a1, 0x26c0, 0x26e1, 0x2720, 0x2741, 0x2760, 0x2781, 0x27a0, 0x27c1, 0x27e0,
+    0x2801, 0x2820, 0x2841, 0x2860, 0x2881, 0x28a0, 0x28c1, 0x28e0, 0x2901, 0x2940, 0x2961, 0x2980,
+    0x29a1, 0x29c0, 0x29e1, 0x2a00, 0x2a21, 0x2a40, 0x2a61, 0x2a80, 0x2aa1, 0x2ac0, 0x2ae1, 0x2b00,
+    0x2b21, 0x2b40, 0x2b61, 0x2b80, 0x2ba1, 0x2bc0, 0x2be1, 0x2c00, 0x2c21, 0x2c40, 0x2c61, 0x2c80,
+    0x2ca1, 0x2cc0, 0x2ce1, 0x2d00, 0x2d21, 0x2d40, 0x2d61, 0x2d80, 0x2da1, 0x2dc0, 0x2de1, 0x2e00,
+    0x2e21, 0x2e40, 0x2e61, 0x2e80, 0x2ea1, 0x2ec0, 0x2ee1, 0x2f00, 0x2f41, 0x2f60, 0x2f81, 0x2fa0,
+    0x2fc1, 0x3020, 0x3061, 0x3080, 0x30a1, 0x30c0, 0x3101, 0x3120, 0x3181, 0x31c0, 0x3241, 0x3260,
+    0x32a1, 0x32c0, 0x3321, 0x3380, 0x33c1, 0x33e0, 0x3421, 0x3440, 0x3461, 0x3480, 0x34a1, 0x34c0,
+    0x3501, 0x3520, 0x3541, 0x3580, 0x35a1, 0x35c0, 0x3601, 0x3620, 0x3681, 0x36a0, 0x36c1, 0x36e0,
+    0x3721, 0x3764, 0x3780, 0x37a1, 0x3804, 0x3880, 0x38a2, 0x38c1, 0x38e0, 0x3902, 0x3921, 0x3940,
+    0x3962, 0x3981, 0x39a0, 0x39c1, 0x39e0, 0x3a01, 0x3a20, 0x3a41, 0x3a60, 0x3a81, 0x3aa0, 0x3ac1,
+    0x3ae0, 0x3b01, 0x3b20, 0x3b41, 0x3b60, 0x3b81, 0x3bc0, 0x3be1, 0x3c00, 0x3c21, 0x3c40, 0x3c61,
+    0x3c80, 0x3ca1, 0x3cc0, 0x3ce1, 0x3d00, 0x3d21, 0x3d40, 0x3d61, 0x3d80, 0x3da1, 0x3dc0, 0x3de1,
+    0x3e20, 0x3e42, 0x3e61, 0x3e80, 0x3ea1, 0x3ec0, 0x3f21, 0x3f40, 0x3f61, 0x3f80, 0x3fa1, 0x3fc0,
+    0x3fe1, 0x4000, 0x4021, 0x4040, 0x4061, 0x4080, 0x40a1, 0x40c0, 0x40e1, 0x4100, 0x4121, 0x4140,
+    0x4161, 0x4180, 0x41a1, 0x41c0, 0x41e1, 0x4200, 0x4221, 0x4240, 0x4261, 0x4280, 0x42a1, 0x42c0,
+    0x42e1, 0x4300, 0x4321, 0x4340, 0x4361, 0x4380, 0x43a1, 0x43c0, 0x43e1, 0x4400, 0x4421, 0x4440,
+    0x4461, 0x4480, 0x44a1, 0x44c0, 0x44e1, 0x4500, 0x4521, 0x4540, 0x4561, 0x4580, 0x45a1, 0x45c0,
+    0x45e1, 0x4600, 0x4621, 0x4640, 0x4661, 0x4740, 0x4781, 0x47a0, 0x47e1, 0x4820, 0x4841, 0x4860,
+    0x48e1, 0x4900, 0x4921, 0x4940, 0x4961, 0x4980, 0x49a1, 0x49c0, 0x49e1, 0x5284, 0x52a1, 0x5603,
+    0x5854, 0x58c3, 0x5a54, 0x5c03, 0x5cb4, 0x5d83, 0x5db4, 0x5dc3, 0x5df4, 0x6005, 0x6e00, 0x6e21,
+    0x6e40, 0x6e61, 0x6e83, 0x6eb4, 0x6ec0, 0x6ee1, 0x6f1d, 0x6f43, 0x6f61, 0x6fd1, 0x6fe0, 0x701d,
+    0x7094, 0x70c0, 0x70f1, 0x7100, 0x717d, 0x7180, 0x71bd, 0x71c0, 0x7201, 0x7220, 0x745d, 0x7460,
+    0x7581, 0x79e0, 0x7a01, 0x7a40, 0x7aa1, 0x7b00, 0x7b21, 0x7b40, 0x7b61, 0x7b80, 0x7ba1, 0x7bc0,
+    0x7be1, 0x7c00, 0x7c21, 0x7c40, 0x7c61, 0x7c80, 0x7ca1, 0x7cc0, 0x7ce1, 0x7d00, 0x7d21, 0x7d40,
+    0x7d61, 0x7d80, 0x7da1, 0x7dc0, 0x7de1, 0x7e80, 0x7ea1, 0x7ed2, 0x7ee0, 0x7f01, 0x7f20, 0x7f61,
+    0x7fa0, 0x8601, 0x8c00, 0x8c21, 0x8c40, 0x8c61, 0x8c80, 0x8ca1, 0x8cc0, 0x8ce1, 0x8d00, 0x8d21,
+    0x8d40, 0x8d61, 0x8d80, 0x8da1, 0x8dc0, 0x8de1, 0x8e00, 0x8e21, 0x8e40, 0x8e61, 0x8e80, 0x8ea1,
+    0x8ec0, 0x8ee1, 0x8f00, 0x8f21, 0x8f40, 0x8f61, 0x8f80, 0x8fa1, 0x8fc0, 0x8fe1, 0x9000, 0x9021,
+    0x9055, 0x9065, 0x9107, 0x9140, 0x9161, 0x9180, 0x91a1, 0x91c0, 0x91e1, 0x9200, 0x9221, 0x9240,
+    0x9261, 0x9280, 0x92a1, 0x92c0, 0x92e1, 0x9300, 0x9321, 0x9340, 0x9361, 0x9380, 0x93a1, 0x93c0,
+    0x93e1, 0x9400, 0x9421, 0x9440, 0x9461, 0x9480, 0x94a1, 0x94c0, 0x94e1, 0x9500, 0x9521, 0x9540,
+    0x9561, 0x9580, 0x95a1, 0x95c0, 0x95e1, 0x9600, 0x9621, 0x9640, 0x9661, 0x9680, 0x96a1, 0x96c0,
+    0x96e1, 0x9700, 0x9721, 0x9740, 0x9761, 0x9780, 0x97a1, 0x97c0, 0x97e1, 0x9800, 0x9841, 0x9860,
+    0x9881, 0x98a0, 0x98c1, 0x98e0, 0x9901, 0x9920, 0x9941, 0x9960, 0x9981, 0x99a0, 0x99c1, 0x9a00,
+    0x9a21, 0x9a40, 0x9a61, 0x9a80, 0x9aa1, 0x9ac0, 0x9ae1, 0x9b00, 0x9b21, 0x9b40, 0x9b61, 0x9b80,
+    0x9ba1, 0x9bc0, 0x9be1, 0x9c00, 0x9c21, 0x9c40, 0x9c61, 0x9c80, 0x9ca1, 0x9cc0, 0x9ce1, 0x9d00,
+    0x9d21, 0x9d40, 0x9d61, 0x9d80, 0x9da1, 0x9dc0, 0x9de1, 0x9e00, 0x9e21, 0x9e40, 0x9e61, 0x9e80,
+    0x9ea1, 0x9ec0, 0x9ee1, 0x9f00, 0x9f21, 0x9f40, 0x9f61, 0x9f80, 0x9fa1, 0x9fc0, 0x9fe1, 0xa000,
+    0xa021, 0xa040, 0xa061, 0xa080, 0xa0a1, 0xa0c0, 0xa0e1, 0xa100, 0xa121, 0xa140, 0xa161, 0xa180,
+    0xa1a1, 0xa1c0, 0xa1e1, 0xa200, 0xa221, 0xa240, 0xa261, 0xa280, 0xa2a1, 0xa2c0, 0xa2e1, 0xa300,
+    0xa321, 0xa340, 0xa361, 0xa380, 0xa3a1, 0xa3c0, 0xa3e1, 0xa400, 0xa421, 0xa440, 0xa461, 0xa480,
+    0xa4a1, 0xa4c0, 0xa4e1, 0xa500, 0xa521, 0xa540, 0xa561, 0xa580, 0xa5a1, 0xa5c0, 0xa5e1, 0xa61d,
+    0xa620, 0xaafd, 0xab23, 0xab51, 0xac01, 0xb131, 0xb14c, 0xb17d, 0xb1b5, 0xb1f3, 0xb21d, 0xb225,
+    0xb7cc, 0xb7e5, 0xb811, 0xb825, 0xb871, 0xb885, 0xb8d1, 0xb8e5, 0xb91d, 0xba04, 0xbd7d, 0xbde4,
+    0xbe71, 0xbebd, 0xc01a, 0xc0d2, 0xc131, 0xc173, 0xc191, 0xc1d5, 0xc205, 0xc371, 0xc39a, 0xc3b1,
+    0xc404, 0xc803, 0xc824, 0xc965, 0xcc08, 0xcd51, 0xcdc4, 0xce05, 0xce24, 0xda91, 0xdaa4, 0xdac5,
+    0xdbba, 0xdbd5, 0xdbe5, 0xdca3, 0xdce5, 0xdd35, 0xdd45, 0xddc4, 0xde08, 0xdf44, 0xdfb5, 0xdfe4,
+    0xe011, 0xe1dd, 0xe1fa, 0xe204, 0xe225, 0xe244, 0xe605, 0xe97d, 0xe9a4, 0xf4c5, 0xf624, 0xf65d,
+    0xf808, 0xf944, 0xfd65, 0xfe83, 0xfed5, 0xfef1, 0xff43, 0xff7d, 0xffa5, 0xffd3, 0x10004,
+    0x102c5, 0x10343, 0x10365, 0x10483, 0x104a5, 0x10503, 0x10525, 0x105dd, 0x10611, 0x107fd,
+    0x10804, 0x10b25, 0x10b9d, 0x10bd1, 0x10bfd, 0x10c04, 0x10d7d, 0x10e04, 0x11114, 0x11124,
+    0x111fd, 0x1121a, 0x1125d, 0x11305, 0x11404, 0x11923, 0x11945, 0x11c5a, 0x11c65, 0x12066,
+    0x12084, 0x12745, 0x12766, 0x12785, 0x127a4, 0x127c6, 0x12825, 0x12926, 0x129a5, 0x129c6,
+    0x12a04, 0x12a25, 0x12b04, 0x12c45, 0x12c91, 0x12cc8, 0x12e11, 0x12e23, 0x12e44, 0x13025,
+    0x13046, 0x1309d, 0x130a4, 0x131bd, 0x131e4, 0x1323d, 0x13264, 0x1353d, 0x13544, 0x1363d,
+    0x13644, 0x1367d, 0x136c4, 0x1375d, 0x13785, 0x137a4, 0x137c6, 0x13825, 0x138bd, 0x138e6,
+    0x1393d, 0x13966, 0x139a5, 0x139c4, 0x139fd, 0x13ae6, 0x13b1d, 0x13b84, 0x13bdd, 0x13be4,
+    0x13c45, 0x13c9d, 0x13cc8, 0x13e04, 0x13e53, 0x13e8a, 0x13f55, 0x13f73, 0x13f84, 0x13fb1,
+    0x13fc5, 0x13ffd, 0x14025, 0x14066, 0x1409d, 0x140a4, 0x1417d, 0x141e4, 0x1423d, 0x14264,
+    0x1453d, 0x14544, 0x1463d, 0x14644, 0x1469d, 0x146a4, 0x146fd, 0x14704, 0x1475d, 0x14785,
+    0x147bd, 0x147c6, 0x14825, 0x1487d, 0x148e5, 0x1493d, 0x14965, 0x149dd, 0x14a25, 0x14a5d,
+    0x14b24, 0x14bbd, 0x14bc4, 0x14bfd, 0x14cc8, 0x14e05, 0x14e44, 0x14ea5, 0x14ed1, 0x14efd,
+    0x15025, 0x15066, 0x1509d, 0x150a4, 0x151dd, 0x151e4, 0x1525d, 0x15264, 0x1553d, 0x15544,
+    0x1563d, 0x15644, 0x1569d, 0x156a4, 0x1575d, 0x15785, 0x157a4, 0x157c6, 0x15825, 0x158dd,
+    0x158e5, 0x15926, 0x1595d, 0x15966, 0x159a5, 0x159dd, 0x15a04, 0x15a3d, 0x15c04, 0x15c45,
+    0x15c9d, 0x15cc8, 0x15e11, 0x15e33, 0x15e5d, 0x15f24, 0x15f45, 0x1601d, 0x16025, 0x16046,
+    0x1609d, 0x160a4, 0x161bd, 0x161e4, 0x1623d, 0x16264, 0x1653d, 0x16544, 0x1663d, 0x16644,
+    0x1669d, 0x166a4, 0x1675d, 0x16785, 0x167a4, 0x167c6, 0x167e5, 0x16806, 0x16825, 0x168bd,
+    0x168e6, 0x1693d, 0x16966, 0x169a5, 0x169dd, 0x16aa5, 0x16ae6, 0x16b1d, 0x16b84, 0x16bdd,
+    0x16be4, 0x16c45, 0x16c9d, 0x16cc8, 0x16e15, 0x16e24, 0x16e4a, 0x16f1d, 0x17045, 0x17064,
+    0x1709d, 0x170a4, 0x1717d, 0x171c4, 0x1723d, 0x17244, 0x172dd, 0x17324, 0x1737d, 0x17384,
+    0x173bd, 0x173c4, 0x1741d, 0x17464, 0x174bd, 0x17504, 0x1757d, 0x175c4, 0x1775d, 0x177c6,
+    0x17805, 0x17826, 0x1787d, 0x178c6, 0x1793d, 0x17946, 0x179a5, 0x179dd, 0x17a04, 0x17a3d,
+    0x17ae6, 0x17b1d, 0x17cc8, 0x17e0a, 0x17e75, 0x17f33, 0x17f55, 0x17f7d, 0x18005, 0x18026,
+    0x18085, 0x180a4, 0x181bd, 0x181c4, 0x1823d, 0x18244, 0x1853d, 0x18544, 0x1875d, 0x18785,
+    0x187a4, 0x187c5, 0x18826, 0x188bd, 0x188c5, 0x1893d, 0x18945, 0x189dd, 0x18aa5, 0x18afd,
+    0x18b04, 0x18b7d, 0x18ba4, 0x18bdd, 0x18c04, 0x18c45, 0x18c9d, 0x18cc8, 0x18e1d, 0x18ef1,
+    0x18f0a, 0x18ff5, 0x19004, 0x19025, 0x19046, 0x19091, 0x190a4, 0x191bd, 0x191c4, 0x1923d,
+    0x19244, 0x1953d, 0x19544, 0x1969d, 0x196a4, 0x1975d, 0x19785, 0x197a4, 0x197c6, 0x197e5,
+    0x19806, 0x198bd, 0x198c5, 0x198e6, 0x1993d, 0x19946, 0x19985, 0x199dd, 0x19aa6, 0x19afd,
+    0x19ba4, 0x19bfd, 0x19c04, 0x19c45, 0x19c9d, 0x19cc8, 0x19e1d, 0x19e24, 0x19e7d, 0x1a005,
+    0x1a046, 0x1a084, 0x1a1bd, 0x1a1c4, 0x1a23d, 0x1a244, 0x1a765, 0x1a7a4, 0x1a7c6, 0x1a825,
+    0x1a8bd, 0x1a8c6, 0x1a93d, 0x1a946, 0x1a9a5, 0x1a9c4, 0x1a9f5, 0x1aa1d, 0x1aa84, 0x1aae6,
+    0x1ab0a, 0x1abe4, 0x1ac45, 0x1ac9d, 0x1acc8, 0x1ae0a, 0x1af35, 0x1af44, 0x1b01d, 0x1b025,
+    0x1b046, 0x1b09d, 0x1b0a4, 0x1b2fd, 0x1b344, 0x1b65d, 0x1b664, 0x1b79d, 0x1b7a4, 0x1b7dd,
+    0x1b804, 0x1b8fd, 0x1b945, 0x1b97d, 0x1b9e6, 0x1ba45, 0x1babd, 0x1bac5, 0x1bafd, 0x1bb06,
+    0x1bc1d, 0x1bcc8, 0x1be1d, 0x1be46, 0x1be91, 0x1bebd, 0x1c024, 0x1c625, 0x1c644, 0x1c685,
+    0x1c77d, 0x1c7f3, 0x1c804, 0x1c8c3, 0x1c8e5, 0x1c9f1, 0x1ca08, 0x1cb51, 0x1cb9d, 0x1d024,
+    0x1d07d, 0x1d084, 0x1d0bd, 0x1d0c4, 0x1d17d, 0x1d184, 0x1d49d, 0x1d4a4, 0x1d4dd, 0x1d4e4,
+    0x1d625, 0x1d644, 0x1d685, 0x1d7a4, 0x1d7dd, 0x1d804, 0x1d8bd, 0x1d8c3, 0x1d8fd, 0x1d905,
+    0x1d9dd, 0x1da08, 0x1db5d, 0x1db84, 0x1dc1d, 0x1e004, 0x1e035, 0x1e091, 0x1e275, 0x1e291,
+    0x1e2b5, 0x1e305, 0x1e355, 0x1e408, 0x1e54a, 0x1e695, 0x1e6a5, 0x1e6d5, 0x1e6e5, 0x1e715,
+    0x1e725, 0x1e74d, 0x1e76e, 0x1e78d, 0x1e7ae, 0x1e7c6, 0x1e804, 0x1e91d, 0x1e924, 0x1edbd,
+    0x1ee25, 0x1efe6, 0x1f005, 0x1f0b1, 0x1f0c5, 0x1f104, 0x1f1a5, 0x1f31d, 0x1f325, 0x1f7bd,
+    0x1f7d5, 0x1f8c5, 0x1f8f5, 0x1f9bd, 0x1f9d5, 0x1fa11, 0x1fab5, 0x1fb31, 0x1fb7d, 0x20004,
+    0x20566, 0x205a5, 0x20626, 0x20645, 0x20706, 0x20725, 0x20766, 0x207a5, 0x207e4, 0x20808,
+    0x20951, 0x20a04, 0x20ac6, 0x20b05, 0x20b44, 0x20bc5, 0x20c24, 0x20c46, 0x20ca4, 0x20ce6,
+    0x20dc4, 0x20e25, 0x20ea4, 0x21045, 0x21066, 0x210a5, 0x210e6, 0x211a5, 0x211c4, 0x211e6,
+    0x21208, 0x21346, 0x213a5, 0x213d5, 0x21400, 0x218dd, 0x218e0, 0x2191d, 0x219a0, 0x219dd,
+    0x21a01, 0x21f71, 0x21f83, 0x21fa1, 0x22004, 0x2493d, 0x24944, 0x249dd, 0x24a04, 0x24afd,
+    0x24b04, 0x24b3d, 0x24b44, 0x24bdd, 0x24c04, 0x2513d, 0x25144, 0x251dd, 0x25204, 0x2563d,
+    0x25644, 0x256dd, 0x25704, 0x257fd, 0x25804, 0x2583d, 0x25844, 0x258dd, 0x25904, 0x25afd,
+    0x25b04, 0x2623d, 0x26244, 0x262dd, 0x26304, 0x26b7d, 0x26ba5, 0x26c11, 0x26d2a, 0x26fbd,
+    0x27004, 0x27215, 0x2735d, 0x27400, 0x27edd, 0x27f01, 0x27fdd, 0x2800c, 0x28024, 0x2cdb5,
+    0x2cdd1, 0x2cde4, 0x2d016, 0x2d024, 0x2d36d, 0x2d38e, 0x2d3bd, 0x2d404, 0x2dd71, 0x2ddc9,
+    0x2de24, 0x2df3d, 0x2e004, 0x2e245, 0x2e2a6, 0x2e2dd, 0x2e3e4, 0x2e645, 0x2e686, 0x2e6b1,
+    0x2e6fd, 0x2e804, 0x2ea45, 0x2ea9d, 0x2ec04, 0x2edbd, 0x2edc4, 0x2ee3d, 0x2ee45, 0x2ee9d,
+    0x2f004, 0x2f685, 0x2f6c6, 0x2f6e5, 0x2f7c6, 0x2f8c5, 0x2f8e6, 0x2f925, 0x2fa91, 0x2fae3,
+    0x2fb11, 0x2fb73, 0x2fb84, 0x2fba5, 0x2fbdd, 0x2fc08, 0x2fd5d, 0x2fe0a, 0x2ff5d, 0x30011,
+    0x300cc, 0x300f1, 0x30165, 0x301da, 0x301e5, 0x30208, 0x3035d, 0x30404, 0x30863, 0x30884,
+    0x30f3d, 0x31004, 0x310a5, 0x310e4, 0x31525, 0x31544, 0x3157d, 0x31604, 0x31edd, 0x32004,
+    0x323fd, 0x32405, 0x32466, 0x324e5, 0x32526, 0x3259d, 0x32606, 0x32645, 0x32666, 0x32725,
+    0x3279d, 0x32815, 0x3283d, 0x32891, 0x328c8, 0x32a04, 0x32ddd, 0x32e04, 0x32ebd, 0x33004,
+    0x3359d, 0x33604, 0x3395d, 0x33a08, 0x33b4a, 0x33b7d, 0x33bd5, 0x34004, 0x342e5, 0x34326,
+    0x34365, 0x3439d, 0x343d1, 0x34404, 0x34aa6, 0x34ac5, 0x34ae6, 0x34b05, 0x34bfd, 0x34c05,
+    0x34c26, 0x34c45, 0x34c66, 0x34ca5, 0x34da6, 0x34e65, 0x34fbd, 0x34fe5, 0x35008, 0x3515d,
+    0x35208, 0x3535d, 0x35411, 0x354e3, 0x35511, 0x355dd, 0x35605, 0x357c7, 0x357e5, 0x359fd,
+    0x36005, 0x36086, 0x360a4, 0x36685, 0x366a6, 0x366c5, 0x36766, 0x36785, 0x367a6, 0x36845,
+    0x36866, 0x368a4, 0x369bd, 0x36a08, 0x36b51, 0x36c35, 0x36d65, 0x36e95, 0x36fb1, 0x36ffd,
+    0x37005, 0x37046, 0x37064, 0x37426, 0x37445, 0x374c6, 0x37505, 0x37546, 0x37565, 0x375c4,
+    0x37608, 0x37744, 0x37cc5, 0x37ce6, 0x37d05, 0x37d46, 0x37da5, 0x37dc6, 0x37de5, 0x37e46,
+    0x37e9d, 0x37f91, 0x38004, 0x38486, 0x38585, 0x38686, 0x386c5, 0x3871d, 0x38771, 0x38808,
+    0x3895d, 0x389a4, 0x38a08, 0x38b44, 0x38f03, 0x38fd1, 0x39001, 0x3913d, 0x39200, 0x3977d,
+    0x397a0, 0x39811, 0x3991d, 0x39a05, 0x39a71, 0x39a85, 0x39c26, 0x39c45, 0x39d24, 0x39da5,
+    0x39dc4, 0x39e85, 0x39ea4, 0x39ee6, 0x39f05, 0x39f44, 0x39f7d, 0x3a001, 0x3a583, 0x3ad61,
+    0x3af03, 0x3af21, 0x3b363, 0x3b805, 0x3c000, 0x3c021, 0x3c040, 0x3c061, 0x3c080, 0x3c0a1,
+    0x3c0c0, 0x3c0e1, 0x3c100, 0x3c121, 0x3c140, 0x3c161, 0x3c180, 0x3c1a1, 0x3c1c0, 0x3c1e1,
+    0x3c200, 0x3c221, 0x3c240, 0x3c261, 0x3c280, 0x3c2a1, 0x3c2c0, 0x3c2e1, 0x3c300, 0x3c321,
+    0x3c340, 0x3c361, 0x3c380, 0x3c3a1, 0x3c3c0, 0x3c3e1, 0x3c400, 0x3c421, 0x3c440, 0x3c461,
+    0x3c480, 0x3c4a1, 0x3c4c0, 0x3c4e1, 0x3c500, 0x3c521, 0x3c540, 0x3c561, 0x3c580, 0x3c5a1,
+    0x3c5c0, 0x3c5e1, 0x3c600, 0x3c621, 0x3c640, 0x3c661, 0x3c680, 0x3c6a1, 0x3c6c0, 0x3c6e1,
+    0x3c700, 0x3c721, 0x3c740, 0x3c761, 0x3c780, 0x3c7a1, 0x3c7c0, 0x3c7e1, 0x3c800, 0x3c821,
+    0x3c840, 0x3c861, 0x3c880, 0x3c8a1, 0x3c8c0, 0x3c8e1, 0x3c900, 0x3c921, 0x3c940, 0x3c961,
+    0x3c980, 0x3c9a1, 0x3c9c0, 0x3c9e1, 0x3ca00, 0x3ca21, 0x3ca40, 0x3ca61, 0x3ca80, 0x3caa1,
+    0x3cac0, 0x3cae1, 0x3cb00, 0x3cb21, 0x3cb40, 0x3cb61, 0x3cb80, 0x3cba1, 0x3cbc0, 0x3cbe1,
+    0x3cc00, 0x3cc21, 0x3cc40, 0x3cc61, 0x3cc80, 0x3cca1, 0x3ccc0, 0x3cce1, 0x3cd00, 0x3cd21,
+    0x3cd40, 0x3cd61, 0x3cd80, 0x3cda1, 0x3cdc0, 0x3cde1, 0x3ce00, 0x3ce21, 0x3ce40, 0x3ce61,
+    0x3ce80, 0x3cea1, 0x3cec0, 0x3cee1, 0x3cf00, 0x3cf21, 0x3cf40, 0x3cf61, 0x3cf80, 0x3cfa1,
+    0x3cfc0, 0x3cfe1, 0x3d000, 0x3d021, 0x3d040, 0x3d061, 0x3d080, 0x3d0a1, 0x3d0c0, 0x3d0e1,
+    0x3d100, 0x3d121, 0x3d140, 0x3d161, 0x3d180, 0x3d1a1, 0x3d1c0, 0x3d1e1, 0x3d200, 0x3d221,
+    0x3d240, 0x3d261, 0x3d280, 0x3d2a1, 0x3d3c0, 0x3d3e1, 0x3d400, 0x3d421, 0x3d440, 0x3d461,
+    0x3d480, 0x3d4a1, 0x3d4c0, 0x3d4e1, 0x3d500, 0x3d521, 0x3d540, 0x3d561, 0x3d580, 0x3d5a1,
+    0x3d5c0, 0x3d5e1, 0x3d600, 0x3d621, 0x3d640, 0x3d661, 0x3d680, 0x3d6a1, 0x3d6c0, 0x3d6e1,
+    0x3d700, 0x3d721, 0x3d740, 0x3d761, 0x3d780, 0x3d7a1, 0x3d7c0, 0x3d7e1, 0x3d800, 0x3d821,
+    0x3d840, 0x3d861, 0x3d880, 0x3d8a1, 0x3d8c0, 0x3d8e1, 0x3d900, 0x3d921, 0x3d940, 0x3d961,
+    0x3d980, 0x3d9a1, 0x3d9c0, 0x3d9e1, 0x3da00, 0x3da21, 0x3da40, 0x3da61, 0x3da80, 0x3daa1,
+    0x3dac0, 0x3dae1, 0x3db00, 0x3db21, 0x3db40, 0x3db61, 0x3db80, 0x3dba1, 0x3dbc0, 0x3dbe1,
+    0x3dc00, 0x3dc21, 0x3dc40, 0x3dc61, 0x3dc80, 0x3dca1, 0x3dcc0, 0x3dce1, 0x3dd00, 0x3dd21,
+    0x3dd40, 0x3dd61, 0x3dd80, 0x3dda1, 0x3ddc0, 0x3dde1, 0x3de00, 0x3de21, 0x3de40, 0x3de61,
+    0x3de80, 0x3dea1, 0x3dec0, 0x3dee1, 0x3df00, 0x3df21, 0x3df40, 0x3df61, 0x3df80, 0x3dfa1,
+    0x3dfc0, 0x3dfe1, 0x3e100, 0x3e201, 0x3e2dd, 0x3e300, 0x3e3dd, 0x3e401, 0x3e500, 0x3e601,
+    0x3e700, 0x3e801, 0x3e8dd, 0x3e900, 0x3e9dd, 0x3ea01, 0x3eb1d, 0x3eb20, 0x3eb5d, 0x3eb60,
+    0x3eb9d, 0x3eba0, 0x3ebdd, 0x3ebe0, 0x3ec01, 0x3ed00, 0x3ee01, 0x3efdd, 0x3f001, 0x3f102,
+    0x3f201, 0x3f302, 0x3f401, 0x3f502, 0x3f601, 0x3f6bd, 0x3f6c1, 0x3f700, 0x3f782, 0x3f7b4,
+    0x3f7c1, 0x3f7f4, 0x3f841, 0x3f8bd, 0x3f8c1, 0x3f900, 0x3f982, 0x3f9b4, 0x3fa01, 0x3fa9d,
+    0x3fac1, 0x3fb00, 0x3fb9d, 0x3fbb4, 0x3fc01, 0x3fd00, 0x3fdb4, 0x3fe1d, 0x3fe41, 0x3febd,
+    0x3fec1, 0x3ff00, 0x3ff82, 0x3ffb4, 0x3fffd, 0x40016, 0x4017a, 0x4020c, 0x402d1, 0x4030f,
+    0x40330, 0x4034d, 0x4036f, 0x403b0, 0x403cd, 0x403ef, 0x40411, 0x40517, 0x40538, 0x4055a,
+    0x405f6, 0x40611, 0x4072f, 0x40750, 0x40771, 0x407eb, 0x40831, 0x40892, 0x408ad, 0x408ce,
+    0x408f1, 0x40a52, 0x40a71, 0x40a8b, 0x40ab1, 0x40bf6, 0x40c1a, 0x40cbd, 0x40cda, 0x40e0a,
+    0x40e23, 0x40e5d, 0x40e8a, 0x40f52, 0x40fad, 0x40fce, 0x40fe3, 0x4100a, 0x41152, 0x411ad,
+    0x411ce, 0x411fd, 0x41203, 0x413bd, 0x41413, 0x4183d, 0x41a05, 0x41ba7, 0x41c25, 0x41c47,
+    0x41ca5, 0x41e3d, 0x42015, 0x42040, 0x42075, 0x420e0, 0x42115, 0x42141, 0x42160, 0x421c1,
+    0x42200, 0x42261, 0x42295, 0x422a0, 0x422d5, 0x42312, 0x42320, 0x423d5, 0x42480, 0x424b5,
+    0x424c0, 0x424f5, 0x42500, 0x42535, 0x42540, 0x425d5, 0x425e1, 0x42600, 0x42681, 0x426a4,
+    0x42721, 0x42755, 0x42781, 0x427c0, 0x42812, 0x428a0, 0x428c1, 0x42955, 0x42972, 0x42995,
+    0x429c1, 0x429f5, 0x42a0a, 0x42c09, 0x43060, 0x43081, 0x430a9, 0x4312a, 0x43155, 0x4319d,
+    0x43212, 0x432b5, 0x43352, 0x43395, 0x43412, 0x43435, 0x43472, 0x43495, 0x434d2, 0x434f5,
+    0x435d2, 0x435f5, 0x439d2, 0x43a15, 0x43a52, 0x43a75, 0x43a92, 0x43ab5, 0x43e92, 0x46015,
+    0x4610d, 0x4612e, 0x4614d, 0x4616e, 0x46195, 0x46412, 0x46455, 0x4652d, 0x4654e, 0x46575,
+    0x46f92, 0x46fb5, 0x47372, 0x47695, 0x47b92, 0x47c55, 0x484fd, 0x48815, 0x4897d, 0x48c0a,
+    0x49395, 0x49d4a, 0x4a015, 0x4b6f2, 0x4b715, 0x4b832, 0x4b855, 0x4bf12, 0x4c015, 0x4cdf2,
+    0x4ce15, 0x4ed0d, 0x4ed2e, 0x4ed4d, 0x4ed6e, 0x4ed8d, 0x4edae, 0x4edcd, 0x4edee, 0x4ee0d,
+    0x4ee2e, 0x4ee4d, 0x4ee6e, 0x4ee8d, 0x4eeae, 0x4eeca, 0x4f295, 0x4f812, 0x4f8ad, 0x4f8ce,
+    0x4f8f2, 0x4fccd, 0x4fcee, 0x4fd0d, 0x4fd2e, 0x4fd4d, 0x4fd6e, 0x4fd8d, 0x4fdae, 0x4fdcd,
+    0x4fdee, 0x4fe12, 0x50015, 0x52012, 0x5306d, 0x5308e, 0x530ad, 0x530ce, 0x530ed, 0x5310e,
+    0x5312d, 0x5314e, 0x5316d, 0x5318e, 0x531ad, 0x531ce, 0x531ed, 0x5320e, 0x5322d, 0x5324e,
+    0x5326d, 0x5328e, 0x532ad, 0x532ce, 0x532ed, 0x5330e, 0x53332, 0x53b0d, 0x53b2e, 0x53b4d,
+    0x53b6e, 0x53b92, 0x53f8d, 0x53fae, 0x53fd2, 0x56015, 0x56612, 0x568b5, 0x568f2, 0x569b5,
+    0x56e9d, 0x56ed5, 0x572dd, 0x572f5, 0x58000, 0x58601, 0x58c00, 0x58c21, 0x58c40, 0x58ca1,
+    0x58ce0, 0x58d01, 0x58d20, 0x58d41, 0x58d60, 0x58d81, 0x58da0, 0x58e21, 0x58e40, 0x58e61,
+    0x58ea0, 0x58ec1, 0x58f83, 0x58fc0, 0x59021, 0x59040, 0x59061, 0x59080, 0x590a1, 0x590c0,
+    0x590e1, 0x59100, 0x59121, 0x59140, 0x59161, 0x59180, 0x591a1, 0x591c0, 0x591e1, 0x59200,
+    0x59221, 0x59240, 0x59261, 0x59280, 0x592a1, 0x592c0, 0x592e1, 0x59300, 0x59321, 0x59340,
+    0x59361, 0x59380, 0x593a1, 0x593c0, 0x593e1, 0x59400, 0x59421, 0x59440, 0x59461, 0x59480,
+    0x594a1, 0x594c0, 0x594e1, 0x59500, 0x59521, 0x59540, 0x59561, 0x59580, 0x595a1, 0x595c0,
+    0x595e1, 0x59600, 0x59621, 0x59640, 0x59661, 0x59680, 0x596a1, 0x596c0, 0x596e1, 0x59700,
+    0x59721, 0x59740, 0x59761, 0x59780, 0x597a1, 0x597c0, 0x597e1, 0x59800, 0x59821, 0x59840,
+    0x59861, 0x59880, 0x598a1, 0x598c0, 0x598e1, 0x59900, 0x59921, 0x59940, 0x59961, 0x59980,
+    0x599a1, 0x599c0, 0x599e1, 0x59a00, 0x59a21, 0x59a40, 0x59a61, 0x59a80, 0x59aa1, 0x59ac0,
+    0x59ae1, 0x59b00, 0x59b21, 0x59b40, 0x59b61, 0x59b80, 0x59ba1, 0x59bc0, 0x59be1, 0x59c00,
+    0x59c21, 0x59c40, 0x59c61, 0x59cb5, 0x59d60, 0x59d81, 0x59da0, 0x59dc1, 0x59de5, 0x59e40,
+    0x59e61, 0x59e9d, 0x59f31, 0x59faa, 0x59fd1, 0x5a001, 0x5a4dd, 0x5a4e1, 0x5a51d, 0x5a5a1,
+    0x5a5dd, 0x5a604, 0x5ad1d, 0x5ade3, 0x5ae11, 0x5ae3d, 0x5afe5, 0x5b004, 0x5b2fd, 0x5b404,
+    0x5b4fd, 0x5b504, 0x5b5fd, 0x5b604, 0x5b6fd, 0x5b704, 0x5b7fd, 0x5b804, 0x5b8fd, 0x5b904,
+    0x5b9fd, 0x5ba04, 0x5bafd, 0x5bb04, 0x5bbfd, 0x5bc05, 0x5c011, 0x5c04f, 0x5c070, 0x5c08f,
+    0x5c0b0, 0x5c0d1, 0x5c12f, 0x5c150, 0x5c171, 0x5c18f, 0x5c1b0, 0x5c1d1, 0x5c2ec, 0x5c311,
+    0x5c34c, 0x5c371, 0x5c38f, 0x5c3b0, 0x5c3d1, 0x5c40f, 0x5c430, 0x5c44d, 0x5c46e, 0x5c48d,
+    0x5c4ae, 0x5c4cd, 0x5c4ee, 0x5c50d, 0x5c52e, 0x5c551, 0x5c5e3, 0x5c611, 0x5c74c, 0x5c791,
+    0x5c80c, 0x5c831, 0x5c84d, 0x5c871, 0x5ca15, 0x5ca51, 0x5caad, 0x5cace, 0x5caed, 0x5cb0e,
+    0x5cb2d, 0x5cb4e, 0x5cb6d, 0x5cb8e, 0x5cbac, 0x5cbdd, 0x5d015, 0x5d35d, 0x5d375, 0x5de9d,
+    0x5e015, 0x5fadd, 0x5fe15, 0x5ff9d, 0x60016, 0x60031, 0x60095, 0x600a3, 0x600c4, 0x600e9,
+    0x6010d, 0x6012e, 0x6014d, 0x6016e, 0x6018d, 0x601ae, 0x601cd, 0x601ee, 0x6020d, 0x6022e,
+    0x60255, 0x6028d, 0x602ae, 0x602cd, 0x602ee, 0x6030d, 0x6032e, 0x6034d, 0x6036e, 0x6038c,
+    0x603ad, 0x603ce, 0x60415, 0x60429, 0x60545, 0x605c6, 0x6060c, 0x60623, 0x606d5, 0x60709,
+    0x60763, 0x60784, 0x607b1, 0x607d5, 0x6081d, 0x60824, 0x612fd, 0x61325, 0x61374, 0x613a3,
+    0x613e4, 0x6140c, 0x61424, 0x61f71, 0x61f83, 0x61fe4, 0x6201d, 0x620a4, 0x6261d, 0x62624,
+    0x631fd, 0x63215, 0x6324a, 0x632d5, 0x63404, 0x63815, 0x63c9d, 0x63e04, 0x64015, 0x643fd,
+    0x6440a, 0x64555, 0x6490a, 0x64a15, 0x64a2a, 0x64c15, 0x6500a, 0x65155, 0x6562a, 0x65815,
+    0x68004, 0x9b815, 0x9c004, 0x1402a3, 0x1402c4, 0x1491bd, 0x149215, 0x1498fd, 0x149a04, 0x149f03,
+    0x149fd1, 0x14a004, 0x14c183, 0x14c1b1, 0x14c204, 0x14c408, 0x14c544, 0x14c59d, 0x14c800,
+    0x14c821, 0x14c840, 0x14c861, 0x14c880, 0x14c8a1, 0x14c8c0, 0x14c8e1, 0x14c900, 0x14c921,
+    0x14c940, 0x14c961, 0x14c980, 0x14c9a1, 0x14c9c0, 0x14c9e1, 0x14ca00, 0x14ca21, 0x14ca40,
+    0x14ca61, 0x14ca80, 0x14caa1, 0x14cac0, 0x14cae1, 0x14cb00, 0x14cb21, 0x14cb40, 0x14cb61,
+    0x14cb80, 0x14cba1, 0x14cbc0, 0x14cbe1, 0x14cc00, 0x14cc21, 0x14cc40, 0x14cc61, 0x14cc80,
+    0x14cca1, 0x14ccc0, 0x14cce1, 0x14cd00, 0x14cd21, 0x14cd40, 0x14cd61, 0x14cd80, 0x14cda1,
+    0x14cdc4, 0x14cde5, 0x14ce07, 0x14ce71, 0x14ce85, 0x14cfd1, 0x14cfe3, 0x14d000, 0x14d021,
+    0x14d040, 0x14d061, 0x14d080, 0x14d0a1, 0x14d0c0, 0x14d0e1, 0x14d100, 0x14d121, 0x14d140,
+    0x14d161, 0x14d180, 0x14d1a1, 0x14d1c0, 0x14d1e1, 0x14d200, 0x14d221, 0x14d240, 0x14d261,
+    0x14d280, 0x14d2a1, 0x14d2c0, 0x14d2e1, 0x14d300, 0x14d321, 0x14d340, 0x14d361, 0x14d383,
+    0x14d3c5, 0x14d404, 0x14dcc9, 0x14de05, 0x14de51, 0x14df1d, 0x14e014, 0x14e2e3, 0x14e414,
+    0x14e440, 0x14e461, 0x14e480, 0x14e4a1, 0x14e4c0, 0x14e4e1, 0x14e500, 0x14e521, 0x14e540,
+    0x14e561, 0x14e580, 0x14e5a1, 0x14e5c0, 0x14e5e1, 0x14e640, 0x14e661, 0x14e680, 0x14e6a1,
+    0x14e6c0, 0x14e6e1, 0x14e700, 0x14e721, 0x14e740, 0x14e761, 0x14e780, 0x14e7a1, 0x14e7c0,
+    0x14e7e1, 0x14e800, 0x14e821, 0x14e840, 0x14e861, 0x14e880, 0x14e8a1, 0x14e8c0, 0x14e8e1,
+    0x14e900, 0x14e921, 0x14e940, 0x14e961, 0x14e980, 0x14e9a1, 0x14e9c0, 0x14e9e1, 0x14ea00,
+    0x14ea21, 0x14ea40, 0x14ea61, 0x14ea80, 0x14eaa1, 0x14eac0, 0x14eae1, 0x14eb00, 0x14eb21,
+    0x14eb40, 0x14eb61, 0x14eb80, 0x14eba1, 0x14ebc0, 0x14ebe1, 0x14ec00, 0x14ec21, 0x14ec40,
+    0x14ec61, 0x14ec80, 0x14eca1, 0x14ecc0, 0x14ece1, 0x14ed00, 0x14ed21, 0x14ed40, 0x14ed61,
+    0x14ed80, 0x14eda1, 0x14edc0, 0x14ede1, 0x14ee03, 0x14ee21, 0x14ef20, 0x14ef41, 0x14ef60,
+    0x14ef81, 0x14efa0, 0x14efe1, 0x14f000, 0x14f021, 0x14f040, 0x14f061, 0x14f080, 0x14f0a1,
+    0x14f0c0, 0x14f0e1, 0x14f103, 0x14f134, 0x14f160, 0x14f181, 0x14f1a0, 0x14f1c1, 0x14f1e4,
+    0x14f200, 0x14f221, 0x14f240, 0x14f261, 0x14f2c0, 0x14f2e1, 0x14f300, 0x14f321, 0x14f340,
+    0x14f361, 0x14f380, 0x14f3a1, 0x14f3c0, 0x14f3e1, 0x14f400, 0x14f421, 0x14f440, 0x14f461,
+    0x14f480, 0x14f4a1, 0x14f4c0, 0x14f4e1, 0x14f500, 0x14f521, 0x14f540, 0x14f5e1, 0x14f600,
+    0x14f6a1, 0x14f6c0, 0x14f6e1, 0x14f700, 0x14f721, 0x14f740, 0x14f761, 0x14f780, 0x14f7a1,
+    0x14f7c0, 0x14f7e1, 0x14f800, 0x14f821, 0x14f840, 0x14f861, 0x14f880, 0x14f901, 0x14f920,
+    0x14f941, 0x14f97d, 0x14fa00, 0x14fa21, 0x14fa5d, 0x14fa61, 0x14fa9d, 0x14faa1, 0x14fac0,
+    0x14fae1, 0x14fb00, 0x14fb21, 0x14fb5d, 0x14fe43, 0x14fea0, 0x14fec1, 0x14fee4, 0x14ff03,
+    0x14ff41, 0x14ff64, 0x150045, 0x150064, 0x1500c5, 0x1500e4, 0x150165, 0x150184, 0x150466,
+    0x1504a5, 0x1504e6, 0x150515, 0x150585, 0x1505bd, 0x15060a, 0x1506d5, 0x150713, 0x150735,
+    0x15075d, 0x150804, 0x150e91, 0x150f1d, 0x151006, 0x151044, 0x151686, 0x151885, 0x1518dd,
+    0x1519d1, 0x151a08, 0x151b5d, 0x151c05, 0x151e44, 0x151f11, 0x151f64, 0x151f91, 0x151fa4,
+    0x151fe5, 0x152008, 0x152144, 0x1524c5, 0x1525d1, 0x152604, 0x1528e5, 0x152a46, 0x152a9d,
+    0x152bf1, 0x152c04, 0x152fbd, 0x153005, 0x153066, 0x153084, 0x153665, 0x153686, 0x1536c5,
+    0x153746, 0x153785, 0x1537c6, 0x153831, 0x1539dd, 0x1539e3, 0x153a08, 0x153b5d, 0x153bd1,
+    0x153c04, 0x153ca5, 0x153cc3, 0x153ce4, 0x153e08, 0x153f44, 0x153ffd, 0x154004, 0x154525,
+    0x1545e6, 0x154625, 0x154666, 0x1546a5, 0x1546fd, 0x154804, 0x154865, 0x154884, 0x154985,
+    0x1549a6, 0x1549dd, 0x154a08, 0x154b5d, 0x154b91, 0x154c04, 0x154e03, 0x154e24, 0x154ef5,
+    0x154f44, 0x154f66, 0x154f85, 0x154fa6, 0x154fc4, 0x155605, 0x155624, 0x155645, 0x1556a4,
+    0x1556e5, 0x155724, 0x1557c5, 0x155804, 0x155825, 0x155844, 0x15587d, 0x155b64, 0x155ba3,
+    0x155bd1, 0x155c04, 0x155d66, 0x155d85, 0x155dc6, 0x155e11, 0x155e44, 0x155e63, 0x155ea6,
+    0x155ec5, 0x155efd, 0x156024, 0x1560fd, 0x156124, 0x1561fd, 0x156224, 0x1562fd, 0x156404,
+    0x1564fd, 0x156504, 0x1565fd, 0x156601, 0x156b74, 0x156b83, 0x156c01, 0x156d23, 0x156d54,
+    0x156d9d, 0x156e01, 0x157804, 0x157c66, 0x157ca5, 0x157cc6, 0x157d05, 0x157d26, 0x157d71,
+    0x157d86, 0x157da5, 0x157ddd, 0x157e08, 0x157f5d, 0x158004, 0x1af49d, 0x1af604, 0x1af8fd,
+    0x1af964, 0x1aff9d, 0x1b001b, 0x1c001c, 0x1f2004, 0x1f4ddd, 0x1f4e04, 0x1f5b5d, 0x1f6001,
+    0x1f60fd, 0x1f6261, 0x1f631d, 0x1f63a4, 0x1f63c5, 0x1f63e4, 0x1f6532, 0x1f6544, 0x1f66fd,
+    0x1f6704, 0x1f67bd, 0x1f67c4, 0x1f67fd, 0x1f6804, 0x1f685d, 0x1f6864, 0x1f68bd, 0x1f68c4,
+    0x1f7654, 0x1f787d, 0x1f7a64, 0x1fa7ce, 0x1fa7ed, 0x1fa815, 0x1faa04, 0x1fb21d, 0x1fb244,
+    0x1fb91d, 0x1fb9f5, 0x1fba1d, 0x1fbe04, 0x1fbf93, 0x1fbfb5, 0x1fc005, 0x1fc211, 0x1fc2ed,
+    0x1fc30e, 0x1fc331, 0x1fc35d, 0x1fc405, 0x1fc611, 0x1fc62c, 0x1fc66b, 0x1fc6ad, 0x1fc6ce,
+    0x1fc6ed, 0x1fc70e, 0x1fc72d, 0x1fc74e, 0x1fc76d, 0x1fc78e, 0x1fc7ad, 0x1fc7ce, 0x1fc7ed,
+    0x1fc80e, 0x1fc82d, 0x1fc84e, 0x1fc86d, 0x1fc88e, 0x1fc8b1, 0x1fc8ed, 0x1fc90e, 0x1fc931,
+    0x1fc9ab, 0x1fca11, 0x1fca7d, 0x1fca91, 0x1fcb0c, 0x1fcb2d, 0x1fcb4e, 0x1fcb6d, 0x1fcb8e,
+    0x1fcbad, 0x1fcbce, 0x1fcbf1, 0x1fcc52, 0x1fcc6c, 0x1fcc92, 0x1fccfd, 0x1fcd11, 0x1fcd33,
+    0x1fcd51, 0x1fcd9d, 0x1fce04, 0x1fcebd, 0x1fcec4, 0x1fdfbd, 0x1fdffa, 0x1fe01d, 0x1fe031,
+    0x1fe093, 0x1fe0b1, 0x1fe10d, 0x1fe12e, 0x1fe151, 0x1fe172, 0x1fe191, 0x1fe1ac, 0x1fe1d1,
+    0x1fe208, 0x1fe351, 0x1fe392, 0x1fe3f1, 0x1fe420, 0x1fe76d, 0x1fe791, 0x1fe7ae, 0x1fe7d4,
+    0x1fe7eb, 0x1fe814, 0x1fe821, 0x1feb6d, 0x1feb92, 0x1febae, 0x1febd2, 0x1febed, 0x1fec0e,
+    0x1fec31, 0x1fec4d, 0x1fec6e, 0x1fec91, 0x1fecc4, 0x1fee03, 0x1fee24, 0x1ff3c3, 0x1ff404,
+    0x1ff7fd, 0x1ff844, 0x1ff91d, 0x1ff944, 0x1ffa1d, 0x1ffa44, 0x1ffb1d, 0x1ffb44, 0x1ffbbd,
+    0x1ffc13, 0x1ffc52, 0x1ffc74, 0x1ffc95, 0x1ffcb3, 0x1ffcfd, 0x1ffd15, 0x1ffd32, 0x1ffdb5,
+    0x1ffdfd, 0x1fff3a, 0x1fff95, 0x1fffdd, 0x200004, 0x20019d, 0x2001a4, 0x2004fd, 0x200504,
+    0x20077d, 0x200784, 0x2007dd, 0x2007e4, 0x2009dd, 0x200a04, 0x200bdd, 0x201004, 0x201f7d,
+    0x202011, 0x20207d, 0x2020ea, 0x20269d, 0x2026f5, 0x202809, 0x202eaa, 0x202f35, 0x20314a,
+    0x203195, 0x2031fd, 0x203215, 0x2033bd, 0x203415, 0x20343d, 0x203a15, 0x203fa5, 0x203fdd,
+    0x205004, 0x2053bd, 0x205404, 0x205a3d, 0x205c05, 0x205c2a, 0x205f9d, 0x206004, 0x20640a,
+    0x20649d, 0x2065a4, 0x206829, 0x206844, 0x206949, 0x20697d, 0x206a04, 0x206ec5, 0x206f7d,
+    0x207004, 0x2073dd, 0x2073f1, 0x207404, 0x20789d, 0x207904, 0x207a11, 0x207a29, 0x207add,
+    0x208000, 0x208501, 0x208a04, 0x2093dd, 0x209408, 0x20955d, 0x209600, 0x209a9d, 0x209b01,
+    0x209f9d, 0x20a004, 0x20a51d, 0x20a604, 0x20ac9d, 0x20adf1, 0x20ae00, 0x20af7d, 0x20af80,
+    0x20b17d, 0x20b180, 0x20b27d, 0x20b280, 0x20b2dd, 0x20b2e1, 0x20b45d, 0x20b461, 0x20b65d,
+    0x20b661, 0x20b75d, 0x20b761, 0x20b7bd, 0x20c004, 0x20e6fd, 0x20e804, 0x20eadd, 0x20ec04,
+    0x20ed1d, 0x20f003, 0x20f0dd, 0x20f0e3, 0x20f63d, 0x20f643, 0x20f77d, 0x210004, 0x2100dd,
+    0x210104, 0x21013d, 0x210144, 0x2106dd, 0x2106e4, 0x21073d, 0x210784, 0x2107bd, 0x2107e4,
+    0x210add, 0x210af1, 0x210b0a, 0x210c04, 0x210ef5, 0x210f2a, 0x211004, 0x2113fd, 0x2114ea,
+    0x21161d, 0x211c04, 0x211e7d, 0x211e84, 0x211edd, 0x211f6a, 0x212004, 0x2122ca, 0x21239d,
+    0x2123f1, 0x212404, 0x21275d, 0x2127f1, 0x21281d, 0x213004, 0x21371d, 0x21378a, 0x2137c4,
+    0x21380a, 0x213a1d, 0x213a4a, 0x214004, 0x214025, 0x21409d, 0x2140a5, 0x2140fd, 0x214185,
+    0x214204, 0x21429d, 0x2142a4, 0x21431d, 0x214324, 0x2146dd, 0x214705, 0x21477d, 0x2147e5,
+    0x21480a, 0x21493d, 0x214a11, 0x214b3d, 0x214c04, 0x214faa, 0x214ff1, 0x215004, 0x2153aa,
+    0x21541d, 0x215804, 0x215915, 0x215924, 0x215ca5, 0x215cfd, 0x215d6a, 0x215e11, 0x215efd,
+    0x216004, 0x2166dd, 0x216731, 0x216804, 0x216add, 0x216b0a, 0x216c04, 0x216e7d, 0x216f0a,
+    0x217004, 0x21725d, 0x217331, 0x2173bd, 0x21752a, 0x21761d, 0x218004, 0x21893d, 0x219000,
+    0x21967d, 0x219801, 0x219e7d, 0x219f4a, 0x21a004, 0x21a485, 0x21a51d, 0x21a608, 0x21a75d,
+    0x21cc0a, 0x21cffd, 0x21d004, 0x21d55d, 0x21d565, 0x21d5ac, 0x21d5dd, 0x21d604, 0x21d65d,
+    0x21e004, 0x21e3aa, 0x21e4e4, 0x21e51d, 0x21e604, 0x21e8c5, 0x21ea2a, 0x21eab1, 0x21eb5d,
+    0x21ee04, 0x21f045, 0x21f0d1, 0x21f15d, 0x21f604, 0x21f8aa, 0x21f99d, 0x21fc04, 0x21fefd,
+    0x220006, 0x220025, 0x220046, 0x220064, 0x220705, 0x2208f1, 0x2209dd, 0x220a4a, 0x220cc8,
+    0x220e05, 0x220e24, 0x220e65, 0x220ea4, 0x220edd, 0x220fe5, 0x221046, 0x221064, 0x221606,
+    0x221665, 0x2216e6, 0x221725, 0x221771, 0x2217ba, 0x2217d1, 0x221845, 0x22187d, 0x2219ba,
+    0x2219dd, 0x221a04, 0x221d3d, 0x221e08, 0x221f5d, 0x222005, 0x222064, 0x2224e5, 0x222586,
+    0x2225a5, 0x2226bd, 0x2226c8, 0x222811, 0x222884, 0x2228a6, 0x2228e4, 0x22291d, 0x222a04,
+    0x222e65, 0x222e91, 0x222ec4, 0x222efd, 0x223005, 0x223046, 0x223064, 0x223666, 0x2236c5,
+    0x2237e6, 0x223824, 0x2238b1, 0x223925, 0x2239b1, 0x2239c6, 0x2239e5, 0x223a08, 0x223b44,
+    0x223b71, 0x223b84, 0x223bb1, 0x223c1d, 0x223c2a, 0x223ebd, 0x224004, 0x22425d, 0x224264,
+    0x224586, 0x2245e5, 0x224646, 0x224685, 0x2246a6, 0x2246c5, 0x224711, 0x2247c5, 0x2247fd,
+    0x225004, 0x2250fd, 0x225104, 0x22513d, 0x225144, 0x2251dd, 0x2251e4, 0x2253dd, 0x2253e4,
+    0x225531, 0x22555d, 0x225604, 0x225be5, 0x225c06, 0x225c65, 0x225d7d, 0x225e08, 0x225f5d,
+    0x226005, 0x226046, 0x22609d, 0x2260a4, 0x2261bd, 0x2261e4, 0x22623d, 0x226264, 0x22653d,
+    0x226544, 0x22663d, 0x226644, 0x22669d, 0x2266a4, 0x22675d, 0x226765, 0x2267a4, 0x2267c6,
+    0x226805, 0x226826, 0x2268bd, 0x2268e6, 0x22693d, 0x226966, 0x2269dd, 0x226a04, 0x226a3d,
+    0x226ae6, 0x226b1d, 0x226ba4, 0x226c46, 0x226c9d, 0x226cc5, 0x226dbd, 0x226e05, 0x226ebd,
+    0x228004, 0x2286a6, 0x228705, 0x228806, 0x228845, 0x2288a6, 0x2288c5, 0x2288e4, 0x228971,
+    0x228a08, 0x228b51, 0x228b9d, 0x228bb1, 0x228bc5, 0x228be4, 0x228c5d, 0x229004, 0x229606,
+    0x229665, 0x229726, 0x229745, 0x229766, 0x2297e5, 0x229826, 0x229845, 0x229884, 0x2298d1,
+    0x2298e4, 0x22991d, 0x229a08, 0x229b5d, 0x22b004, 0x22b5e6, 0x22b645, 0x22b6dd, 0x22b706,
+    0x22b785, 0x22b7c6, 0x22b7e5, 0x22b831, 0x22bb04, 0x22bb85, 0x22bbdd, 0x22c004, 0x22c606,
+    0x22c665, 0x22c766, 0x22c7a5, 0x22c7c6, 0x22c7e5, 0x22c831, 0x22c884, 0x22c8bd, 0x22ca08,
+    0x22cb5d, 0x22cc11, 0x22cdbd, 0x22d004, 0x22d565, 0x22d586, 0x22d5a5, 0x22d5c6, 0x22d605,
+    0x22d6c6, 0x22d6e5, 0x22d704, 0x22d731, 0x22d75d, 0x22d808, 0x22d95d, 0x22e004, 0x22e37d,
+    0x22e3a5, 0x22e406, 0x22e445, 0x22e4c6, 0x22e4e5, 0x22e59d, 0x22e608, 0x22e74a, 0x22e791,
+    0x22e7f5, 0x22e804, 0x22e8fd, 0x230004, 0x230586, 0x2305e5, 0x230706, 0x230725, 0x230771,
+    0x23079d, 0x231400, 0x231801, 0x231c08, 0x231d4a, 0x231e7d, 0x231fe4, 0x2320fd, 0x232124,
+    0x23215d, 0x232184, 0x23229d, 0x2322a4, 0x2322fd, 0x232304, 0x232606, 0x2326dd, 0x2326e6,
+    0x23273d, 0x232765, 0x2327a6, 0x2327c5, 0x2327e4, 0x232806, 0x232824, 0x232846, 0x232865,
+    0x232891, 0x2328fd, 0x232a08, 0x232b5d, 0x233404, 0x23351d, 0x233544, 0x233a26, 0x233a85,
+    0x233b1d, 0x233b45, 0x233b86, 0x233c05, 0x233c24, 0x233c51, 0x233c64, 0x233c86, 0x233cbd,
+    0x234004, 0x234025, 0x234164, 0x234665, 0x234726, 0x234744, 0x234765, 0x2347f1, 0x2348e5,
+    0x23491d, 0x234a04, 0x234a25, 0x234ae6, 0x234b25, 0x234b84, 0x235145, 0x2352e6, 0x235305,
+    0x235351, 0x2353a4, 0x2353d1, 0x23547d, 0x235604, 0x235f3d, 0x238004, 0x23813d, 0x238144,
+    0x2385e6, 0x238605, 0x2386fd, 0x238705, 0x2387c6, 0x2387e5, 0x238804, 0x238831, 0x2388dd,
+    0x238a08, 0x238b4a, 0x238dbd, 0x238e11, 0x238e44, 0x23921d, 0x239245, 0x23951d, 0x239526,
+    0x239545, 0x239626, 0x239645, 0x239686, 0x2396a5, 0x2396fd, 0x23a004, 0x23a0fd, 0x23a104,
+    0x23a15d, 0x23a164, 0x23a625, 0x23a6fd, 0x23a745, 0x23a77d, 0x23a785, 0x23a7dd, 0x23a7e5,
+    0x23a8c4, 0x23a8e5, 0x23a91d, 0x23aa08, 0x23ab5d, 0x23ac04, 0x23acdd, 0x23ace4, 0x23ad3d,
+    0x23ad44, 0x23b146, 0x23b1fd, 0x23b205, 0x23b25d, 0x23b266, 0x23b2a5, 0x23b2c6, 0x23b2e5,
+    0x23b304, 0x23b33d, 0x23b408, 0x23b55d, 0x23dc04, 0x23de65, 0x23dea6, 0x23def1, 0x23df3d,
+    0x23f604, 0x23f63d, 0x23f80a, 0x23fab5, 0x23fbb3, 0x23fc35, 0x23fe5d, 0x23fff1, 0x240004,
+    0x24735d, 0x248009, 0x248dfd, 0x248e11, 0x248ebd, 0x249004, 0x24a89d, 0x25f204, 0x25fe31,
+    0x25fe7d, 0x260004, 0x2685fd, 0x26861a, 0x26873d, 0x288004, 0x28c8fd, 0x2d0004, 0x2d473d,
+    0x2d4804, 0x2d4bfd, 0x2d4c08, 0x2d4d5d, 0x2d4dd1, 0x2d4e04, 0x2d57fd, 0x2d5808, 0x2d595d,
+    0x2d5a04, 0x2d5ddd, 0x2d5e05, 0x2d5eb1, 0x2d5edd, 0x2d6004, 0x2d6605, 0x2d66f1, 0x2d6795,
+    0x2d6803, 0x2d6891, 0x2d68b5, 0x2d68dd, 0x2d6a08, 0x2d6b5d, 0x2d6b6a, 0x2d6c5d, 0x2d6c64,
+    0x2d6f1d, 0x2d6fa4, 0x2d721d, 0x2dc800, 0x2dcc01, 0x2dd00a, 0x2dd2f1, 0x2dd37d, 0x2de004,
+    0x2de97d, 0x2de9e5, 0x2dea04, 0x2dea26, 0x2df11d, 0x2df1e5, 0x2df263, 0x2df41d, 0x2dfc03,
+    0x2dfc51, 0x2dfc63, 0x2dfc85, 0x2dfcbd, 0x2dfe06, 0x2dfe5d, 0x2e0004, 0x30ff1d, 0x310004,
+    0x319add, 0x31a004, 0x31a13d, 0x35fe03, 0x35fe9d, 0x35fea3, 0x35ff9d, 0x35ffa3, 0x35fffd,
+    0x360004, 0x36247d, 0x362a04, 0x362a7d, 0x362c84, 0x362d1d, 0x362e04, 0x365f9d, 0x378004,
+    0x378d7d, 0x378e04, 0x378fbd, 0x379004, 0x37913d, 0x379204, 0x37935d, 0x379395, 0x3793a5,
+    0x3793f1, 0x37941a, 0x37949d, 0x39e005, 0x39e5dd, 0x39e605, 0x39e8fd, 0x39ea15, 0x39f89d,
+    0x3a0015, 0x3a1edd, 0x3a2015, 0x3a24fd, 0x3a2535, 0x3a2ca6, 0x3a2ce5, 0x3a2d55, 0x3a2da6,
+    0x3a2e7a, 0x3a2f65, 0x3a3075, 0x3a30a5, 0x3a3195, 0x3a3545, 0x3a35d5, 0x3a3d7d, 0x3a4015,
+    0x3a4845, 0x3a48b5, 0x3a48dd, 0x3a5c0a, 0x3a5e9d, 0x3a6015, 0x3a6afd, 0x3a6c0a, 0x3a6f3d,
+    0x3a8000, 0x3a8341, 0x3a8680, 0x3a89c1, 0x3a8abd, 0x3a8ac1, 0x3a8d00, 0x3a9041, 0x3a9380,
+    0x3a93bd, 0x3a93c0, 0x3a941d, 0x3a9440, 0x3a947d, 0x3a94a0, 0x3a94fd, 0x3a9520, 0x3a95bd,
+    0x3a95c0, 0x3a96c1, 0x3a975d, 0x3a9761, 0x3a979d, 0x3a97a1, 0x3a989d, 0x3a98a1, 0x3a9a00,
+    0x3a9d41, 0x3aa080, 0x3aa0dd, 0x3aa0e0, 0x3aa17d, 0x3aa1a0, 0x3aa2bd, 0x3aa2c0, 0x3aa3bd,
+    0x3aa3c1, 0x3aa700, 0x3aa75d, 0x3aa760, 0x3aa7fd, 0x3aa800, 0x3aa8bd, 0x3aa8c0, 0x3aa8fd,
+    0x3aa940, 0x3aaa3d, 0x3aaa41, 0x3aad80, 0x3ab0c1, 0x3ab400, 0x3ab741, 0x3aba80, 0x3abdc1,
+    0x3ac100, 0x3ac441, 0x3ac780, 0x3acac1, 0x3ace00, 0x3ad141, 0x3ad4dd, 0x3ad500, 0x3ad832,
+    0x3ad841, 0x3adb72, 0x3adb81, 0x3adc40, 0x3adf72, 0x3adf81, 0x3ae2b2, 0x3ae2c1, 0x3ae380,
+    0x3ae6b2, 0x3ae6c1, 0x3ae9f2, 0x3aea01, 0x3aeac0, 0x3aedf2, 0x3aee01, 0x3af132, 0x3af141,
+    0x3af200, 0x3af532, 0x3af541, 0x3af872, 0x3af881, 0x3af940, 0x3af961, 0x3af99d, 0x3af9c8,
+    0x3b0015, 0x3b4005, 0x3b46f5, 0x3b4765, 0x3b4db5, 0x3b4ea5, 0x3b4ed5, 0x3b5085, 0x3b50b5,
+    0x3b50f1, 0x3b519d, 0x3b5365, 0x3b541d, 0x3b5425, 0x3b561d, 0x3be001, 0x3be144, 0x3be161,
+    0x3be3fd, 0x3c0005, 0x3c00fd, 0x3c0105, 0x3c033d, 0x3c0365, 0x3c045d, 0x3c0465, 0x3c04bd,
+    0x3c04c5, 0x3c057d, 0x3c2004, 0x3c25bd, 0x3c2605, 0x3c26e3, 0x3c27dd, 0x3c2808, 0x3c295d,
+    0x3c29c4, 0x3c29f5, 0x3c2a1d, 0x3c5204, 0x3c55c5, 0x3c55fd, 0x3c5804, 0x3c5d85, 0x3c5e08,
+    0x3c5f5d, 0x3c5ff3, 0x3c601d, 0x3cfc04, 0x3cfcfd, 0x3cfd04, 0x3cfd9d, 0x3cfda4, 0x3cfdfd,
+    0x3cfe04, 0x3cfffd, 0x3d0004, 0x3d18bd, 0x3d18ea, 0x3d1a05, 0x3d1afd, 0x3d2000, 0x3d2441,
+    0x3d2885, 0x3d2963, 0x3d299d, 0x3d2a08, 0x3d2b5d, 0x3d2bd1, 0x3d2c1d, 0x3d8e2a, 0x3d9595,
+    0x3d95aa, 0x3d9613, 0x3d962a, 0x3d96bd, 0x3da02a, 0x3da5d5, 0x3da5ea, 0x3da7dd, 0x3dc004,
+    0x3dc09d, 0x3dc0a4, 0x3dc41d, 0x3dc424, 0x3dc47d, 0x3dc484, 0x3dc4bd, 0x3dc4e4, 0x3dc51d,
+    0x3dc524, 0x3dc67d, 0x3dc684, 0x3dc71d, 0x3dc724, 0x3dc75d, 0x3dc764, 0x3dc79d, 0x3dc844,
+    0x3dc87d, 0x3dc8e4, 0x3dc91d, 0x3dc924, 0x3dc95d, 0x3dc964, 0x3dc99d, 0x3dc9a4, 0x3dca1d,
+    0x3dca24, 0x3dca7d, 0x3dca84, 0x3dcabd, 0x3dcae4, 0x3dcb1d, 0x3dcb24, 0x3dcb5d, 0x3dcb64,
+    0x3dcb9d, 0x3dcba4, 0x3dcbdd, 0x3dcbe4, 0x3dcc1d, 0x3dcc24, 0x3dcc7d, 0x3dcc84, 0x3dccbd,
+    0x3dcce4, 0x3dcd7d, 0x3dcd84, 0x3dce7d, 0x3dce84, 0x3dcf1d, 0x3dcf24, 0x3dcfbd, 0x3dcfc4,
+    0x3dcffd, 0x3dd004, 0x3dd15d, 0x3dd164, 0x3dd39d, 0x3dd424, 0x3dd49d, 0x3dd4a4, 0x3dd55d,
+    0x3dd564, 0x3dd79d, 0x3dde12, 0x3dde5d, 0x3e0015, 0x3e059d, 0x3e0615, 0x3e129d, 0x3e1415,
+    0x3e15fd, 0x3e1635, 0x3e181d, 0x3e1835, 0x3e1a1d, 0x3e1a35, 0x3e1edd, 0x3e200a, 0x3e21b5,
+    0x3e35dd, 0x3e3cd5, 0x3e407d, 0x3e4215, 0x3e479d, 0x3e4815, 0x3e493d, 0x3e4a15, 0x3e4a5d,
+    0x3e4c15, 0x3e4cdd, 0x3e6015, 0x3e7f74, 0x3e8015, 0x3edb1d, 0x3edbb5, 0x3eddbd, 0x3ede15,
+    0x3edfbd, 0x3ee015, 0x3eee9d, 0x3ef015, 0x3efb3d, 0x3efc15, 0x3efd9d, 0x3efe15, 0x3efe3d,
+    0x3f0015, 0x3f019d, 0x3f0215, 0x3f091d, 0x3f0a15, 0x3f0b5d, 0x3f0c15, 0x3f111d, 0x3f1215,
+    0x3f15dd, 0x3f1615, 0x3f165d, 0x3f2015, 0x3f4a9d, 0x3f4c15, 0x3f4ddd, 0x3f4e15, 0x3f4ebd,
+    0x3f4f15, 0x3f4fbd, 0x3f5015, 0x3f50fd, 0x3f5215, 0x3f55bd, 0x3f5615, 0x3f577d, 0x3f5815,
+    0x3f58dd, 0x3f5a15, 0x3f5b5d, 0x3f5c15, 0x3f5d1d, 0x3f5e15, 0x3f5efd, 0x3f6015, 0x3f727d,
+    0x3f7295, 0x3f797d, 0x3f7e08, 0x3f7f5d, 0x400004, 0x54dc1d, 0x54e004, 0x56e73d, 0x56e804,
+    0x5703dd, 0x570404, 0x59d45d, 0x59d604, 0x5d7c3d, 0x5f0004, 0x5f43dd, 0x600004, 0x62697d,
+    0x1c0003a, 0x1c0005d, 0x1c0041a, 0x1c0101d, 0x1c02005, 0x1c03e1d, 0x1e0001c, 0x1ffffdd,
+    0x200001c, 0x21fffdd,
+];
+
+/// Start of each range (shifted by 3) and its East Asian width
+pub(super) static EAST_ASIAN_WIDTH: [u32; 1861] = [
+    0x0, 0x104, 0x3f8, 0x509, 0x514, 0x521, 0x52c, 0x539, 0x548, 0x551, 0x558, 0x564, 0x569, 0x57c,
+    0x581, 0x5a8, 0x5b1, 0x5d8, 0x5e1, 0x600, 0x631, 0x638, 0x681, 0x688, 0x6b9, 0x6c8, 0x6f1,
+    0x710, 0x731, 0x738, 0x741, 0x758, 0x761, 0x770, 0x781, 0x788, 0x791, 0x7a0, 0x7b9, 0x7d8,
+    0x7e1, 0x7e8, 0x7f1, 0x7f8, 0x809, 0x810, 0x889, 0x890, 0x899, 0x8a0, 0x8d9, 0x8e0, 0x931,
+    0x940, 0x959, 0x960, 0x989, 0x9a0, 0x9c1, 0x9c8, 0x9f9, 0xa18, 0xa21, 0xa28, 0xa41, 0xa60,
+    0xa69, 0xa70, 0xa91, 0xaa0, 0xb31, 0xb40, 0xb59, 0xb60, 0xe71, 0xe78, 0xe81, 0xe88, 0xe91,
+    0xe98, 0xea1, 0xea8, 0xeb1, 0xeb8, 0xec1, 0xec8, 0xed1, 0xed8, 0xee1, 0xee8, 0x1289, 0x1290,
+    0x1309, 0x1310, 0x1621, 0x1628, 0x1639, 0x1640, 0x1649, 0x1660, 0x1669, 0x1670, 0x1681, 0x1688,
+    0x16c1, 0x16e0, 0x16e9, 0x16f0, 0x16f9, 0x1700, 0x1801, 0x1b80, 0x1bc3, 0x1bd0, 0x1c03, 0x1c20,
+    0x1c5b, 0x1c60, 0x1c6b, 0x1c70, 0x1c89, 0x1d13, 0x1d19, 0x1d50, 0x1d89, 0x1e10, 0x1e19, 0x1e50,
+    0x2009, 0x2010, 0x2081, 0x2280, 0x2289, 0x2290, 0x2983, 0x2988, 0x2abb, 0x2ac8, 0x2c5b, 0x2c68,
+    0x2c83, 0x2c88, 0x2e43, 0x2e80, 0x2f5b, 0x2f78, 0x2fab, 0x3000, 0x3873, 0x3878, 0x3a5b, 0x3a68,
+    0x3d93, 0x3e00, 0x3fdb, 0x3fe8, 0x4173, 0x4180, 0x41fb, 0x4200, 0x42e3, 0x42f0, 0x42fb, 0x4300,
+    0x435b, 0x4380, 0x447b, 0x4480, 0x4493, 0x44c0, 0x4c23, 0x4c28, 0x4c6b, 0x4c78, 0x4c8b, 0x4c98,
+    0x4d4b, 0x4d50, 0x4d8b, 0x4d90, 0x4d9b, 0x4db0, 0x4dd3, 0x4de0, 0x4e2b, 0x4e38, 0x4e4b, 0x4e58,
+    0x4e7b, 0x4eb8, 0x4ec3, 0x4ee0, 0x4ef3, 0x4ef8, 0x4f23, 0x4f30, 0x4ffb, 0x5008, 0x5023, 0x5028,
+    0x505b, 0x5078, 0x508b, 0x5098, 0x514b, 0x5150, 0x518b, 0x5190, 0x51a3, 0x51a8, 0x51bb, 0x51c0,
+    0x51d3, 0x51e0, 0x51eb, 0x51f0, 0x521b, 0x5238, 0x524b, 0x5258, 0x5273, 0x5288, 0x5293, 0x52c8,
+    0x52eb, 0x52f0, 0x52fb, 0x5330, 0x53bb, 0x5408, 0x5423, 0x5428, 0x5473, 0x5478, 0x5493, 0x5498,
+    0x554b, 0x5550, 0x558b, 0x5590, 0x55a3, 0x55a8, 0x55d3, 0x55e0, 0x5633, 0x5638, 0x5653, 0x5658,
+    0x5673, 0x5680, 0x568b, 0x5700, 0x5723, 0x5730, 0x5793, 0x57c8, 0x5803, 0x5808, 0x5823, 0x5828,
+    0x586b, 0x5878, 0x588b, 0x5898, 0x594b, 0x5950, 0x598b, 0x5990, 0x59a3, 0x59a8, 0x59d3, 0x59e0,
+    0x5a2b, 0x5a38, 0x5a4b, 0x5a58, 0x5a73, 0x5aa8, 0x5ac3, 0x5ae0, 0x5af3, 0x5af8, 0x5b23, 0x5b30,
+    0x5bc3, 0x5c10, 0x5c23, 0x5c28, 0x5c5b, 0x5c70, 0x5c8b, 0x5c90, 0x5cb3, 0x5cc8, 0x5cdb, 0x5ce0,
+    0x5ceb, 0x5cf0, 0x5d03, 0x5d18, 0x5d2b, 0x5d40, 0x5d5b, 0x5d70, 0x5dd3, 0x5df0, 0x5e1b, 0x5e30,
+    0x5e4b, 0x5e50, 0x5e73, 0x5e80, 0x5e8b, 0x5eb8, 0x5ec3, 0x5f30, 0x5fdb, 0x6000, 0x606b, 0x6070,
+    0x608b, 0x6090, 0x614b, 0x6150, 0x61d3, 0x61e0, 0x622b, 0x6230, 0x624b, 0x6250, 0x6273, 0x62a8,
+    0x62bb, 0x62c0, 0x62db, 0x62e8, 0x62f3, 0x6300, 0x6323, 0x6330, 0x6383, 0x63b8, 0x646b, 0x6470,
+    0x648b, 0x6490, 0x654b, 0x6550, 0x65a3, 0x65a8, 0x65d3, 0x65e0, 0x662b, 0x6630, 0x664b, 0x6650,
+    0x6673, 0x66a8, 0x66bb, 0x66e8, 0x66fb, 0x6700, 0x6723, 0x6730, 0x6783, 0x6788, 0x679b, 0x6800,
+    0x686b, 0x6870, 0x688b, 0x6890, 0x6a2b, 0x6a30, 0x6a4b, 0x6a50, 0x6a83, 0x6aa0, 0x6b23, 0x6b30,
+    0x6c03, 0x6c08, 0x6c23, 0x6c28, 0x6cbb, 0x6cd0, 0x6d93, 0x6d98, 0x6de3, 0x6de8, 0x6df3, 0x6e00,
+    0x6e3b, 0x6e50, 0x6e5b, 0x6e78, 0x6eab, 0x6eb0, 0x6ebb, 0x6ec0, 0x6f03, 0x6f30, 0x6f83, 0x6f90,
+    0x6fab, 0x7008, 0x71db, 0x71f8, 0x72e3, 0x7408, 0x741b, 0x7420, 0x742b, 0x7430, 0x745b, 0x7460,
+    0x7523, 0x7528, 0x7533, 0x7538, 0x75f3, 0x7600, 0x762b, 0x7630, 0x763b, 0x7640, 0x7673, 0x7680,
+    0x76d3, 0x76e0, 0x7703, 0x7800, 0x7a43, 0x7a48, 0x7b6b, 0x7b88, 0x7cc3, 0x7cc8, 0x7deb, 0x7df0,
+    0x7e6b, 0x7e70, 0x7edb, 0x8000, 0x8633, 0x8638, 0x8643, 0x8668, 0x8673, 0x8680, 0x8805, 0x8b00,
+    0x924b, 0x9250, 0x9273, 0x9280, 0x92bb, 0x92c0, 0x92cb, 0x92d0, 0x92f3, 0x9300, 0x944b, 0x9450,
+    0x9473, 0x9480, 0x958b, 0x9590, 0x95b3, 0x95c0, 0x95fb, 0x9600, 0x960b, 0x9610, 0x9633, 0x9640,
+    0x96bb, 0x96c0, 0x988b, 0x9890, 0x98b3, 0x98c0, 0x9adb, 0x9ae8, 0x9beb, 0x9c00, 0x9cd3, 0x9d00,
+    0x9fb3, 0x9fc0, 0x9ff3, 0xa000, 0xb4eb, 0xb500, 0xb7cb, 0xb800, 0xb8b3, 0xb8f8, 0xb9bb, 0xba00,
+    0xbaa3, 0xbb00, 0xbb6b, 0xbb70, 0xbb8b, 0xbb90, 0xbba3, 0xbc00, 0xbef3, 0xbf00, 0xbf53, 0xbf80,
+    0xbfd3, 0xc000, 0xc0d3, 0xc100, 0xc3cb, 0xc400, 0xc55b, 0xc580, 0xc7b3, 0xc800, 0xc8fb, 0xc900,
+    0xc963, 0xc980, 0xc9e3, 0xca00, 0xca0b, 0xca20, 0xcb73, 0xcb80, 0xcbab, 0xcc00, 0xcd63, 0xcd80,
+    0xce53, 0xce80, 0xcedb, 0xcef0, 0xd0e3, 0xd0f0, 0xd2fb, 0xd300, 0xd3eb, 0xd3f8, 0xd453, 0xd480,
+    0xd4d3, 0xd500, 0xd573, 0xd580, 0xd67b, 0xd800, 0xda6b, 0xda80, 0xdbfb, 0xdc00, 0xdfa3, 0xdfe0,
+    0xe1c3, 0xe1d8, 0xe253, 0xe268, 0xe44b, 0xe480, 0xe5db, 0xe5e8, 0xe643, 0xe680, 0xe7db, 0xe800,
+    0xf8b3, 0xf8c0, 0xf8f3, 0xf900, 0xfa33, 0xfa40, 0xfa73, 0xfa80, 0xfac3, 0xfac8, 0xfad3, 0xfad8,
+    0xfae3, 0xfae8, 0xfaf3, 0xfaf8, 0xfbf3, 0xfc00, 0xfdab, 0xfdb0, 0xfe2b, 0xfe30, 0xfea3, 0xfeb0,
+    0xfee3, 0xfee8, 0xff83, 0xff90, 0xffab, 0xffb0, 0xfffb, 0x10000, 0x10081, 0x10088, 0x10099,
+    0x100b8, 0x100c1, 0x100d0, 0x100e1, 0x100f0, 0x10101, 0x10118, 0x10121, 0x10140, 0x10181,
+    0x10188, 0x10191, 0x101a0, 0x101a9, 0x101b0, 0x101d9, 0x101e0, 0x101f1, 0x101f8, 0x1032b,
+    0x10330, 0x10393, 0x103a1, 0x103a8, 0x103f9, 0x10400, 0x10409, 0x10428, 0x1047b, 0x10480,
+    0x104eb, 0x10500, 0x1054a, 0x10550, 0x10561, 0x10568, 0x1060b, 0x10680, 0x1078b, 0x10800,
+    0x10819, 0x10820, 0x10829, 0x10830, 0x10849, 0x10850, 0x10899, 0x108a0, 0x108b1, 0x108b8,
+    0x10909, 0x10918, 0x10931, 0x10938, 0x10959, 0x10960, 0x10a99, 0x10aa8, 0x10ad9, 0x10af8,
+    0x10b01, 0x10b60, 0x10b81, 0x10bd0, 0x10c49, 0x10c50, 0x10c63, 0x10c81, 0x10cd0, 0x10dc1,
+    0x10dd0, 0x10e91, 0x10e98, 0x10ea1, 0x10ea8, 0x10f39, 0x10f40, 0x11001, 0x11008, 0x11011,
+    0x11020, 0x11039, 0x11048, 0x11059, 0x11060, 0x11079, 0x11080, 0x11089, 0x11090, 0x110a9,
+    0x110b0, 0x110d1, 0x110d8, 0x110e9, 0x11108, 0x11119, 0x11120, 0x11129, 0x11130, 0x11139,
+    0x11168, 0x11171, 0x11178, 0x111a1, 0x111c0, 0x111e1, 0x111f0, 0x11241, 0x11248, 0x11261,
+    0x11268, 0x11291, 0x11298, 0x11301, 0x11310, 0x11321, 0x11340, 0x11351, 0x11360, 0x11371,
+    0x11380, 0x11411, 0x11420, 0x11431, 0x11440, 0x114a9, 0x114b0, 0x114c9, 0x114d0, 0x11529,
+    0x11530, 0x115f9, 0x11600, 0x11891, 0x11898, 0x118d5, 0x118e0, 0x1194d, 0x11958, 0x11f4d,
+    0x11f68, 0x11f85, 0x11f88, 0x11f9d, 0x11fa0, 0x1213b, 0x12200, 0x1225b, 0x12301, 0x12750,
+    0x12759, 0x12a60, 0x12a81, 0x12ba0, 0x12c01, 0x12c80, 0x12c91, 0x12cb0, 0x12d01, 0x12d10,
+    0x12d19, 0x12d50, 0x12d91, 0x12da0, 0x12db1, 0x12dc0, 0x12de1, 0x12df0, 0x12e01, 0x12e10,
+    0x12e31, 0x12e48, 0x12e59, 0x12e60, 0x12e71, 0x12e90, 0x12f11, 0x12f30, 0x12f79, 0x12f80,
+    0x12fed, 0x12ff8, 0x13029, 0x13038, 0x13049, 0x13050, 0x13071, 0x13080, 0x130a5, 0x130b0,
+    0x130e1, 0x130e8, 0x130f1, 0x130f8, 0x13201, 0x13208, 0x13211, 0x13218, 0x13245, 0x132a0,
+    0x13301, 0x13310, 0x13319, 0x13330, 0x13339, 0x13358, 0x13361, 0x13370, 0x13379, 0x13380,
+    0x133fd, 0x13400, 0x1349d, 0x134a0, 0x134f1, 0x13500, 0x1350d, 0x13510, 0x13555, 0x13560,
+    0x135ed, 0x135f9, 0x13600, 0x13625, 0x13631, 0x13675, 0x13679, 0x136a5, 0x136a9, 0x13710,
+    0x13719, 0x13720, 0x13741, 0x13755, 0x13759, 0x13795, 0x137a1, 0x137ad, 0x137b1, 0x137d5,
+    0x137d9, 0x137ed, 0x137f1, 0x13800, 0x1382d, 0x13830, 0x13855, 0x13860, 0x13945, 0x13948,
+    0x139e9, 0x139f0, 0x13a65, 0x13a68, 0x13a75, 0x13a78, 0x13a9d, 0x13ab0, 0x13abd, 0x13ac0,
+    0x13bb1, 0x13c00, 0x13cad, 0x13cc0, 0x13d85, 0x13d88, 0x13dfd, 0x13e00, 0x13f34, 0x13f70,
+    0x14c2c, 0x14c38, 0x158dd, 0x158e8, 0x15a85, 0x15a88, 0x15aad, 0x15ab1, 0x15ad0, 0x15ba3,
+    0x15bb0, 0x15cb3, 0x15cb8, 0x167a3, 0x167c8, 0x16933, 0x16938, 0x16943, 0x16968, 0x16973,
+    0x16980, 0x16b43, 0x16b78, 0x16b8b, 0x16bf8, 0x16cbb, 0x16d00, 0x16d3b, 0x16d40, 0x16d7b,
+    0x16d80, 0x16dbb, 0x16dc0, 0x16dfb, 0x16e00, 0x16e3b, 0x16e40, 0x16e7b, 0x16e80, 0x16ebb,
+    0x16ec0, 0x16efb, 0x16f00, 0x172f3, 0x17405, 0x174d3, 0x174dd, 0x177a3, 0x17805, 0x17eb3,
+    0x17f85, 0x17fe3, 0x1800d, 0x181f8, 0x18203, 0x1820d, 0x184bb, 0x184cd, 0x18803, 0x1882d,
+    0x18983, 0x1898d, 0x18c7b, 0x18c85, 0x18f23, 0x18f85, 0x190fb, 0x19105, 0x19241, 0x19285,
+    0x26e00, 0x27005, 0x5246b, 0x52485, 0x5263b, 0x52680, 0x53163, 0x53200, 0x537c3, 0x53800,
+    0x53e5b, 0x53e80, 0x53e93, 0x53e98, 0x53ea3, 0x53ea8, 0x53ed3, 0x53f90, 0x5416b, 0x54180,
+    0x541d3, 0x54200, 0x543c3, 0x54400, 0x54633, 0x54670, 0x546d3, 0x54700, 0x54aa3, 0x54af8,
+    0x54b05, 0x54beb, 0x54c00, 0x54e73, 0x54e78, 0x54ed3, 0x54ef0, 0x54ffb, 0x55000, 0x551bb,
+    0x55200, 0x55273, 0x55280, 0x552d3, 0x552e0, 0x5561b, 0x556d8, 0x557bb, 0x55808, 0x5583b,
+    0x55848, 0x5587b, 0x55888, 0x558bb, 0x55900, 0x5593b, 0x55940, 0x5597b, 0x55980, 0x55b63,
+    0x55b80, 0x55f73, 0x55f80, 0x55fd3, 0x56005, 0x6bd23, 0x6bd80, 0x6be3b, 0x6be58, 0x6bfe3,
+    0x6c000, 0x70001, 0x7c805, 0x7d373, 0x7d385, 0x7d6d3, 0x7d800, 0x7d83b, 0x7d898, 0x7d8c3,
+    0x7d8e8, 0x7d9bb, 0x7d9c0, 0x7d9eb, 0x7d9f0, 0x7d9fb, 0x7da00, 0x7da13, 0x7da18, 0x7da2b,
+    0x7da30, 0x7de1b, 0x7de98, 0x7ec83, 0x7ec90, 0x7ee43, 0x7ee78, 0x7ee83, 0x7ef80, 0x7f001,
+    0x7f085, 0x7f0d3, 0x7f100, 0x7f185, 0x7f29b, 0x7f2a5, 0x7f33b, 0x7f345, 0x7f363, 0x7f380,
+    0x7f3ab, 0x7f3b0, 0x7f7eb, 0x7f7f8, 0x7f803, 0x7fb0a, 0x7fdfb, 0x7fe12, 0x7fe43, 0x7fe52,
+    0x7fe83, 0x7fe92, 0x7fec3, 0x7fed2, 0x7feeb, 0x7ff42, 0x7ff7b, 0x7ffc8, 0x7ffe9, 0x7fff3,
+    0x80000, 0x80063, 0x80068, 0x8013b, 0x80140, 0x801db, 0x801e0, 0x801f3, 0x801f8, 0x80273,
+    0x80280, 0x802f3, 0x80400, 0x807db, 0x80800, 0x8081b, 0x80838, 0x809a3, 0x809b8, 0x80c7b,
+    0x80c80, 0x80ceb, 0x80d00, 0x80d0b, 0x80e80, 0x80ff3, 0x81400, 0x814eb, 0x81500, 0x8168b,
+    0x81700, 0x817e3, 0x81800, 0x81923, 0x81968, 0x81a5b, 0x81a80, 0x81bdb, 0x81c00, 0x81cf3,
+    0x81cf8, 0x81e23, 0x81e40, 0x81eb3, 0x82000, 0x824f3, 0x82500, 0x82553, 0x82580, 0x826a3,
+    0x826c0, 0x827e3, 0x82800, 0x82943, 0x82980, 0x82b23, 0x82b78, 0x82bdb, 0x82be0, 0x82c5b,
+    0x82c60, 0x82c9b, 0x82ca0, 0x82cb3, 0x82cb8, 0x82d13, 0x82d18, 0x82d93, 0x82d98, 0x82dd3,
+    0x82dd8, 0x82deb, 0x83000, 0x839bb, 0x83a00, 0x83ab3, 0x83b00, 0x83b43, 0x83c00, 0x83c33,
+    0x83c38, 0x83d8b, 0x83d90, 0x83ddb, 0x84000, 0x84033, 0x84040, 0x8404b, 0x84050, 0x841b3,
+    0x841b8, 0x841cb, 0x841e0, 0x841eb, 0x841f8, 0x842b3, 0x842b8, 0x844fb, 0x84538, 0x84583,
+    0x84700, 0x8479b, 0x847a0, 0x847b3, 0x847d8, 0x848e3, 0x848f8, 0x849d3, 0x849f8, 0x84a03,
+    0x84c00, 0x84dc3, 0x84de0, 0x84e83, 0x84e90, 0x85023, 0x85028, 0x8503b, 0x85060, 0x850a3,
+    0x850a8, 0x850c3, 0x850c8, 0x851b3, 0x851c0, 0x851db, 0x851f8, 0x8524b, 0x85280, 0x852cb,
+    0x85300, 0x85503, 0x85600, 0x8573b, 0x85758, 0x857bb, 0x85800, 0x859b3, 0x859c8, 0x85ab3,
+    0x85ac0, 0x85b9b, 0x85bc0, 0x85c93, 0x85cc8, 0x85ceb, 0x85d48, 0x85d83, 0x86000, 0x8624b,
+    0x86400, 0x8659b, 0x86600, 0x8679b, 0x867d0, 0x86943, 0x86980, 0x869d3, 0x87300, 0x873fb,
+    0x87400, 0x87553, 0x87558, 0x87573, 0x87580, 0x87593, 0x87800, 0x87943, 0x87980, 0x87ad3,
+    0x87b80, 0x87c53, 0x87d80, 0x87e63, 0x87f00, 0x87fbb, 0x88000, 0x88273, 0x88290, 0x883b3,
+    0x883f8, 0x8861b, 0x88668, 0x88673, 0x88680, 0x8874b, 0x88780, 0x887d3, 0x88800, 0x889ab,
+    0x889b0, 0x88a43, 0x88a80, 0x88bbb, 0x88c00, 0x88f03, 0x88f08, 0x88fab, 0x89000, 0x89093,
+    0x89098, 0x891fb, 0x89400, 0x8943b, 0x89440, 0x8944b, 0x89450, 0x89473, 0x89478, 0x894f3,
+    0x894f8, 0x89553, 0x89580, 0x8975b, 0x89780, 0x897d3, 0x89800, 0x89823, 0x89828, 0x8986b,
+    0x89878, 0x8988b, 0x89898, 0x8994b, 0x89950, 0x8998b, 0x89990, 0x899a3, 0x899a8, 0x899d3,
+    0x899d8, 0x89a2b, 0x89a38, 0x89a4b, 0x89a58, 0x89a73, 0x89a80, 0x89a8b, 0x89ab8, 0x89ac3,
+    0x89ae8, 0x89b23, 0x89b30, 0x89b6b, 0x89b80, 0x89bab, 0x8a000, 0x8a2e3, 0x8a2e8, 0x8a313,
+    0x8a400, 0x8a643, 0x8a680, 0x8a6d3, 0x8ac00, 0x8adb3, 0x8adc0, 0x8aef3, 0x8b000, 0x8b22b,
+    0x8b280, 0x8b2d3, 0x8b300, 0x8b36b, 0x8b400, 0x8b5d3, 0x8b600, 0x8b653, 0x8b800, 0x8b8db,
+    0x8b8e8, 0x8b963, 0x8b980, 0x8ba3b, 0x8c000, 0x8c1e3, 0x8c500, 0x8c79b, 0x8c7f8, 0x8c83b,
+    0x8c848, 0x8c853, 0x8c860, 0x8c8a3, 0x8c8a8, 0x8c8bb, 0x8c8c0, 0x8c9b3, 0x8c9b8, 0x8c9cb,
+    0x8c9d8, 0x8ca3b, 0x8ca80, 0x8cad3, 0x8cd00, 0x8cd43, 0x8cd50, 0x8cec3, 0x8ced0, 0x8cf2b,
+    0x8d000, 0x8d243, 0x8d280, 0x8d51b, 0x8d580, 0x8d7cb, 0x8e000, 0x8e04b, 0x8e050, 0x8e1bb,
+    0x8e1c0, 0x8e233, 0x8e280, 0x8e36b, 0x8e380, 0x8e483, 0x8e490, 0x8e543, 0x8e548, 0x8e5bb,
+    0x8e800, 0x8e83b, 0x8e840, 0x8e853, 0x8e858, 0x8e9bb, 0x8e9d0, 0x8e9db, 0x8e9e0, 0x8e9f3,
+    0x8e9f8, 0x8ea43, 0x8ea80, 0x8ead3, 0x8eb00, 0x8eb33, 0x8eb38, 0x8eb4b, 0x8eb50, 0x8ec7b,
+    0x8ec80, 0x8ec93, 0x8ec98, 0x8eccb, 0x8ed00, 0x8ed53, 0x8f700, 0x8f7cb, 0x8fd80, 0x8fd8b,
+    0x8fe00, 0x8ff93, 0x8fff8, 0x91cd3, 0x92000, 0x9237b, 0x92380, 0x923ab, 0x92400, 0x92a23,
+    0x97c80, 0x97f9b, 0x98000, 0x9a17b, 0x9a180, 0x9a1cb, 0xa2000, 0xa323b, 0xb4000, 0xb51cb,
+    0xb5200, 0xb52fb, 0xb5300, 0xb5353, 0xb5370, 0xb55fb, 0xb5600, 0xb5653, 0xb5680, 0xb5773,
+    0xb5780, 0xb57b3, 0xb5800, 0xb5a33, 0xb5a80, 0xb5ad3, 0xb5ad8, 0xb5b13, 0xb5b18, 0xb5bc3,
+    0xb5be8, 0xb5c83, 0xb7200, 0xb74db, 0xb7800, 0xb7a5b, 0xb7a78, 0xb7c43, 0xb7c78, 0xb7d03,
+    0xb7f05, 0xb7f2b, 0xb7f85, 0xb7f93, 0xb8005, 0xc3fc3, 0xc4005, 0xc66b3, 0xc6805, 0xc684b,
+    0xd7f85, 0xd7fa3, 0xd7fad, 0xd7fe3, 0xd7fed, 0xd7ffb, 0xd8005, 0xd891b, 0xd8a85, 0xd8a9b,
+    0xd8b25, 0xd8b43, 0xd8b85, 0xd97e3, 0xde000, 0xde35b, 0xde380, 0xde3eb, 0xde400, 0xde44b,
+    0xde480, 0xde4d3, 0xde4e0, 0xde523, 0xe7800, 0xe7973, 0xe7980, 0xe7a3b, 0xe7a80, 0xe7e23,
+    0xe8000, 0xe87b3, 0xe8800, 0xe893b, 0xe8948, 0xe8f5b, 0xe9000, 0xe9233, 0xe9700, 0xe97a3,
+    0xe9800, 0xe9abb, 0xe9b00, 0xe9bcb, 0xea000, 0xea2ab, 0xea2b0, 0xea4eb, 0xea4f0, 0xea503,
+    0xea510, 0xea51b, 0xea528, 0xea53b, 0xea548, 0xea56b, 0xea570, 0xea5d3, 0xea5d8, 0xea5e3,
+    0xea5e8, 0xea623, 0xea628, 0xea833, 0xea838, 0xea85b, 0xea868, 0xea8ab, 0xea8b0, 0xea8eb,
+    0xea8f0, 0xea9d3, 0xea9d8, 0xea9fb, 0xeaa00, 0xeaa2b, 0xeaa30, 0xeaa3b, 0xeaa50, 0xeaa8b,
+    0xeaa90, 0xeb533, 0xeb540, 0xebe63, 0xebe70, 0xed463, 0xed4d8, 0xed503, 0xed508, 0xed583,
+    0xef800, 0xef8fb, 0xf0000, 0xf003b, 0xf0040, 0xf00cb, 0xf00d8, 0xf0113, 0xf0118, 0xf012b,
+    0xf0130, 0xf015b, 0xf0800, 0xf096b, 0xf0980, 0xf09f3, 0xf0a00, 0xf0a53, 0xf0a70, 0xf0a83,
+    0xf1480, 0xf157b, 0xf1600, 0xf17d3, 0xf17f8, 0xf1803, 0xf3f00, 0xf3f3b, 0xf3f40, 0xf3f63,
+    0xf3f68, 0xf3f7b, 0xf3f80, 0xf3ffb, 0xf4000, 0xf462b, 0xf4638, 0xf46bb, 0xf4800, 0xf4a63,
+    0xf4a80, 0xf4ad3, 0xf4af0, 0xf4b03, 0xf6388, 0xf65ab, 0xf6808, 0xf69f3, 0xf7000, 0xf7023,
+    0xf7028, 0xf7103, 0xf7108, 0xf711b, 0xf7120, 0xf712b, 0xf7138, 0xf7143, 0xf7148, 0xf719b,
+    0xf71a0, 0xf71c3, 0xf71c8, 0xf71d3, 0xf71d8, 0xf71e3, 0xf7210, 0xf721b, 0xf7238, 0xf7243,
+    0xf7248, 0xf7253, 0xf7258, 0xf7263, 0xf7268, 0xf7283, 0xf7288, 0xf729b, 0xf72a0, 0xf72ab,
+    0xf72b8, 0xf72c3, 0xf72c8, 0xf72d3, 0xf72d8, 0xf72e3, 0xf72e8, 0xf72f3, 0xf72f8, 0xf7303,
+    0xf7308, 0xf731b, 0xf7320, 0xf732b, 0xf7338, 0xf735b, 0xf7360, 0xf739b, 0xf73a0, 0xf73c3,
+    0xf73c8, 0xf73eb, 0xf73f0, 0xf73fb, 0xf7400, 0xf7453, 0xf7458, 0xf74e3, 0xf7508, 0xf7523,
+    0xf7528, 0xf7553, 0xf7558, 0xf75e3, 0xf7780, 0xf7793, 0xf8000, 0xf8025, 0xf8028, 0xf8163,
+    0xf8180, 0xf84a3, 0xf8500, 0xf857b, 0xf8588, 0xf8603, 0xf8608, 0xf867d, 0xf8683, 0xf8688,
+    0xf87b3, 0xf8801, 0xf8858, 0xf8881, 0xf8970, 0xf8981, 0xf8b50, 0xf8b81, 0xf8c75, 0xf8c79,
+    0xf8c8d, 0xf8cd9, 0xf8d68, 0xf8d73, 0xf8f30, 0xf9005, 0xf901b, 0xf9085, 0xf91e3, 0xf9205,
+    0xf924b, 0xf9285, 0xf9293, 0xf9305, 0xf9333, 0xf9805, 0xf9908, 0xf996d, 0xf99b0, 0xf99bd,
+    0xf9be8, 0xf9bf5, 0xf9ca0, 0xf9d05, 0xf9e58, 0xf9e7d, 0xf9ea0, 0xf9f05, 0xf9f88, 0xf9fa5,
+    0xf9fa8, 0xf9fc5, 0xfa1f8, 0xfa205, 0xfa208, 0xfa215, 0xfa7e8, 0xfa7fd, 0xfa9f0, 0xfaa5d,
+    0xfaa78, 0xfaa85, 0xfab40, 0xfabd5, 0xfabd8, 0xfacad, 0xfacb8, 0xfad25, 0xfad28, 0xfafdd,
+    0xfb280, 0xfb405, 0xfb630, 0xfb665, 0xfb668, 0xfb685, 0xfb698, 0xfb6ad, 0xfb6c3, 0xfb6ed,
+    0xfb700, 0xfb75d, 0xfb76b, 0xfb780, 0xfb7a5, 0xfb7eb, 0xfb800, 0xfbba3, 0xfbc00, 0xfbecb,
+    0xfbf05, 0xfbf63, 0xfbf85, 0xfbf8b, 0xfc000, 0xfc063, 0xfc080, 0xfc243, 0xfc280, 0xfc2d3,
+    0xfc300, 0xfc443, 0xfc480, 0xfc573, 0xfc580, 0xfc593, 0xfc800, 0xfc865, 0xfc9d8, 0xfc9e5,
+    0xfca30, 0xfca3d, 0xfd000, 0xfd2a3, 0xfd300, 0xfd373, 0xfd385, 0xfd3ab, 0xfd3c5, 0xfd3eb,
+    0xfd405, 0xfd43b, 0xfd485, 0xfd56b, 0xfd585, 0xfd5db, 0xfd605, 0xfd633, 0xfd685, 0xfd6d3,
+    0xfd705, 0xfd743, 0xfd785, 0xfd7bb, 0xfd800, 0xfdc9b, 0xfdca0, 0xfde5b, 0xfdf80, 0xfdfd3,
+    0x100005, 0x153703, 0x153805, 0x15b9cb, 0x15ba05, 0x15c0f3, 0x15c105, 0x167513, 0x167585,
+    0x175f0b, 0x17c005, 0x17d0f3, 0x180005, 0x189a5b, 0x700008, 0x700013, 0x700100, 0x700403,
+    0x700801, 0x700f83, 0x780001, 0x7ffff3, 0x800001, 0x87fff3,
+];
+
+/// Start of each range (shifted by 8) and its index into `SCRIPT_CODES`
+pub(super) static SCRIPT: [u32; 1638] = [
+    0xa0, 0x4144, 0x5ba0, 0x6144, 0x7ba0, 0xaa44, 0xaba0, 0xba44, 0xbba0, 0xc044, 0xd7a0, 0xd844,
+    0xf7a0, 0xf844, 0x2b9a0, 0x2e044, 0x2e5a0, 0x2ea0d, 0x2eca0, 0x3009f, 0x3702b, 0x374a0, 0x3752b,
+    0x378a1, 0x37a2b, 0x37ea0, 0x37f2b, 0x380a1, 0x3842b, 0x385a0, 0x3862b, 0x387a0, 0x3882b,
+    0x38ba1, 0x38c2b, 0x38da1, 0x38e2b, 0x3a2a1, 0x3a32b, 0x3e218, 0x3f02b, 0x4001b, 0x4859f,
+    0x4871b, 0x530a1, 0x53105, 0x557a1, 0x55905, 0x58ba1, 0x58d05, 0x590a1, 0x59132, 0x5c8a1,
+    0x5d032, 0x5eba1, 0x5ef32, 0x5f5a1, 0x60003, 0x605a0, 0x60603, 0x60ca0, 0x60d03, 0x61ba0,
+    0x61c03, 0x61fa0, 0x62003, 0x640a0, 0x64103, 0x64b9f, 0x65603, 0x6709f, 0x67103, 0x6dda0,
+    0x6de03, 0x70084, 0x70ea1, 0x70f84, 0x74ba1, 0x74d84, 0x75003, 0x7808f, 0x7b2a1, 0x7c060,
+    0x7fba1, 0x7fd60, 0x80075, 0x82ea1, 0x83075, 0x83fa1, 0x8404e, 0x85ca1, 0x85e4e, 0x85fa1,
+    0x86084, 0x86ba1, 0x87003, 0x88fa1, 0x89003, 0x892a1, 0x89803, 0x8e2a0, 0x8e303, 0x9001c,
+    0x9519f, 0x9551c, 0x964a0, 0x9661c, 0x9800b, 0x984a1, 0x9850b, 0x98da1, 0x98f0b, 0x991a1,
+    0x9930b, 0x9a9a1, 0x9aa0b, 0x9b1a1, 0x9b20b, 0x9b3a1, 0x9b60b, 0x9baa1, 0x9bc0b, 0x9c5a1,
+    0x9c70b, 0x9c9a1, 0x9cb0b, 0x9cfa1, 0x9d70b, 0x9d8a1, 0x9dc0b, 0x9dea1, 0x9df0b, 0x9e4a1,
+    0x9e60b, 0x9ffa1, 0xa012d, 0xa04a1, 0xa052d, 0xa0ba1, 0xa0f2d, 0xa11a1, 0xa132d, 0xa29a1,
+    0xa2a2d, 0xa31a1, 0xa322d, 0xa34a1, 0xa352d, 0xa37a1, 0xa382d, 0xa3aa1, 0xa3c2d, 0xa3da1,
+    0xa3e2d, 0xa43a1, 0xa472d, 0xa49a1, 0xa4b2d, 0xa4ea1, 0xa512d, 0xa52a1, 0xa592d, 0xa5da1,
+    0xa5e2d, 0xa5fa1, 0xa662d, 0xa77a1, 0xa812c, 0xa84a1, 0xa852c, 0xa8ea1, 0xa8f2c, 0xa92a1,
+    0xa932c, 0xaa9a1, 0xaaa2c, 0xab1a1, 0xab22c, 0xab4a1, 0xab52c, 0xabaa1, 0xabc2c, 0xac6a1,
+    0xac72c, 0xacaa1, 0xacb2c, 0xacea1, 0xad02c, 0xad1a1, 0xae02c, 0xae4a1, 0xae62c, 0xaf2a1,
+    0xaf92c, 0xb00a1, 0xb0165, 0xb04a1, 0xb0565, 0xb0da1, 0xb0f65, 0xb11a1, 0xb1365, 0xb29a1,
+    0xb2a65, 0xb31a1, 0xb3265, 0xb34a1, 0xb3565, 0xb3aa1, 0xb3c65, 0xb45a1, 0xb4765, 0xb49a1,
+    0xb4b65, 0xb4ea1, 0xb5565, 0xb58a1, 0xb5c65, 0xb5ea1, 0xb5f65, 0xb64a1, 0xb6665, 0xb78a1,
+    0xb8289, 0xb84a1, 0xb8589, 0xb8ba1, 0xb8e89, 0xb91a1, 0xb9289, 0xb96a1, 0xb9989, 0xb9ba1,
+    0xb9c89, 0xb9da1, 0xb9e89, 0xba0a1, 0xba389, 0xba5a1, 0xba889, 0xbaba1, 0xbae89, 0xbbaa1,
+    0xbbe89, 0xbc3a1, 0xbc689, 0xbc9a1, 0xbca89, 0xbcea1, 0xbd089, 0xbd1a1, 0xbd789, 0xbd8a1,
+    0xbe689, 0xbfba1, 0xc008c, 0xc0da1, 0xc0e8c, 0xc11a1, 0xc128c, 0xc29a1, 0xc2a8c, 0xc3aa1,
+    0xc3c8c, 0xc45a1, 0xc468c, 0xc49a1, 0xc4a8c, 0xc4ea1, 0xc558c, 0xc57a1, 0xc588c, 0xc5ba1,
+    0xc5d8c, 0xc5ea1, 0xc608c, 0xc64a1, 0xc668c, 0xc70a1, 0xc778c, 0xc8040, 0xc8da1, 0xc8e40,
+    0xc91a1, 0xc9240, 0xca9a1, 0xcaa40, 0xcb4a1, 0xcb540, 0xcbaa1, 0xcbc40, 0xcc5a1, 0xcc640,
+    0xcc9a1, 0xcca40, 0xccea1, 0xcd540, 0xcd7a1, 0xcdd40, 0xcdfa1, 0xce040, 0xce4a1, 0xce640,
+    0xcf0a1, 0xcf140, 0xcf3a1, 0xd0055, 0xd0da1, 0xd0e55, 0xd11a1, 0xd1255, 0xd45a1, 0xd4655,
+    0xd49a1, 0xd4a55, 0xd50a1, 0xd5455, 0xd64a1, 0xd6655, 0xd80a1, 0xd817d, 0xd84a1, 0xd857d,
+    0xd97a1, 0xd9a7d, 0xdb2a1, 0xdb37d, 0xdbca1, 0xdbd7d, 0xdbea1, 0xdc07d, 0xdc7a1, 0xdca7d,
+    0xdcba1, 0xdcf7d, 0xdd5a1, 0xdd67d, 0xdd7a1, 0xdd87d, 0xde0a1, 0xde67d, 0xdf0a1, 0xdf27d,
+    0xdf5a1, 0xe0190, 0xe3ba1, 0xe3fa0, 0xe4090, 0xe5ca1, 0xe8143, 0xe83a1, 0xe8443, 0xe85a1,
+    0xe8643, 0xe8ba1, 0xe8c43, 0xea4a1, 0xea543, 0xea6a1, 0xea743, 0xebea1, 0xec043, 0xec5a1,
+    0xec643, 0xec7a1, 0xec843, 0xecea1, 0xed043, 0xedaa1, 0xedc43, 0xee0a1, 0xf0091, 0xf48a1,
+    0xf4991, 0xf6da1, 0xf7191, 0xf98a1, 0xf9991, 0xfbda1, 0xfbe91, 0xfcda1, 0xfce91, 0xfd5a0,
+    0xfd991, 0xfdba1, 0x10005b, 0x10a025, 0x10c6a1, 0x10c725, 0x10c8a1, 0x10cd25, 0x10cea1,
+    0x10d025, 0x10fba0, 0x10fc25, 0x11002e, 0x120024, 0x1249a1, 0x124a24, 0x124ea1, 0x125024,
+    0x1257a1, 0x125824, 0x1259a1, 0x125a24, 0x125ea1, 0x126024, 0x1289a1, 0x128a24, 0x128ea1,
+    0x129024, 0x12b1a1, 0x12b224, 0x12b6a1, 0x12b824, 0x12bfa1, 0x12c024, 0x12c1a1, 0x12c224,
+    0x12c6a1, 0x12c824, 0x12d7a1, 0x12d824, 0x1311a1, 0x131224, 0x1316a1, 0x131824, 0x135ba1,
+    0x135d24, 0x137da1, 0x138024, 0x139aa1, 0x13a016, 0x13f6a1, 0x13f816, 0x13fea1, 0x140013,
+    0x168062, 0x169da1, 0x16a074, 0x16eba0, 0x16ee74, 0x16f9a1, 0x17008e, 0x1716a1, 0x171f8e,
+    0x172030, 0x1735a0, 0x1737a1, 0x174011, 0x1754a1, 0x176085, 0x176da1, 0x176e85, 0x1771a1,
+    0x177285, 0x1774a1, 0x17803d, 0x17dea1, 0x17e03d, 0x17eaa1, 0x17f03d, 0x17faa1, 0x180057,
+    0x1802a0, 0x180457, 0x1805a0, 0x180657, 0x181aa1, 0x182057, 0x1879a1, 0x188057, 0x18aba1,
+    0x18b013, 0x18f6a1, 0x190046, 0x191fa1, 0x192046, 0x192ca1, 0x193046, 0x193ca1, 0x194046,
+    0x1941a1, 0x194446, 0x195087, 0x196ea1, 0x197087, 0x1975a1, 0x198088, 0x19aca1, 0x19b088,
+    0x19caa1, 0x19d088, 0x19dba1, 0x19de88, 0x19e03d, 0x1a0010, 0x1a1ca1, 0x1a1e10, 0x1a2042,
+    0x1a5fa1, 0x1a6042, 0x1a7da1, 0x1a7f42, 0x1a8aa1, 0x1a9042, 0x1a9aa1, 0x1aa042, 0x1aaea1,
+    0x1ab09f, 0x1acfa1, 0x1b0007, 0x1b4da1, 0x1b5007, 0x1b7fa1, 0x1b8082, 0x1bc00a, 0x1bf4a1,
+    0x1bfc0a, 0x1c0045, 0x1c38a1, 0x1c3b45, 0x1c4aa1, 0x1c4d45, 0x1c5063, 0x1c801b, 0x1c89a1,
+    0x1c9025, 0x1cbba1, 0x1cbd25, 0x1cc082, 0x1cc8a1, 0x1cd09f, 0x1cd3a0, 0x1cd49f, 0x1ce1a0,
+    0x1ce29f, 0x1ce9a0, 0x1ced9f, 0x1ceea0, 0x1cf49f, 0x1cf5a0, 0x1cf89f, 0x1cfaa0, 0x1cfba1,
+    0x1d0044, 0x1d262b, 0x1d2b1b, 0x1d2c44, 0x1d5d2b, 0x1d6244, 0x1d662b, 0x1d6b44, 0x1d781b,
+    0x1d7944, 0x1dbf2b, 0x1dc09f, 0x1e0044, 0x1f002b, 0x1f16a1, 0x1f182b, 0x1f1ea1, 0x1f202b,
+    0x1f46a1, 0x1f482b, 0x1f4ea1, 0x1f502b, 0x1f58a1, 0x1f592b, 0x1f5aa1, 0x1f5b2b, 0x1f5ca1,
+    0x1f5d2b, 0x1f5ea1, 0x1f5f2b, 0x1f7ea1, 0x1f802b, 0x1fb5a1, 0x1fb62b, 0x1fc5a1, 0x1fc62b,
+    0x1fd4a1, 0x1fd62b, 0x1fdca1, 0x1fdd2b, 0x1ff0a1, 0x1ff22b, 0x1ff5a1, 0x1ff62b, 0x1fffa1,
+    0x2000a0, 0x200c9f, 0x200ea0, 0x2065a1, 0x2066a0, 0x207144, 0x2072a1, 0x2074a0, 0x207f44,
+    0x2080a0, 0x208fa1, 0x209044, 0x209da1, 0x20a0a0, 0x20c1a1, 0x20d09f, 0x20f1a1, 0x2100a0,
+    0x21262b, 0x2127a0, 0x212a44, 0x212ca0, 0x213244, 0x2133a0, 0x214e44, 0x214fa0, 0x216044,
+    0x2189a0, 0x218ca1, 0x2190a0, 0x2427a1, 0x2440a0, 0x244ba1, 0x2460a0, 0x28000f, 0x2900a0,
+    0x2b74a1, 0x2b76a0, 0x2b96a1, 0x2b97a0, 0x2c0026, 0x2c6044, 0x2c8018, 0x2cf4a1, 0x2cf918,
+    0x2d0025, 0x2d26a1, 0x2d2725, 0x2d28a1, 0x2d2d25, 0x2d2ea1, 0x2d308d, 0x2d68a1, 0x2d6f8d,
+    0x2d71a1, 0x2d7f8d, 0x2d8024, 0x2d97a1, 0x2da024, 0x2da7a1, 0x2da824, 0x2dafa1, 0x2db024,
+    0x2db7a1, 0x2db824, 0x2dbfa1, 0x2dc024, 0x2dc7a1, 0x2dc824, 0x2dcfa1, 0x2dd024, 0x2dd7a1,
+    0x2dd824, 0x2ddfa1, 0x2de01b, 0x2e00a0, 0x2e5ea1, 0x2e802f, 0x2e9aa1, 0x2e9b2f, 0x2ef4a1,
+    0x2f002f, 0x2fd6a1, 0x2ff0a0, 0x2ffca1, 0x3000a0, 0x30052f, 0x3006a0, 0x30072f, 0x3008a0,
+    0x30212f, 0x302a9f, 0x302e2e, 0x3030a0, 0x30382f, 0x303ca0, 0x3040a1, 0x304133, 0x3097a1,
+    0x30999f, 0x309ba0, 0x309d33, 0x30a0a0, 0x30a13b, 0x30fba0, 0x30fd3b, 0x3100a1, 0x31050d,
+    0x3130a1, 0x31312e, 0x318fa1, 0x3190a0, 0x31a00d, 0x31c0a0, 0x31e4a1, 0x31f03b, 0x32002e,
+    0x321fa1, 0x3220a0, 0x32602e, 0x327fa0, 0x32d03b, 0x32ffa0, 0x33003b, 0x3358a0, 0x34002f,
+    0x4dc0a0, 0x4e002f, 0xa0009d, 0xa48da1, 0xa4909d, 0xa4c7a1, 0xa4d049, 0xa50096, 0xa62ca1,
+    0xa6401b, 0xa6a008, 0xa6f8a1, 0xa700a0, 0xa72244, 0xa788a0, 0xa78b44, 0xa7cba1, 0xa7d044,
+    0xa7d2a1, 0xa7d344, 0xa7d4a1, 0xa7d544, 0xa7daa1, 0xa7f244, 0xa80083, 0xa82da1, 0xa830a0,
+    0xa83aa1, 0xa8406c, 0xa878a1, 0xa88077, 0xa8c6a1, 0xa8ce77, 0xa8daa1, 0xa8e01c, 0xa9003a,
+    0xa92ea0, 0xa92f3a, 0xa93072, 0xa954a1, 0xa95f72, 0xa9602e, 0xa97da1, 0xa98039, 0xa9cea1,
+    0xa9cfa0, 0xa9d039, 0xa9daa1, 0xa9de39, 0xa9e05b, 0xa9ffa1, 0xaa0015, 0xaa37a1, 0xaa4015,
+    0xaa4ea1, 0xaa5015, 0xaa5aa1, 0xaa5c15, 0xaa605b, 0xaa808b, 0xaac3a1, 0xaadb8b, 0xaae059,
+    0xaaf7a1, 0xab0124, 0xab07a1, 0xab0924, 0xab0fa1, 0xab1124, 0xab17a1, 0xab2024, 0xab27a1,
+    0xab2824, 0xab2fa1, 0xab3044, 0xab5ba0, 0xab5c44, 0xab652b, 0xab6644, 0xab6aa0, 0xab6ca1,
+    0xab7016, 0xabc059, 0xabeea1, 0xabf059, 0xabfaa1, 0xac002e, 0xd7a4a1, 0xd7b02e, 0xd7c7a1,
+    0xd7cb2e, 0xd7fca1, 0xf9002f, 0xfa6ea1, 0xfa702f, 0xfadaa1, 0xfb0044, 0xfb07a1, 0xfb1305,
+    0xfb18a1, 0xfb1d32, 0xfb37a1, 0xfb3832, 0xfb3da1, 0xfb3e32, 0xfb3fa1, 0xfb4032, 0xfb42a1,
+    0xfb4332, 0xfb45a1, 0xfb4632, 0xfb5003, 0xfbc3a1, 0xfbd303, 0xfd3ea0, 0xfd4003, 0xfd90a1,
+    0xfd9203, 0xfdc8a1, 0xfdcf03, 0xfdd0a1, 0xfdf003, 0xfe009f, 0xfe10a0, 0xfe1aa1, 0xfe209f,
+    0xfe2e1b, 0xfe30a0, 0xfe53a1, 0xfe54a0, 0xfe67a1, 0xfe68a0, 0xfe6ca1, 0xfe7003, 0xfe75a1,
+    0xfe7603, 0xfefda1, 0xfeffa0, 0xff00a1, 0xff01a0, 0xff2144, 0xff3ba0, 0xff4144, 0xff5ba0,
+    0xff663b, 0xff70a0, 0xff713b, 0xff9ea0, 0xffa02e, 0xffbfa1, 0xffc22e, 0xffc8a1, 0xffca2e,
+    0xffd0a1, 0xffd22e, 0xffd8a1, 0xffda2e, 0xffdda1, 0xffe0a0, 0xffe7a1, 0xffe8a0, 0xffefa1,
+    0xfff9a0, 0xfffea1, 0x1000048, 0x1000ca1, 0x1000d48, 0x10027a1, 0x1002848, 0x1003ba1, 0x1003c48,
+    0x1003ea1, 0x1003f48, 0x1004ea1, 0x1005048, 0x1005ea1, 0x1008048, 0x100fba1, 0x10100a0,
+    0x10103a1, 0x10107a0, 0x10134a1, 0x10137a0, 0x101402b, 0x1018fa1, 0x10190a0, 0x1019da1,
+    0x101a02b, 0x101a1a1, 0x101d0a0, 0x101fd9f, 0x101fea1, 0x102804a, 0x1029da1, 0x102a014,
+    0x102d1a1, 0x102e09f, 0x102e1a0, 0x102fca1, 0x1030038, 0x10324a1, 0x1032d38, 0x1033029,
+    0x1034ba1, 0x103506b, 0x1037ba1, 0x1038095, 0x1039ea1, 0x1039f95, 0x103a09a, 0x103c4a1,
+    0x103c89a, 0x103d6a1, 0x104001f, 0x1045079, 0x1048067, 0x1049ea1, 0x104a067, 0x104aaa1,
+    0x104b066, 0x104d4a1, 0x104d866, 0x104fca1, 0x1050022, 0x10528a1, 0x1053001, 0x10564a1,
+    0x1056f01, 0x1057097, 0x1057ba1, 0x1057c97, 0x1058ba1, 0x1058c97, 0x10593a1, 0x1059497,
+    0x10596a1, 0x1059797, 0x105a2a1, 0x105a397, 0x105b2a1, 0x105b397, 0x105baa1, 0x105bb97,
+    0x105bda1, 0x1060047, 0x10737a1, 0x1074047, 0x10756a1, 0x1076047, 0x10768a1, 0x1078044,
+    0x10786a1, 0x1078744, 0x107b1a1, 0x107b244, 0x107bba1, 0x108001a, 0x10806a1, 0x108081a,
+    0x10809a1, 0x1080a1a, 0x10836a1, 0x108371a, 0x10839a1, 0x1083c1a, 0x1083da1, 0x1083f1a,
+    0x1084004, 0x10856a1, 0x1085704, 0x1086069, 0x108805e, 0x1089fa1, 0x108a75e, 0x108b0a1,
+    0x108e031, 0x108f3a1, 0x108f431, 0x108f6a1, 0x108fb31, 0x109006f, 0x1091ca1, 0x1091f6f,
+    0x109204b, 0x1093aa1, 0x1093f4b, 0x10940a1, 0x1098054, 0x109a053, 0x109b8a1, 0x109bc53,
+    0x109d0a1, 0x109d253, 0x10a003c, 0x10a04a1, 0x10a053c, 0x10a07a1, 0x10a0c3c, 0x10a14a1,
+    0x10a153c, 0x10a18a1, 0x10a193c, 0x10a36a1, 0x10a383c, 0x10a3ba1, 0x10a3f3c, 0x10a49a1,
+    0x10a503c, 0x10a59a1, 0x10a6076, 0x10a805d, 0x10aa0a1, 0x10ac04f, 0x10ae7a1, 0x10aeb4f,
+    0x10af7a1, 0x10b0006, 0x10b36a1, 0x10b3906, 0x10b4071, 0x10b56a1, 0x10b5871, 0x10b606d,
+    0x10b73a1, 0x10b786d, 0x10b806e, 0x10b92a1, 0x10b996e, 0x10b9da1, 0x10ba96e, 0x10bb0a1,
+    0x10c0064, 0x10c49a1, 0x10c8037, 0x10cb3a1, 0x10cc037, 0x10cf3a1, 0x10cfa37, 0x10d0073,
+    0x10d28a1, 0x10d3073, 0x10d3aa1, 0x10e6003, 0x10e7fa1, 0x10e809c, 0x10eaaa1, 0x10eab9c,
+    0x10eaea1, 0x10eb09c, 0x10eb2a1, 0x10f007f, 0x10f28a1, 0x10f307e, 0x10f5aa1, 0x10f7068,
+    0x10f8aa1, 0x10fb017, 0x10fcca1, 0x10fe023, 0x10ff7a1, 0x110000e, 0x1104ea1, 0x110520e,
+    0x11076a1, 0x1107f0e, 0x1108041, 0x110c3a1, 0x110cd41, 0x110cea1, 0x110d080, 0x110e9a1,
+    0x110f080, 0x110faa1, 0x1110012, 0x11135a1, 0x1113612, 0x11148a1, 0x111504c, 0x11177a1,
+    0x111807a, 0x111e0a1, 0x111e17d, 0x111f5a1, 0x112003e, 0x11212a1, 0x112133e, 0x1123fa1,
+    0x112805a, 0x11287a1, 0x112885a, 0x11289a1, 0x1128a5a, 0x1128ea1, 0x1128f5a, 0x1129ea1,
+    0x1129f5a, 0x112aaa1, 0x112b07c, 0x112eba1, 0x112f07c, 0x112faa1, 0x113002a, 0x11304a1,
+    0x113052a, 0x1130da1, 0x1130f2a, 0x11311a1, 0x113132a, 0x11329a1, 0x1132a2a, 0x11331a1,
+    0x113322a, 0x11334a1, 0x113352a, 0x1133aa1, 0x1133b9f, 0x1133c2a, 0x11345a1, 0x113472a,
+    0x11349a1, 0x1134b2a, 0x1134ea1, 0x113502a, 0x11351a1, 0x113572a, 0x11358a1, 0x1135d2a,
+    0x11364a1, 0x113662a, 0x1136da1, 0x113702a, 0x11375a1, 0x114005f, 0x1145ca1, 0x1145d5f,
+    0x11462a1, 0x1148092, 0x114c8a1, 0x114d092, 0x114daa1, 0x115807b, 0x115b6a1, 0x115b87b,
+    0x115dea1, 0x1160056, 0x11645a1, 0x1165056, 0x1165aa1, 0x1166057, 0x1166da1, 0x1168086,
+    0x116baa1, 0x116c086, 0x116caa1, 0x1170002, 0x1171ba1, 0x1171d02, 0x1172ca1, 0x1173002,
+    0x11747a1, 0x118001e, 0x1183ca1, 0x118a098, 0x118f3a1, 0x118ff98, 0x119001d, 0x11907a1,
+    0x119091d, 0x1190aa1, 0x1190c1d, 0x11914a1, 0x119151d, 0x11917a1, 0x119181d, 0x11936a1,
+    0x119371d, 0x11939a1, 0x1193b1d, 0x11947a1, 0x119501d, 0x1195aa1, 0x119a05c, 0x119a8a1,
+    0x119aa5c, 0x119d8a1, 0x119da5c, 0x119e5a1, 0x11a009e, 0x11a48a1, 0x11a5081, 0x11aa3a1,
+    0x11ab013, 0x11ac06a, 0x11af9a1, 0x11c000c, 0x11c09a1, 0x11c0a0c, 0x11c37a1, 0x11c380c,
+    0x11c46a1, 0x11c500c, 0x11c6da1, 0x11c7050, 0x11c90a1, 0x11c9250, 0x11ca8a1, 0x11ca950,
+    0x11cb7a1, 0x11d0028, 0x11d07a1, 0x11d0828, 0x11d0aa1, 0x11d0b28, 0x11d37a1, 0x11d3a28,
+    0x11d3ba1, 0x11d3c28, 0x11d3ea1, 0x11d3f28, 0x11d48a1, 0x11d5028, 0x11d5aa1, 0x11d6027,
+    0x11d66a1, 0x11d6727, 0x11d69a1, 0x11d6a27, 0x11d8fa1, 0x11d9027, 0x11d92a1, 0x11d9327,
+    0x11d99a1, 0x11da027, 0x11daaa1, 0x11ee04d, 0x11ef9a1, 0x11fb049, 0x11fb1a1, 0x11fc089,
+    0x11ff2a1, 0x11fff89, 0x120009b, 0x1239aa1, 0x124009b, 0x1246fa1, 0x124709b, 0x12475a1,
+    0x124809b, 0x12544a1, 0x12f9019, 0x12ff3a1, 0x1300021, 0x1342fa1, 0x1343021, 0x13439a1,
+    0x1440034, 0x14647a1, 0x1680008, 0x16a39a1, 0x16a4058, 0x16a5fa1, 0x16a6058, 0x16a6aa1,
+    0x16a6e58, 0x16a7093, 0x16abfa1, 0x16ac093, 0x16acaa1, 0x16ad009, 0x16aeea1, 0x16af009,
+    0x16af6a1, 0x16b0035, 0x16b46a1, 0x16b5035, 0x16b5aa1, 0x16b5b35, 0x16b62a1, 0x16b6335,
+    0x16b78a1, 0x16b7d35, 0x16b90a1, 0x16e4051, 0x16e9ba1, 0x16f0070, 0x16f4ba1, 0x16f4f70,
+    0x16f88a1, 0x16f8f70, 0x16fa0a1, 0x16fe08a, 0x16fe161, 0x16fe22f, 0x16fe43f, 0x16fe5a1,
+    0x16ff02f, 0x16ff2a1, 0x170008a, 0x187f8a1, 0x188008a, 0x18b003f, 0x18cd6a1, 0x18d008a,
+    0x18d09a1, 0x1aff03b, 0x1aff4a1, 0x1aff53b, 0x1affca1, 0x1affd3b, 0x1afffa1, 0x1b0003b,
+    0x1b00133, 0x1b1203b, 0x1b123a1, 0x1b15033, 0x1b153a1, 0x1b1643b, 0x1b168a1, 0x1b17061,
+    0x1b2fca1, 0x1bc0020, 0x1bc6ba1, 0x1bc7020, 0x1bc7da1, 0x1bc8020, 0x1bc89a1, 0x1bc9020,
+    0x1bc9aa1, 0x1bc9c20, 0x1bca0a0, 0x1bca4a1, 0x1cf009f, 0x1cf2ea1, 0x1cf309f, 0x1cf47a1,
+    0x1cf50a0, 0x1cfc4a1, 0x1d000a0, 0x1d0f6a1, 0x1d100a0, 0x1d127a1, 0x1d129a0, 0x1d1679f,
+    0x1d16aa0, 0x1d17b9f, 0x1d183a0, 0x1d1859f, 0x1d18ca0, 0x1d1aa9f, 0x1d1aea0, 0x1d1eba1,
+    0x1d2002b, 0x1d246a1, 0x1d2e0a0, 0x1d2f4a1, 0x1d300a0, 0x1d357a1, 0x1d360a0, 0x1d379a1,
+    0x1d400a0, 0x1d455a1, 0x1d456a0, 0x1d49da1, 0x1d49ea0, 0x1d4a0a1, 0x1d4a2a0, 0x1d4a3a1,
+    0x1d4a5a0, 0x1d4a7a1, 0x1d4a9a0, 0x1d4ada1, 0x1d4aea0, 0x1d4baa1, 0x1d4bba0, 0x1d4bca1,
+    0x1d4bda0, 0x1d4c4a1, 0x1d4c5a0, 0x1d506a1, 0x1d507a0, 0x1d50ba1, 0x1d50da0, 0x1d515a1,
+    0x1d516a0, 0x1d51da1, 0x1d51ea0, 0x1d53aa1, 0x1d53ba0, 0x1d53fa1, 0x1d540a0, 0x1d545a1,
+    0x1d546a0, 0x1d547a1, 0x1d54aa0, 0x1d551a1, 0x1d552a0, 0x1d6a6a1, 0x1d6a8a0, 0x1d7cca1,
+    0x1d7cea0, 0x1d80078, 0x1da8ca1, 0x1da9b78, 0x1daa0a1, 0x1daa178, 0x1dab0a1, 0x1df0044,
+    0x1df1fa1, 0x1e00026, 0x1e007a1, 0x1e00826, 0x1e019a1, 0x1e01b26, 0x1e022a1, 0x1e02326,
+    0x1e025a1, 0x1e02626, 0x1e02ba1, 0x1e10036, 0x1e12da1, 0x1e13036, 0x1e13ea1, 0x1e14036,
+    0x1e14aa1, 0x1e14e36, 0x1e150a1, 0x1e29094, 0x1e2afa1, 0x1e2c099, 0x1e2faa1, 0x1e2ff99,
+    0x1e300a1, 0x1e7e024, 0x1e7e7a1, 0x1e7e824, 0x1e7eca1, 0x1e7ed24, 0x1e7efa1, 0x1e7f024,
+    0x1e7ffa1, 0x1e80052, 0x1e8c5a1, 0x1e8c752, 0x1e8d7a1, 0x1e90000, 0x1e94ca1, 0x1e95000,
+    0x1e95aa1, 0x1e95e00, 0x1e960a1, 0x1ec71a0, 0x1ecb5a1, 0x1ed01a0, 0x1ed3ea1, 0x1ee0003,
+    0x1ee04a1, 0x1ee0503, 0x1ee20a1, 0x1ee2103, 0x1ee23a1, 0x1ee2403, 0x1ee25a1, 0x1ee2703,
+    0x1ee28a1, 0x1ee2903, 0x1ee33a1, 0x1ee3403, 0x1ee38a1, 0x1ee3903, 0x1ee3aa1, 0x1ee3b03,
+    0x1ee3ca1, 0x1ee4203, 0x1ee43a1, 0x1ee4703, 0x1ee48a1, 0x1ee4903, 0x1ee4aa1, 0x1ee4b03,
+    0x1ee4ca1, 0x1ee4d03, 0x1ee50a1, 0x1ee5103, 0x1ee53a1, 0x1ee5403, 0x1ee55a1, 0x1ee5703,
+    0x1ee58a1, 0x1ee5903, 0x1ee5aa1, 0x1ee5b03, 0x1ee5ca1, 0x1ee5d03, 0x1ee5ea1, 0x1ee5f03,
+    0x1ee60a1, 0x1ee6103, 0x1ee63a1, 0x1ee6403, 0x1ee65a1, 0x1ee6703, 0x1ee6ba1, 0x1ee6c03,
+    0x1ee73a1, 0x1ee7403, 0x1ee78a1, 0x1ee7903, 0x1ee7da1, 0x1ee7e03, 0x1ee7fa1, 0x1ee8003,
+    0x1ee8aa1, 0x1ee8b03, 0x1ee9ca1, 0x1eea103, 0x1eea4a1, 0x1eea503, 0x1eeaaa1, 0x1eeab03,
+    0x1eebca1, 0x1eef003, 0x1eef2a1, 0x1f000a0, 0x1f02ca1, 0x1f030a0, 0x1f094a1, 0x1f0a0a0,
+    0x1f0afa1, 0x1f0b1a0, 0x1f0c0a1, 0x1f0c1a0, 0x1f0d0a1, 0x1f0d1a0, 0x1f0f6a1, 0x1f100a0,
+    0x1f1aea1, 0x1f1e6a0, 0x1f20033, 0x1f201a0, 0x1f203a1, 0x1f210a0, 0x1f23ca1, 0x1f240a0,
+    0x1f249a1, 0x1f250a0, 0x1f252a1, 0x1f260a0, 0x1f266a1, 0x1f300a0, 0x1f6d8a1, 0x1f6dda0,
+    0x1f6eda1, 0x1f6f0a0, 0x1f6fda1, 0x1f700a0, 0x1f774a1, 0x1f780a0, 0x1f7d9a1, 0x1f7e0a0,
+    0x1f7eca1, 0x1f7f0a0, 0x1f7f1a1, 0x1f800a0, 0x1f80ca1, 0x1f810a0, 0x1f848a1, 0x1f850a0,
+    0x1f85aa1, 0x1f860a0, 0x1f888a1, 0x1f890a0, 0x1f8aea1, 0x1f8b0a0, 0x1f8b2a1, 0x1f900a0,
+    0x1fa54a1, 0x1fa60a0, 0x1fa6ea1, 0x1fa70a0, 0x1fa75a1, 0x1fa78a0, 0x1fa7da1, 0x1fa80a0,
+    0x1fa87a1, 0x1fa90a0, 0x1faada1, 0x1fab0a0, 0x1fabba1, 0x1fac0a0, 0x1fac6a1, 0x1fad0a0,
+    0x1fadaa1, 0x1fae0a0, 0x1fae8a1, 0x1faf0a0, 0x1faf7a1, 0x1fb00a0, 0x1fb93a1, 0x1fb94a0,
+    0x1fbcba1, 0x1fbf0a0, 0x1fbfaa1, 0x200002f, 0x2a6e0a1, 0x2a7002f, 0x2b739a1, 0x2b7402f,
+    0x2b81ea1, 0x2b8202f, 0x2cea2a1, 0x2ceb02f, 0x2ebe1a1, 0x2f8002f, 0x2fa1ea1, 0x300002f,
+    0x3134ba1, 0xe0001a0, 0xe0002a1, 0xe0020a0, 0xe0080a1, 0xe01009f, 0xe01f0a1,
+];
+
+/// ISO 15924 codes of scripts, as big-endian packed ASCII
+pub(super) static SCRIPT_CODES: [u32; 162] = [
+    0x41646c6d, 0x41676862, 0x41686f6d, 0x41726162, 0x41726d69, 0x41726d6e, 0x41767374, 0x42616c69,
+    0x42616d75, 0x42617373, 0x4261746b, 0x42656e67, 0x42686b73, 0x426f706f, 0x42726168, 0x42726169,
+    0x42756769, 0x42756864, 0x43616b6d, 0x43616e73, 0x43617269, 0x4368616d, 0x43686572, 0x43687273,
+    0x436f7074, 0x43706d6e, 0x43707274, 0x4379726c, 0x44657661, 0x4469616b, 0x446f6772, 0x44737274,
+    0x4475706c, 0x45677970, 0x456c6261, 0x456c796d, 0x45746869, 0x47656f72, 0x476c6167, 0x476f6e67,
+    0x476f6e6d, 0x476f7468, 0x4772616e, 0x4772656b, 0x47756a72, 0x47757275, 0x48616e67, 0x48616e69,
+    0x48616e6f, 0x48617472, 0x48656272, 0x48697261, 0x486c7577, 0x486d6e67, 0x486d6e70, 0x48756e67,
+    0x4974616c, 0x4a617661, 0x4b616c69, 0x4b616e61, 0x4b686172, 0x4b686d72, 0x4b686f6a, 0x4b697473,
+    0x4b6e6461, 0x4b746869, 0x4c616e61, 0x4c616f6f, 0x4c61746e, 0x4c657063, 0x4c696d62, 0x4c696e61,
+    0x4c696e62, 0x4c697375, 0x4c796369, 0x4c796469, 0x4d61686a, 0x4d616b61, 0x4d616e64, 0x4d616e69,
+    0x4d617263, 0x4d656466, 0x4d656e64, 0x4d657263, 0x4d65726f, 0x4d6c796d, 0x4d6f6469, 0x4d6f6e67,
+    0x4d726f6f, 0x4d746569, 0x4d756c74, 0x4d796d72, 0x4e616e64, 0x4e617262, 0x4e626174, 0x4e657761,
+    0x4e6b6f6f, 0x4e736875, 0x4f67616d, 0x4f6c636b, 0x4f726b68, 0x4f727961, 0x4f736765, 0x4f736d61,
+    0x4f756772, 0x50616c6d, 0x50617563, 0x5065726d, 0x50686167, 0x50686c69, 0x50686c70, 0x50686e78,
+    0x506c7264, 0x50727469, 0x526a6e67, 0x526f6867, 0x52756e72, 0x53616d72, 0x53617262, 0x53617572,
+    0x53676e77, 0x53686177, 0x53687264, 0x53696464, 0x53696e64, 0x53696e68, 0x536f6764, 0x536f676f,
+    0x536f7261, 0x536f796f, 0x53756e64, 0x53796c6f, 0x53797263, 0x54616762, 0x54616b72, 0x54616c65,
+    0x54616c75, 0x54616d6c, 0x54616e67, 0x54617674, 0x54656c75, 0x54666e67, 0x54676c67, 0x54686161,
+    0x54686169, 0x54696274, 0x54697268, 0x546e7361, 0x546f746f, 0x55676172, 0x56616969, 0x56697468,
+    0x57617261, 0x5763686f, 0x5870656f, 0x58737578, 0x59657a69, 0x59696969, 0x5a616e62, 0x5a696e68,
+    0x5a797979, 0x5a7a7a7a,
+];
+
+/// Numeric values, indexed by `NUMERIC_RANGES`
+pub(super) static NUMERIC_VALUES: [f64; 142] = [
+    -0.5, 0.0, 0.003125, 0.00625, 0.0125, 0.015625,
+    0.025, 0.03125, 0.0375, 0.046875, 0.05, 0.0625,
+    0.08333333333333333, 0.1, 0.1111111111111111, 0.125, 0.14285714285714285, 0.15,
+    0.16666666666666666, 0.1875, 0.2, 0.25, 0.3333333333333333, 0.375,
+    0.4, 0.4166666666666667, 0.5, 0.5833333333333334, 0.6, 0.625,
+    0.6666666666666666, 0.75, 0.8, 0.8333333333333334, 0.875, 0.9166666666666666,
+    1.0, 1.5, 2.0, 2.5, 3.0, 3.5,
+    4.0, 4.5, 5.0, 5.5, 6.0, 6.5,
+    7.0, 7.5, 8.0, 8.5, 9.0, 10.0,
+    11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
+    23.0, 24.0, 25.0, 26.0, 27.0, 28.0,
+    29.0, 30.0, 31.0, 32.0, 33.0, 34.0,
+    35.0, 36.0, 37.0, 38.0, 39.0, 40.0,
+    41.0, 42.0, 43.0, 44.0, 45.0, 46.0,
+    47.0, 48.0, 49.0, 50.0, 60.0, 70.0,
+    80.0, 90.0, 100.0, 200.0, 300.0, 400.0,
+    500.0, 600.0, 700.0, 800.0, 900.0, 1000.0,
+    2000.0, 3000.0, 4000.0, 5000.0, 6000.0, 7000.0,
+    8000.0, 9000.0, 10000.0, 20000.0, 30000.0, 40000.0,
+    50000.0, 60000.0, 70000.0, 80000.0, 90000.0, 100000.0,
+    200000.0, 216000.0, 300000.0, 400000.0, 432000.0, 500000.0,
+    600000.0, 700000.0, 800000.0, 900000.0, 1000000.0, 10000000.0,
+    20000000.0, 100000000.0, 10000000000.0, 1000000000000.0,
+];
+
+/// Start of each range, its length (bit 7 set if the values increase by one) and the
+/// index of the numeric value of its first code point
+pub(super) static NUMERIC_RANGES: [(u32, u8, u8); 741] = [
+    (0x30, 0x8a, 1),
+    (0xb2, 0x82, 38),
+    (0xb9, 0x1, 36),
+    (0xbc, 0x1, 21),
+    (0xbd, 0x1, 26),
+    (0xbe, 0x1, 31),
+    (0x660, 0x8a, 1),
+    (0x6f0, 0x8a, 1),
+    (0x7c0, 0x8a, 1),
+    (0x966, 0x8a, 1),
+    (0x9e6, 0x8a, 1),
+    (0x9f4, 0x1, 11),
+    (0x9f5, 0x1, 15),
+    (0x9f6, 0x1, 19),
+    (0x9f7, 0x1, 21),
+    (0x9f8, 0x1, 31),
+    (0x9f9, 0x1, 59),
+    (0xa66, 0x8a, 1),
+    (0xae6, 0x8a, 1),
+    (0xb66, 0x8a, 1),
+    (0xb72, 0x1, 21),
+    (0xb73, 0x1, 26),
+    (0xb74, 0x1, 31),
+    (0xb75, 0x1, 11),
+    (0xb76, 0x1, 15),
+    (0xb77, 0x1, 19),
+    (0xbe6, 0x8b, 1),
+    (0xbf1, 0x1, 98),
+    (0xbf2, 0x1, 107),
+    (0xc66, 0x8a, 1),
+    (0xc78, 0x84, 1),
+    (0xc7c, 0x83, 36),
+    (0xce6, 0x8a, 1),
+    (0xd58, 0x1, 3),
+    (0xd59, 0x1, 6),
+    (0xd5a, 0x1, 8),
+    (0xd5b, 0x1, 10),
+    (0xd5c, 0x1, 13),
+    (0xd5d, 0x1, 17),
+    (0xd5e, 0x1, 20),
+    (0xd66, 0x8b, 1),
+    (0xd71, 0x1, 98),
+    (0xd72, 0x1, 107),
+    (0xd73, 0x1, 21),
+    (0xd74, 0x1, 26),
+    (0xd75, 0x1, 31),
+    (0xd76, 0x1, 11),
+    (0xd77, 0x1, 15),
+    (0xd78, 0x1, 19),
+    (0xde6, 0x8a, 1),
+    (0xe50, 0x8a, 1),
+    (0xed0, 0x8a, 1),
+    (0xf20, 0x8a, 1),
+    (0xf2a, 0x89, 26),
+    (0xf33, 0x1, 0),
+    (0x1040, 0x8a, 1),
+    (0x1090, 0x8a, 1),
+    (0x1369, 0x8a, 36),
+    (0x1373, 0x1, 63),
+    (0x1374, 0x1, 73),
+    (0x1375, 0x1, 83),
+    (0x1376, 0x1, 93),
+    (0x1377, 0x1, 94),
+    (0x1378, 0x1, 95),
+    (0x1379, 0x1, 96),
+    (0x137a, 0x1, 97),
+    (0x137b, 0x1, 98),
+    (0x137c, 0x1, 116),
+    (0x16ee, 0x83, 60),
+    (0x17e0, 0x8a, 1),
+    (0x17f0, 0x8a, 1),
+    (0x1810, 0x8a, 1),
+    (0x1946, 0x8a, 1),
+    (0x19d0, 0x8a, 1),
+    (0x19da, 0x1, 36),
+    (0x1a80, 0x8a, 1),
+    (0x1a90, 0x8a, 1),
+    (0x1b50, 0x8a, 1),
+    (0x1bb0, 0x8a, 1),
+    (0x1c40, 0x8a, 1),
+    (0x1c50, 0x8a, 1),
+    (0x2070, 0x1, 1),
+    (0x2074, 0x86, 42),
+    (0x2080, 0x8a, 1),
+    (0x2150, 0x1, 16),
+    (0x2151, 0x1, 14),
+    (0x2152, 0x1, 13),
+    (0x2153, 0x1, 22),
+    (0x2154, 0x1, 30),
+    (0x2155, 0x1, 20),
+    (0x2156, 0x1, 24),
+    (0x2157, 0x1, 28),
+    (0x2158, 0x1, 32),
+    (0x2159, 0x1, 18),
+    (0x215a, 0x1, 33),
+    (0x215b, 0x1, 15),
+    (0x215c, 0x1, 23),
+    (0x215d, 0x1, 29),
+    (0x215e, 0x1, 34),
+    (0x215f, 0x2, 36),
+    (0x2161, 0x8b, 38),
+    (0x216c, 0x1, 93),
+    (0x216d, 0x1, 98),
+    (0x216e, 0x1, 102),
+    (0x216f, 0x1, 107),
+    (0x2170, 0x8c, 36),
+    (0x217c, 0x1, 93),
+    (0x217d, 0x1, 98),
+    (0x217e, 0x1, 102),
+    (0x217f, 0x2, 107),
+    (0x2181, 0x1, 111),
+    (0x2182, 0x1, 116),
+    (0x2185, 0x1, 46),
+    (0x2186, 0x1, 93),
+    (0x2187, 0x1, 120),
+    (0x2188, 0x1, 125),
+    (0x2189, 0x1, 1),
+    (0x2460, 0x94, 36),
+    (0x2474, 0x94, 36),
+    (0x2488, 0x94, 36),
+    (0x24ea, 0x1, 1),
+    (0x24eb, 0x8a, 54),
+    (0x24f5, 0x8a, 36),
+    (0x24ff, 0x1, 1),
+    (0x2776, 0x8a, 36),
+    (0x2780, 0x8a, 36),
+    (0x278a, 0x8a, 36),
+    (0x2cfd, 0x1, 26),
+    (0x3007, 0x1, 1),
+    (0x3021, 0x89, 36),
+    (0x3038, 0x1, 53),
+    (0x3039, 0x1, 63),
+    (0x303a, 0x1, 73),
+    (0x3192, 0x84, 36),
+    (0x3220, 0x8a, 36),
+    (0x3248, 0x1, 53),
+    (0x3249, 0x1, 63),
+    (0x324a, 0x1, 73),
+    (0x324b, 0x1, 83),
+    (0x324c, 0x1, 93),
+    (0x324d, 0x1, 94),
+    (0x324e, 0x1, 95),
+    (0x324f, 0x1, 96),
+    (0x3251, 0x8f, 64),
+    (0x3280, 0x8a, 36),
+    (0x32b1, 0x8f, 79),
+    (0x3405, 0x1, 44),
+    (0x3483, 0x1, 38),
+    (0x382a, 0x1, 44),
+    (0x3b4d, 0x1, 48),
+    (0x4e00, 0x1, 36),
+    (0x4e03, 0x1, 48),
+    (0x4e07, 0x1, 116),
+    (0x4e09, 0x1, 40),
+    (0x4e5d, 0x1, 52),
+    (0x4e8c, 0x1, 38),
+    (0x4e94, 0x1, 44),
+    (0x4e96, 0x1, 42),
+    (0x4ebf, 0x1, 139),
+    (0x4ec0, 0x1, 53),
+    (0x4edf, 0x1, 107),
+    (0x4ee8, 0x1, 40),
+    (0x4f0d, 0x1, 44),
+    (0x4f70, 0x1, 98),
+    (0x5104, 0x1, 139),
+    (0x5146, 0x1, 141),
+    (0x5169, 0x1, 38),
+    (0x516b, 0x1, 50),
+    (0x516d, 0x1, 46),
+    (0x5341, 0x1, 53),
+    (0x5343, 0x1, 107),
+    (0x5344, 0x1, 63),
+    (0x5345, 0x1, 73),
+    (0x534c, 0x1, 83),
+    (0x53c1, 0x4, 40),
+    (0x56db, 0x1, 42),
+    (0x58f1, 0x1, 36),
+    (0x58f9, 0x1, 36),
+    (0x5e7a, 0x1, 36),
+    (0x5efe, 0x1, 52),
+    (0x5eff, 0x1, 63),
+    (0x5f0c, 0x83, 36),
+    (0x5f10, 0x1, 38),
+    (0x62fe, 0x1, 53),
+    (0x634c, 0x1, 50),
+    (0x67d2, 0x1, 48),
+    (0x6f06, 0x1, 48),
+    (0x7396, 0x1, 52),
+    (0x767e, 0x1, 98),
+    (0x8086, 0x1, 42),
+    (0x842c, 0x1, 116),
+    (0x8cae, 0x1, 38),
+    (0x8cb3, 0x1, 38),
+    (0x8d30, 0x1, 38),
+    (0x9621, 0x1, 107),
+    (0x9646, 0x1, 46),
+    (0x964c, 0x1, 98),
+    (0x9678, 0x1, 46),
+    (0x96f6, 0x1, 1),
+    (0xa620, 0x8a, 1),
+    (0xa6e6, 0x89, 36),
+    (0xa6ef, 0x1, 1),
+    (0xa830, 0x1, 21),
+    (0xa831, 0x1, 26),
+    (0xa832, 0x1, 31),
+    (0xa833, 0x1, 11),
+    (0xa834, 0x1, 15),
+    (0xa835, 0x1, 19),
+    (0xa8d0, 0x8a, 1),
+    (0xa900, 0x8a, 1),
+    (0xa9d0, 0x8a, 1),
+    (0xa9f0, 0x8a, 1),
+    (0xaa50, 0x8a, 1),
+    (0xabf0, 0x8a, 1),
+    (0xf96b, 0x1, 40),
+    (0xf973, 0x1, 53),
+    (0xf978, 0x1, 38),
+    (0xf9b2, 0x1, 1),
+    (0xf9d1, 0x1, 46),
+    (0xf9d3, 0x1, 46),
+    (0xf9fd, 0x1, 53),
+    (0xff10, 0x8a, 1),
+    (0x10107, 0x8a, 36),
+    (0x10111, 0x1, 63),
+    (0x10112, 0x1, 73),
+    (0x10113, 0x1, 83),
+    (0x10114, 0x1, 93),
+    (0x10115, 0x1, 94),
+    (0x10116, 0x1, 95),
+    (0x10117, 0x1, 96),
+    (0x10118, 0x1, 97),
+    (0x10119, 0x1, 98),
+    (0x1011a, 0x1, 99),
+    (0x1011b, 0x1, 100),
+    (0x1011c, 0x1, 101),
+    (0x1011d, 0x1, 102),
+    (0x1011e, 0x1, 103),
+    (0x1011f, 0x1, 104),
+    (0x10120, 0x1, 105),
+    (0x10121, 0x1, 106),
+    (0x10122, 0x1, 107),
+    (0x10123, 0x1, 108),
+    (0x10124, 0x1, 109),
+    (0x10125, 0x1, 110),
+    (0x10126, 0x1, 111),
+    (0x10127, 0x1, 112),
+    (0x10128, 0x1, 113),
+    (0x10129, 0x1, 114),
+    (0x1012a, 0x1, 115),
+    (0x1012b, 0x1, 116),
+    (0x1012c, 0x1, 117),
+    (0x1012d, 0x1, 118),
+    (0x1012e, 0x1, 119),
+    (0x1012f, 0x1, 120),
+    (0x10130, 0x1, 121),
+    (0x10131, 0x1, 122),
+    (0x10132, 0x1, 123),
+    (0x10133, 0x1, 124),
+    (0x10140, 0x1, 21),
+    (0x10141, 0x1, 26),
+    (0x10142, 0x1, 36),
+    (0x10143, 0x1, 44),
+    (0x10144, 0x1, 93),
+    (0x10145, 0x1, 102),
+    (0x10146, 0x1, 111),
+    (0x10147, 0x1, 120),
+    (0x10148, 0x1, 44),
+    (0x10149, 0x1, 53),
+    (0x1014a, 0x1, 93),
+    (0x1014b, 0x1, 98),
+    (0x1014c, 0x1, 102),
+    (0x1014d, 0x1, 107),
+    (0x1014e, 0x1, 111),
+    (0x1014f, 0x1, 44),
+    (0x10150, 0x1, 53),
+    (0x10151, 0x1, 93),
+    (0x10152, 0x1, 98),
+    (0x10153, 0x1, 102),
+    (0x10154, 0x1, 107),
+    (0x10155, 0x1, 116),
+    (0x10156, 0x1, 120),
+    (0x10157, 0x1, 53),
+    (0x10158, 0x3, 36),
+    (0x1015b, 0x4, 38),
+    (0x1015f, 0x1, 44),
+    (0x10160, 0x5, 53),
+    (0x10165, 0x1, 73),
+    (0x10166, 0x4, 93),
+    (0x1016a, 0x1, 98),
+    (0x1016b, 0x1, 100),
+    (0x1016c, 0x5, 102),
+    (0x10171, 0x1, 107),
+    (0x10172, 0x1, 111),
+    (0x10173, 0x1, 44),
+    (0x10174, 0x1, 93),
+    (0x10175, 0x2, 26),
+    (0x10177, 0x1, 30),
+    (0x10178, 0x1, 31),
+    (0x1018a, 0x1, 1),
+    (0x1018b, 0x1, 21),
+    (0x102e1, 0x8a, 36),
+    (0x102eb, 0x1, 63),
+    (0x102ec, 0x1, 73),
+    (0x102ed, 0x1, 83),
+    (0x102ee, 0x1, 93),
+    (0x102ef, 0x1, 94),
+    (0x102f0, 0x1, 95),
+    (0x102f1, 0x1, 96),
+    (0x102f2, 0x1, 97),
+    (0x102f3, 0x1, 98),
+    (0x102f4, 0x1, 99),
+    (0x102f5, 0x1, 100),
+    (0x102f6, 0x1, 101),
+    (0x102f7, 0x1, 102),
+    (0x102f8, 0x1, 103),
+    (0x102f9, 0x1, 104),
+    (0x102fa, 0x1, 105),
+    (0x102fb, 0x1, 106),
+    (0x10320, 0x1, 36),
+    (0x10321, 0x1, 44),
+    (0x10322, 0x1, 53),
+    (0x10323, 0x1, 93),
+    (0x10341, 0x1, 97),
+    (0x1034a, 0x1, 106),
+    (0x103d1, 0x82, 36),
+    (0x103d3, 0x1, 53),
+    (0x103d4, 0x1, 63),
+    (0x103d5, 0x1, 98),
+    (0x104a0, 0x8a, 1),
+    (0x10858, 0x83, 36),
+    (0x1085b, 0x1, 53),
+    (0x1085c, 0x1, 63),
+    (0x1085d, 0x1, 98),
+    (0x1085e, 0x1, 107),
+    (0x1085f, 0x1, 116),
+    (0x10879, 0x85, 36),
+    (0x1087e, 0x1, 53),
+    (0x1087f, 0x1, 63),
+    (0x108a7, 0x84, 36),
+    (0x108ab, 0x82, 42),
+    (0x108ad, 0x1, 53),
+    (0x108ae, 0x1, 63),
+    (0x108af, 0x1, 98),
+    (0x108fb, 0x1, 36),
+    (0x108fc, 0x1, 44),
+    (0x108fd, 0x1, 53),
+    (0x108fe, 0x1, 63),
+    (0x108ff, 0x1, 98),
+    (0x10916, 0x1, 36),
+    (0x10917, 0x1, 53),
+    (0x10918, 0x1, 63),
+    (0x10919, 0x1, 98),
+    (0x1091a, 0x82, 38),
+    (0x109bc, 0x1, 35),
+    (0x109bd, 0x1, 26),
+    (0x109c0, 0x8a, 36),
+    (0x109ca, 0x1, 63),
+    (0x109cb, 0x1, 73),
+    (0x109cc, 0x1, 83),
+    (0x109cd, 0x1, 93),
+    (0x109ce, 0x1, 94),
+    (0x109cf, 0x1, 95),
+    (0x109d2, 0x1, 98),
+    (0x109d3, 0x1, 99),
+    (0x109d4, 0x1, 100),
+    (0x109d5, 0x1, 101),
+    (0x109d6, 0x1, 102),
+    (0x109d7, 0x1, 103),
+    (0x109d8, 0x1, 104),
+    (0x109d9, 0x1, 105),
+    (0x109da, 0x1, 106),
+    (0x109db, 0x1, 107),
+    (0x109dc, 0x1, 108),
+    (0x109dd, 0x1, 109),
+    (0x109de, 0x1, 110),
+    (0x109df, 0x1, 111),
+    (0x109e0, 0x1, 112),
+    (0x109e1, 0x1, 113),
+    (0x109e2, 0x1, 114),
+    (0x109e3, 0x1, 115),
+    (0x109e4, 0x1, 116),
+    (0x109e5, 0x1, 117),
+    (0x109e6, 0x1, 118),
+    (0x109e7, 0x1, 119),
+    (0x109e8, 0x1, 120),
+    (0x109e9, 0x1, 121),
+    (0x109ea, 0x1, 122),
+    (0x109eb, 0x1, 123),
+    (0x109ec, 0x1, 124),
+    (0x109ed, 0x1, 125),
+    (0x109ee, 0x1, 126),
+    (0x109ef, 0x1, 128),
+    (0x109f0, 0x1, 129),
+    (0x109f1, 0x1, 131),
+    (0x109f2, 0x1, 132),
+    (0x109f3, 0x1, 133),
+    (0x109f4, 0x1, 134),
+    (0x109f5, 0x1, 135),
+    (0x109f6, 0x1, 12),
+    (0x109f7, 0x1, 18),
+    (0x109f8, 0x1, 21),
+    (0x109f9, 0x1, 22),
+    (0x109fa, 0x1, 25),
+    (0x109fb, 0x1, 26),
+    (0x109fc, 0x1, 27),
+    (0x109fd, 0x1, 30),
+    (0x109fe, 0x1, 31),
+    (0x109ff, 0x1, 33),
+    (0x10a40, 0x84, 36),
+    (0x10a44, 0x1, 53),
+    (0x10a45, 0x1, 63),
+    (0x10a46, 0x1, 98),
+    (0x10a47, 0x1, 107),
+    (0x10a48, 0x1, 26),
+    (0x10a7d, 0x1, 36),
+    (0x10a7e, 0x1, 93),
+    (0x10a9d, 0x1, 36),
+    (0x10a9e, 0x1, 53),
+    (0x10a9f, 0x1, 63),
+    (0x10aeb, 0x1, 36),
+    (0x10aec, 0x1, 44),
+    (0x10aed, 0x1, 53),
+    (0x10aee, 0x1, 63),
+    (0x10aef, 0x1, 98),
+    (0x10b58, 0x84, 36),
+    (0x10b5c, 0x1, 53),
+    (0x10b5d, 0x1, 63),
+    (0x10b5e, 0x1, 98),
+    (0x10b5f, 0x1, 107),
+    (0x10b78, 0x84, 36),
+    (0x10b7c, 0x1, 53),
+    (0x10b7d, 0x1, 63),
+    (0x10b7e, 0x1, 98),
+    (0x10b7f, 0x1, 107),
+    (0x10ba9, 0x84, 36),
+    (0x10bad, 0x1, 53),
+    (0x10bae, 0x1, 63),
+    (0x10baf, 0x1, 98),
+    (0x10cfa, 0x1, 36),
+    (0x10cfb, 0x1, 44),
+    (0x10cfc, 0x1, 53),
+    (0x10cfd, 0x1, 93),
+    (0x10cfe, 0x1, 98),
+    (0x10cff, 0x1, 107),
+    (0x10d30, 0x8a, 1),
+    (0x10e60, 0x8a, 36),
+    (0x10e6a, 0x1, 63),
+    (0x10e6b, 0x1, 73),
+    (0x10e6c, 0x1, 83),
+    (0x10e6d, 0x1, 93),
+    (0x10e6e, 0x1, 94),
+    (0x10e6f, 0x1, 95),
+    (0x10e70, 0x1, 96),
+    (0x10e71, 0x1, 97),
+    (0x10e72, 0x1, 98),
+    (0x10e73, 0x1, 99),
+    (0x10e74, 0x1, 100),
+    (0x10e75, 0x1, 101),
+    (0x10e76, 0x1, 102),
+    (0x10e77, 0x1, 103),
+    (0x10e78, 0x1, 104),
+    (0x10e79, 0x1, 105),
+    (0x10e7a, 0x1, 106),
+    (0x10e7b, 0x1, 26),
+    (0x10e7c, 0x1, 21),
+    (0x10e7d, 0x1, 22),
+    (0x10e7e, 0x1, 30),
+    (0x10f1d, 0x85, 36),
+    (0x10f22, 0x1, 53),
+    (0x10f23, 0x1, 63),
+    (0x10f24, 0x1, 73),
+    (0x10f25, 0x1, 98),
+    (0x10f26, 0x1, 26),
+    (0x10f51, 0x1, 36),
+    (0x10f52, 0x1, 53),
+    (0x10f53, 0x1, 63),
+    (0x10f54, 0x1, 98),
+    (0x10fc5, 0x84, 36),
+    (0x10fc9, 0x1, 53),
+    (0x10fca, 0x1, 63),
+    (0x10fcb, 0x1, 98),
+    (0x11052, 0x8a, 36),
+    (0x1105c, 0x1, 63),
+    (0x1105d, 0x1, 73),
+    (0x1105e, 0x1, 83),
+    (0x1105f, 0x1, 93),
+    (0x11060, 0x1, 94),
+    (0x11061, 0x1, 95),
+    (0x11062, 0x1, 96),
+    (0x11063, 0x1, 97),
+    (0x11064, 0x1, 98),
+    (0x11065, 0x1, 107),
+    (0x11066, 0x8a, 1),
+    (0x110f0, 0x8a, 1),
+    (0x11136, 0x8a, 1),
+    (0x111d0, 0x8a, 1),
+    (0x111e1, 0x8a, 36),
+    (0x111eb, 0x1, 63),
+    (0x111ec, 0x1, 73),
+    (0x111ed, 0x1, 83),
+    (0x111ee, 0x1, 93),
+    (0x111ef, 0x1, 94),
+    (0x111f0, 0x1, 95),
+    (0x111f1, 0x1, 96),
+    (0x111f2, 0x1, 97),
+    (0x111f3, 0x1, 98),
+    (0x111f4, 0x1, 107),
+    (0x112f0, 0x8a, 1),
+    (0x11450, 0x8a, 1),
+    (0x114d0, 0x8a, 1),
+    (0x11650, 0x8a, 1),
+    (0x116c0, 0x8a, 1),
+    (0x11730, 0x8b, 1),
+    (0x1173b, 0x1, 63),
+    (0x118e0, 0x8b, 1),
+    (0x118eb, 0x1, 63),
+    (0x118ec, 0x1, 73),
+    (0x118ed, 0x1, 83),
+    (0x118ee, 0x1, 93),
+    (0x118ef, 0x1, 94),
+    (0x118f0, 0x1, 95),
+    (0x118f1, 0x1, 96),
+    (0x118f2, 0x1, 97),
+    (0x11950, 0x8a, 1),
+    (0x11c50, 0x8a, 1),
+    (0x11c5a, 0x8a, 36),
+    (0x11c64, 0x1, 63),
+    (0x11c65, 0x1, 73),
+    (0x11c66, 0x1, 83),
+    (0x11c67, 0x1, 93),
+    (0x11c68, 0x1, 94),
+    (0x11c69, 0x1, 95),
+    (0x11c6a, 0x1, 96),
+    (0x11c6b, 0x1, 97),
+    (0x11c6c, 0x1, 98),
+    (0x11d50, 0x8a, 1),
+    (0x11da0, 0x8a, 1),
+    (0x11fc0, 0x1, 2),
+    (0x11fc1, 0x1, 3),
+    (0x11fc2, 0x1, 4),
+    (0x11fc3, 0x1, 5),
+    (0x11fc4, 0x1, 6),
+    (0x11fc5, 0x1, 7),
+    (0x11fc6, 0x1, 8),
+    (0x11fc7, 0x1, 9),
+    (0x11fc8, 0x1, 10),
+    (0x11fc9, 0x2, 11),
+    (0x11fcb, 0x1, 13),
+    (0x11fcc, 0x1, 15),
+    (0x11fcd, 0x1, 17),
+    (0x11fce, 0x1, 19),
+    (0x11fcf, 0x1, 20),
+    (0x11fd0, 0x1, 21),
+    (0x11fd1, 0x2, 26),
+    (0x11fd3, 0x1, 31),
+    (0x11fd4, 0x1, 2),
+    (0x12400, 0x88, 38),
+    (0x12408, 0x87, 40),
+    (0x1240f, 0x86, 42),
+    (0x12415, 0x89, 36),
+    (0x1241e, 0x85, 36),
+    (0x12423, 0x82, 38),
+    (0x12425, 0x87, 40),
+    (0x1242c, 0x83, 36),
+    (0x1242f, 0x83, 40),
+    (0x12432, 0x1, 127),
+    (0x12433, 0x1, 130),
+    (0x12434, 0x83, 36),
+    (0x12437, 0x83, 40),
+    (0x1243a, 0x2, 40),
+    (0x1243c, 0x4, 42),
+    (0x12440, 0x82, 46),
+    (0x12442, 0x2, 48),
+    (0x12444, 0x2, 50),
+    (0x12446, 0x4, 52),
+    (0x1244a, 0x85, 38),
+    (0x1244f, 0x84, 36),
+    (0x12453, 0x82, 42),
+    (0x12455, 0x1, 44),
+    (0x12456, 0x82, 38),
+    (0x12458, 0x82, 36),
+    (0x1245a, 0x1, 22),
+    (0x1245b, 0x1, 30),
+    (0x1245c, 0x1, 33),
+    (0x1245d, 0x1, 22),
+    (0x1245e, 0x1, 30),
+    (0x1245f, 0x1, 15),
+    (0x12460, 0x1, 21),
+    (0x12461, 0x1, 18),
+    (0x12462, 0x2, 21),
+    (0x12464, 0x1, 26),
+    (0x12465, 0x1, 22),
+    (0x12466, 0x1, 30),
+    (0x12467, 0x1, 83),
+    (0x12468, 0x1, 93),
+    (0x12469, 0x86, 42),
+    (0x16a60, 0x8a, 1),
+    (0x16ac0, 0x8a, 1),
+    (0x16b50, 0x8a, 1),
+    (0x16b5b, 0x1, 53),
+    (0x16b5c, 0x1, 98),
+    (0x16b5d, 0x1, 116),
+    (0x16b5e, 0x1, 136),
+    (0x16b5f, 0x1, 139),
+    (0x16b60, 0x1, 140),
+    (0x16b61, 0x1, 141),
+    (0x16e80, 0x94, 1),
+    (0x16e94, 0x83, 36),
+    (0x1d2e0, 0x94, 1),
+    (0x1d360, 0x8a, 36),
+    (0x1d36a, 0x1, 63),
+    (0x1d36b, 0x1, 73),
+    (0x1d36c, 0x1, 83),
+    (0x1d36d, 0x1, 93),
+    (0x1d36e, 0x1, 94),
+    (0x1d36f, 0x1, 95),
+    (0x1d370, 0x1, 96),
+    (0x1d371, 0x1, 97),
+    (0x1d372, 0x85, 36),
+    (0x1d377, 0x1, 36),
+    (0x1d378, 0x1, 44),
+    (0x1d7ce, 0x8a, 1),
+    (0x1d7d8, 0x8a, 1),
+    (0x1d7e2, 0x8a, 1),
+    (0x1d7ec, 0x8a, 1),
+    (0x1d7f6, 0x8a, 1),
+    (0x1e140, 0x8a, 1),
+    (0x1e2f0, 0x8a, 1),
+    (0x1e8c7, 0x89, 36),
+    (0x1e950, 0x8a, 1),
+    (0x1ec71, 0x8a, 36),
+    (0x1ec7b, 0x1, 63),
+    (0x1ec7c, 0x1, 73),
+    (0x1ec7d, 0x1, 83),
+    (0x1ec7e, 0x1, 93),
+    (0x1ec7f, 0x1, 94),
+    (0x1ec80, 0x1, 95),
+    (0x1ec81, 0x1, 96),
+    (0x1ec82, 0x1, 97),
+    (0x1ec83, 0x1, 98),
+    (0x1ec84, 0x1, 99),
+    (0x1ec85, 0x1, 100),
+    (0x1ec86, 0x1, 101),
+    (0x1ec87, 0x1, 102),
+    (0x1ec88, 0x1, 103),
+    (0x1ec89, 0x1, 104),
+    (0x1ec8a, 0x1, 105),
+    (0x1ec8b, 0x1, 106),
+    (0x1ec8c, 0x1, 107),
+    (0x1ec8d, 0x1, 108),
+    (0x1ec8e, 0x1, 109),
+    (0x1ec8f, 0x1, 110),
+    (0x1ec90, 0x1, 111),
+    (0x1ec91, 0x1, 112),
+    (0x1ec92, 0x1, 113),
+    (0x1ec93, 0x1, 114),
+    (0x1ec94, 0x1, 115),
+    (0x1ec95, 0x1, 116),
+    (0x1ec96, 0x1, 117),
+    (0x1ec97, 0x1, 118),
+    (0x1ec98, 0x1, 119),
+    (0x1ec99, 0x1, 120),
+    (0x1ec9a, 0x1, 121),
+    (0x1ec9b, 0x1, 122),
+    (0x1ec9c, 0x1, 123),
+    (0x1ec9d, 0x1, 124),
+    (0x1ec9e, 0x1, 125),
+    (0x1ec9f, 0x1, 126),
+    (0x1eca0, 0x1, 125),
+    (0x1eca1, 0x1, 137),
+    (0x1eca2, 0x1, 138),
+    (0x1eca3, 0x89, 36),
+    (0x1ecad, 0x1, 21),
+    (0x1ecae, 0x1, 26),
+    (0x1ecaf, 0x1, 31),
+    (0x1ecb1, 0x82, 36),
+    (0x1ecb3, 0x1, 116),
+    (0x1ecb4, 0x1, 125),
+    (0x1ed01, 0x8a, 36),
+    (0x1ed0b, 0x1, 63),
+    (0x1ed0c, 0x1, 73),
+    (0x1ed0d, 0x1, 83),
+    (0x1ed0e, 0x1, 93),
+    (0x1ed0f, 0x1, 94),
+    (0x1ed10, 0x1, 95),
+    (0x1ed11, 0x1, 96),
+    (0x1ed12, 0x1, 97),
+    (0x1ed13, 0x1, 98),
+    (0x1ed14, 0x1, 99),
+    (0x1ed15, 0x1, 100),
+    (0x1ed16, 0x1, 101),
+    (0x1ed17, 0x1, 102),
+    (0x1ed18, 0x1, 103),
+    (0x1ed19, 0x1, 104),
+    (0x1ed1a, 0x1, 105),
+    (0x1ed1b, 0x1, 106),
+    (0x1ed1c, 0x1, 107),
+    (0x1ed1d, 0x1, 108),
+    (0x1ed1e, 0x1, 109),
+    (0x1ed1f, 0x1, 110),
+    (0x1ed20, 0x1, 111),
+    (0x1ed21, 0x1, 112),
+    (0x1ed22, 0x1, 113),
+    (0x1ed23, 0x1, 114),
+    (0x1ed24, 0x1, 115),
+    (0x1ed25, 0x1, 116),
+    (0x1ed26, 0x1, 117),
+    (0x1ed27, 0x1, 118),
+    (0x1ed28, 0x1, 119),
+    (0x1ed29, 0x1, 120),
+    (0x1ed2a, 0x1, 121),
+    (0x1ed2b, 0x1, 122),
+    (0x1ed2c, 0x1, 123),
+    (0x1ed2d, 0x1, 124),
+    (0x1ed2f, 0x89, 38),
+    (0x1ed38, 0x1, 101),
+    (0x1ed39, 0x1, 103),
+    (0x1ed3a, 0x1, 108),
+    (0x1ed3b, 0x1, 116),
+    (0x1ed3c, 0x1, 26),
+    (0x1ed3d, 0x1, 18),
+    (0x1f100, 0x2, 1),
+    (0x1f102, 0x89, 36),
+    (0x1f10b, 0x2, 1),
+    (0x1fbf0, 0x8a, 1),
+    (0x20001, 0x1, 48),
+    (0x20064, 0x1, 42),
+    (0x200e2, 0x1, 42),
+    (0x20121, 0x1, 44),
+    (0x2092a, 0x1, 36),
+    (0x20983, 0x1, 73),
+    (0x2098c, 0x1, 83),
+    (0x2099c, 0x1, 83),
+    (0x20aea, 0x1, 46),
+    (0x20afd, 0x1, 40),
+    (0x20b19, 0x1, 40),
+    (0x22390, 0x1, 38),
+    (0x22998, 0x1, 40),
+    (0x23b1b, 0x1, 40),
+    (0x2626d, 0x1, 42),
+    (0x2f890, 0x1, 52),
+];
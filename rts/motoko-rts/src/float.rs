@@ -1,10 +1,13 @@
+use crate::float_conv::{format_shortest, parse_float, ParseFloatError, MAX_SHORTEST_LEN};
 use crate::memory::Memory;
-use crate::text::text_of_ptr_size;
+use crate::rts_trap_with;
+use crate::text::{blob_of_text, text_of_ptr_size};
 use crate::types::{Bytes, Value};
 
 use motoko_rts_macros::ic_mem_fn;
 
-// The meaning of the `mode` parameter is documented in motoko-base, function Float.format()
+// The meaning of the `mode` parameter is documented in motoko-base, function Float.format().
+// Mode 4 is the shortest text that parses back to the same float, `prec` is ignored.
 #[ic_mem_fn]
 unsafe fn float_fmt<M: Memory>(mem: &mut M, a: f64, prec: u32, mode: u32) -> Value {
     // prec and mode are tagged small words (`Nat8`s), so we shift 24 bits. See
@@ -12,6 +15,12 @@ unsafe fn float_fmt<M: Memory>(mem: &mut M, a: f64, prec: u32, mode: u32) -> Val
     let mode = mode >> 24;
     let prec = core::cmp::min(prec >> 24, 100) as usize;
 
+    if mode == 4 {
+        let mut buf = [0u8; MAX_SHORTEST_LEN];
+        let n_written = format_shortest(a, &mut buf);
+        return text_of_ptr_size(mem, buf.as_ptr(), Bytes(n_written as u32));
+    }
+
     // 320 bytes needed for max precision (1.7e308)
     let buf = [0u8; 320];
 
@@ -36,3 +45,20 @@ unsafe fn float_fmt<M: Memory>(mem: &mut M, a: f64, prec: u32, mode: u32) -> Val
 
     text_of_ptr_size(mem, buf.as_ptr(), Bytes(n_written as u32))
 }
+
+/// Parses a text as a decimal float (see `float_conv::parse_float` for the syntax), rounding to
+/// the nearest float. Traps on invalid input.
+#[ic_mem_fn]
+unsafe fn text_to_float<M: Memory>(mem: &mut M, s: Value) -> f64 {
+    let blob = blob_of_text(mem, s).as_blob();
+    let bytes = core::slice::from_raw_parts(blob.payload_const(), blob.len().as_usize());
+    match parse_float(bytes) {
+        Ok(f) => f,
+        Err(ParseFloatError::Empty) => rts_trap_with("text_to_float: empty text"),
+        Err(ParseFloatError::InvalidCharacter) => rts_trap_with("text_to_float: invalid character"),
+        Err(ParseFloatError::MissingDigits) => rts_trap_with("text_to_float: no digits"),
+        Err(ParseFloatError::MissingExponent) => {
+            rts_trap_with("text_to_float: no digits in exponent")
+        }
+    }
+}
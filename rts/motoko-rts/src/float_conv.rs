@@ -0,0 +1,582 @@
+//! Conversions between `f64` and decimal text that don't depend on libc: shortest round-trip
+//! formatting, and correctly rounded parsing.
+//!
+//! Both directions are exact, using a small fixed-size big integer instead of approximations:
+//!
+//! - `format_shortest` generates the shortest digit sequence that parses back to the same float
+//!   (Burger & Dybvig, "Printing Floating-Point Numbers Quickly and Accurately", the same
+//!   algorithm Rust's `Display` falls back to). The output is rendered like Rust's `Display`:
+//!   positional notation, no exponent, no trailing `.0`.
+//!
+//! - `parse_float` scales the decimal significand and divides by the decimal scale bit by bit,
+//!   which gives the correctly rounded (round half to even) result for any input. Inputs that are
+//!   exactly representable go through the usual fast path.
+
+use core::cmp::Ordering;
+
+/// Maximum length of the output of `format_shortest`: `-0.` followed by 323 zeros and `5` for
+/// the smallest negative subnormal
+pub const MAX_SHORTEST_LEN: usize = 327;
+
+/// Maximum number of significant digits used when parsing. Halfway points between two adjacent
+/// floats have at most 767 significant digits, so any digits after this are only relevant for
+/// being non-zero.
+const MAX_SIG_DIGITS: u32 = 800;
+
+/// Powers of ten that are exactly representable as `f64`
+const POW10: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+const BIG_LIMBS: usize = 130;
+
+/// Unsigned big integer with a fixed capacity of `BIG_LIMBS` 32-bit limbs (enough for the
+/// largest intermediate values in both algorithms)
+#[derive(Clone)]
+struct Big {
+    /// Number of used limbs, the most significant used limb is non-zero
+    len: usize,
+    /// Little-endian limbs
+    limbs: [u32; BIG_LIMBS],
+}
+
+impl Big {
+    fn from_u64(n: u64) -> Self {
+        let mut big = Big {
+            len: 2,
+            limbs: [0; BIG_LIMBS],
+        };
+        big.limbs[0] = n as u32;
+        big.limbs[1] = (n >> 32) as u32;
+        big.normalize();
+        big
+    }
+
+    fn normalize(&mut self) {
+        while self.len > 0 && self.limbs[self.len - 1] == 0 {
+            self.len -= 1;
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bit_len(&self) -> u32 {
+        if self.len == 0 {
+            0
+        } else {
+            self.len as u32 * 32 - self.limbs[self.len - 1].leading_zeros()
+        }
+    }
+
+    fn push_limb(&mut self, limb: u32) {
+        if limb != 0 {
+            self.limbs[self.len] = limb;
+            self.len += 1;
+        }
+    }
+
+    fn mul_add_small(&mut self, m: u32, a: u32) -> &mut Self {
+        let mut carry = u64::from(a);
+        for limb in &mut self.limbs[..self.len] {
+            let v = u64::from(*limb) * u64::from(m) + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        self.push_limb(carry as u32);
+        self
+    }
+
+    fn mul_small(&mut self, m: u32) -> &mut Self {
+        if m == 0 {
+            for limb in &mut self.limbs[..self.len] {
+                *limb = 0;
+            }
+            self.len = 0;
+            return self;
+        }
+        self.mul_add_small(m, 0)
+    }
+
+    fn mul_pow10(&mut self, mut n: u32) -> &mut Self {
+        while n >= 9 {
+            self.mul_small(1_000_000_000);
+            n -= 9;
+        }
+        self.mul_small(10u32.pow(n))
+    }
+
+    fn mul_pow2(&mut self, n: u32) -> &mut Self {
+        if self.len == 0 {
+            return self;
+        }
+        let limb_shift = (n / 32) as usize;
+        let bit_shift = n % 32;
+
+        if bit_shift != 0 {
+            let mut carry = 0;
+            for limb in &mut self.limbs[..self.len] {
+                let v = *limb;
+                *limb = (v << bit_shift) | carry;
+                carry = v >> (32 - bit_shift);
+            }
+            self.push_limb(carry);
+        }
+
+        if limb_shift != 0 {
+            self.limbs.copy_within(0..self.len, limb_shift);
+            for limb in &mut self.limbs[..limb_shift] {
+                *limb = 0;
+            }
+            self.len += limb_shift;
+        }
+
+        self
+    }
+
+    fn add(&mut self, other: &Big) -> &mut Self {
+        let len = core::cmp::max(self.len, other.len);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let v = u64::from(self.limbs[i]) + u64::from(other.limbs[i]) + carry;
+            self.limbs[i] = v as u32;
+            carry = v >> 32;
+        }
+        self.len = len;
+        self.push_limb(carry as u32);
+        self
+    }
+
+    /// Subtracts `other`, which must not be larger than `self`
+    fn sub(&mut self, other: &Big) -> &mut Self {
+        debug_assert!(self.cmp(other) != Ordering::Less);
+        let mut borrow = 0i64;
+        for i in 0..self.len {
+            let v = i64::from(self.limbs[i]) - i64::from(other.limbs[i]) - borrow;
+            self.limbs[i] = v as u32;
+            borrow = if v < 0 { 1 } else { 0 };
+        }
+        self.normalize();
+        self
+    }
+
+    fn cmp(&self, other: &Big) -> Ordering {
+        self.len.cmp(&other.len).then_with(|| {
+            self.limbs[..self.len]
+                .iter()
+                .rev()
+                .cmp(other.limbs[..other.len].iter().rev())
+        })
+    }
+}
+
+/// Decomposes a finite, non-negative float into mantissa and binary exponent, with the biased
+/// exponent field
+fn decode(x: f64) -> (u64, i32, u32) {
+    let bits = x.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7FF) as u32;
+    let fraction = bits & ((1 << 52) - 1);
+    if biased_exp == 0 {
+        (fraction, -1074, biased_exp)
+    } else {
+        (fraction | (1 << 52), biased_exp as i32 - 1075, biased_exp)
+    }
+}
+
+/// Shortest digits of a finite, positive float: writes the digits `d1 d2 ... dn` to `digits` and
+/// returns `(n, k)`, such that the float is the closest one to `0.d1d2...dn * 10^k`
+fn shortest_digits(x: f64, digits: &mut [u8; 17]) -> (usize, i32) {
+    let (mant, exp, biased_exp) = decode(x);
+    debug_assert!(mant != 0);
+
+    // Scaled value `r / s` and distances to the midpoints to the neighbouring floats `m_plus / s`
+    // and `m_minus / s`. All values are scaled by 2 (by 4 when the gap to the predecessor is
+    // smaller) to keep them integral.
+    let asymmetric = mant == 1 << 52 && biased_exp > 1;
+    let (mut r, mut s, mut m_plus, mut m_minus);
+    if exp >= 0 {
+        if !asymmetric {
+            r = Big::from_u64(mant);
+            r.mul_pow2(exp as u32 + 1);
+            s = Big::from_u64(2);
+            m_plus = Big::from_u64(1);
+            m_plus.mul_pow2(exp as u32);
+        } else {
+            r = Big::from_u64(mant);
+            r.mul_pow2(exp as u32 + 2);
+            s = Big::from_u64(4);
+            m_plus = Big::from_u64(1);
+            m_plus.mul_pow2(exp as u32 + 1);
+        }
+        m_minus = Big::from_u64(1);
+        m_minus.mul_pow2(exp as u32);
+    } else {
+        if !asymmetric {
+            r = Big::from_u64(mant << 1);
+            s = Big::from_u64(1);
+            s.mul_pow2((1 - exp) as u32);
+            m_plus = Big::from_u64(1);
+        } else {
+            r = Big::from_u64(mant << 2);
+            s = Big::from_u64(1);
+            s.mul_pow2((2 - exp) as u32);
+            m_plus = Big::from_u64(2);
+        }
+        m_minus = Big::from_u64(1);
+    }
+
+    // Digits that are exactly on the midpoint are allowed when the mantissa is even, as the
+    // midpoint then rounds back to it
+    let inclusive = mant % 2 == 0;
+
+    // Estimate `k` such that `10^(k-1) <= high < 10^k` from the binary exponent of the value,
+    // `k = floor((bits - 1) * log10(2)) + 1`. The estimate is either right or one too small, which
+    // is fixed below.
+    let bits = 64 - mant.leading_zeros() as i32 + exp;
+    let mut k = (((bits - 1) * 78913) >> 18) + 1;
+    if k >= 0 {
+        s.mul_pow10(k as u32);
+    } else {
+        r.mul_pow10(-k as u32);
+        m_plus.mul_pow10(-k as u32);
+        m_minus.mul_pow10(-k as u32);
+    }
+
+    let mut high = r.clone();
+    high.add(&m_plus);
+    let too_small = match high.cmp(&s) {
+        Ordering::Greater => true,
+        Ordering::Equal => inclusive,
+        Ordering::Less => false,
+    };
+    if too_small {
+        s.mul_small(10);
+        k += 1;
+    }
+
+    let mut n = 0;
+    loop {
+        r.mul_small(10);
+        m_plus.mul_small(10);
+        m_minus.mul_small(10);
+
+        let mut digit = 0;
+        while r.cmp(&s) != Ordering::Less {
+            r.sub(&s);
+            digit += 1;
+        }
+
+        let low = match r.cmp(&m_minus) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        };
+        let mut high = r.clone();
+        high.add(&m_plus);
+        let high = match high.cmp(&s) {
+            Ordering::Greater => true,
+            Ordering::Equal => inclusive,
+            Ordering::Less => false,
+        };
+
+        if !low && !high {
+            digits[n] = digit;
+            n += 1;
+            continue;
+        }
+
+        // Last digit: round up when only rounding up stays in range, or when both stay in range
+        // and the remainder is at least one half
+        let round_up = high && (!low || r.mul_small(2).cmp(&s) != Ordering::Less);
+        digits[n] = if round_up { digit + 1 } else { digit };
+        return (n + 1, k);
+    }
+}
+
+/// Formats a float with the shortest digit sequence that parses back to the same float, in the
+/// same way as Rust's `Display` for `f64`. Returns the number of bytes written to `buf`.
+pub fn format_shortest(x: f64, buf: &mut [u8; MAX_SHORTEST_LEN]) -> usize {
+    let mut len = 0;
+    let mut push = |bytes: &[u8]| {
+        buf[len..len + bytes.len()].copy_from_slice(bytes);
+        len += bytes.len();
+    };
+
+    if x.is_nan() {
+        push(b"NaN");
+        return len;
+    }
+
+    if x.is_sign_negative() {
+        push(b"-");
+    }
+
+    let x = f64::from_bits(x.to_bits() & !(1 << 63));
+    if x.is_infinite() {
+        push(b"inf");
+    } else if x == 0.0 {
+        push(b"0");
+    } else {
+        let mut digits = [0u8; 17];
+        let (n, k) = shortest_digits(x, &mut digits);
+        let digits = &mut digits[..n];
+        for digit in digits.iter_mut() {
+            *digit += b'0';
+        }
+
+        if k <= 0 {
+            push(b"0.");
+            for _ in 0..-k {
+                push(b"0");
+            }
+            push(digits);
+        } else if (k as usize) < n {
+            push(&digits[..k as usize]);
+            push(b".");
+            push(&digits[k as usize..]);
+        } else {
+            push(digits);
+            for _ in n..k as usize {
+                push(b"0");
+            }
+        }
+    }
+
+    len
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseFloatError {
+    /// The input is empty
+    Empty,
+    /// The input contains a character that is not valid at its position
+    InvalidCharacter,
+    /// There are no digits before or after the decimal point
+    MissingDigits,
+    /// The exponent marker `e` or `E` is not followed by digits
+    MissingExponent,
+}
+
+/// Parses a decimal floating point number, rounding to the nearest float (ties to even).
+///
+/// The syntax is the one accepted by Rust's `f64::from_str`: an optional sign, followed by
+/// `inf`, `infinity`, `nan` (case insensitive), or digits with an optional decimal point and an
+/// optional exponent (`e` or `E`, optional sign, digits). Numbers too large for `f64` are
+/// infinite, numbers too small are zero.
+pub fn parse_float(s: &[u8]) -> Result<f64, ParseFloatError> {
+    if s.is_empty() {
+        return Err(ParseFloatError::Empty);
+    }
+
+    let (negative, s) = match s[0] {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    let special = [
+        (&b"inf"[..], f64::INFINITY),
+        (&b"infinity"[..], f64::INFINITY),
+        (&b"nan"[..], f64::NAN),
+    ];
+    for (name, value) in special.iter() {
+        if s.eq_ignore_ascii_case(name) {
+            return Ok(if negative { -value } else { *value });
+        }
+    }
+
+    let abs = parse_decimal(s)?;
+    Ok(if negative { -abs } else { abs })
+}
+
+/// Parses an unsigned decimal number
+fn parse_decimal(s: &[u8]) -> Result<f64, ParseFloatError> {
+    // The value is `significand * 10^exp10`, with the significand limited to `MAX_SIG_DIGITS`
+    // digits. `truncated` records whether any of the digits dropped after that were non-zero.
+    let mut significand = Big::from_u64(0);
+    let mut n_sig_digits: u32 = 0;
+    let mut truncated = false;
+    let mut exp10: i64 = 0;
+    let mut n_digits = 0;
+
+    let mut i = 0;
+    let mut after_point = false;
+    while i < s.len() {
+        match s[i] {
+            b'.' if !after_point => after_point = true,
+            c @ b'0'..=b'9' => {
+                n_digits += 1;
+                let digit = (c - b'0') as u32;
+                if significand.is_zero() && digit == 0 {
+                    // Leading zero
+                    if after_point {
+                        exp10 -= 1;
+                    }
+                } else if n_sig_digits < MAX_SIG_DIGITS {
+                    significand.mul_add_small(10, digit);
+                    n_sig_digits += 1;
+                    if after_point {
+                        exp10 -= 1;
+                    }
+                } else {
+                    truncated |= digit != 0;
+                    if !after_point {
+                        exp10 += 1;
+                    }
+                }
+            }
+            b'e' | b'E' => break,
+            _ => return Err(ParseFloatError::InvalidCharacter),
+        }
+        i += 1;
+    }
+
+    if n_digits == 0 {
+        return Err(ParseFloatError::MissingDigits);
+    }
+
+    if i < s.len() {
+        // Exponent, saturated to a value that is large enough to make any significand overflow
+        // or underflow
+        let mut exp = &s[i + 1..];
+        let exp_negative = match exp.first() {
+            Some(b'-') => {
+                exp = &exp[1..];
+                true
+            }
+            Some(b'+') => {
+                exp = &exp[1..];
+                false
+            }
+            _ => false,
+        };
+        if exp.is_empty() {
+            return Err(ParseFloatError::MissingExponent);
+        }
+        let mut exp_value: i64 = 0;
+        for c in exp {
+            if !c.is_ascii_digit() {
+                return Err(ParseFloatError::InvalidCharacter);
+            }
+            exp_value = core::cmp::min(exp_value * 10 + (c - b'0') as i64, 1_000_000);
+        }
+        exp10 += if exp_negative { -exp_value } else { exp_value };
+    }
+
+    if significand.is_zero() {
+        return Ok(0.0);
+    }
+
+    if truncated {
+        // Make the significand strictly larger than the truncated one. The last digit is beyond
+        // the precision of any halfway point, so only its being non-zero matters.
+        significand.mul_add_small(10, 1);
+        n_sig_digits += 1;
+        exp10 -= 1;
+    }
+
+    // The value is `0.d1d2... * 10^point`, check the bounds of `f64` (max. ~1.8e308, smallest
+    // subnormal ~4.9e-324)
+    let point = n_sig_digits as i64 + exp10;
+    if point > 310 {
+        return Ok(f64::INFINITY);
+    }
+    if point < -324 {
+        return Ok(0.0);
+    }
+
+    // Fast path: significand and power of ten are exactly representable, so one correctly
+    // rounded operation gives the result
+    if n_sig_digits <= 15 && exp10.abs() <= 22 {
+        let mantissa = significand.limbs[0] as u64 | (significand.limbs[1] as u64) << 32;
+        let mantissa = mantissa as f64;
+        let scale = POW10[exp10.abs() as usize];
+        return Ok(if exp10 < 0 {
+            mantissa / scale
+        } else {
+            mantissa * scale
+        });
+    }
+
+    let (mut num, mut den) = if exp10 >= 0 {
+        significand.mul_pow10(exp10 as u32);
+        (significand, Big::from_u64(1))
+    } else {
+        let mut den = Big::from_u64(1);
+        den.mul_pow10(-exp10 as u32);
+        (significand, den)
+    };
+
+    Ok(round_quotient(&mut num, &mut den))
+}
+
+/// Converts a positive rational number `num / den` to the nearest float (ties to even)
+fn round_quotient(num: &mut Big, den: &mut Big) -> f64 {
+    // Binary exponent: `2^e <= num / den < 2^(e+1)`
+    let mut e = num.bit_len() as i32 - den.bit_len() as i32;
+    scale_pow2(num, den, e);
+    if num.cmp(den) == Ordering::Less {
+        e -= 1;
+        num.mul_pow2(1);
+    }
+    // Now `1 <= num / den < 2`
+
+    if e > 1023 {
+        return f64::INFINITY;
+    }
+
+    // Exponent of the least significant mantissa bit
+    let mut ulp_exp = core::cmp::max(e - 52, -1074);
+
+    // Mantissa bits, followed by a rounding bit
+    let n_bits = e - ulp_exp + 2;
+    if n_bits <= 0 {
+        // Less than half of the smallest subnormal
+        return 0.0;
+    }
+
+    let mut bits: u64 = 0;
+    for i in 0..n_bits {
+        if i != 0 {
+            num.mul_pow2(1);
+        }
+        bits <<= 1;
+        if num.cmp(den) != Ordering::Less {
+            num.sub(den);
+            bits |= 1;
+        }
+    }
+    let sticky = !num.is_zero();
+
+    let mut mant = bits >> 1;
+    if bits & 1 != 0 && (sticky || mant & 1 != 0) {
+        mant += 1;
+    }
+
+    if mant == 1 << 53 {
+        mant >>= 1;
+        ulp_exp += 1;
+    }
+
+    if mant < 1 << 52 {
+        // Subnormal
+        return f64::from_bits(mant);
+    }
+
+    let biased_exp = (ulp_exp + 1075) as u64;
+    if biased_exp >= 0x7FF {
+        return f64::INFINITY;
+    }
+    f64::from_bits(biased_exp << 52 | (mant & ((1 << 52) - 1)))
+}
+
+/// Divides `num / den` by `2^e`
+fn scale_pow2(num: &mut Big, den: &mut Big, e: i32) {
+    if e >= 0 {
+        den.mul_pow2(e as u32);
+    } else {
+        num.mul_pow2(-e as u32);
+    }
+}
@@ -0,0 +1,141 @@
+//! Hashing of `Text` and `Blob` values with SipHash-2-4
+//!
+//! `text_hash` feeds the leaves of a text rope into the hasher one after the other, so the result
+//! only depends on the contents of the text, not on the shape of the rope, and equals the
+//! `blob_hash` of the straightened text.
+//!
+//! The key is fixed: hashes may end up in stable variables (e.g. in hash maps) and need to stay the
+//! same across upgrades.
+//!
+//! Reference: https://www.aumasson.jp/siphash/siphash.pdf
+
+use crate::text::for_each_leaf;
+use crate::types::Value;
+
+use core::slice;
+
+const KEY0: u64 = 0x6d6f_746f_6b6f_5f68; // "motoko_h"
+const KEY1: u64 = 0x6173_685f_6b65_7931; // "ash_key1"
+
+/// Incremental SipHash-2-4
+pub struct SipHasher {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    /// Bytes not yet processed, as a little-endian number
+    tail: u64,
+    /// Number of bytes in `tail`
+    n_tail: usize,
+    /// Number of bytes written so far
+    length: usize,
+}
+
+impl SipHasher {
+    pub fn new_with_keys(key0: u64, key1: u64) -> Self {
+        SipHasher {
+            v0: key0 ^ 0x736f_6d65_7073_6575,
+            v1: key1 ^ 0x646f_7261_6e64_6f6d,
+            v2: key0 ^ 0x6c79_6765_6e65_7261,
+            v3: key1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            n_tail: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    fn sip_round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        self.sip_round();
+        self.sip_round();
+        self.v0 ^= m;
+    }
+
+    pub fn write(&mut self, mut bytes: &[u8]) {
+        self.length += bytes.len();
+
+        // Complete the word started by the previous write
+        if self.n_tail != 0 {
+            while self.n_tail < 8 {
+                match bytes.split_first() {
+                    None => return,
+                    Some((byte, rest)) => {
+                        self.tail |= u64::from(*byte) << (8 * self.n_tail);
+                        self.n_tail += 1;
+                        bytes = rest;
+                    }
+                }
+            }
+            self.compress(self.tail);
+            self.tail = 0;
+            self.n_tail = 0;
+        }
+
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let mut le_bytes = [0u8; 8];
+            le_bytes.copy_from_slice(word);
+            self.compress(u64::from_le_bytes(le_bytes));
+        }
+
+        for byte in words.remainder() {
+            self.tail |= u64::from(*byte) << (8 * self.n_tail);
+            self.n_tail += 1;
+        }
+    }
+
+    pub fn finish(mut self) -> u64 {
+        let last = ((self.length as u64 & 0xFF) << 56) | self.tail;
+        self.compress(last);
+        self.v2 ^= 0xFF;
+        self.sip_round();
+        self.sip_round();
+        self.sip_round();
+        self.sip_round();
+        self.v0 ^ self.v1 ^ self.v2 ^ self.v3
+    }
+}
+
+/// Folds a 64-bit hash into the 32 bits of a Motoko `Hash`
+fn fold(hash: u64) -> u32 {
+    (hash ^ (hash >> 32)) as u32
+}
+
+/// Hash of a text. Equal texts have equal hashes, regardless of how they were concatenated.
+#[no_mangle]
+pub unsafe extern "C" fn text_hash(s: Value) -> u32 {
+    let mut hasher = SipHasher::new_with_keys(KEY0, KEY1);
+    for_each_leaf(s, &mut |leaf| hasher.write(leaf.as_bytes()));
+    fold(hasher.finish())
+}
+
+/// Hash of a blob. The hash of a text is the hash of its UTF-8 encoding as a blob.
+#[no_mangle]
+pub unsafe extern "C" fn blob_hash(b: Value) -> u32 {
+    let blob = b.as_blob();
+    let mut hasher = SipHasher::new_with_keys(KEY0, KEY1);
+    hasher.write(slice::from_raw_parts(
+        blob.payload_const(),
+        blob.len().as_usize(),
+    ));
+    fold(hasher.finish())
+}
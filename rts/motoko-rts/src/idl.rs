@@ -1,13 +1,19 @@
 #![allow(non_upper_case_globals)]
+pub mod json;
+pub mod print;
+
 use crate::bitrel::BitRel;
-use crate::buf::{read_byte, read_word, skip_leb128, Buf};
+use crate::buf::{read_byte, Buf};
+use crate::hash::SipHasher;
 use crate::idl_trap_with;
-use crate::leb128::{leb128_decode, sleb128_decode};
+use crate::leb128::{leb128_decode_checked, sleb128_decode_checked};
 use crate::memory::{alloc_blob, Memory};
-use crate::types::Words;
-use crate::utf8::utf8_validate;
+use crate::print::WriteBuf;
+use crate::types::{Bytes, Words};
+use crate::utf8::utf8_valid;
 
 use core::cmp::min;
+use core::fmt;
 
 use motoko_rts_macros::ic_mem_fn;
 
@@ -15,39 +21,365 @@ use motoko_rts_macros::ic_mem_fn;
 // IDL constants
 //
 
-const IDL_PRIM_null: i32 = -1;
-const IDL_PRIM_bool: i32 = -2;
-const IDL_PRIM_nat: i32 = -3;
-const IDL_PRIM_int: i32 = -4;
-const IDL_PRIM_nat8: i32 = -5;
-const IDL_PRIM_nat16: i32 = -6;
-const IDL_PRIM_nat32: i32 = -7;
-const IDL_PRIM_nat64: i32 = -8;
-const IDL_PRIM_int8: i32 = -9;
-const IDL_PRIM_int16: i32 = -10;
-const IDL_PRIM_int32: i32 = -11;
-const IDL_PRIM_int64: i32 = -12;
-const IDL_PRIM_float32: i32 = -13;
-const IDL_PRIM_float64: i32 = -14;
-const IDL_PRIM_text: i32 = -15;
-const IDL_PRIM_reserved: i32 = -16;
-const IDL_PRIM_empty: i32 = -17;
-
-const IDL_CON_opt: i32 = -18;
-const IDL_CON_vec: i32 = -19;
-const IDL_CON_record: i32 = -20;
-const IDL_CON_variant: i32 = -21;
-const IDL_CON_func: i32 = -22;
-const IDL_CON_service: i32 = -23;
-
-const IDL_REF_principal: i32 = -24;
-
-const IDL_CON_alias: i32 = 1;
+pub const IDL_PRIM_null: i32 = -1;
+pub const IDL_PRIM_bool: i32 = -2;
+pub const IDL_PRIM_nat: i32 = -3;
+pub const IDL_PRIM_int: i32 = -4;
+pub const IDL_PRIM_nat8: i32 = -5;
+pub const IDL_PRIM_nat16: i32 = -6;
+pub const IDL_PRIM_nat32: i32 = -7;
+pub const IDL_PRIM_nat64: i32 = -8;
+pub const IDL_PRIM_int8: i32 = -9;
+pub const IDL_PRIM_int16: i32 = -10;
+pub const IDL_PRIM_int32: i32 = -11;
+pub const IDL_PRIM_int64: i32 = -12;
+pub const IDL_PRIM_float32: i32 = -13;
+pub const IDL_PRIM_float64: i32 = -14;
+pub const IDL_PRIM_text: i32 = -15;
+pub const IDL_PRIM_reserved: i32 = -16;
+pub const IDL_PRIM_empty: i32 = -17;
+
+pub const IDL_CON_opt: i32 = -18;
+pub const IDL_CON_vec: i32 = -19;
+pub const IDL_CON_record: i32 = -20;
+pub const IDL_CON_variant: i32 = -21;
+pub const IDL_CON_func: i32 = -22;
+pub const IDL_CON_service: i32 = -23;
+
+pub const IDL_REF_principal: i32 = -24;
+
+pub const IDL_CON_alias: i32 = 1;
+
+// Annotations of function types
+pub const IDL_FUNC_ANNOT_query: u8 = 1;
+pub const IDL_FUNC_ANNOT_oneway: u8 = 2;
+pub const IDL_FUNC_ANNOT_composite_query: u8 = 3;
 
 const IDL_PRIM_lowest: i32 = -17;
 
-pub unsafe fn leb128_decode_ptr(buf: *mut Buf) -> (u32, *mut u8) {
-    (leb128_decode(buf), (*buf).ptr)
+//
+// Errors
+//
+
+/// Why a message cannot be decoded. The functions called by generated code trap with the
+/// message of the error, their `_checked` versions return it. Errors in decoding a message come
+/// with where they happened (see `IdlErrorAt`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlError {
+    EmptyInput,
+    MissingMagic,
+    TooManyTypes,
+    IllegalTypeTable,
+    PrimitiveInTypeTable,
+    InvalidTypeArgument,
+    TagsOutOfOrder,
+    InvalidFuncAnnotation,
+    MethodNamesOutOfOrder,
+    MethodNotConstructor,
+    MethodNotFunc,
+    ByteReadOutOfBuffer,
+    WordReadOutOfBuffer,
+    AdvanceOutOfBuffer,
+    Leb128Overflow,
+    NotUtf8,
+    QuotaExceeded,
+    InvalidByteTag,
+    SkipEmpty,
+    UnknownPrim,
+    SkipReferences,
+    FutureReferences,
+    NestedTooDeeply { levels: u32 },
+    RecordCycle { t: i32 },
+    VariantTagTooLarge { tag: u32, t: i32, cases: u32 },
+    UnexpectedAlias,
+    NumberTooLarge,
+}
+
+pub type IdlResult<T> = Result<T, IdlError>;
+
+impl fmt::Display for IdlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdlError::EmptyInput => f.write_str(
+                "empty input. Expected Candid-encoded argument, but received a zero-length argument",
+            ),
+            IdlError::MissingMagic => f.write_str("missing magic bytes"),
+            IdlError::TooManyTypes => f.write_str("too many types"),
+            IdlError::IllegalTypeTable => f.write_str("illegal type table"),
+            IdlError::PrimitiveInTypeTable => f.write_str("primitive type in type table"),
+            IdlError::InvalidTypeArgument => f.write_str("invalid type argument"),
+            IdlError::TagsOutOfOrder => f.write_str("variant or record tag out of order"),
+            IdlError::InvalidFuncAnnotation => f.write_str("func annotation not within 1..3"),
+            IdlError::MethodNamesOutOfOrder => f.write_str("service method names out of order"),
+            IdlError::MethodNotConstructor => {
+                f.write_str("service method arg not a constructor type")
+            }
+            IdlError::MethodNotFunc => f.write_str("service method arg not a function type"),
+            IdlError::ByteReadOutOfBuffer => f.write_str("byte read out of buffer"),
+            IdlError::WordReadOutOfBuffer => f.write_str("word read out of buffer"),
+            IdlError::AdvanceOutOfBuffer => f.write_str("advance out of buffer"),
+            IdlError::Leb128Overflow => f.write_str("LEB128 number out of range"),
+            IdlError::NotUtf8 => f.write_str("string is not UTF-8"),
+            IdlError::QuotaExceeded => f.write_str("decoding quota exceeded"),
+            IdlError::InvalidByteTag => f.write_str("skip_any: byte tag not 0 or 1"),
+            IdlError::SkipEmpty => f.write_str("skip_any: encountered empty"),
+            IdlError::UnknownPrim => f.write_str("skip_any: unknown prim"),
+            IdlError::SkipReferences => f.write_str("skip_any: skipping references"),
+            IdlError::FutureReferences => f.write_str("skip_any: future value with references"),
+            IdlError::NestedTooDeeply { levels } => write!(
+                f,
+                "skip_any: value nested too deeply (more than {} levels)",
+                levels
+            ),
+            IdlError::RecordCycle { t } => write!(
+                f,
+                "skip_any: recursive record type {} contains itself without data",
+                t
+            ),
+            IdlError::VariantTagTooLarge { tag, t, cases } => write!(
+                f,
+                "skip_any: variant tag {} too large for type {} with {} cases",
+                tag, t, cases
+            ),
+            IdlError::UnexpectedAlias => f.write_str("sub: unexpected alias"),
+            IdlError::NumberTooLarge => f.write_str("number too large to convert"),
+        }
+    }
+}
+
+/// An `IdlError`, with where in the message it happened
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlErrorAt {
+    pub error: IdlError,
+    /// Where decoding stopped, in bytes from the start of the message last passed to
+    /// `parse_idl_header`
+    pub offset: u32,
+    /// The type table entry being parsed, or the innermost one being decoded
+    pub type_index: Option<u32>,
+    pub path: IdlPath,
+}
+
+impl IdlErrorAt {
+    unsafe fn new(error: IdlError, ptr: *mut u8, type_index: Option<u32>, path: IdlPath) -> Self {
+        IdlErrorAt {
+            error,
+            offset: (ptr as usize).wrapping_sub(IDL_MESSAGE_START as usize) as u32,
+            type_index,
+            path,
+        }
+    }
+}
+
+impl fmt::Display for IdlErrorAt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (byte {}", self.error, self.offset)?;
+        if let Some(t) = self.type_index {
+            write!(f, ", type {}", t)?;
+        }
+        if self.path.depth() > 0 {
+            write!(f, ", path {}", self.path)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// A step into a value. Only arguments, records and vectors are recorded: options and variants
+/// have one nested value, the type table tells which.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlPathSegment {
+    Arg(u32),
+    Field(u32),
+    Element(u32),
+}
+
+impl fmt::Display for IdlPathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdlPathSegment::Arg(i) => write!(f, "arg {}", i),
+            IdlPathSegment::Field(tag) => write!(f, "field {:#x}", tag),
+            IdlPathSegment::Element(i) => write!(f, "vec[{}]", i),
+        }
+    }
+}
+
+/// Number of segments kept at each end of a path
+const IDL_PATH_ENDS: usize = 4;
+
+/// The path to a value, outermost segment first. Only the first and last `IDL_PATH_ENDS`
+/// segments are kept, so that error messages fit into the trap message buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlPath {
+    segments: [IdlPathSegment; 2 * IDL_PATH_ENDS],
+    depth: u32,
+}
+
+impl IdlPath {
+    pub const EMPTY: IdlPath = IdlPath {
+        segments: [IdlPathSegment::Arg(0); 2 * IDL_PATH_ENDS],
+        depth: 0,
+    };
+
+    /// Number of segments, including the ones not kept
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn push(&mut self, segment: IdlPathSegment) {
+        let kept = min(self.depth as usize, 2 * IDL_PATH_ENDS);
+        if kept == 2 * IDL_PATH_ENDS {
+            self.segments
+                .copy_within(IDL_PATH_ENDS + 1.., IDL_PATH_ENDS);
+            self.segments[kept - 1] = segment;
+        } else {
+            self.segments[kept] = segment;
+        }
+        self.depth += 1;
+    }
+
+    /// The segments kept, outermost first. If any were dropped, they were between the first and
+    /// the last `IDL_PATH_ENDS`.
+    pub fn segments(&self) -> &[IdlPathSegment] {
+        &self.segments[..min(self.depth as usize, 2 * IDL_PATH_ENDS)]
+    }
+}
+
+impl fmt::Display for IdlPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.segments().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" / ")?;
+            }
+            if i == IDL_PATH_ENDS && self.depth as usize > 2 * IDL_PATH_ENDS {
+                write!(f, "({} more) / ", self.depth as usize - 2 * IDL_PATH_ENDS)?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// The start of the message last passed to `parse_idl_header`, error offsets are relative to it
+static mut IDL_MESSAGE_START: *mut u8 = core::ptr::null_mut();
+
+/// The argument being decoded, as set by the generated code with `idl_set_arg`. Errors of
+/// `skip_any` and `find_field` start their paths with it.
+static mut IDL_ARG: Option<u32> = None;
+
+#[no_mangle]
+pub unsafe extern "C" fn idl_set_arg(arg: u32) {
+    IDL_ARG = Some(arg);
+}
+
+unsafe fn arg_path() -> IdlPath {
+    let mut path = IdlPath::EMPTY;
+    if let Some(arg) = IDL_ARG {
+        path.push(IdlPathSegment::Arg(arg));
+    }
+    path
+}
+
+impl IdlError {
+    pub unsafe fn trap(self) -> ! {
+        trap_with_error(self)
+    }
+}
+
+impl IdlErrorAt {
+    pub unsafe fn trap(self) -> ! {
+        trap_with_error(self)
+    }
+}
+
+unsafe fn trap_with_error<E: fmt::Display>(error: E) -> ! {
+    // Stays within the buffer of `trap_with_prefix`, with the prefix
+    let mut buf = [0u8; 480];
+    let mut fmt = WriteBuf::new(&mut buf);
+    let _ = fmt::write(&mut fmt, format_args!("{}", error));
+    idl_trap_with(fmt.as_str())
+}
+
+unsafe fn or_trap<T, E: fmt::Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => trap_with_error(err),
+    }
+}
+
+//
+// Reading the message. Like the functions of `buf` and `leb128`, but these return errors instead
+// of trapping.
+//
+
+unsafe fn remaining(buf: *mut Buf) -> usize {
+    ((*buf).end as usize).saturating_sub((*buf).ptr as usize)
+}
+
+unsafe fn read_u8(buf: *mut Buf) -> IdlResult<u8> {
+    if remaining(buf) == 0 {
+        return Err(IdlError::ByteReadOutOfBuffer);
+    }
+    Ok(read_byte(buf))
+}
+
+/// Read a little-endian word
+unsafe fn read_u32(buf: *mut Buf) -> IdlResult<u32> {
+    if remaining(buf) < 4 {
+        return Err(IdlError::WordReadOutOfBuffer);
+    }
+    let p = (*buf).ptr;
+    let word = u32::from_le_bytes([*p, *p.add(1), *p.add(2), *p.add(3)]);
+    (*buf).ptr = p.add(4);
+    Ok(word)
+}
+
+unsafe fn advance(buf: *mut Buf, n: u32) -> IdlResult<()> {
+    if remaining(buf) < n as usize {
+        return Err(IdlError::AdvanceOutOfBuffer);
+    }
+    (*buf).ptr = (*buf).ptr.add(n as usize);
+    Ok(())
+}
+
+/// Returns the number of bytes of the (s)leb128 number at the start of the buffer, checking that
+/// it ends in the buffer
+unsafe fn leb128_len(buf: *mut Buf) -> IdlResult<u32> {
+    let mut len = 0;
+    loop {
+        if len as usize == remaining(buf) {
+            return Err(IdlError::ByteReadOutOfBuffer);
+        }
+        if *(*buf).ptr.add(len as usize) & 0b1000_0000 == 0 {
+            return Ok(len + 1);
+        }
+        len += 1;
+    }
+}
+
+unsafe fn read_leb128(buf: *mut Buf) -> IdlResult<u32> {
+    leb128_len(buf)?;
+    leb128_decode_checked(buf).ok_or(IdlError::Leb128Overflow)
+}
+
+unsafe fn read_sleb128(buf: *mut Buf) -> IdlResult<i32> {
+    leb128_len(buf)?;
+    sleb128_decode_checked(buf).ok_or(IdlError::Leb128Overflow)
+}
+
+/// Can also be used for sleb
+unsafe fn skip_leb128(buf: *mut Buf) -> IdlResult<()> {
+    let len = leb128_len(buf)?;
+    advance(buf, len)
+}
+
+/// Reads a text (or method name), returns its length and start
+unsafe fn read_text(buf: *mut Buf) -> IdlResult<(u32, *mut u8)> {
+    let len = read_leb128(buf)?;
+    let p = (*buf).ptr;
+    advance(buf, len)?; // advance first; does the bounds check
+    if !utf8_valid(p as *const _, len) {
+        return Err(IdlError::NotUtf8);
+    }
+    Ok((len, p))
 }
 
 unsafe fn is_primitive_type(ty: i32) -> bool {
@@ -73,24 +405,67 @@ unsafe fn utf8_cmp(len1: u32, p1: *mut u8, len2: u32, p2: *mut u8) -> i32 {
     }
 }
 
-unsafe fn check_typearg(ty: i32, n_types: u32) {
+//
+// Decoding quota
+//
+
+/// Default of `IDL_DECODING_QUOTA`. Type table entries and type references take at least one
+/// byte each, so the headers of messages of the maximal size (2MiB) stay well within this.
+const DEFAULT_DECODING_QUOTA: u64 = 1 << 22;
+
+/// Work earned by skipping values for every byte they take. Skipping a value nested in up to this
+/// many others (e.g. the `nat8` in `vec record { record { nat8 } }`) pays for itself.
+const DECODING_QUOTA_PER_BYTE: u64 = 8;
+
+/// Work allowed for decoding one message: every type table entry and type reference in the
+/// header, every skipped value and every step of the subtype check is charged one unit, and
+/// skipping earns `DECODING_QUOTA_PER_BYTE` units for every byte consumed. This bounds the cost
+/// of small messages that describe huge values, e.g. `vec null` with a huge count, while the cost
+/// of big messages may grow with their size. Set by `moc --idl-decoding-quota` at start-up.
+static mut IDL_DECODING_QUOTA: u64 = DEFAULT_DECODING_QUOTA;
+
+/// Work left for decoding the current message, reset by `parse_idl_header`
+static mut IDL_WORK_LEFT: u64 = DEFAULT_DECODING_QUOTA;
+
+#[no_mangle]
+pub unsafe extern "C" fn idl_set_decoding_quota(quota: u64) {
+    IDL_DECODING_QUOTA = quota;
+}
+
+unsafe fn charge(work: u64) -> IdlResult<()> {
+    if IDL_WORK_LEFT < work {
+        return Err(IdlError::QuotaExceeded);
+    }
+    IDL_WORK_LEFT -= work;
+    Ok(())
+}
+
+/// Credit the work for skipping `bytes` bytes of values
+unsafe fn earn(bytes: usize) {
+    IDL_WORK_LEFT = IDL_WORK_LEFT.saturating_add(bytes as u64 * DECODING_QUOTA_PER_BYTE);
+}
+
+unsafe fn check_typearg(ty: i32, n_types: u32) -> IdlResult<()> {
+    charge(1)?;
     // Arguments to type constructors can be primitive types or type indices
     if !(is_primitive_type(ty) || (ty >= 0 && (ty as u32) < n_types)) {
-        idl_trap_with("invalid type argument");
+        return Err(IdlError::InvalidTypeArgument);
     }
+    Ok(())
 }
 
-unsafe fn parse_fields(buf: *mut Buf, n_types: u32) {
+unsafe fn parse_fields(buf: *mut Buf, n_types: u32) -> IdlResult<()> {
     let mut next_valid = 0;
-    for n in (1..=leb128_decode(buf)).rev() {
-        let tag = leb128_decode(buf);
+    for n in (1..=read_leb128(buf)?).rev() {
+        let tag = read_leb128(buf)?;
         if (tag < next_valid) || (tag == 0xFFFFFFFF && n > 1) {
-            idl_trap_with("variant or record tag out of order");
+            return Err(IdlError::TagsOutOfOrder);
         }
-        next_valid = tag + 1;
-        let t = sleb128_decode(buf);
-        check_typearg(t, n_types);
+        next_valid = tag.wrapping_add(1); // only wraps for the last field
+        let t = read_sleb128(buf)?;
+        check_typearg(t, n_types)?;
     }
+    Ok(())
 }
 
 // NB. This function assumes the allocation does not need to survive GC
@@ -115,8 +490,11 @@ unsafe fn alloc<M: Memory>(mem: &mut M, size: Words<u32>) -> *mut u8 {
 ///
 /// * returns a pointer to the beginning of the list of main types
 ///   (again via pointer argument, for lack of multi-value returns in C ABI)
+///
+/// * starts a new decoding quota (see `IDL_DECODING_QUOTA`), which is unlimited for the extended
+///   format of stable variables
 #[ic_mem_fn]
-unsafe fn parse_idl_header<M: Memory>(
+pub unsafe fn parse_idl_header<M: Memory>(
     mem: &mut M,
     extended: bool,
     buf: *mut Buf,
@@ -124,23 +502,77 @@ unsafe fn parse_idl_header<M: Memory>(
     typtbl_size_out: *mut u32,
     main_types_out: *mut *mut u8,
 ) {
+    or_trap(parse_idl_header_checked(
+        mem,
+        extended,
+        buf,
+        typtbl_out,
+        typtbl_size_out,
+        main_types_out,
+    ))
+}
+
+/// Like `parse_idl_header`, but returns an error instead of trapping. On errors, `buf` is left
+/// where the error is.
+pub unsafe fn parse_idl_header_checked<M: Memory>(
+    mem: &mut M,
+    extended: bool,
+    buf: *mut Buf,
+    typtbl_out: *mut *mut *mut u8,
+    typtbl_size_out: *mut u32,
+    main_types_out: *mut *mut u8,
+) -> Result<(), IdlErrorAt> {
+    IDL_MESSAGE_START = (*buf).ptr;
+    IDL_ARG = None;
+
+    let mut type_index = None;
+    let mut path = IdlPath::EMPTY;
+    parse_header(
+        mem,
+        extended,
+        buf,
+        typtbl_out,
+        typtbl_size_out,
+        main_types_out,
+        &mut type_index,
+        &mut path,
+    )
+    .map_err(|error| IdlErrorAt::new(error, (*buf).ptr, type_index, path))
+}
+
+/// `parse_idl_header_checked`, setting `type_index` to the type table entry and `path` to the
+/// argument it is looking at
+unsafe fn parse_header<M: Memory>(
+    mem: &mut M,
+    extended: bool,
+    buf: *mut Buf,
+    typtbl_out: *mut *mut *mut u8,
+    typtbl_size_out: *mut u32,
+    main_types_out: *mut *mut u8,
+    type_index: &mut Option<u32>,
+    path: &mut IdlPath,
+) -> IdlResult<()> {
+    IDL_WORK_LEFT = if extended {
+        u64::MAX
+    } else {
+        IDL_DECODING_QUOTA
+    };
+
     if (*buf).ptr == (*buf).end {
-        idl_trap_with(
-            "empty input. Expected Candid-encoded argument, but received a zero-length argument",
-        );
+        return Err(IdlError::EmptyInput);
     }
 
     // Magic bytes (DIDL)
-    if read_word(buf) != 0x4C444944 {
-        idl_trap_with("missing magic bytes");
+    if read_u32(buf)? != 0x4C444944 {
+        return Err(IdlError::MissingMagic);
     }
 
     // Create a table for the type description
-    let n_types = leb128_decode(buf);
+    let n_types = read_leb128(buf)?;
 
     // Early sanity check
-    if (*buf).ptr.add(n_types as usize) >= (*buf).end {
-        idl_trap_with("too many types");
+    if n_types as usize >= remaining(buf) {
+        return Err(IdlError::TooManyTypes);
     }
 
     // Let the caller know about the table size
@@ -151,62 +583,51 @@ unsafe fn parse_idl_header<M: Memory>(
 
     // Go through the table
     for i in 0..n_types {
+        *type_index = Some(i);
+        charge(1)?;
         *typtbl.add(i as usize) = (*buf).ptr;
 
-        let ty = sleb128_decode(buf);
+        let ty = read_sleb128(buf)?;
 
         if extended && ty == IDL_CON_alias {
             // internal
             // See Note [mutable stable values] in codegen/compile.ml
-            let t = sleb128_decode(buf);
-            check_typearg(t, n_types);
+            let t = read_sleb128(buf)?;
+            check_typearg(t, n_types)?;
         } else if ty >= 0 {
-            idl_trap_with("illegal type table"); // illegal
+            return Err(IdlError::IllegalTypeTable); // illegal
         } else if is_primitive_type(ty) {
             // illegal
-            idl_trap_with("primitive type in type table");
+            return Err(IdlError::PrimitiveInTypeTable);
         } else if ty == IDL_CON_opt {
-            let t = sleb128_decode(buf);
-            check_typearg(t, n_types);
+            let t = read_sleb128(buf)?;
+            check_typearg(t, n_types)?;
         } else if ty == IDL_CON_vec {
-            let t = sleb128_decode(buf);
-            check_typearg(t, n_types);
+            let t = read_sleb128(buf)?;
+            check_typearg(t, n_types)?;
         } else if ty == IDL_CON_record {
-            parse_fields(buf, n_types);
+            parse_fields(buf, n_types)?;
         } else if ty == IDL_CON_variant {
-            parse_fields(buf, n_types);
+            parse_fields(buf, n_types)?;
         } else if ty == IDL_CON_func {
             // Arg types
-            for _ in 0..leb128_decode(buf) {
-                let t = sleb128_decode(buf);
-                check_typearg(t, n_types);
+            for _ in 0..read_leb128(buf)? {
+                let t = read_sleb128(buf)?;
+                check_typearg(t, n_types)?;
             }
             // Ret types
-            for _ in 0..leb128_decode(buf) {
-                let t = sleb128_decode(buf);
-                check_typearg(t, n_types);
+            for _ in 0..read_leb128(buf)? {
+                let t = read_sleb128(buf)?;
+                check_typearg(t, n_types)?;
             }
             // Annotations
-            for _ in 0..leb128_decode(buf) {
-                let a = read_byte(buf);
-                if !(1 <= a && a <= 2) {
-                    idl_trap_with("func annotation not within 1..2");
-                }
-                // TODO: shouldn't we also check
-                // * 1 (query) or 2 (oneway), but not both
-                // * 2 -> |Ret types| == 0
-                // c.f. https://github.com/dfinity/candid/issues/318
-                // NB: if this code changes, change sub type check below accordingly
-            }
+            read_func_annotations(buf)?;
         } else if ty == IDL_CON_service {
             let mut last_len: u32 = 0 as u32;
             let mut last_p = core::ptr::null_mut();
-            for _ in 0..leb128_decode(buf) {
-                // Name
-                let (len, p) = leb128_decode_ptr(buf);
-                buf.advance(len);
-                // Method names must be valid unicode
-                utf8_validate(p as *const _, len);
+            for _ in 0..read_leb128(buf)? {
+                // Name, must be valid unicode
+                let (len, p) = read_text(buf)?;
                 // Method names must be in order
                 if last_p != core::ptr::null_mut() {
                     let cmp = libc::memcmp(
@@ -215,20 +636,20 @@ unsafe fn parse_idl_header<M: Memory>(
                         min(last_len, len) as usize,
                     );
                     if cmp > 0 || (cmp == 0 && last_len >= len) {
-                        idl_trap_with("service method names out of order");
+                        return Err(IdlError::MethodNamesOutOfOrder);
                     }
                 }
                 last_len = len;
                 last_p = p;
 
                 // Type
-                let t = sleb128_decode(buf);
-                check_typearg(t, n_types);
+                let t = read_sleb128(buf)?;
+                check_typearg(t, n_types)?;
             }
         } else {
             // Future type
-            let n = leb128_decode(buf);
-            buf.advance(n);
+            let n = read_leb128(buf)?;
+            advance(buf, n)?;
         }
     }
 
@@ -236,218 +657,489 @@ unsafe fn parse_idl_header<M: Memory>(
     // and validate that all service method types are really function types
     // (We could not do that in the first run because of possible forward
     // references
+    // This reads with the main buf, so that errors are located, and moves it back afterwards.
+    let table_end = (*buf).ptr;
     for i in 0..n_types {
-        // do not modify the main buf
-        let mut tmp_buf = Buf {
-            end: (*buf).end,
-            ptr: *typtbl.add(i as usize),
-        };
+        *type_index = Some(i);
+        (*buf).ptr = *typtbl.add(i as usize);
 
-        let ty = sleb128_decode(&mut tmp_buf);
+        let ty = read_sleb128(buf)?;
         if ty == IDL_CON_service {
-            for _ in 0..leb128_decode(&mut tmp_buf) {
+            for _ in 0..read_leb128(buf)? {
                 // Name
-                let len = leb128_decode(&mut tmp_buf);
-                Buf::advance(&mut tmp_buf, len);
+                let len = read_leb128(buf)?;
+                advance(buf, len)?;
                 // Type
-                let t = sleb128_decode(&mut tmp_buf);
+                let t = read_sleb128(buf)?;
                 if !(t >= 0 && (t as u32) < n_types) {
-                    idl_trap_with("service method arg not a constructor type");
+                    return Err(IdlError::MethodNotConstructor);
                 }
-                let mut tmp_buf2 = Buf {
+                let mut tmp_buf = Buf {
                     end: (*buf).end,
                     ptr: *typtbl.add(t as usize),
                 };
-                let mty = sleb128_decode(&mut tmp_buf2);
+                let mty = read_sleb128(&mut tmp_buf)?;
                 if mty != IDL_CON_func {
-                    idl_trap_with("service method arg not a function type");
+                    return Err(IdlError::MethodNotFunc);
                 }
             }
         }
     }
+    (*buf).ptr = table_end;
+    *type_index = None;
 
     // Now read the main types
     *main_types_out = (*buf).ptr;
-    for _ in 0..leb128_decode(buf) {
-        let t = sleb128_decode(buf);
-        check_typearg(t, n_types);
+    for i in 0..read_leb128(buf)? {
+        *path = IdlPath::EMPTY;
+        path.push(IdlPathSegment::Arg(i));
+        let t = read_sleb128(buf)?;
+        check_typearg(t, n_types)?;
     }
 
     *typtbl_out = typtbl;
+    Ok(())
 }
 
 // used for opt, bool, references...
-unsafe fn read_byte_tag(buf: *mut Buf) -> u8 {
-    let b = read_byte(buf);
+unsafe fn read_byte_tag(buf: *mut Buf) -> IdlResult<u8> {
+    let b = read_u8(buf)?;
     if b > 1 {
-        idl_trap_with("skip_any: byte tag not 0 or 1");
+        return Err(IdlError::InvalidByteTag);
     }
-    b
+    Ok(b)
 }
 
-unsafe fn skip_blob(buf: *mut Buf) {
-    let len = leb128_decode(buf);
-    buf.advance(len);
+/// Reads the annotations of a function type, as a set with bit `a` for annotation `a`.
+/// Unlike future types, annotations have no extension mechanism in the spec: a message with an
+/// annotation that we do not know cannot be understood, and is rejected.
+unsafe fn read_func_annotations(buf: *mut Buf) -> IdlResult<u8> {
+    // TODO: shouldn't we also check
+    // * only one of query, oneway and composite_query
+    // * oneway -> |Ret types| == 0
+    // c.f. https://github.com/dfinity/candid/issues/318
+    let mut annotations = 0;
+    for _ in 0..read_leb128(buf)? {
+        let a = read_u8(buf)?;
+        if !(IDL_FUNC_ANNOT_query..=IDL_FUNC_ANNOT_composite_query).contains(&a) {
+            return Err(IdlError::InvalidFuncAnnotation);
+        }
+        annotations |= 1 << a;
+    }
+    Ok(annotations)
+}
+
+/// Skips a value of a future type. Its references would be in the reference table, which
+/// messages on the IC do not have, so a value with references is rejected.
+unsafe fn skip_future(buf: *mut Buf) -> IdlResult<()> {
+    let n_data = read_leb128(buf)?;
+    let n_ref = read_leb128(buf)?;
+    advance(buf, n_data)?;
+    if n_ref > 0 {
+        return Err(IdlError::FutureReferences);
+    }
+    Ok(())
+}
+
+unsafe fn skip_blob(buf: *mut Buf) -> IdlResult<()> {
+    let len = read_leb128(buf)?;
+    advance(buf, len)
+}
+
+unsafe fn skip_text(buf: *mut Buf) -> IdlResult<()> {
+    read_text(buf)?;
+    Ok(())
+}
+
+/// Memory for the stack of `skip_any`, in bytes. Values nested more deeply trap. The conversions
+/// to and from JSON (see `json`) use the same budget. Set by `moc --idl-skip-budget` at start-up.
+static mut SKIP_ANY_BUDGET: u32 = 1 << 20;
+
+/// Number of frames of the stack of `skip_any` that are allocated on the (Rust) stack. Deeper
+/// values spill to the heap.
+const SKIP_ANY_INLINE_FRAMES: u32 = 32;
+
+#[no_mangle]
+pub unsafe extern "C" fn skip_any_set_budget(bytes: u32) {
+    SKIP_ANY_BUDGET = bytes;
 }
 
-unsafe fn skip_text(buf: *mut Buf) {
-    let (len, p) = leb128_decode_ptr(buf);
-    buf.advance(len); // advance first; does the bounds check
-    utf8_validate(p as *const _, len);
+/// A value that `skip_any` is in the middle of
+#[derive(Clone, Copy)]
+enum SkipFrame {
+    /// A record of type `t` that started at `start` in the data, with `fields` fields left, the
+    /// next one at `tb` in the type table. `tag` is the field being skipped.
+    Record {
+        t: i32,
+        start: *mut u8,
+        tb: *mut u8,
+        fields: u32,
+        tag: u32,
+    },
+    /// A vector of type `t` with elements of type `it`, with `count` elements left after the
+    /// one at `index`. `first` is where the first element started, until the second one starts,
+    /// and null afterwards.
+    Vec {
+        t: i32,
+        it: i32,
+        first: *mut u8,
+        count: u32,
+        index: u32,
+    },
 }
 
-unsafe fn skip_any_vec(buf: *mut Buf, typtbl: *mut *mut u8, t: i32, count: u32) {
-    if count == 0 {
-        return;
+const EMPTY_SKIP_FRAME: SkipFrame = SkipFrame::Vec {
+    t: 0,
+    it: 0,
+    first: core::ptr::null_mut(),
+    count: 0,
+    index: 0,
+};
+
+/// A stack of frames, starting in `inline` frames on the (Rust) stack, and spilling to the heap
+/// up to `SKIP_ANY_BUDGET` bytes
+struct Stack<F> {
+    frames: *mut F,
+    len: u32,
+    capacity: u32,
+}
+
+type SkipStack = Stack<SkipFrame>;
+
+impl<F: Copy> Stack<F> {
+    fn new(inline: &mut [F]) -> Self {
+        Stack {
+            frames: inline.as_mut_ptr(),
+            len: 0,
+            capacity: inline.len() as u32,
+        }
     }
-    let ptr_before = (*buf).ptr;
-    skip_any(buf, typtbl, t, 0);
-    let ptr_after = (*buf).ptr;
-    if ptr_after == ptr_before {
-        // this looks like a vec null bomb, or equivalent, where skip_any
-        // makes no progress. No point in calling it over and over again.
-        // (This is easier to detect this way than by analyzing the type table,
-        // where we’d have to chase single-field-records.)
-        return;
+
+    unsafe fn push<M: Memory>(&mut self, mem: &mut M, frame: F) -> IdlResult<()> {
+        if self.len == self.capacity {
+            let capacity = 2 * self.capacity;
+            let bytes = capacity as usize * core::mem::size_of::<F>();
+            if bytes > SKIP_ANY_BUDGET as usize {
+                return Err(IdlError::NestedTooDeeply { levels: self.len });
+            }
+            let frames = alloc(mem, Bytes(bytes as u32).to_words()) as *mut F;
+            core::ptr::copy_nonoverlapping(self.frames, frames, self.len as usize);
+            self.frames = frames;
+            self.capacity = capacity;
+        }
+        *self.frames.add(self.len as usize) = frame;
+        self.len += 1;
+        Ok(())
+    }
+
+    unsafe fn top(&mut self) -> Option<&mut F> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(&mut *self.frames.add(self.len as usize - 1))
+        }
     }
-    for _ in 1..count {
-        skip_any(buf, typtbl, t, 0);
+
+    unsafe fn pop(&mut self) {
+        self.len -= 1;
+    }
+
+    /// The frames, from the outermost
+    unsafe fn frames(&self) -> &[F] {
+        core::slice::from_raw_parts(self.frames, self.len as usize)
+    }
+}
+
+impl SkipStack {
+    /// Adds the values being skipped to `path`
+    unsafe fn add_to_path(&self, path: &mut IdlPath) {
+        for i in 0..self.len {
+            path.push(match *self.frames.add(i as usize) {
+                SkipFrame::Record { tag, .. } => IdlPathSegment::Field(tag),
+                SkipFrame::Vec { index, .. } => IdlPathSegment::Element(index),
+            });
+        }
+    }
+
+    /// Fails if a record of type `t` is already being skipped, and no data was consumed since it
+    /// started at `ptr`. Its fields would then recurse forever.
+    unsafe fn check_record_cycle(&self, t: i32, ptr: *mut u8) -> IdlResult<()> {
+        // Frames are pushed in data order, so only the top ones can start at `ptr`
+        for i in (0..self.len).rev() {
+            match *self.frames.add(i as usize) {
+                SkipFrame::Record { t: t2, start, .. } if start == ptr => {
+                    if t2 == t {
+                        return Err(IdlError::RecordCycle { t });
+                    }
+                }
+                _ => break,
+            }
+        }
+        Ok(())
     }
 }
 
 // Assumes buf is the encoding of type t, and fast-forwards past that
 // Assumes all type references in the typtbl are already checked
 //
-// This is currently implemented recursively, but we could
-// do this in a loop (by maintaining a stack of the t arguments)
-#[no_mangle]
-unsafe extern "C" fn skip_any(buf: *mut Buf, typtbl: *mut *mut u8, t: i32, depth: i32) {
-    if depth > 100 {
-        idl_trap_with("skip_any: too deeply nested record");
+// Nested values are skipped with an explicit stack, limited by `SKIP_ANY_BUDGET`
+#[ic_mem_fn]
+pub unsafe fn skip_any<M: Memory>(mem: &mut M, buf: *mut Buf, typtbl: *mut *mut u8, t: i32) {
+    or_trap(skip_any_checked(mem, buf, typtbl, t))
+}
+
+/// Like `skip_any`, but returns an error instead of trapping
+pub unsafe fn skip_any_checked<M: Memory>(
+    mem: &mut M,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    t: i32,
+) -> Result<(), IdlErrorAt> {
+    skip_at(mem, buf, typtbl, t, arg_path())
+}
+
+/// `skip_any_checked` of a value at `path`
+unsafe fn skip_at<M: Memory>(
+    mem: &mut M,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    t: i32,
+    mut path: IdlPath,
+) -> Result<(), IdlErrorAt> {
+    let mut inline_frames = [EMPTY_SKIP_FRAME; SKIP_ANY_INLINE_FRAMES as usize];
+    let mut stack = SkipStack::new(&mut inline_frames);
+
+    let mut current = t;
+    skip_values(mem, &mut stack, buf, typtbl, t, &mut current).map_err(|error| {
+        stack.add_to_path(&mut path);
+        let type_index = if current >= 0 {
+            Some(current as u32)
+        } else {
+            None
+        };
+        IdlErrorAt::new(error, (*buf).ptr, type_index, path)
+    })
+}
+
+/// The loop of `skip_any`, setting `current` to the innermost type in the type table being
+/// skipped
+unsafe fn skip_values<M: Memory>(
+    mem: &mut M,
+    stack: &mut SkipStack,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    t: i32,
+    current: &mut i32,
+) -> IdlResult<()> {
+    let mut next = Some(t);
+    loop {
+        if let Some(t) = next {
+            if t >= 0 {
+                *current = t;
+            }
+            let start = (*buf).ptr;
+            next = skip_one(mem, stack, buf, typtbl, t)?;
+            earn((*buf).ptr as usize - start as usize);
+            continue;
+        }
+
+        // Continue with the innermost unfinished value
+        let mut finished = false;
+        match stack.top() {
+            None => return Ok(()),
+            Some(SkipFrame::Record {
+                t, tb, fields, tag, ..
+            }) => {
+                *current = *t;
+                if *fields == 0 {
+                    finished = true;
+                } else {
+                    let mut b = Buf {
+                        ptr: *tb,
+                        end: (*buf).end,
+                    };
+                    *tag = read_leb128(&mut b)?;
+                    next = Some(read_sleb128(&mut b)?);
+                    *tb = b.ptr;
+                    *fields -= 1;
+                }
+            }
+            Some(SkipFrame::Vec {
+                t,
+                it,
+                first,
+                count,
+                index,
+            }) => {
+                *current = *t;
+                if *count == 0 {
+                    finished = true;
+                } else if (*buf).ptr == *first {
+                    // this looks like a vec null bomb, or equivalent, where skipping an element
+                    // makes no progress. No point in doing it over and over again.
+                    // (This is easier to detect this way than by analyzing the type table,
+                    // where we’d have to chase single-field-records.)
+                    // The elements are charged nevertheless, as decoding them would cost.
+                    charge(*count as u64)?;
+                    finished = true;
+                } else {
+                    *first = core::ptr::null_mut();
+                    *count -= 1;
+                    *index += 1;
+                    next = Some(*it);
+                }
+            }
+        }
+        if finished {
+            stack.pop();
+        }
     }
+}
 
+/// Skips a value of type `t`, or starts skipping it. Values with one nested value return its
+/// type. Records and vectors push a frame for their nested values.
+unsafe fn skip_one<M: Memory>(
+    mem: &mut M,
+    stack: &mut SkipStack,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    t: i32,
+) -> IdlResult<Option<i32>> {
+    charge(1)?;
     if t < 0 {
         // Primitive type
         match t {
             IDL_PRIM_null | IDL_PRIM_reserved => {}
             IDL_PRIM_bool => {
-                read_byte_tag(buf);
+                read_byte_tag(buf)?;
             }
             IDL_PRIM_nat | IDL_PRIM_int => {
-                skip_leb128(buf);
+                skip_leb128(buf)?;
             }
             IDL_PRIM_nat8 | IDL_PRIM_int8 => {
-                buf.advance(1);
+                advance(buf, 1)?;
             }
             IDL_PRIM_nat16 | IDL_PRIM_int16 => {
-                buf.advance(2);
+                advance(buf, 2)?;
             }
             IDL_PRIM_nat32 | IDL_PRIM_int32 | IDL_PRIM_float32 => {
-                buf.advance(4);
+                advance(buf, 4)?;
             }
             IDL_PRIM_nat64 | IDL_PRIM_int64 | IDL_PRIM_float64 => {
-                buf.advance(8);
+                advance(buf, 8)?;
             }
-            IDL_PRIM_text => skip_text(buf),
+            IDL_PRIM_text => skip_text(buf)?,
             IDL_PRIM_empty => {
-                idl_trap_with("skip_any: encountered empty");
+                return Err(IdlError::SkipEmpty);
             }
             IDL_REF_principal => {
-                if read_byte_tag(buf) != 0 {
-                    skip_blob(buf);
+                if read_byte_tag(buf)? != 0 {
+                    skip_blob(buf)?;
                 }
             }
             _ => {
-                idl_trap_with("skip_any: unknown prim");
+                return Err(IdlError::UnknownPrim);
             }
         }
-    } else {
-        // t >= 0
-        let mut tb = Buf {
-            ptr: *typtbl.add(t as usize),
-            end: (*buf).end,
-        };
-        let tc = sleb128_decode(&mut tb);
-        match tc {
-            IDL_CON_opt => {
-                let it = sleb128_decode(&mut tb);
-                if read_byte_tag(buf) != 0 {
-                    skip_any(buf, typtbl, it, 0);
-                }
+        return Ok(None);
+    }
+
+    // t >= 0
+    let mut tb = Buf {
+        ptr: *typtbl.add(t as usize),
+        end: (*buf).end,
+    };
+    let tc = read_sleb128(&mut tb)?;
+    match tc {
+        IDL_CON_opt => {
+            let it = read_sleb128(&mut tb)?;
+            if read_byte_tag(buf)? != 0 {
+                return Ok(Some(it));
             }
-            IDL_CON_vec => {
-                let it = sleb128_decode(&mut tb);
-                let count = leb128_decode(buf);
-                skip_any_vec(buf, typtbl, it, count);
-            }
-            IDL_CON_record => {
-                for _ in 0..leb128_decode(&mut tb) {
-                    skip_leb128(&mut tb);
-                    let it = sleb128_decode(&mut tb);
-                    // This is just a quick check; we should be keeping
-                    // track of all enclosing records to detect larger loops
-                    if it == t {
-                        idl_trap_with("skip_any: recursive record");
-                    }
-                    skip_any(buf, typtbl, it, depth + 1);
-                }
+        }
+        IDL_CON_vec => {
+            let it = read_sleb128(&mut tb)?;
+            let count = read_leb128(buf)?;
+            if count > 0 {
+                let frame = SkipFrame::Vec {
+                    t,
+                    it,
+                    first: (*buf).ptr,
+                    count: count - 1,
+                    index: 0,
+                };
+                stack.push(mem, frame)?;
+                return Ok(Some(it));
             }
-            IDL_CON_variant => {
-                let n = leb128_decode(&mut tb);
-                let i = leb128_decode(buf);
-                if i >= n {
-                    idl_trap_with("skip_any: variant tag too large");
-                }
-                for _ in 0..i {
-                    skip_leb128(&mut tb);
-                    skip_leb128(&mut tb);
-                }
-                skip_leb128(&mut tb);
-                let it = sleb128_decode(&mut tb);
-                skip_any(buf, typtbl, it, 0);
+        }
+        IDL_CON_record => {
+            let fields = read_leb128(&mut tb)?;
+            if fields > 0 {
+                stack.check_record_cycle(t, (*buf).ptr)?;
+                let frame = SkipFrame::Record {
+                    t,
+                    start: (*buf).ptr,
+                    tb: tb.ptr,
+                    fields,
+                    tag: 0,
+                };
+                stack.push(mem, frame)?;
             }
-            IDL_CON_func => {
-                if read_byte_tag(buf) == 0 {
-                    idl_trap_with("skip_any: skipping references");
-                } else {
-                    if read_byte_tag(buf) == 0 {
-                        idl_trap_with("skip_any: skipping references");
-                    } else {
-                        skip_blob(buf)
-                    }
-                    skip_text(buf)
-                }
+        }
+        IDL_CON_variant => {
+            let n = read_leb128(&mut tb)?;
+            let i = read_leb128(buf)?;
+            if i >= n {
+                return Err(IdlError::VariantTagTooLarge {
+                    tag: i,
+                    t,
+                    cases: n,
+                });
             }
-            IDL_CON_service => {
-                if read_byte_tag(buf) == 0 {
-                    idl_trap_with("skip_any: skipping references");
-                } else {
-                    skip_blob(buf)
-                }
+            for _ in 0..i {
+                skip_leb128(&mut tb)?;
+                skip_leb128(&mut tb)?;
             }
-            IDL_CON_alias => {
-                // See Note [mutable stable values] in codegen/compile.ml
-                let it = sleb128_decode(&mut tb);
-                let tag = read_byte_tag(buf);
-                if tag == 0 {
-                    buf.advance(8);
-                    // this is the contents (not a reference)
-                    skip_any(buf, typtbl, it, 0);
+            skip_leb128(&mut tb)?;
+            return Ok(Some(read_sleb128(&mut tb)?));
+        }
+        IDL_CON_func => {
+            if read_byte_tag(buf)? == 0 {
+                return Err(IdlError::SkipReferences);
+            } else {
+                if read_byte_tag(buf)? == 0 {
+                    return Err(IdlError::SkipReferences);
                 } else {
-                    buf.advance(4);
+                    skip_blob(buf)?
                 }
+                skip_text(buf)?
             }
-            _ => {
-                // Future type
-                let n_data = leb128_decode(buf);
-                let n_ref = leb128_decode(buf);
-                buf.advance(n_data);
-                if n_ref > 0 {
-                    idl_trap_with("skip_any: skipping references");
-                }
+        }
+        IDL_CON_service => {
+            if read_byte_tag(buf)? == 0 {
+                return Err(IdlError::SkipReferences);
+            } else {
+                skip_blob(buf)?
             }
         }
+        IDL_CON_alias => {
+            // See Note [mutable stable values] in codegen/compile.ml
+            let it = read_sleb128(&mut tb)?;
+            let tag = read_byte_tag(buf)?;
+            if tag == 0 {
+                advance(buf, 8)?;
+                // this is the contents (not a reference)
+                return Ok(Some(it));
+            } else {
+                advance(buf, 4)?;
+            }
+        }
+        _ => {
+            // Future type
+            skip_future(buf)?;
+        }
     }
+    Ok(None)
 }
 
 /*
@@ -477,47 +1169,312 @@ If the tag does not exist:
          or at the value past the record
   n:     the number of fields left, including the field pointed to by tb
 */
-#[no_mangle]
-unsafe extern "C" fn find_field(
+#[ic_mem_fn]
+pub unsafe fn find_field<M: Memory>(
+    mem: &mut M,
     tb: *mut Buf,
     buf: *mut Buf,
     typtbl: *mut *mut u8,
     tag: u32,
     n: *mut u8,
 ) -> u32 {
+    or_trap(find_field_checked(mem, tb, buf, typtbl, tag, n))
+}
+
+/// Like `find_field`, but returns an error instead of trapping
+pub unsafe fn find_field_checked<M: Memory>(
+    mem: &mut M,
+    tb: *mut Buf,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    tag: u32,
+    n: *mut u8,
+) -> Result<u32, IdlErrorAt> {
     while *n > 0 {
         let last_p = (*tb).ptr;
-        let this_tag = leb128_decode(tb);
+        let this_tag = read_leb128(tb).map_err(|error| at_field(error, buf, None))?;
         if this_tag < tag {
-            let it = sleb128_decode(tb);
-            skip_any(buf, typtbl, it, 0);
+            let it = read_sleb128(tb).map_err(|error| at_field(error, buf, Some(this_tag)))?;
+            skip_field(mem, buf, typtbl, it, this_tag)?;
             *n -= 1;
         } else if tag == this_tag {
             *n -= 1;
-            return 1;
+            return Ok(1);
         } else {
             // Rewind reading tag
             (*tb).ptr = last_p;
-            return 0;
+            return Ok(0);
         }
     }
 
-    0
+    Ok(0)
 }
 
-#[no_mangle]
-unsafe extern "C" fn skip_fields(tb: *mut Buf, buf: *mut Buf, typtbl: *mut *mut u8, n: *mut u8) {
+#[ic_mem_fn]
+pub unsafe fn skip_fields<M: Memory>(
+    mem: &mut M,
+    tb: *mut Buf,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    n: *mut u8,
+) {
+    or_trap(skip_fields_checked(mem, tb, buf, typtbl, n))
+}
+
+/// Like `skip_fields`, but returns an error instead of trapping
+pub unsafe fn skip_fields_checked<M: Memory>(
+    mem: &mut M,
+    tb: *mut Buf,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    n: *mut u8,
+) -> Result<(), IdlErrorAt> {
     while *n > 0 {
-        skip_leb128(tb);
-        let it = sleb128_decode(tb);
-        skip_any(buf, typtbl, it, 0);
+        let tag = read_leb128(tb).map_err(|error| at_field(error, buf, None))?;
+        let it = read_sleb128(tb).map_err(|error| at_field(error, buf, Some(tag)))?;
+        skip_field(mem, buf, typtbl, it, tag)?;
         *n -= 1;
     }
+    Ok(())
+}
+
+/// Skips the value of field `tag`, of type `t`
+unsafe fn skip_field<M: Memory>(
+    mem: &mut M,
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    t: i32,
+    tag: u32,
+) -> Result<(), IdlErrorAt> {
+    let mut path = arg_path();
+    path.push(IdlPathSegment::Field(tag));
+    skip_at(mem, buf, typtbl, t, path)
+}
+
+/// Locates an error of `find_field` or `skip_fields` reading the fields of a record type, with
+/// the data at `buf`
+unsafe fn at_field(error: IdlError, buf: *mut Buf, tag: Option<u32>) -> IdlErrorAt {
+    let mut path = arg_path();
+    if let Some(tag) = tag {
+        path.push(IdlPathSegment::Field(tag));
+    }
+    IdlErrorAt::new(error, (*buf).ptr, None, path)
+}
+
+//
+// Explaining subtype check failures
+//
+
+/// Whether `idl_sub` records why it returns false, see `idl_sub_failure`. The generated code
+/// does not enable this, as explaining disables the cache of `idl_sub_cached`.
+static mut IDL_SUB_EXPLAIN: bool = false;
+
+static mut IDL_SUB_FAILURE: Option<IdlSubFailure> = None;
+
+pub unsafe fn idl_sub_set_explain(explain: bool) {
+    IDL_SUB_EXPLAIN = explain;
+    IDL_SUB_FAILURE = None;
+}
+
+/// Why the last `idl_sub` returned false, if explaining is enabled with `idl_sub_set_explain`
+pub unsafe fn idl_sub_failure() -> Option<IdlSubFailure> {
+    IDL_SUB_FAILURE
+}
+
+/// The first pair of types that `idl_sub` found not to be related. As a negative result is
+/// returned right away, this is the innermost pair, not one of its parents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlSubFailure {
+    pub context: IdlSubContext,
+    /// The types, with their type constructors (for primitive types, the types themselves).
+    /// In function arguments, the types are from the second and the first type table.
+    pub t1: i32,
+    pub con1: i32,
+    pub t2: i32,
+    pub con2: i32,
+    pub reason: IdlSubReason,
+}
+
+/// Where a pair of types is in the pair of types containing it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlSubContext {
+    /// The types passed to `idl_sub`
+    Top,
+    Element,
+    Field(u32),
+    Case(u32),
+    Arg(u32),
+    Result(u32),
+    Method(IdlName),
+}
+
+/// Why a pair of types is not related
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlSubReason {
+    /// Different primitive types or type constructors
+    Incompatible,
+    /// The first function type has an argument that the second lacks, and it is not optional
+    MissingArg(u32),
+    /// The second function type has a result that the first lacks, and it is not optional
+    MissingResult(u32),
+    /// The function types have different annotations
+    Annotations,
+    /// The second record type has a field that the first lacks, and it is not optional
+    MissingField(u32),
+    /// The first variant type has a case that the second lacks
+    MissingCase(u32),
+    /// The second service type has a method that the first lacks
+    MissingMethod(IdlName),
+    /// An earlier check found the types not to be related
+    Disproved,
+}
+
+/// Length of the prefix of names kept by `IdlName`
+const IDL_NAME_PREFIX: usize = 24;
+
+/// A method name of a service type. Only a prefix is kept, as the type table may be gone when the
+/// name is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlName {
+    prefix: [u8; IDL_NAME_PREFIX],
+    len: u32,
+}
+
+impl IdlName {
+    unsafe fn new(p: *mut u8, len: u32) -> Self {
+        let mut prefix = [0u8; IDL_NAME_PREFIX];
+        let kept = min(len as usize, IDL_NAME_PREFIX);
+        core::ptr::copy_nonoverlapping(p, prefix.as_mut_ptr(), kept);
+        IdlName { prefix, len }
+    }
 }
 
-unsafe fn is_opt_reserved(typtbl: *mut *mut u8, end: *mut u8, t: i32) -> bool {
+impl fmt::Display for IdlName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kept = &self.prefix[..min(self.len as usize, IDL_NAME_PREFIX)];
+        // The prefix may end in the middle of a character
+        let text = match core::str::from_utf8(kept) {
+            Ok(text) => text,
+            Err(err) => core::str::from_utf8(&kept[..err.valid_up_to()]).unwrap(),
+        };
+        f.write_str(text)?;
+        if self.len as usize > IDL_NAME_PREFIX {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+fn type_name(con: i32) -> &'static str {
+    match con {
+        IDL_PRIM_null => "null",
+        IDL_PRIM_bool => "bool",
+        IDL_PRIM_nat => "nat",
+        IDL_PRIM_int => "int",
+        IDL_PRIM_nat8 => "nat8",
+        IDL_PRIM_nat16 => "nat16",
+        IDL_PRIM_nat32 => "nat32",
+        IDL_PRIM_nat64 => "nat64",
+        IDL_PRIM_int8 => "int8",
+        IDL_PRIM_int16 => "int16",
+        IDL_PRIM_int32 => "int32",
+        IDL_PRIM_int64 => "int64",
+        IDL_PRIM_float32 => "float32",
+        IDL_PRIM_float64 => "float64",
+        IDL_PRIM_text => "text",
+        IDL_PRIM_reserved => "reserved",
+        IDL_PRIM_empty => "empty",
+        IDL_CON_opt => "opt",
+        IDL_CON_vec => "vec",
+        IDL_CON_record => "record",
+        IDL_CON_variant => "variant",
+        IDL_CON_func => "func",
+        IDL_CON_service => "service",
+        IDL_REF_principal => "principal",
+        IDL_CON_alias => "alias",
+        _ => "future type",
+    }
+}
+
+impl IdlSubFailure {
+    fn fmt_type(f: &mut fmt::Formatter, t: i32, con: i32) -> fmt::Result {
+        f.write_str(type_name(con))?;
+        if t >= 0 {
+            write!(f, " (type {})", t)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for IdlSubFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.context {
+            IdlSubContext::Top => {}
+            IdlSubContext::Element => f.write_str("vec element : ")?,
+            IdlSubContext::Field(tag) => write!(f, "field {:#x} : ", tag)?,
+            IdlSubContext::Case(tag) => write!(f, "case {:#x} : ", tag)?,
+            IdlSubContext::Arg(i) => write!(f, "argument {} : ", i)?,
+            IdlSubContext::Result(i) => write!(f, "result {} : ", i)?,
+            IdlSubContext::Method(name) => write!(f, "method `{}` : ", name)?,
+        }
+        Self::fmt_type(f, self.t1, self.con1)?;
+        f.write_str(" is not a subtype of ")?;
+        Self::fmt_type(f, self.t2, self.con2)?;
+        match self.reason {
+            IdlSubReason::Incompatible => Ok(()),
+            IdlSubReason::MissingArg(i) => {
+                write!(f, ": argument {} is missing and not optional", i)
+            }
+            IdlSubReason::MissingResult(i) => {
+                write!(f, ": result {} is missing and not optional", i)
+            }
+            IdlSubReason::Annotations => f.write_str(": the annotations differ"),
+            IdlSubReason::MissingField(tag) => {
+                write!(f, ": field {:#x} is missing and not optional", tag)
+            }
+            IdlSubReason::MissingCase(tag) => write!(f, ": case {:#x} is missing", tag),
+            IdlSubReason::MissingMethod(name) => write!(f, ": method `{}` is missing", name),
+            IdlSubReason::Disproved => f.write_str(" (as checked before)"),
+        }
+    }
+}
+
+/// Records the failure of `sub`, unless one was recorded already
+unsafe fn explain(
+    context: IdlSubContext,
+    t1: i32,
+    con1: i32,
+    t2: i32,
+    con2: i32,
+    reason: IdlSubReason,
+) {
+    if IDL_SUB_EXPLAIN && IDL_SUB_FAILURE.is_none() {
+        IDL_SUB_FAILURE = Some(IdlSubFailure {
+            context,
+            t1,
+            con1,
+            t2,
+            con2,
+            reason,
+        });
+    }
+}
+
+/// The type constructor of `t`, or `t` itself for primitive types
+unsafe fn unfold(typtbl: *mut *mut u8, end: *mut u8, t: i32) -> IdlResult<i32> {
+    if t < 0 {
+        return Ok(t);
+    }
+    let mut tb = Buf {
+        ptr: *typtbl.add(t as usize),
+        end,
+    };
+    read_sleb128(&mut tb)
+}
+
+unsafe fn is_opt_reserved(typtbl: *mut *mut u8, end: *mut u8, t: i32) -> IdlResult<bool> {
     if is_primitive_type(t) {
-        return t == IDL_PRIM_reserved;
+        return Ok(t == IDL_PRIM_reserved);
     }
 
     // unfold t
@@ -528,9 +1485,9 @@ unsafe fn is_opt_reserved(typtbl: *mut *mut u8, end: *mut u8, t: i32) -> bool {
         end: end,
     };
 
-    t = sleb128_decode(&mut tb);
+    t = read_sleb128(&mut tb)?;
 
-    return t == IDL_CON_opt;
+    return Ok(t == IDL_CON_opt);
 }
 
 // TODO: consider storing fixed args typtbl1...end2 in `rel` to use less stack.
@@ -543,14 +1500,23 @@ unsafe fn sub(
     end2: *mut u8,
     t1: i32,
     t2: i32,
-) -> bool {
+    context: IdlSubContext,
+) -> IdlResult<bool> {
+    charge(1)?;
     if t1 >= 0 && t2 >= 0 {
         let t1 = t1 as u32;
         let t2 = t2 as u32;
         if rel.visited(p, t1, t2) {
             // visited? (bit 0)
             // return assumed or determined result
-            return rel.related(p, t1, t2);
+            let related = rel.related(p, t1, t2);
+            if !related && IDL_SUB_EXPLAIN {
+                let con1 = unfold(typtbl1, end1, t1 as i32)?;
+                let con2 = unfold(typtbl2, end2, t2 as i32)?;
+                let reason = IdlSubReason::Disproved;
+                explain(context, t1 as i32, con1, t2 as i32, con2, reason);
+            }
+            return Ok(related);
         };
         // cache and continue
         rel.visit(p, t1, t2); // mark visited
@@ -559,7 +1525,7 @@ unsafe fn sub(
 
     /* primitives reflexive */
     if is_primitive_type(t1) && is_primitive_type(t2) && t1 == t2 {
-        return true;
+        return Ok(true);
     }
 
     // unfold t1, if necessary
@@ -572,11 +1538,7 @@ unsafe fn sub(
         end: end1,
     };
 
-    let u1 = if t1 >= 0 {
-        sleb128_decode(&mut tb1)
-    } else {
-        t1
-    };
+    let u1 = if t1 >= 0 { read_sleb128(&mut tb1)? } else { t1 };
 
     // unfold t2, if necessary
     let mut tb2 = Buf {
@@ -588,189 +1550,183 @@ unsafe fn sub(
         end: end2,
     };
 
-    let u2 = if t2 >= 0 {
-        sleb128_decode(&mut tb2)
-    } else {
-        t2
-    };
+    let u2 = if t2 >= 0 { read_sleb128(&mut tb2)? } else { t2 };
 
     // NB we use a trivial labelled loop so we can factor out the common failure continuation.
-    // exit either via 'return true' or 'break 'return_false' to memoize the negative result
+    // exit either via 'return true' or 'break 'return_false' to memoize the negative result.
+    // Failures of the types themselves, not of nested types, set `reason` first.
+    let mut reason = IdlSubReason::Incompatible;
     'return_false: loop {
         match (u1, u2) {
-            (_, IDL_CON_alias) | (IDL_CON_alias, _) => idl_trap_with("sub: unexpected alias"),
+            (_, IDL_CON_alias) | (IDL_CON_alias, _) => return Err(IdlError::UnexpectedAlias),
             (_, IDL_PRIM_reserved)
             | (IDL_PRIM_empty, _)
             | (IDL_PRIM_nat, IDL_PRIM_int)
-            | (_, IDL_CON_opt) => return true, // apparently, this is admissable
+            | (_, IDL_CON_opt) => return Ok(true), // apparently, this is admissable
             (IDL_CON_vec, IDL_CON_vec) => {
-                let t11 = sleb128_decode(&mut tb1);
-                let t21 = sleb128_decode(&mut tb2);
-                if sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21) {
-                    return true;
+                let t11 = read_sleb128(&mut tb1)?;
+                let t21 = read_sleb128(&mut tb2)?;
+                let context = IdlSubContext::Element;
+                if sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21, context)? {
+                    return Ok(true);
                 } else {
                     break 'return_false;
                 }
             }
             (IDL_CON_func, IDL_CON_func) => {
                 // contra in domain
-                let in1 = leb128_decode(&mut tb1);
-                let mut in2 = leb128_decode(&mut tb2);
-                for _ in 0..in1 {
-                    let t11 = sleb128_decode(&mut tb1);
+                let in1 = read_leb128(&mut tb1)?;
+                let mut in2 = read_leb128(&mut tb2)?;
+                for i in 0..in1 {
+                    let t11 = read_sleb128(&mut tb1)?;
                     if in2 == 0 {
-                        if !is_opt_reserved(typtbl1, end1, t11) {
+                        if !is_opt_reserved(typtbl1, end1, t11)? {
+                            reason = IdlSubReason::MissingArg(i);
                             break 'return_false;
                         }
                     } else {
-                        let t21 = sleb128_decode(&mut tb2);
+                        let t21 = read_sleb128(&mut tb2)?;
                         in2 -= 1;
                         // NB: invert p and args!
-                        if !sub(rel, !p, typtbl2, typtbl1, end2, end1, t21, t11) {
+                        let context = IdlSubContext::Arg(i);
+                        if !sub(rel, !p, typtbl2, typtbl1, end2, end1, t21, t11, context)? {
                             break 'return_false;
                         }
                     }
                 }
                 while in2 > 0 {
-                    let _ = sleb128_decode(&mut tb2);
+                    let _ = read_sleb128(&mut tb2)?;
                     in2 -= 1;
                 }
                 // co in range
-                let mut out1 = leb128_decode(&mut tb1);
-                let out2 = leb128_decode(&mut tb2);
-                for _ in 0..out2 {
-                    let t21 = sleb128_decode(&mut tb2);
+                let mut out1 = read_leb128(&mut tb1)?;
+                let out2 = read_leb128(&mut tb2)?;
+                for i in 0..out2 {
+                    let t21 = read_sleb128(&mut tb2)?;
                     if out1 == 0 {
-                        if !is_opt_reserved(typtbl2, end2, t21) {
+                        if !is_opt_reserved(typtbl2, end2, t21)? {
+                            reason = IdlSubReason::MissingResult(i);
                             break 'return_false;
                         }
                     } else {
-                        let t11 = sleb128_decode(&mut tb1);
+                        let t11 = read_sleb128(&mut tb1)?;
                         out1 -= 1;
-                        if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21) {
+                        let context = IdlSubContext::Result(i);
+                        if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21, context)? {
                             break 'return_false;
                         }
                     }
                 }
                 while out1 > 0 {
-                    let _ = sleb128_decode(&mut tb1);
+                    let _ = read_sleb128(&mut tb1)?;
                     out1 -= 1;
                 }
-                // check annotations (that we care about)
-                // TODO: more generally, we would check equality of 256-bit bit-vectors,
-                // but validity ensures each entry is 1 or 2 (for now)
-                // c.f. https://github.com/dfinity/candid/issues/318
-                let mut a11 = false;
-                let mut a12 = false;
-                for _ in 0..leb128_decode(&mut tb1) {
-                    match read_byte(&mut tb1) {
-                        1 => a11 = true,
-                        2 => a12 = true,
-                        _ => {}
-                    }
-                }
-                let mut a21 = false;
-                let mut a22 = false;
-                for _ in 0..leb128_decode(&mut tb2) {
-                    match read_byte(&mut tb2) {
-                        1 => a21 = true,
-                        2 => a22 = true,
-                        _ => {}
-                    }
-                }
-                if (a11 == a21) && (a12 == a22) {
-                    return true;
+                // check annotations: the same set, regardless of order and repetition
+                // (validity ensures each is one that we know)
+                if read_func_annotations(&mut tb1)? == read_func_annotations(&mut tb2)? {
+                    return Ok(true);
                 } else {
+                    reason = IdlSubReason::Annotations;
                     break 'return_false;
                 }
             }
             (IDL_CON_record, IDL_CON_record) => {
-                let mut n1 = leb128_decode(&mut tb1);
-                let n2 = leb128_decode(&mut tb2);
+                let mut n1 = read_leb128(&mut tb1)?;
+                let n2 = read_leb128(&mut tb2)?;
                 let mut tag1 = 0;
                 let mut t11 = 0;
                 let mut advance = true;
                 for _ in 0..n2 {
-                    let tag2 = leb128_decode(&mut tb2);
-                    let t21 = sleb128_decode(&mut tb2);
-                    if n1 == 0 {
+                    let tag2 = read_leb128(&mut tb2)?;
+                    let t21 = read_sleb128(&mut tb2)?;
+                    if n1 == 0 && advance {
                         // check all remaining fields optional
-                        if !is_opt_reserved(typtbl2, end2, t21) {
+                        if !is_opt_reserved(typtbl2, end2, t21)? {
+                            reason = IdlSubReason::MissingField(tag2);
                             break 'return_false;
                         }
                         continue;
                     };
                     if advance {
                         loop {
-                            tag1 = leb128_decode(&mut tb1);
-                            t11 = sleb128_decode(&mut tb1);
+                            tag1 = read_leb128(&mut tb1)?;
+                            t11 = read_sleb128(&mut tb1)?;
                             n1 -= 1;
                             if !(tag1 < tag2 && n1 > 0) {
                                 break;
                             }
                         }
                     };
-                    if tag1 > tag2 {
-                        if !is_opt_reserved(typtbl2, end2, t21) {
+                    // `tag1 < tag2` if the fields of t1 ran out
+                    if tag1 != tag2 {
+                        if !is_opt_reserved(typtbl2, end2, t21)? {
                             // missing, non_opt field
+                            reason = IdlSubReason::MissingField(tag2);
                             break 'return_false;
                         }
                         advance = false; // reconsider this field in next round
                         continue;
                     };
-                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21) {
+                    let context = IdlSubContext::Field(tag2);
+                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21, context)? {
                         break 'return_false;
                     }
                     advance = true;
                 }
-                return true;
+                return Ok(true);
             }
             (IDL_CON_variant, IDL_CON_variant) => {
-                let n1 = leb128_decode(&mut tb1);
-                let mut n2 = leb128_decode(&mut tb2);
+                let n1 = read_leb128(&mut tb1)?;
+                let mut n2 = read_leb128(&mut tb2)?;
                 for _ in 0..n1 {
+                    let tag1 = read_leb128(&mut tb1)?;
                     if n2 == 0 {
+                        reason = IdlSubReason::MissingCase(tag1);
                         break 'return_false;
                     };
-                    let tag1 = leb128_decode(&mut tb1);
-                    let t11 = sleb128_decode(&mut tb1);
+                    let t11 = read_sleb128(&mut tb1)?;
                     let mut tag2: u32;
                     let mut t21: i32;
                     loop {
-                        tag2 = leb128_decode(&mut tb2);
-                        t21 = sleb128_decode(&mut tb2);
+                        tag2 = read_leb128(&mut tb2)?;
+                        t21 = read_sleb128(&mut tb2)?;
                         n2 -= 1;
                         if !(tag2 < tag1 && n2 > 0) {
                             break;
                         }
                     }
                     if tag1 != tag2 {
+                        reason = IdlSubReason::MissingCase(tag1);
                         break 'return_false;
                     };
-                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21) {
+                    let context = IdlSubContext::Case(tag1);
+                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21, context)? {
                         break 'return_false;
                     }
                 }
-                return true;
+                return Ok(true);
             }
             (IDL_CON_service, IDL_CON_service) => {
-                let mut n1 = leb128_decode(&mut tb1);
-                let n2 = leb128_decode(&mut tb2);
+                let mut n1 = read_leb128(&mut tb1)?;
+                let n2 = read_leb128(&mut tb2)?;
                 for _ in 0..n2 {
+                    let len2 = read_leb128(&mut tb2)?;
+                    let p2 = tb2.ptr;
+                    advance(&mut tb2, len2)?;
                     if n1 == 0 {
+                        reason = IdlSubReason::MissingMethod(IdlName::new(p2, len2));
                         break 'return_false;
                     };
-                    let (len2, p2) = leb128_decode_ptr(&mut tb2);
-                    Buf::advance(&mut tb2, len2);
-                    let t21 = sleb128_decode(&mut tb2);
+                    let t21 = read_sleb128(&mut tb2)?;
                     let mut len1: u32;
                     let mut p1: *mut u8;
                     let mut t11: i32;
                     let mut cmp: i32;
                     loop {
-                        (len1, p1) = leb128_decode_ptr(&mut tb1);
-                        Buf::advance(&mut tb1, len1);
-                        t11 = sleb128_decode(&mut tb1);
+                        len1 = read_leb128(&mut tb1)?;
+                        p1 = tb1.ptr;
+                        advance(&mut tb1, len1)?;
+                        t11 = read_sleb128(&mut tb1)?;
                         n1 -= 1;
                         cmp = utf8_cmp(len1, p1, len2, p2);
                         if cmp < 0 && n1 > 0 {
@@ -779,13 +1735,15 @@ unsafe fn sub(
                         break;
                     }
                     if cmp != 0 {
+                        reason = IdlSubReason::MissingMethod(IdlName::new(p2, len2));
                         break 'return_false;
                     };
-                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21) {
+                    let context = IdlSubContext::Method(IdlName::new(p2, len2));
+                    if !sub(rel, p, typtbl1, typtbl2, end1, end2, t11, t21, context)? {
                         break 'return_false;
                     }
                 }
-                return true;
+                return Ok(true);
             }
             // default
             (_, _) => {
@@ -797,17 +1755,19 @@ unsafe fn sub(
     if t1 >= 0 && t2 >= 0 {
         rel.disprove(p, t1 as u32, t2 as u32);
     }
+    // (the nested types explained the failure already, if they caused it)
+    explain(context, t1, u1, t2, u2, reason);
     // .. only then return false
-    return false;
+    return Ok(false);
 }
 
 #[no_mangle]
-unsafe extern "C" fn idl_sub_buf_words(typtbl_size1: u32, typtbl_size2: u32) -> u32 {
+pub unsafe extern "C" fn idl_sub_buf_words(typtbl_size1: u32, typtbl_size2: u32) -> u32 {
     return BitRel::words(typtbl_size1, typtbl_size2);
 }
 
 #[no_mangle]
-unsafe extern "C" fn idl_sub_buf_init(rel_buf: *mut u32, typtbl_size1: u32, typtbl_size2: u32) {
+pub unsafe extern "C" fn idl_sub_buf_init(rel_buf: *mut u32, typtbl_size1: u32, typtbl_size2: u32) {
     let rel = BitRel {
         ptr: rel_buf,
         end: rel_buf.add(idl_sub_buf_words(typtbl_size1, typtbl_size2) as usize),
@@ -818,7 +1778,7 @@ unsafe extern "C" fn idl_sub_buf_init(rel_buf: *mut u32, typtbl_size1: u32, typt
 }
 
 #[no_mangle]
-unsafe extern "C" fn idl_sub(
+pub unsafe extern "C" fn idl_sub(
     rel_buf: *mut u32, // a buffer with at least 2 * typtbl_size1 * typtbl_size2 bits
     typtbl1: *mut *mut u8,
     typtbl2: *mut *mut u8,
@@ -829,6 +1789,31 @@ unsafe extern "C" fn idl_sub(
     t1: i32,
     t2: i32,
 ) -> bool {
+    or_trap(idl_sub_checked(
+        rel_buf,
+        typtbl1,
+        typtbl2,
+        typtbl_end1,
+        typtbl_end2,
+        typtbl_size1,
+        typtbl_size2,
+        t1,
+        t2,
+    ))
+}
+
+/// Like `idl_sub`, but returns an error instead of trapping
+pub unsafe fn idl_sub_checked(
+    rel_buf: *mut u32,
+    typtbl1: *mut *mut u8,
+    typtbl2: *mut *mut u8,
+    typtbl_end1: *mut u8,
+    typtbl_end2: *mut u8,
+    typtbl_size1: u32,
+    typtbl_size2: u32,
+    t1: i32,
+    t2: i32,
+) -> IdlResult<bool> {
     debug_assert!(rel_buf != (0 as *mut u32));
 
     let rel = BitRel {
@@ -840,6 +1825,10 @@ unsafe extern "C" fn idl_sub(
 
     debug_assert!(t1 < (typtbl_size1 as i32) && t2 < (typtbl_size2 as i32));
 
+    if IDL_SUB_EXPLAIN {
+        IDL_SUB_FAILURE = None;
+    }
+
     return sub(
         &rel,
         true,
@@ -849,5 +1838,188 @@ unsafe extern "C" fn idl_sub(
         typtbl_end2,
         t1,
         t2,
+        IdlSubContext::Top,
     );
 }
+
+//
+// Caching subtype checks across messages
+//
+
+/// Number of incoming type tables in the cache of `idl_sub_cached`
+const SUB_CACHE_TABLES: usize = 8;
+
+/// Longest incoming type table, in bytes, that `idl_sub_cached` caches. Subtype checks with longer
+/// ones are not cached.
+const SUB_CACHE_TABLE_BYTES: usize = 1024;
+
+/// Number of verdicts cached per incoming type table
+const SUB_CACHE_VERDICTS: usize = 16;
+
+/// Verdicts of `idl_sub` with one incoming type table. The table is copied: its hash only selects
+/// the entry, and a table with the same hash must not be taken for it.
+#[derive(Clone, Copy)]
+struct SubCacheEntry {
+    hash: u64,
+    /// The expected type table, see `idl_sub_cached`
+    typtbl2: *mut *mut u8,
+    typtbl_size1: u32,
+    len: u32,
+    table: [u8; SUB_CACHE_TABLE_BYTES],
+    /// `(t1, t2, verdict)`
+    verdicts: [(i32, i32, bool); SUB_CACHE_VERDICTS],
+    n_verdicts: u32,
+    /// Where the next verdict goes, when all are used
+    next_verdict: u32,
+}
+
+const EMPTY_SUB_CACHE_ENTRY: SubCacheEntry = SubCacheEntry {
+    hash: 0,
+    typtbl2: core::ptr::null_mut(),
+    typtbl_size1: 0,
+    len: 0,
+    table: [0; SUB_CACHE_TABLE_BYTES],
+    verdicts: [(0, 0, false); SUB_CACHE_VERDICTS],
+    n_verdicts: 0,
+    next_verdict: 0,
+};
+
+/// The cache is in static memory, so it survives GC without being a root
+static mut SUB_CACHE: [SubCacheEntry; SUB_CACHE_TABLES] = [EMPTY_SUB_CACHE_ENTRY; SUB_CACHE_TABLES];
+
+impl SubCacheEntry {
+    fn lookup(&self, t1: i32, t2: i32) -> Option<bool> {
+        self.verdicts[..self.n_verdicts as usize]
+            .iter()
+            .find(|&&(u1, u2, _)| (u1, u2) == (t1, t2))
+            .map(|&(_, _, verdict)| verdict)
+    }
+
+    fn insert(&mut self, t1: i32, t2: i32, verdict: bool) {
+        if (self.n_verdicts as usize) < SUB_CACHE_VERDICTS {
+            self.verdicts[self.n_verdicts as usize] = (t1, t2, verdict);
+            self.n_verdicts += 1;
+        } else {
+            self.verdicts[self.next_verdict as usize] = (t1, t2, verdict);
+            self.next_verdict = (self.next_verdict + 1) % SUB_CACHE_VERDICTS as u32;
+        }
+    }
+}
+
+/// The cache entry for the incoming type table, emptied if it was used for another one. `None` if
+/// the table is too long to be cached.
+unsafe fn sub_cache_entry(
+    typtbl1: *mut *mut u8,
+    typtbl2: *mut *mut u8,
+    typtbl_end1: *mut u8,
+    typtbl_size1: u32,
+) -> Option<&'static mut SubCacheEntry> {
+    let start = if typtbl_size1 == 0 {
+        typtbl_end1
+    } else {
+        *typtbl1
+    };
+    let len = typtbl_end1 as usize - start as usize;
+    if len > SUB_CACHE_TABLE_BYTES {
+        return None;
+    }
+    let table = core::slice::from_raw_parts(start, len);
+
+    let mut hasher = SipHasher::new_with_keys(0, 0);
+    hasher.write(&typtbl_size1.to_le_bytes());
+    hasher.write(&(typtbl2 as usize).to_le_bytes());
+    hasher.write(table);
+    let hash = hasher.finish();
+
+    let entry = &mut SUB_CACHE[(hash % SUB_CACHE_TABLES as u64) as usize];
+    let hit = entry.hash == hash
+        && entry.typtbl2 == typtbl2
+        && entry.typtbl_size1 == typtbl_size1
+        && entry.len as usize == len
+        && &entry.table[..len] == table;
+    if !hit {
+        entry.hash = hash;
+        entry.typtbl2 = typtbl2;
+        entry.typtbl_size1 = typtbl_size1;
+        entry.len = len as u32;
+        entry.table[..len].copy_from_slice(table);
+        entry.n_verdicts = 0;
+        entry.next_verdict = 0;
+    }
+    Some(entry)
+}
+
+/// Like `idl_sub`, but the verdicts are cached across messages, for incoming type tables that are
+/// not too long. `typtbl2` must be the static type table of the generated code, which never
+/// changes: it is only identified by its address.
+///
+/// Checks are not cached while explaining them (see `idl_sub_set_explain`).
+#[no_mangle]
+pub unsafe extern "C" fn idl_sub_cached(
+    rel_buf: *mut u32,
+    typtbl1: *mut *mut u8,
+    typtbl2: *mut *mut u8,
+    typtbl_end1: *mut u8,
+    typtbl_end2: *mut u8,
+    typtbl_size1: u32,
+    typtbl_size2: u32,
+    t1: i32,
+    t2: i32,
+) -> bool {
+    or_trap(idl_sub_cached_checked(
+        rel_buf,
+        typtbl1,
+        typtbl2,
+        typtbl_end1,
+        typtbl_end2,
+        typtbl_size1,
+        typtbl_size2,
+        t1,
+        t2,
+    ))
+}
+
+/// Like `idl_sub_cached`, but returns an error instead of trapping
+pub unsafe fn idl_sub_cached_checked(
+    rel_buf: *mut u32,
+    typtbl1: *mut *mut u8,
+    typtbl2: *mut *mut u8,
+    typtbl_end1: *mut u8,
+    typtbl_end2: *mut u8,
+    typtbl_size1: u32,
+    typtbl_size2: u32,
+    t1: i32,
+    t2: i32,
+) -> IdlResult<bool> {
+    let mut entry = None;
+    if !IDL_SUB_EXPLAIN {
+        entry = sub_cache_entry(typtbl1, typtbl2, typtbl_end1, typtbl_size1);
+    }
+    if let Some(verdict) = entry.as_ref().and_then(|entry| entry.lookup(t1, t2)) {
+        return Ok(verdict);
+    }
+
+    let verdict = idl_sub_checked(
+        rel_buf,
+        typtbl1,
+        typtbl2,
+        typtbl_end1,
+        typtbl_end2,
+        typtbl_size1,
+        typtbl_size2,
+        t1,
+        t2,
+    )?;
+    if let Some(entry) = entry {
+        entry.insert(t1, t2, verdict);
+    }
+    Ok(verdict)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn idl_sub_cache_clear() {
+    for entry in SUB_CACHE.iter_mut() {
+        entry.n_verdicts = 0;
+        entry.next_verdict = 0;
+    }
+}
@@ -0,0 +1,1529 @@
+//! Converting Candid messages to JSON and back, for canisters that serve HTTP requests.
+//!
+//! The JSON text of a message is an array of its arguments. Values are mapped as follows:
+//!
+//! * `null` and `reserved` are `null`, `bool` is `true` or `false`.
+//!
+//! * `nat`, `int`, `nat64` and `int64` are strings of decimal numbers, as JSON numbers beyond
+//!   2^53 lose precision in most decoders. Other integers and floats are numbers. Floats that are
+//!   not finite are `null`, and cannot be converted back. Integers are converted from JSON
+//!   numbers and strings alike.
+//!
+//! * `text` is a string, `blob` (`vec nat8`) is a string in base64, other vectors are arrays.
+//!   Blobs are converted from arrays of numbers too.
+//!
+//! * `opt t` is `null` or the value. If `t` is `null`, `reserved` or an `opt` itself, so that
+//!   `null` would be ambiguous, it is `[]` or an array of the value.
+//!
+//! * A record is an object, and a variant an object with one field, the case. Their keys are the
+//!   names given for them, or else their hashes in decimal. When converting from JSON, keys that
+//!   are decimal numbers are hashes, and other keys names. Fields that are missing and of type
+//!   `opt`, `null` or `reserved` are `null`, and unknown fields are ignored.
+//!
+//! * `principal` and `service` are strings with the textual format of principals, `func` is an
+//!   array of the principal and the method name.
+//!
+//! Like `skip_any`, the conversions use an explicit stack, and are charged to the decoding quota.
+
+use super::print::{
+    read_bytes, read_principal, write_principal, IdlNames, Number, Stop, TextArray, TextWriter,
+    NUMBER_LIMBS,
+};
+use super::{
+    advance, charge, is_opt_reserved, or_trap, parse_idl_header_checked, read_byte_tag,
+    read_leb128, read_sleb128, read_text, read_u32, read_u8, skip_future, type_name, IDL_CON_func,
+    IDL_CON_opt, IDL_CON_record, IDL_CON_service, IDL_CON_variant, IDL_CON_vec, IDL_PRIM_bool,
+    IDL_PRIM_empty, IDL_PRIM_float32, IDL_PRIM_float64, IDL_PRIM_int, IDL_PRIM_int16,
+    IDL_PRIM_int32, IDL_PRIM_int64, IDL_PRIM_int8, IDL_PRIM_nat, IDL_PRIM_nat16, IDL_PRIM_nat32,
+    IDL_PRIM_nat64, IDL_PRIM_nat8, IDL_PRIM_null, IDL_PRIM_reserved, IDL_PRIM_text,
+    IDL_REF_principal, IdlError, IdlErrorAt, IdlPath, IdlPathSegment, Stack,
+};
+use crate::buf::Buf;
+use crate::memory::{alloc_blob, Memory};
+use crate::text::{alloc_text_blob, blob_of_text};
+use crate::types::{Bytes, Value};
+
+use core::fmt::{self, Write};
+use core::{slice, str};
+
+use motoko_rts_macros::ic_mem_fn;
+
+/// Number of frames of the stacks of the conversions that are allocated on the (Rust) stack.
+/// Deeper values spill to the heap, like in `skip_any`.
+const JSON_INLINE_FRAMES: usize = 32;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Whether `null` is a value of `t`, so that `opt t` is converted with an array
+unsafe fn is_nullable(typtbl: *mut *mut u8, end: *mut u8, t: i32) -> Result<bool, IdlError> {
+    Ok(t == IDL_PRIM_null || is_opt_reserved(typtbl, end, t)?)
+}
+
+//
+// Candid to JSON
+//
+
+/// Converts the Candid message `blob` to JSON. `names` is an array of `Text`, the names of record
+/// fields and variant cases. Traps if the message is malformed.
+#[ic_mem_fn]
+pub unsafe fn idl_to_json<M: Memory>(mem: &mut M, blob: Value, names: Value) -> Value {
+    let blob = blob.as_blob();
+    let start = blob.payload_const() as *mut u8;
+    let end = start.add(blob.len().as_usize());
+    let names = TextArray(names);
+
+    // The text is measured first, and then written
+    let mut size = Measure(0);
+    let mut buf = Buf { ptr: start, end };
+    or_trap(idl_to_json_checked(mem, &mut buf, &names, &mut size));
+    let text = alloc_text_blob(mem, Bytes(size.0 as u32));
+    let bytes = slice::from_raw_parts_mut(text.as_blob_mut().payload_addr(), size.0);
+    let mut buf = Buf { ptr: start, end };
+    or_trap(idl_to_json_checked(
+        mem,
+        &mut buf,
+        &names,
+        &mut TextWriter::new(bytes),
+    ));
+    text
+}
+
+/// Like `idl_to_json`, but writing to `out`, and returning an error instead of trapping. A full
+/// `out` is not an error.
+pub unsafe fn idl_to_json_checked<M: Memory, N: IdlNames + ?Sized, W: Write>(
+    mem: &mut M,
+    buf: *mut Buf,
+    names: &N,
+    out: &mut W,
+) -> Result<(), IdlErrorAt> {
+    let mut typtbl = core::ptr::null_mut();
+    let mut n_types = 0;
+    let mut main_types = core::ptr::null_mut();
+    parse_idl_header_checked(mem, false, buf, &mut typtbl, &mut n_types, &mut main_types)?;
+
+    let mut inline_frames = [ToJsonFrame::Single { close: "" }; JSON_INLINE_FRAMES];
+    let mut stack = Stack::new(&mut inline_frames);
+    let mut to_json = ToJson {
+        buf,
+        typtbl,
+        names,
+        current: -1,
+    };
+    match to_json.convert(mem, &mut stack, main_types, out) {
+        Ok(()) | Err(Stop::Full) => Ok(()),
+        Err(Stop::Error(error)) => Err(to_json.locate(&stack, error)),
+    }
+}
+
+/// Counts the bytes written
+struct Measure(usize);
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Escapes what is written for a JSON string
+struct JsonEscape<'a, W: Write>(&'a mut W);
+
+impl<'a, W: Write> Write for JsonEscape<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if (c as u32) < 0x20 => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_string<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    out.write_str("\"")?;
+    JsonEscape(out).write_str(s)?;
+    out.write_str("\"")
+}
+
+fn write_base64<W: Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    out.write_str("\"")?;
+    for chunk in bytes.chunks(3) {
+        let mut group = 0u32;
+        for (i, &b) in chunk.iter().enumerate() {
+            group |= (b as u32) << (16 - 8 * i);
+        }
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let c = BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize];
+                out.write_char(c as char)?;
+            } else {
+                out.write_str("=")?;
+            }
+        }
+    }
+    out.write_str("\"")
+}
+
+/// A value being converted to JSON
+#[derive(Clone, Copy)]
+enum ToJsonFrame {
+    /// The arguments of the message, with `count` left, the type of the next one at `types`
+    Args {
+        types: *mut u8,
+        count: u32,
+        index: u32,
+    },
+    /// A record of type `t`, with `fields` fields left, the next one at `tb` in the type table.
+    /// `tag` is the field being converted.
+    Record {
+        t: i32,
+        tb: *mut u8,
+        fields: u32,
+        tag: u32,
+        index: u32,
+    },
+    /// A vector of type `t` with elements of type `it`, with `count` left after the one at `index`
+    Vec {
+        t: i32,
+        it: i32,
+        count: u32,
+        index: u32,
+    },
+    /// A value with one nested value, followed by `close`
+    Single { close: &'static str },
+}
+
+struct ToJson<'a, N: IdlNames + ?Sized> {
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    names: &'a N,
+    /// The innermost type in the type table being converted
+    current: i32,
+}
+
+impl<'a, N: IdlNames + ?Sized> ToJson<'a, N> {
+    /// Where the conversion failed
+    unsafe fn locate(&self, stack: &Stack<ToJsonFrame>, error: IdlError) -> IdlErrorAt {
+        let mut path = IdlPath::EMPTY;
+        for frame in stack.frames() {
+            match *frame {
+                ToJsonFrame::Args { index, .. } if index > 0 => {
+                    path.push(IdlPathSegment::Arg(index - 1))
+                }
+                ToJsonFrame::Record { tag, index, .. } if index > 0 => {
+                    path.push(IdlPathSegment::Field(tag))
+                }
+                ToJsonFrame::Vec { index, .. } => path.push(IdlPathSegment::Element(index)),
+                _ => {}
+            }
+        }
+        let type_index = if self.current >= 0 {
+            Some(self.current as u32)
+        } else {
+            None
+        };
+        IdlErrorAt::new(error, (*self.buf).ptr, type_index, path)
+    }
+
+    unsafe fn key<W: Write>(&self, tag: u32, out: &mut W) -> Result<(), Stop> {
+        out.write_str("\"")?;
+        if !self.names.write_name(tag, &mut JsonEscape(out))? {
+            write!(out, "{}", tag)?;
+        }
+        out.write_str("\":")?;
+        Ok(())
+    }
+
+    unsafe fn convert<M: Memory, W: Write>(
+        &mut self,
+        mem: &mut M,
+        stack: &mut Stack<ToJsonFrame>,
+        main_types: *mut u8,
+        out: &mut W,
+    ) -> Result<(), Stop> {
+        let buf = self.buf;
+        let mut types = Buf {
+            ptr: main_types,
+            end: (*buf).end,
+        };
+        let count = read_leb128(&mut types)?;
+        out.write_str("[")?;
+        let frame = ToJsonFrame::Args {
+            types: types.ptr,
+            count,
+            index: 0,
+        };
+        stack.push(mem, frame)?;
+
+        let mut next = None;
+        loop {
+            if let Some(t) = next {
+                if t >= 0 {
+                    self.current = t;
+                }
+                next = self.value(mem, stack, out, t)?;
+                continue;
+            }
+
+            // Continue with the innermost unfinished value
+            let mut finished = false;
+            match stack.top() {
+                None => return Ok(()),
+                Some(ToJsonFrame::Args {
+                    types,
+                    count,
+                    index,
+                }) => {
+                    if *count == 0 {
+                        out.write_str("]")?;
+                        finished = true;
+                    } else {
+                        if *index > 0 {
+                            out.write_str(",")?;
+                        }
+                        let mut b = Buf {
+                            ptr: *types,
+                            end: (*buf).end,
+                        };
+                        next = Some(read_sleb128(&mut b)?);
+                        *types = b.ptr;
+                        *count -= 1;
+                        *index += 1;
+                    }
+                }
+                Some(ToJsonFrame::Record {
+                    t,
+                    tb,
+                    fields,
+                    tag,
+                    index,
+                }) => {
+                    self.current = *t;
+                    if *fields == 0 {
+                        out.write_str("}")?;
+                        finished = true;
+                    } else {
+                        if *index > 0 {
+                            out.write_str(",")?;
+                        }
+                        let mut b = Buf {
+                            ptr: *tb,
+                            end: (*buf).end,
+                        };
+                        *tag = read_leb128(&mut b)?;
+                        next = Some(read_sleb128(&mut b)?);
+                        *tb = b.ptr;
+                        *fields -= 1;
+                        *index += 1;
+                        self.key(*tag, out)?;
+                    }
+                }
+                Some(ToJsonFrame::Vec {
+                    t,
+                    it,
+                    count,
+                    index,
+                }) => {
+                    self.current = *t;
+                    if *count == 0 {
+                        out.write_str("]")?;
+                        finished = true;
+                    } else {
+                        out.write_str(",")?;
+                        *count -= 1;
+                        *index += 1;
+                        next = Some(*it);
+                    }
+                }
+                Some(ToJsonFrame::Single { close }) => {
+                    out.write_str(close)?;
+                    finished = true;
+                }
+            }
+            if finished {
+                stack.pop();
+            }
+        }
+    }
+
+    /// Converts a value of type `t`, or starts converting it, like `skip_one`
+    unsafe fn value<M: Memory, W: Write>(
+        &mut self,
+        mem: &mut M,
+        stack: &mut Stack<ToJsonFrame>,
+        out: &mut W,
+        t: i32,
+    ) -> Result<Option<i32>, Stop> {
+        let buf = self.buf;
+        charge(1)?;
+        if t < 0 {
+            match t {
+                IDL_PRIM_null | IDL_PRIM_reserved => out.write_str("null")?,
+                IDL_PRIM_bool => match read_byte_tag(buf)? {
+                    0 => out.write_str("false")?,
+                    _ => out.write_str("true")?,
+                },
+                IDL_PRIM_nat | IDL_PRIM_int => match Number::read(buf, t == IDL_PRIM_int)? {
+                    Some(n) => write!(out, "\"{}\"", n)?,
+                    None => return Err(IdlError::NumberTooLarge.into()),
+                },
+                IDL_PRIM_nat8 => write!(out, "{}", read_u8(buf)?)?,
+                IDL_PRIM_nat16 => write!(out, "{}", u16::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_nat32 => write!(out, "{}", read_u32(buf)?)?,
+                IDL_PRIM_nat64 => write!(out, "\"{}\"", u64::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_int8 => write!(out, "{}", read_u8(buf)? as i8)?,
+                IDL_PRIM_int16 => write!(out, "{}", i16::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_int32 => write!(out, "{}", read_u32(buf)? as i32)?,
+                IDL_PRIM_int64 => write!(out, "\"{}\"", i64::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_float32 => match f32::from_le_bytes(read_bytes(buf)?) {
+                    x if x.is_finite() => write!(out, "{:?}", x)?,
+                    _ => out.write_str("null")?,
+                },
+                IDL_PRIM_float64 => match f64::from_le_bytes(read_bytes(buf)?) {
+                    x if x.is_finite() => write!(out, "{:?}", x)?,
+                    _ => out.write_str("null")?,
+                },
+                IDL_PRIM_text => {
+                    let (len, p) = read_text(buf)?;
+                    write_string(
+                        out,
+                        str::from_utf8_unchecked(slice::from_raw_parts(p, len as usize)),
+                    )?;
+                }
+                IDL_PRIM_empty => return Err(IdlError::SkipEmpty.into()),
+                IDL_REF_principal => {
+                    let bytes = read_principal(buf)?;
+                    out.write_str("\"")?;
+                    write_principal(out, bytes)?;
+                    out.write_str("\"")?;
+                }
+                _ => return Err(IdlError::UnknownPrim.into()),
+            }
+            return Ok(None);
+        }
+
+        let end = (*buf).end;
+        let mut tb = Buf {
+            ptr: *self.typtbl.add(t as usize),
+            end,
+        };
+        match read_sleb128(&mut tb)? {
+            IDL_CON_opt => {
+                let it = read_sleb128(&mut tb)?;
+                let in_array = is_nullable(self.typtbl, end, it)?;
+                if read_byte_tag(buf)? == 0 {
+                    out.write_str(if in_array { "[]" } else { "null" })?;
+                } else {
+                    if in_array {
+                        out.write_str("[")?;
+                        stack.push(mem, ToJsonFrame::Single { close: "]" })?;
+                    }
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_vec => {
+                let it = read_sleb128(&mut tb)?;
+                let count = read_leb128(buf)?;
+                if it == IDL_PRIM_nat8 {
+                    let p = (*buf).ptr;
+                    advance(buf, count)?;
+                    charge(count as u64)?;
+                    write_base64(out, slice::from_raw_parts(p, count as usize))?;
+                } else if count == 0 {
+                    out.write_str("[]")?;
+                } else {
+                    out.write_str("[")?;
+                    let frame = ToJsonFrame::Vec {
+                        t,
+                        it,
+                        count: count - 1,
+                        index: 0,
+                    };
+                    stack.push(mem, frame)?;
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_record => {
+                let fields = read_leb128(&mut tb)?;
+                out.write_str("{")?;
+                let frame = ToJsonFrame::Record {
+                    t,
+                    tb: tb.ptr,
+                    fields,
+                    tag: 0,
+                    index: 0,
+                };
+                stack.push(mem, frame)?;
+            }
+            IDL_CON_variant => {
+                let n = read_leb128(&mut tb)?;
+                let i = read_leb128(buf)?;
+                if i >= n {
+                    return Err(IdlError::VariantTagTooLarge {
+                        tag: i,
+                        t,
+                        cases: n,
+                    }
+                    .into());
+                }
+                for _ in 0..i {
+                    read_leb128(&mut tb)?;
+                    read_sleb128(&mut tb)?;
+                }
+                let tag = read_leb128(&mut tb)?;
+                out.write_str("{")?;
+                self.key(tag, out)?;
+                stack.push(mem, ToJsonFrame::Single { close: "}" })?;
+                return Ok(Some(read_sleb128(&mut tb)?));
+            }
+            IDL_CON_func => {
+                if read_byte_tag(buf)? == 0 {
+                    return Err(IdlError::SkipReferences.into());
+                }
+                let bytes = read_principal(buf)?;
+                let (len, p) = read_text(buf)?;
+                out.write_str("[\"")?;
+                write_principal(out, bytes)?;
+                out.write_str("\",")?;
+                write_string(
+                    out,
+                    str::from_utf8_unchecked(slice::from_raw_parts(p, len as usize)),
+                )?;
+                out.write_str("]")?;
+            }
+            IDL_CON_service => {
+                let bytes = read_principal(buf)?;
+                out.write_str("\"")?;
+                write_principal(out, bytes)?;
+                out.write_str("\"")?;
+            }
+            _ => {
+                // Future type
+                skip_future(buf)?;
+                out.write_str("null")?;
+            }
+        }
+        Ok(None)
+    }
+}
+
+//
+// JSON to Candid
+//
+
+/// Why a JSON text cannot be converted to Candid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonError {
+    /// The expected types are not a Candid type description
+    Types(IdlErrorAt),
+    Syntax,
+    /// A value that is not of type `expected`
+    Mismatch {
+        expected: &'static str,
+    },
+    MissingField {
+        tag: u32,
+    },
+    UnknownCase {
+        tag: u32,
+    },
+    OutOfRange {
+        expected: &'static str,
+    },
+    /// Not the `expected` number of arguments
+    ArgumentCount {
+        expected: u32,
+    },
+    /// E.g. the decoding quota is exceeded
+    Idl(IdlError),
+}
+
+impl From<IdlError> for JsonError {
+    fn from(error: IdlError) -> Self {
+        JsonError::Idl(error)
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JsonError::Types(error) => write!(f, "invalid types: {}", error),
+            JsonError::Syntax => f.write_str("JSON syntax error"),
+            JsonError::Mismatch { expected } => write!(f, "JSON value is not of type {}", expected),
+            JsonError::MissingField { tag } => write!(f, "JSON object lacks field {:#x}", tag),
+            JsonError::UnknownCase { tag } => write!(f, "unknown variant case {:#x}", tag),
+            JsonError::OutOfRange { expected } => write!(f, "number out of range for {}", expected),
+            JsonError::ArgumentCount { expected } => write!(f, "expected {} arguments", expected),
+            JsonError::Idl(error) => error.fmt(f),
+        }
+    }
+}
+
+/// A `JsonError`, with where in the JSON text it happened (0 for `JsonError::Types`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsonErrorAt {
+    pub error: JsonError,
+    /// In bytes from the start of the JSON text
+    pub offset: u32,
+}
+
+impl fmt::Display for JsonErrorAt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error {
+            JsonError::Types(_) => self.error.fmt(f),
+            _ => write!(f, "{} (byte {} of the JSON text)", self.error, self.offset),
+        }
+    }
+}
+
+/// Converts the JSON text `json` to a Candid message, with the types of `types`, a blob with
+/// the header of a Candid message (the type table and the types of the arguments). Traps if the
+/// JSON text does not have these types.
+#[ic_mem_fn]
+pub unsafe fn idl_from_json<M: Memory>(mem: &mut M, json: Value, types: Value) -> Value {
+    let json = blob_of_text(mem, json).as_blob();
+    let json = str::from_utf8_unchecked(slice::from_raw_parts(
+        json.payload_const(),
+        json.len().as_usize(),
+    ));
+    let types = types.as_blob();
+    let start = types.payload_const() as *mut u8;
+    let end = start.add(types.len().as_usize());
+
+    // The message is measured first, and then written
+    let mut size = Encoder::measure();
+    let mut buf = Buf { ptr: start, end };
+    or_trap(idl_from_json_checked(mem, json, &mut buf, &mut size));
+    let blob = alloc_blob(mem, Bytes(size.size() as u32));
+    let bytes = slice::from_raw_parts_mut(blob.as_blob_mut().payload_addr(), size.size());
+    let mut buf = Buf { ptr: start, end };
+    or_trap(idl_from_json_checked(
+        mem,
+        json,
+        &mut buf,
+        &mut Encoder::new(bytes),
+    ));
+    blob
+}
+
+/// Like `idl_from_json`, but writing to `out`, and returning an error instead of trapping
+pub unsafe fn idl_from_json_checked<M: Memory>(
+    mem: &mut M,
+    json: &str,
+    types: *mut Buf,
+    out: &mut Encoder,
+) -> Result<(), JsonErrorAt> {
+    let header = (*types).ptr;
+    let mut typtbl = core::ptr::null_mut();
+    let mut n_types = 0;
+    let mut main_types = core::ptr::null_mut();
+    parse_idl_header_checked(
+        mem,
+        false,
+        types,
+        &mut typtbl,
+        &mut n_types,
+        &mut main_types,
+    )
+    .map_err(|error| JsonErrorAt {
+        error: JsonError::Types(error),
+        offset: 0,
+    })?;
+    out.write(slice::from_raw_parts(
+        header,
+        (*types).ptr as usize - header as usize,
+    ));
+
+    let mut from_json = FromJson {
+        json: json.as_bytes(),
+        pos: 0,
+        typtbl,
+        end: (*types).end,
+    };
+    let mut inline_frames = [FromJsonFrame::Single { close: 0 }; JSON_INLINE_FRAMES];
+    let mut stack = Stack::new(&mut inline_frames);
+    from_json
+        .convert(mem, &mut stack, main_types, out)
+        .map_err(|error| JsonErrorAt {
+            error,
+            offset: from_json.pos as u32,
+        })
+}
+
+/// Where a Candid message is written, or only measured
+pub struct Encoder<'a> {
+    bytes: Option<&'a mut [u8]>,
+    size: usize,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn measure() -> Self {
+        Encoder {
+            bytes: None,
+            size: 0,
+        }
+    }
+
+    /// Writes into `bytes`, which must be large enough
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Encoder {
+            bytes: Some(bytes),
+            size: 0,
+        }
+    }
+
+    /// Bytes written so far
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        if let Some(bytes) = &mut self.bytes {
+            bytes[self.size..self.size + data.len()].copy_from_slice(data);
+        }
+        self.size += data.len();
+    }
+
+    fn byte(&mut self, byte: u8) {
+        self.write(&[byte]);
+    }
+
+    fn leb128(&mut self, mut n: u32) {
+        loop {
+            let byte = (n & 0x7F) as u8;
+            n >>= 7;
+            if n == 0 {
+                self.byte(byte);
+                return;
+            }
+            self.byte(byte | 0x80);
+        }
+    }
+}
+
+impl Number {
+    /// Parses decimal digits, or `None` if the number has too many bits
+    fn parse(negative: bool, digits: &[u8]) -> Option<Number> {
+        let mut limbs = [0u32; NUMBER_LIMBS];
+        for &digit in digits {
+            let mut carry = (digit - b'0') as u64;
+            for limb in limbs.iter_mut() {
+                let n = *limb as u64 * 10 + carry;
+                *limb = n as u32;
+                carry = n >> 32;
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+        let negative = negative && limbs.iter().any(|&limb| limb != 0);
+        Some(Number { negative, limbs })
+    }
+
+    /// Writes the number in (signed) LEB128
+    fn encode(&self, signed: bool, out: &mut Encoder) {
+        // In two's complement, with a limb for the sign
+        let mut limbs = [0u32; NUMBER_LIMBS + 1];
+        limbs[..NUMBER_LIMBS].copy_from_slice(&self.limbs);
+        let fill = if self.negative { u32::MAX } else { 0 };
+        if self.negative {
+            let mut carry = 1u64;
+            for limb in limbs.iter_mut() {
+                let sum = !*limb as u64 + carry;
+                *limb = sum as u32;
+                carry = sum >> 32;
+            }
+        }
+        loop {
+            let byte = (limbs[0] & 0x7F) as u8;
+            for i in 0..limbs.len() {
+                let high = limbs.get(i + 1).copied().unwrap_or(fill);
+                limbs[i] = limbs[i] >> 7 | high << 25;
+            }
+            let done = limbs.iter().all(|&limb| limb == fill)
+                && (!signed || (byte & 0x40 != 0) == self.negative);
+            if done {
+                out.byte(byte);
+                return;
+            }
+            out.byte(byte | 0x80);
+        }
+    }
+
+    /// The number in `size` bytes, little-endian, or `None` if it does not fit
+    fn fixed(&self, size: u32, signed: bool) -> Option<[u8; 8]> {
+        if self.limbs[2..].iter().any(|&limb| limb != 0) {
+            return None;
+        }
+        let magnitude = self.limbs[0] as i128 | (self.limbs[1] as i128) << 32;
+        let value = if self.negative { -magnitude } else { magnitude };
+        let bits = 8 * size;
+        let (min, max) = if signed {
+            (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+        } else {
+            (0, (1i128 << bits) - 1)
+        };
+        if value < min || value > max {
+            return None;
+        }
+        Some((value as i64).to_le_bytes())
+    }
+}
+
+/// Compares what is written with `text`
+struct Matcher<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Write for Matcher<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.text[self.pos..].starts_with(s.as_bytes()) {
+            return Err(fmt::Error);
+        }
+        self.pos += s.len();
+        Ok(())
+    }
+}
+
+fn hex4(raw: &[u8], at: usize) -> Result<u32, JsonError> {
+    let digits = raw.get(at..at + 4).ok_or(JsonError::Syntax)?;
+    digits.iter().try_fold(0, |n, &d| {
+        let d = (d as char).to_digit(16).ok_or(JsonError::Syntax)?;
+        Ok(n * 16 + d)
+    })
+}
+
+/// Calls `f` with the UTF-8 of the contents of a JSON string, still escaped in `raw`
+fn unescape(raw: &[u8], f: &mut dyn FnMut(&[u8])) -> Result<(), JsonError> {
+    let mut i = 0;
+    while i < raw.len() {
+        let run = raw[i..].iter().take_while(|&&b| b != b'\\').count();
+        f(&raw[i..i + run]);
+        i += run;
+        if i == raw.len() {
+            break;
+        }
+
+        // An escape, of `len` bytes
+        let (c, len) = match raw.get(i + 1) {
+            Some(b'"') => ('"', 2),
+            Some(b'\\') => ('\\', 2),
+            Some(b'/') => ('/', 2),
+            Some(b'b') => ('\u{8}', 2),
+            Some(b'f') => ('\u{c}', 2),
+            Some(b'n') => ('\n', 2),
+            Some(b'r') => ('\r', 2),
+            Some(b't') => ('\t', 2),
+            Some(b'u') => {
+                let high = hex4(raw, i + 2)?;
+                if (0xD800..0xDC00).contains(&high) {
+                    // A surrogate pair
+                    if raw.get(i + 6..i + 8) != Some(b"\\u") {
+                        return Err(JsonError::Syntax);
+                    }
+                    let low = hex4(raw, i + 8)?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(JsonError::Syntax);
+                    }
+                    let c = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                    (char::from_u32(c).ok_or(JsonError::Syntax)?, 12)
+                } else {
+                    (char::from_u32(high).ok_or(JsonError::Syntax)?, 6)
+                }
+            }
+            _ => return Err(JsonError::Syntax),
+        };
+        f(c.encode_utf8(&mut [0; 4]).as_bytes());
+        i += len;
+    }
+    Ok(())
+}
+
+/// A value being converted from JSON
+#[derive(Clone, Copy)]
+enum FromJsonFrame {
+    /// The arguments, with `count` left, the type of the next one at `types`
+    Args {
+        types: *mut u8,
+        count: u32,
+        index: u32,
+    },
+    /// A record, with `fields` fields left, the next one at `tb` in the type table. The fields of
+    /// its object start at `object`, and the object ends at `end`.
+    Record {
+        tb: *mut u8,
+        fields: u32,
+        object: u32,
+        end: u32,
+    },
+    /// A vector with elements of type `it`, with `count` left after the current one
+    Vec { it: i32, count: u32 },
+    /// A value with one nested value, followed by `close`
+    Single { close: u8 },
+}
+
+struct FromJson<'a> {
+    json: &'a [u8],
+    /// The position in the JSON text, and of errors
+    pos: usize,
+    typtbl: *mut *mut u8,
+    /// The end of the type description
+    end: *mut u8,
+}
+
+impl<'a> FromJson<'a> {
+    /// Skips whitespace and returns the next byte, or 0 at the end
+    fn peek(&mut self) -> u8 {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.json.get(self.pos) {
+            self.pos += 1;
+        }
+        self.json.get(self.pos).copied().unwrap_or(0)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.peek() != byte {
+            return Err(JsonError::Syntax);
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consumes `word`, if it is next
+    fn word(&mut self, word: &[u8]) -> bool {
+        self.peek();
+        if self.json[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes a string, and returns its contents, still escaped
+    fn string(&mut self) -> Result<&'a [u8], JsonError> {
+        if self.peek() != b'"' {
+            return Err(JsonError::Syntax);
+        }
+        let start = self.pos + 1;
+        let mut i = start;
+        loop {
+            match self.json.get(i) {
+                None => return Err(JsonError::Syntax),
+                Some(b'"') => break,
+                Some(b'\\') => i += 2,
+                Some(&b) if b < 0x20 => return Err(JsonError::Syntax),
+                Some(_) => i += 1,
+            }
+        }
+        let raw = &self.json[start..i];
+        unescape(raw, &mut |_| {})?;
+        self.pos = i + 1;
+        Ok(raw)
+    }
+
+    /// Consumes a number, and returns it
+    fn number(&mut self) -> Result<&'a [u8], JsonError> {
+        self.peek();
+        let json = self.json;
+        let at = |i: usize| json.get(i).copied().unwrap_or(0);
+        let digits = |i: &mut usize| {
+            let start = *i;
+            while at(*i).is_ascii_digit() {
+                *i += 1;
+            }
+            *i > start
+        };
+
+        let start = self.pos;
+        let mut i = start;
+        if at(i) == b'-' {
+            i += 1;
+        }
+        if at(i) == b'0' {
+            i += 1;
+        } else if !digits(&mut i) {
+            return Err(JsonError::Syntax);
+        }
+        if at(i) == b'.' {
+            i += 1;
+            if !digits(&mut i) {
+                return Err(JsonError::Syntax);
+            }
+        }
+        if at(i) == b'e' || at(i) == b'E' {
+            i += 1;
+            if at(i) == b'+' || at(i) == b'-' {
+                i += 1;
+            }
+            if !digits(&mut i) {
+                return Err(JsonError::Syntax);
+            }
+        }
+        self.pos = i;
+        Ok(&json[start..i])
+    }
+
+    /// Consumes an integer, a number or a string of a decimal number
+    fn integer(&mut self, expected: &'static str) -> Result<Number, JsonError> {
+        let start = self.pos;
+        let text = match self.peek() {
+            b'"' => self.string()?,
+            b'-' | b'0'..=b'9' => self.number()?,
+            _ => return Err(JsonError::Mismatch { expected }),
+        };
+        let (negative, digits) = match text.split_first() {
+            Some((b'-', digits)) => (true, digits),
+            _ => (false, text),
+        };
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            self.pos = start;
+            return Err(JsonError::Mismatch { expected });
+        }
+        Number::parse(negative, digits).ok_or_else(|| {
+            self.pos = start;
+            JsonError::OutOfRange { expected }
+        })
+    }
+
+    /// Consumes the key of an object field, and returns its hash, or the number it is
+    fn key(&mut self) -> Result<u32, JsonError> {
+        let raw = self.string()?;
+        if !raw.is_empty() && raw.iter().all(u8::is_ascii_digit) {
+            if let Ok(tag) = unsafe { str::from_utf8_unchecked(raw) }.parse() {
+                return Ok(tag);
+            }
+        }
+        let mut hash = 0u32;
+        unescape(raw, &mut |bytes| {
+            hash = bytes
+                .iter()
+                .fold(hash, |h, &b| h.wrapping_mul(223).wrapping_add(b as u32))
+        })?;
+        Ok(hash)
+    }
+
+    /// Consumes a principal in the textual format, and writes it as a blob
+    fn principal(&mut self, out: &mut Encoder) -> Result<(), JsonError> {
+        let start = self.pos;
+        let invalid = |this: &mut Self| {
+            this.pos = start;
+            Err(JsonError::Mismatch {
+                expected: type_name(IDL_REF_principal),
+            })
+        };
+        if self.peek() != b'"' {
+            return invalid(self);
+        }
+        let text = self.string()?;
+
+        // Base32 of the checksum and at most 29 bytes
+        let mut decoded = [0u8; 4 + 29];
+        let mut len = 0;
+        let mut pending = 0u32;
+        let mut pending_bits = 0;
+        for &c in text {
+            let v = match c {
+                b'-' => continue,
+                b'a'..=b'z' => c - b'a',
+                b'2'..=b'7' => c - b'2' + 26,
+                _ => return invalid(self),
+            };
+            pending = pending << 5 | v as u32;
+            pending_bits += 5;
+            if pending_bits >= 8 {
+                pending_bits -= 8;
+                if len == decoded.len() {
+                    return invalid(self);
+                }
+                decoded[len] = (pending >> pending_bits) as u8;
+                len += 1;
+            }
+        }
+        if len < 4 {
+            return invalid(self);
+        }
+
+        // Only the canonical text, which includes the checksum
+        let bytes = &decoded[4..len];
+        let mut matcher = Matcher { text, pos: 0 };
+        if write_principal(&mut matcher, bytes).is_err() || matcher.pos != text.len() {
+            return invalid(self);
+        }
+        out.leb128(bytes.len() as u32);
+        out.write(bytes);
+        Ok(())
+    }
+
+    /// Consumes a blob in base64, and writes it
+    fn base64(&mut self, out: &mut Encoder) -> Result<(), JsonError> {
+        let start = self.pos;
+        let raw = self.string()?;
+        let data = raw
+            .strip_suffix(b"==")
+            .or_else(|| raw.strip_suffix(b"="))
+            .unwrap_or(raw);
+        let value = |c: u8| BASE64_ALPHABET.iter().position(|&a| a == c);
+        if data.len() % 4 == 1
+            || (data.len() < raw.len() && raw.len() % 4 != 0)
+            || !data.iter().all(|&c| value(c).is_some())
+        {
+            self.pos = start;
+            return Err(JsonError::Mismatch { expected: "blob" });
+        }
+
+        out.leb128((data.len() * 3 / 4) as u32);
+        let mut pending = 0u32;
+        let mut pending_bits = 0;
+        for &c in data {
+            pending = pending << 6 | value(c).unwrap() as u32;
+            pending_bits += 6;
+            if pending_bits >= 8 {
+                pending_bits -= 8;
+                out.byte((pending >> pending_bits) as u8);
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes a value, checking that it is JSON
+    unsafe fn skip_value<M: Memory>(&mut self, mem: &mut M) -> Result<(), JsonError> {
+        // Whether the arrays and objects being skipped are objects
+        let mut inline_frames = [false; JSON_INLINE_FRAMES];
+        let mut stack = Stack::new(&mut inline_frames);
+        loop {
+            charge(1)?;
+            match self.peek() {
+                c @ (b'{' | b'[') => {
+                    let object = c == b'{';
+                    self.pos += 1;
+                    if self.peek() == if object { b'}' } else { b']' } {
+                        self.pos += 1;
+                    } else {
+                        stack.push(mem, object)?;
+                        if object {
+                            self.string()?;
+                            self.expect(b':')?;
+                        }
+                        continue;
+                    }
+                }
+                b'"' => {
+                    self.string()?;
+                }
+                b'-' | b'0'..=b'9' => {
+                    self.number()?;
+                }
+                _ => {
+                    if !(self.word(b"true") || self.word(b"false") || self.word(b"null")) {
+                        return Err(JsonError::Syntax);
+                    }
+                }
+            }
+
+            // After a value, continue with the innermost array or object
+            loop {
+                let object = match stack.top() {
+                    None => return Ok(()),
+                    Some(object) => *object,
+                };
+                match self.peek() {
+                    b',' => {
+                        self.pos += 1;
+                        if object {
+                            self.string()?;
+                            self.expect(b':')?;
+                        }
+                        break;
+                    }
+                    b'}' if object => {
+                        self.pos += 1;
+                        stack.pop();
+                    }
+                    b']' if !object => {
+                        self.pos += 1;
+                        stack.pop();
+                    }
+                    _ => return Err(JsonError::Syntax),
+                }
+            }
+        }
+    }
+
+    /// Moves to the value of field `tag` of the object with fields from `object` on, if there is
+    /// one. The object is already checked.
+    unsafe fn find_field<M: Memory>(
+        &mut self,
+        mem: &mut M,
+        object: usize,
+        tag: u32,
+    ) -> Result<bool, JsonError> {
+        self.pos = object;
+        if self.peek() == b'}' {
+            return Ok(false);
+        }
+        loop {
+            let key = self.key()?;
+            self.expect(b':')?;
+            if key == tag {
+                return Ok(true);
+            }
+            self.skip_value(mem)?;
+            if self.peek() != b',' {
+                return Ok(false);
+            }
+            self.pos += 1;
+        }
+    }
+
+    unsafe fn convert<M: Memory>(
+        &mut self,
+        mem: &mut M,
+        stack: &mut Stack<FromJsonFrame>,
+        main_types: *mut u8,
+        out: &mut Encoder,
+    ) -> Result<(), JsonError> {
+        let mut types = Buf {
+            ptr: main_types,
+            end: self.end,
+        };
+        let count = read_leb128(&mut types)?;
+        self.expect(b'[')?;
+        let frame = FromJsonFrame::Args {
+            types: types.ptr,
+            count,
+            index: 0,
+        };
+        stack.push(mem, frame)?;
+
+        let mut next = None;
+        loop {
+            if let Some(t) = next {
+                next = self.value(mem, stack, out, t)?;
+                continue;
+            }
+
+            // Continue with the innermost unfinished value
+            let mut finished = false;
+            match stack.top() {
+                None => {
+                    if self.peek() != 0 || self.pos != self.json.len() {
+                        return Err(JsonError::Syntax);
+                    }
+                    return Ok(());
+                }
+                Some(FromJsonFrame::Args {
+                    types,
+                    count,
+                    index,
+                }) => {
+                    let c = self.peek();
+                    if *count == 0 {
+                        if c == b',' {
+                            return Err(JsonError::ArgumentCount { expected: *index });
+                        }
+                        self.expect(b']')?;
+                        finished = true;
+                    } else {
+                        if c == b']' {
+                            let expected = *index + *count;
+                            return Err(JsonError::ArgumentCount { expected });
+                        }
+                        if *index > 0 {
+                            self.expect(b',')?;
+                        }
+                        let mut b = Buf {
+                            ptr: *types,
+                            end: self.end,
+                        };
+                        next = Some(read_sleb128(&mut b)?);
+                        *types = b.ptr;
+                        *count -= 1;
+                        *index += 1;
+                    }
+                }
+                Some(FromJsonFrame::Record {
+                    tb,
+                    fields,
+                    object,
+                    end,
+                }) => {
+                    if *fields == 0 {
+                        self.pos = *end as usize;
+                        finished = true;
+                    } else {
+                        let mut b = Buf {
+                            ptr: *tb,
+                            end: self.end,
+                        };
+                        let tag = read_leb128(&mut b)?;
+                        let t = read_sleb128(&mut b)?;
+                        *tb = b.ptr;
+                        *fields -= 1;
+                        let object = *object as usize;
+                        if self.find_field(mem, object, tag)? {
+                            next = Some(t);
+                        } else if is_nullable(self.typtbl, self.end, t)? {
+                            if t != IDL_PRIM_null && t != IDL_PRIM_reserved {
+                                // `opt`, absent
+                                out.byte(0);
+                            }
+                        } else {
+                            self.pos = object - 1;
+                            return Err(JsonError::MissingField { tag });
+                        }
+                    }
+                }
+                Some(FromJsonFrame::Vec { it, count }) => {
+                    if *count == 0 {
+                        self.expect(b']')?;
+                        finished = true;
+                    } else {
+                        self.expect(b',')?;
+                        *count -= 1;
+                        next = Some(*it);
+                    }
+                }
+                Some(FromJsonFrame::Single { close }) => {
+                    let close = *close;
+                    self.expect(close)?;
+                    finished = true;
+                }
+            }
+            if finished {
+                stack.pop();
+            }
+        }
+    }
+
+    /// Converts a value of type `t`, or starts converting it, like `skip_one`
+    unsafe fn value<M: Memory>(
+        &mut self,
+        mem: &mut M,
+        stack: &mut Stack<FromJsonFrame>,
+        out: &mut Encoder,
+        t: i32,
+    ) -> Result<Option<i32>, JsonError> {
+        charge(1)?;
+        let c = self.peek();
+        let mut tb = Buf {
+            ptr: core::ptr::null_mut(),
+            end: self.end,
+        };
+        let con = if t < 0 {
+            t
+        } else {
+            tb.ptr = *self.typtbl.add(t as usize);
+            read_sleb128(&mut tb)?
+        };
+        let expected = type_name(con);
+        let mismatch = JsonError::Mismatch { expected };
+
+        match con {
+            IDL_PRIM_null => {
+                if !self.word(b"null") {
+                    return Err(mismatch);
+                }
+            }
+            IDL_PRIM_reserved => self.skip_value(mem)?,
+            IDL_PRIM_bool => {
+                if self.word(b"true") {
+                    out.byte(1);
+                } else if self.word(b"false") {
+                    out.byte(0);
+                } else {
+                    return Err(mismatch);
+                }
+            }
+            IDL_PRIM_nat | IDL_PRIM_int => {
+                let start = self.pos;
+                let n = self.integer(expected)?;
+                if n.negative && con == IDL_PRIM_nat {
+                    self.pos = start;
+                    return Err(JsonError::OutOfRange { expected });
+                }
+                n.encode(con == IDL_PRIM_int, out);
+            }
+            IDL_PRIM_nat8 | IDL_PRIM_nat16 | IDL_PRIM_nat32 | IDL_PRIM_nat64 | IDL_PRIM_int8
+            | IDL_PRIM_int16 | IDL_PRIM_int32 | IDL_PRIM_int64 => {
+                let (size, signed) = match con {
+                    IDL_PRIM_nat8 => (1, false),
+                    IDL_PRIM_nat16 => (2, false),
+                    IDL_PRIM_nat32 => (4, false),
+                    IDL_PRIM_nat64 => (8, false),
+                    IDL_PRIM_int8 => (1, true),
+                    IDL_PRIM_int16 => (2, true),
+                    IDL_PRIM_int32 => (4, true),
+                    _ => (8, true),
+                };
+                let start = self.pos;
+                let n = self.integer(expected)?;
+                match n.fixed(size, signed) {
+                    Some(bytes) => out.write(&bytes[..size as usize]),
+                    None => {
+                        self.pos = start;
+                        return Err(JsonError::OutOfRange { expected });
+                    }
+                }
+            }
+            IDL_PRIM_float32 | IDL_PRIM_float64 => {
+                if !matches!(c, b'-' | b'0'..=b'9') {
+                    return Err(mismatch);
+                }
+                let number = str::from_utf8_unchecked(self.number()?);
+                if con == IDL_PRIM_float32 {
+                    let x: f32 = number.parse().map_err(|_| JsonError::Syntax)?;
+                    out.write(&x.to_le_bytes());
+                } else {
+                    let x: f64 = number.parse().map_err(|_| JsonError::Syntax)?;
+                    out.write(&x.to_le_bytes());
+                }
+            }
+            IDL_PRIM_text => {
+                if c != b'"' {
+                    return Err(mismatch);
+                }
+                let raw = self.string()?;
+                let mut len = 0;
+                unescape(raw, &mut |bytes| len += bytes.len())?;
+                out.leb128(len as u32);
+                unescape(raw, &mut |bytes| out.write(bytes))?;
+            }
+            IDL_REF_principal => {
+                out.byte(1);
+                self.principal(out)?;
+            }
+            IDL_CON_opt => {
+                let it = read_sleb128(&mut tb)?;
+                if is_nullable(self.typtbl, self.end, it)? {
+                    if c != b'[' {
+                        return Err(mismatch);
+                    }
+                    self.pos += 1;
+                    if self.peek() == b']' {
+                        self.pos += 1;
+                        out.byte(0);
+                    } else {
+                        out.byte(1);
+                        stack.push(mem, FromJsonFrame::Single { close: b']' })?;
+                        return Ok(Some(it));
+                    }
+                } else if self.word(b"null") {
+                    out.byte(0);
+                } else {
+                    out.byte(1);
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_vec => {
+                let it = read_sleb128(&mut tb)?;
+                if it == IDL_PRIM_nat8 && c == b'"' {
+                    self.base64(out)?;
+                    return Ok(None);
+                }
+                if c != b'[' {
+                    return Err(mismatch);
+                }
+
+                // Count the elements first
+                let start = self.pos;
+                self.pos += 1;
+                let mut count = 0;
+                if self.peek() != b']' {
+                    loop {
+                        self.skip_value(mem)?;
+                        count += 1;
+                        if self.peek() != b',' {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                self.expect(b']')?;
+                self.pos = start + 1;
+
+                out.leb128(count);
+                if count == 0 {
+                    self.expect(b']')?;
+                } else {
+                    let frame = FromJsonFrame::Vec {
+                        it,
+                        count: count - 1,
+                    };
+                    stack.push(mem, frame)?;
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_record => {
+                if c != b'{' {
+                    return Err(mismatch);
+                }
+                let fields = read_leb128(&mut tb)?;
+                let object = self.pos + 1;
+                self.skip_value(mem)?;
+                let frame = FromJsonFrame::Record {
+                    tb: tb.ptr,
+                    fields,
+                    object: object as u32,
+                    end: self.pos as u32,
+                };
+                stack.push(mem, frame)?;
+            }
+            IDL_CON_variant => {
+                if c != b'{' {
+                    return Err(mismatch);
+                }
+                self.pos += 1;
+                let start = self.pos;
+                let tag = self.key()?;
+                self.expect(b':')?;
+                let n = read_leb128(&mut tb)?;
+                for i in 0..n {
+                    let case = read_leb128(&mut tb)?;
+                    let it = read_sleb128(&mut tb)?;
+                    if case == tag {
+                        out.leb128(i);
+                        stack.push(mem, FromJsonFrame::Single { close: b'}' })?;
+                        return Ok(Some(it));
+                    }
+                }
+                self.pos = start;
+                return Err(JsonError::UnknownCase { tag });
+            }
+            IDL_CON_func => {
+                if c != b'[' {
+                    return Err(mismatch);
+                }
+                self.pos += 1;
+                out.byte(1);
+                out.byte(1);
+                self.principal(out)?;
+                self.expect(b',')?;
+                if self.peek() != b'"' {
+                    return Err(JsonError::Mismatch {
+                        expected: type_name(IDL_PRIM_text),
+                    });
+                }
+                let raw = self.string()?;
+                let mut len = 0;
+                unescape(raw, &mut |bytes| len += bytes.len())?;
+                out.leb128(len as u32);
+                unescape(raw, &mut |bytes| out.write(bytes))?;
+                self.expect(b']')?;
+            }
+            IDL_CON_service => {
+                out.byte(1);
+                self.principal(out)?;
+            }
+            _ => return Err(mismatch),
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,756 @@
+//! Printing Candid messages in the Candid text format, e.g. `(record { 1 = "a"; 2 = vec { 1; 2 } })`,
+//! for logging and debugging.
+//!
+//! The values are walked like in `skip_any`, with an explicit stack. Field and case names are
+//! printed as their hashes, unless a table of names is given (see `IdlNames`). Values nested more
+//! deeply than a limit are printed as `...`, and the output is cut off when it reaches its limit.
+
+use super::{
+    advance, charge, leb128_len, parse_idl_header_checked, read_byte_tag, read_leb128,
+    read_sleb128, read_text, read_u32, read_u8, skip_any_checked, skip_future, trap_with_error,
+    IDL_CON_alias, IDL_CON_func, IDL_CON_opt, IDL_CON_record, IDL_CON_service, IDL_CON_variant,
+    IDL_CON_vec, IDL_PRIM_bool, IDL_PRIM_empty, IDL_PRIM_float32, IDL_PRIM_float64, IDL_PRIM_int,
+    IDL_PRIM_int16, IDL_PRIM_int32, IDL_PRIM_int64, IDL_PRIM_int8, IDL_PRIM_nat, IDL_PRIM_nat16,
+    IDL_PRIM_nat32, IDL_PRIM_nat64, IDL_PRIM_nat8, IDL_PRIM_null, IDL_PRIM_reserved, IDL_PRIM_text,
+    IDL_REF_principal, IdlError, IdlErrorAt, IdlPath, IdlPathSegment, IdlResult,
+};
+use crate::buf::Buf;
+use crate::memory::Memory;
+use crate::principal_id::update_crc32;
+use crate::text::{alloc_text_blob, for_each_leaf};
+use crate::types::{Bytes, Value};
+
+use core::cmp::min;
+use core::fmt::{self, Write};
+use core::slice;
+
+use motoko_rts_macros::ic_mem_fn;
+
+/// Deepest nesting printed, whatever the limit passed
+const MAX_PRINT_DEPTH: u32 = 32;
+
+/// Size of the `nat` and `int` values that are printed in full, in 32-bit limbs. Larger ones
+/// are printed as `<large number>`.
+pub(super) const NUMBER_LIMBS: usize = 16;
+
+/// Names of record fields and variant cases, looked up by their hash
+pub trait IdlNames {
+    /// Writes the name with hash `tag`, if there is one
+    unsafe fn write_name(&self, tag: u32, out: &mut dyn Write) -> Result<bool, fmt::Error>;
+}
+
+/// The Candid hash of a field or case name
+pub fn idl_hash(name: &[u8]) -> u32 {
+    name.iter()
+        .fold(0u32, |h, &b| h.wrapping_mul(223).wrapping_add(b as u32))
+}
+
+impl IdlNames for [&str] {
+    unsafe fn write_name(&self, tag: u32, out: &mut dyn Write) -> Result<bool, fmt::Error> {
+        match self.iter().find(|name| idl_hash(name.as_bytes()) == tag) {
+            Some(name) => out.write_str(name).map(|()| true),
+            None => Ok(false),
+        }
+    }
+}
+
+/// An array of `Text` values
+pub struct TextArray(pub Value);
+
+impl IdlNames for TextArray {
+    unsafe fn write_name(&self, tag: u32, out: &mut dyn Write) -> Result<bool, fmt::Error> {
+        let array = self.0.as_array();
+        for i in 0..array.len() {
+            let name = array.get(i);
+            let mut hash = 0u32;
+            for_each_leaf(name, &mut |leaf: &str| {
+                for &b in leaf.as_bytes() {
+                    hash = hash.wrapping_mul(223).wrapping_add(b as u32);
+                }
+            });
+            if hash == tag {
+                let mut result = Ok(());
+                for_each_leaf(name, &mut |leaf: &str| {
+                    if result.is_ok() {
+                        result = out.write_str(leaf);
+                    }
+                });
+                return result.map(|()| true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Writes into a buffer until it is full, then fails. A character that does not fit is dropped.
+pub struct TextWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    full: bool,
+}
+
+impl<'a> TextWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        TextWriter {
+            buf,
+            len: 0,
+            full: false,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole characters are written
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Whether something did not fit
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Ends the text in `...` if something did not fit, in place of the last characters. The
+    /// buffer must have room for `...`.
+    pub fn mark_truncated(&mut self) {
+        if !self.full {
+            return;
+        }
+        let text = self.as_str();
+        let mut n = min(self.len, self.buf.len() - 3);
+        while !text.is_char_boundary(n) {
+            n -= 1;
+        }
+        self.buf[n..n + 3].copy_from_slice(b"...");
+        self.len = n + 3;
+    }
+}
+
+impl<'a> Write for TextWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let space = self.buf.len() - self.len;
+        let mut n = min(space, s.len());
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        if n < s.len() {
+            self.full = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+/// Prints the Candid message `blob` as text of at most `max_len` bytes, ending in `...` if it is
+/// cut off, with values nested up to `max_depth` levels. `names` is an array of `Text`, the
+/// names of record fields and variant cases. Traps if the message is malformed.
+#[ic_mem_fn]
+pub unsafe fn idl_print<M: Memory>(
+    mem: &mut M,
+    blob: Value,
+    names: Value,
+    max_depth: u32,
+    max_len: u32,
+) -> Value {
+    let blob = blob.as_blob();
+    let mut buf = Buf {
+        ptr: blob.payload_const() as *mut u8,
+        end: blob.payload_const().add(blob.len().as_usize()) as *mut u8,
+    };
+
+    // The text is shrunk to the printed size afterwards
+    let max_len = max_len.max(3);
+    let text = alloc_text_blob(mem, Bytes(max_len));
+    let bytes = slice::from_raw_parts_mut(text.as_blob_mut().payload_addr(), max_len as usize);
+    let mut out = TextWriter::new(bytes);
+    if let Err(err) = idl_print_checked(mem, &mut buf, &TextArray(names), max_depth, &mut out) {
+        trap_with_error(err)
+    }
+    out.mark_truncated();
+    text.as_blob_mut().shrink(Bytes(out.as_str().len() as u32));
+    text
+}
+
+/// Like `idl_print`, but printing into `out`, and returning an error instead of trapping. A full
+/// `out` is not an error.
+pub unsafe fn idl_print_checked<M: Memory, N: IdlNames + ?Sized>(
+    mem: &mut M,
+    buf: *mut Buf,
+    names: &N,
+    max_depth: u32,
+    out: &mut TextWriter,
+) -> Result<(), IdlErrorAt> {
+    let mut typtbl = core::ptr::null_mut();
+    let mut n_types = 0;
+    let mut main_types = core::ptr::null_mut();
+    parse_idl_header_checked(mem, false, buf, &mut typtbl, &mut n_types, &mut main_types)?;
+
+    let mut types = Buf {
+        ptr: main_types,
+        end: (*buf).end,
+    };
+    let count = read_leb128(&mut types).unwrap(); // checked by `parse_idl_header`
+
+    let mut printer = Printer {
+        buf,
+        typtbl,
+        names,
+        max_depth: min(max_depth, MAX_PRINT_DEPTH),
+        frames: [Frame::Single { close: "" }; MAX_PRINT_DEPTH as usize + 1],
+        len: 0,
+        current: -1,
+    };
+    printer.push(Frame::Args {
+        types: types.ptr,
+        count,
+        index: 0,
+    });
+
+    match printer.print(mem, out) {
+        Ok(()) => Ok(()),
+        Err(Stop::Full) => Ok(()),
+        Err(Stop::Error(error)) => Err(printer.locate(error)),
+    }
+}
+
+/// Why printing stops early
+pub(super) enum Stop {
+    Full,
+    Error(IdlError),
+}
+
+impl From<IdlError> for Stop {
+    fn from(error: IdlError) -> Self {
+        Stop::Error(error)
+    }
+}
+
+impl From<fmt::Error> for Stop {
+    fn from(_: fmt::Error) -> Self {
+        Stop::Full
+    }
+}
+
+/// A value being printed
+#[derive(Clone, Copy)]
+enum Frame {
+    /// The arguments of the message, with `count` left, the type of the next one at `types`
+    Args {
+        types: *mut u8,
+        count: u32,
+        index: u32,
+    },
+    /// A record of type `t`, with `fields` fields left, the next one at `tb` in the type table.
+    /// `tag` is the field being printed.
+    Record {
+        t: i32,
+        tb: *mut u8,
+        fields: u32,
+        tag: u32,
+        index: u32,
+    },
+    /// A vector of type `t` with elements of type `it`, with `count` left after the one at `index`
+    Vec {
+        t: i32,
+        it: i32,
+        count: u32,
+        index: u32,
+    },
+    /// A value with one nested value, followed by `close`
+    Single { close: &'static str },
+}
+
+struct Printer<'a, N: IdlNames + ?Sized> {
+    buf: *mut Buf,
+    typtbl: *mut *mut u8,
+    names: &'a N,
+    max_depth: u32,
+    /// The stack. Its depth is limited by `max_depth`, so it does not need to grow.
+    frames: [Frame; MAX_PRINT_DEPTH as usize + 1],
+    len: u32,
+    /// The innermost type in the type table being printed
+    current: i32,
+}
+
+impl<'a, N: IdlNames + ?Sized> Printer<'a, N> {
+    fn push(&mut self, frame: Frame) {
+        self.frames[self.len as usize] = frame;
+        self.len += 1;
+    }
+
+    /// Where printing failed
+    unsafe fn locate(&self, error: IdlError) -> IdlErrorAt {
+        let mut path = IdlPath::EMPTY;
+        for frame in &self.frames[..self.len as usize] {
+            match *frame {
+                Frame::Args { index, .. } if index > 0 => path.push(IdlPathSegment::Arg(index - 1)),
+                Frame::Record { tag, index, .. } if index > 0 => {
+                    path.push(IdlPathSegment::Field(tag))
+                }
+                Frame::Vec { index, .. } => path.push(IdlPathSegment::Element(index)),
+                _ => {}
+            }
+        }
+        let type_index = if self.current >= 0 {
+            Some(self.current as u32)
+        } else {
+            None
+        };
+        IdlErrorAt::new(error, (*self.buf).ptr, type_index, path)
+    }
+
+    unsafe fn print<M: Memory>(&mut self, mem: &mut M, out: &mut TextWriter) -> Result<(), Stop> {
+        let buf = self.buf;
+        let mut next = None;
+        loop {
+            if let Some(t) = next {
+                if t >= 0 {
+                    self.current = t;
+                }
+                next = self.print_one(mem, out, t)?;
+                continue;
+            }
+
+            // Continue with the innermost unfinished value
+            let names = self.names;
+            let top = self.len as usize - 1;
+            let mut finished = false;
+            match &mut self.frames[top] {
+                Frame::Args {
+                    types,
+                    count,
+                    index,
+                } => {
+                    if *count == 0 {
+                        out.write_str(if *index == 0 { "()" } else { ")" })?;
+                        return Ok(());
+                    }
+                    out.write_str(if *index == 0 { "(" } else { ", " })?;
+                    let mut b = Buf {
+                        ptr: *types,
+                        end: (*buf).end,
+                    };
+                    next = Some(read_sleb128(&mut b)?);
+                    *types = b.ptr;
+                    *count -= 1;
+                    *index += 1;
+                }
+                Frame::Record {
+                    t,
+                    tb,
+                    fields,
+                    tag,
+                    index,
+                } => {
+                    self.current = *t;
+                    if *fields == 0 {
+                        out.write_str(if *index == 0 { "}" } else { " }" })?;
+                        finished = true;
+                    } else {
+                        out.write_str(if *index == 0 { " " } else { "; " })?;
+                        let mut b = Buf {
+                            ptr: *tb,
+                            end: (*buf).end,
+                        };
+                        *tag = read_leb128(&mut b)?;
+                        next = Some(read_sleb128(&mut b)?);
+                        *tb = b.ptr;
+                        *fields -= 1;
+                        *index += 1;
+                        if !names.write_name(*tag, out)? {
+                            write!(out, "{}", *tag)?;
+                        }
+                        out.write_str(" = ")?;
+                    }
+                }
+                Frame::Vec {
+                    t,
+                    it,
+                    count,
+                    index,
+                } => {
+                    self.current = *t;
+                    if *count == 0 {
+                        out.write_str(" }")?;
+                        finished = true;
+                    } else {
+                        out.write_str("; ")?;
+                        *count -= 1;
+                        *index += 1;
+                        next = Some(*it);
+                    }
+                }
+                Frame::Single { close } => {
+                    out.write_str(close)?;
+                    finished = true;
+                }
+            }
+            if finished {
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Prints a value of type `t`, or starts printing it. Values with nested values push a frame
+    /// for them, and like in `skip_one`, return the type of the first one if it is printed next.
+    unsafe fn print_one<M: Memory>(
+        &mut self,
+        mem: &mut M,
+        out: &mut TextWriter,
+        t: i32,
+    ) -> Result<Option<i32>, Stop> {
+        let buf = self.buf;
+        charge(1)?;
+        if t < 0 {
+            match t {
+                IDL_PRIM_null | IDL_PRIM_reserved => out.write_str("null")?,
+                IDL_PRIM_bool => match read_byte_tag(buf)? {
+                    0 => out.write_str("false")?,
+                    _ => out.write_str("true")?,
+                },
+                IDL_PRIM_nat | IDL_PRIM_int => match Number::read(buf, t == IDL_PRIM_int)? {
+                    Some(n) => write!(out, "{}", n)?,
+                    None => out.write_str("<large number>")?,
+                },
+                IDL_PRIM_nat8 => write!(out, "{}", read_u8(buf)?)?,
+                IDL_PRIM_nat16 => write!(out, "{}", u16::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_nat32 => write!(out, "{}", read_u32(buf)?)?,
+                IDL_PRIM_nat64 => write!(out, "{}", u64::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_int8 => write!(out, "{}", read_u8(buf)? as i8)?,
+                IDL_PRIM_int16 => write!(out, "{}", i16::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_int32 => write!(out, "{}", read_u32(buf)? as i32)?,
+                IDL_PRIM_int64 => write!(out, "{}", i64::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_float32 => write!(out, "{:?}", f32::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_float64 => write!(out, "{:?}", f64::from_le_bytes(read_bytes(buf)?))?,
+                IDL_PRIM_text => {
+                    let (len, p) = read_text(buf)?;
+                    let text =
+                        core::str::from_utf8_unchecked(slice::from_raw_parts(p, len as usize));
+                    print_text(out, text)?;
+                }
+                IDL_PRIM_empty => return Err(IdlError::SkipEmpty.into()),
+                IDL_REF_principal => {
+                    out.write_str("principal ")?;
+                    print_principal(out, buf)?;
+                }
+                _ => return Err(IdlError::UnknownPrim.into()),
+            }
+            return Ok(None);
+        }
+
+        let mut tb = Buf {
+            ptr: *self.typtbl.add(t as usize),
+            end: (*buf).end,
+        };
+        let tc = read_sleb128(&mut tb)?;
+
+        if self.len > self.max_depth
+            && matches!(
+                tc,
+                IDL_CON_opt | IDL_CON_vec | IDL_CON_record | IDL_CON_variant
+            )
+        {
+            out.write_str("...")?;
+            if let Err(err) = skip_any_checked(mem, buf, self.typtbl, t) {
+                return Err(err.error.into());
+            }
+            return Ok(None);
+        }
+
+        match tc {
+            IDL_CON_opt => {
+                let it = read_sleb128(&mut tb)?;
+                if read_byte_tag(buf)? == 0 {
+                    out.write_str("null")?;
+                } else {
+                    out.write_str("opt ")?;
+                    self.push(Frame::Single { close: "" });
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_vec => {
+                let it = read_sleb128(&mut tb)?;
+                let count = read_leb128(buf)?;
+                if it == IDL_PRIM_nat8 {
+                    let p = (*buf).ptr;
+                    advance(buf, count)?;
+                    charge(count as u64)?;
+                    out.write_str("blob ")?;
+                    print_blob(out, slice::from_raw_parts(p, count as usize))?;
+                } else if count == 0 {
+                    out.write_str("vec {}")?;
+                } else {
+                    out.write_str("vec { ")?;
+                    self.push(Frame::Vec {
+                        t,
+                        it,
+                        count: count - 1,
+                        index: 0,
+                    });
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_record => {
+                let fields = read_leb128(&mut tb)?;
+                out.write_str("record {")?;
+                self.push(Frame::Record {
+                    t,
+                    tb: tb.ptr,
+                    fields,
+                    tag: 0,
+                    index: 0,
+                });
+            }
+            IDL_CON_variant => {
+                let n = read_leb128(&mut tb)?;
+                let i = read_leb128(buf)?;
+                if i >= n {
+                    return Err(IdlError::VariantTagTooLarge {
+                        tag: i,
+                        t,
+                        cases: n,
+                    }
+                    .into());
+                }
+                for _ in 0..i {
+                    read_leb128(&mut tb)?;
+                    read_sleb128(&mut tb)?;
+                }
+                let tag = read_leb128(&mut tb)?;
+                let it = read_sleb128(&mut tb)?;
+                out.write_str("variant { ")?;
+                if !self.names.write_name(tag, out)? {
+                    write!(out, "{}", tag)?;
+                }
+                if it == IDL_PRIM_null {
+                    out.write_str(" }")?;
+                } else {
+                    out.write_str(" = ")?;
+                    self.push(Frame::Single { close: " }" });
+                    return Ok(Some(it));
+                }
+            }
+            IDL_CON_func => {
+                if read_byte_tag(buf)? == 0 {
+                    return Err(IdlError::SkipReferences.into());
+                }
+                out.write_str("func ")?;
+                print_principal(out, buf)?;
+                out.write_str(".")?;
+                let (len, p) = read_text(buf)?;
+                let method = core::str::from_utf8_unchecked(slice::from_raw_parts(p, len as usize));
+                if is_identifier(method) {
+                    out.write_str(method)?;
+                } else {
+                    print_text(out, method)?;
+                }
+            }
+            IDL_CON_service => {
+                out.write_str("service ")?;
+                print_principal(out, buf)?;
+            }
+            IDL_CON_alias => {
+                // See Note [mutable stable values] in codegen/compile.ml
+                let it = read_sleb128(&mut tb)?;
+                if read_byte_tag(buf)? == 0 {
+                    advance(buf, 8)?;
+                    self.push(Frame::Single { close: "" });
+                    return Ok(Some(it));
+                }
+                advance(buf, 4)?;
+                out.write_str("<alias>")?;
+            }
+            _ => {
+                // Future type
+                skip_future(buf)?;
+                out.write_str("<future type>")?;
+            }
+        }
+        Ok(None)
+    }
+}
+
+pub(super) unsafe fn read_bytes<const N: usize>(buf: *mut Buf) -> IdlResult<[u8; N]> {
+    let p = (*buf).ptr;
+    advance(buf, N as u32)?;
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(slice::from_raw_parts(p, N));
+    Ok(bytes)
+}
+
+/// A `nat` or `int` of up to `32 * NUMBER_LIMBS` bits
+pub(super) struct Number {
+    pub(super) negative: bool,
+    /// The magnitude, least significant limb first
+    pub(super) limbs: [u32; NUMBER_LIMBS],
+}
+
+impl Number {
+    /// Reads a `nat` or `int`, or `None` if it has too many bits
+    pub(super) unsafe fn read(buf: *mut Buf, signed: bool) -> IdlResult<Option<Number>> {
+        let len = leb128_len(buf)?;
+        let bytes = slice::from_raw_parts((*buf).ptr, len as usize);
+        advance(buf, len)?;
+
+        let bits = 7 * len as usize;
+        let too_large = if signed {
+            bits > 32 * NUMBER_LIMBS
+        } else {
+            // Leading zeros do not count
+            let significant = match bytes.iter().rposition(|&b| b & 0x7F != 0) {
+                Some(i) => 7 * i + 8 - (bytes[i] & 0x7F).leading_zeros() as usize,
+                None => 0,
+            };
+            significant > 32 * NUMBER_LIMBS
+        };
+        if too_large {
+            return Ok(None);
+        }
+
+        let mut limbs = [0u32; NUMBER_LIMBS];
+        for (i, &byte) in bytes.iter().enumerate() {
+            let group = (byte & 0x7F) as u64;
+            let bit = 7 * i;
+            if group == 0 || bit >= 32 * NUMBER_LIMBS {
+                continue;
+            }
+            let wide = group << (bit % 32);
+            limbs[bit / 32] |= wide as u32;
+            if bit / 32 + 1 < NUMBER_LIMBS {
+                limbs[bit / 32 + 1] |= (wide >> 32) as u32;
+            }
+        }
+
+        // Negative numbers are in two's complement, in `bits` bits
+        let negative = signed && bytes[len as usize - 1] & 0x40 != 0;
+        if negative {
+            let mut carry = 1u64;
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                let width = min(bits.saturating_sub(32 * i), 32);
+                let mask = if width == 32 {
+                    u32::MAX
+                } else {
+                    (1u32 << width) - 1
+                };
+                let sum = (!*limb & mask) as u64 + carry;
+                *limb = sum as u32 & mask;
+                carry = sum >> 32;
+            }
+        }
+        Ok(Some(Number { negative, limbs }))
+    }
+}
+
+/// In decimal
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        // Chunks of 9 decimal digits, least significant first
+        let mut limbs = self.limbs;
+        let mut chunks = [0u32; (32 * NUMBER_LIMBS + 28) / 29];
+        let mut n_chunks = 0;
+        loop {
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let n = (remainder << 32) | *limb as u64;
+                *limb = (n / 1_000_000_000) as u32;
+                remainder = n % 1_000_000_000;
+            }
+            chunks[n_chunks] = remainder as u32;
+            n_chunks += 1;
+            if limbs.iter().all(|&limb| limb == 0) {
+                break;
+            }
+        }
+        write!(f, "{}", chunks[n_chunks - 1])?;
+        for chunk in chunks[..n_chunks - 1].iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+fn print_text(out: &mut TextWriter, text: &str) -> fmt::Result {
+    out.write_str("\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_str("\"")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn print_blob(out: &mut TextWriter, bytes: &[u8]) -> fmt::Result {
+    out.write_str("\"")?;
+    for &b in bytes {
+        if b.is_ascii_graphic() && b != b'"' && b != b'\\' || b == b' ' {
+            out.write_char(b as char)?;
+        } else {
+            write!(out, "\\{:02x}", b)?;
+        }
+    }
+    out.write_str("\"")
+}
+
+/// Reads a principal, a byte tag and a blob
+pub(super) unsafe fn read_principal<'a>(buf: *mut Buf) -> IdlResult<&'a [u8]> {
+    if read_byte_tag(buf)? == 0 {
+        return Err(IdlError::SkipReferences);
+    }
+    let len = read_leb128(buf)?;
+    let p = (*buf).ptr;
+    advance(buf, len)?;
+    Ok(slice::from_raw_parts(p, len as usize))
+}
+
+/// Writes a principal in the textual format, e.g. `aaaaa-aa`
+pub(super) fn write_principal<W: Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    // Base32 of the CRC32 (big-endian) and the bytes, lowercase, in groups of 5 characters
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let checksum = (!update_crc32(!0, bytes)).to_be_bytes();
+    let mut pending = 0u32;
+    let mut pending_bits = 0;
+    let mut written = 0;
+    let mut emit = |out: &mut W, c: u8| -> fmt::Result {
+        if written > 0 && written % 5 == 0 {
+            out.write_str("-")?;
+        }
+        written += 1;
+        out.write_char(ALPHABET[c as usize] as char)
+    };
+    for &b in checksum.iter().chain(bytes) {
+        pending = (pending << 8) | b as u32;
+        pending_bits += 8;
+        while pending_bits >= 5 {
+            pending_bits -= 5;
+            emit(out, ((pending >> pending_bits) & 0x1F) as u8)?;
+        }
+    }
+    if pending_bits > 0 {
+        emit(out, ((pending << (5 - pending_bits)) & 0x1F) as u8)?;
+    }
+    Ok(())
+}
+
+unsafe fn print_principal(out: &mut TextWriter, buf: *mut Buf) -> Result<(), Stop> {
+    let bytes = read_principal(buf)?;
+    out.write_str("\"")?;
+    write_principal(out, bytes)?;
+    out.write_str("\"")?;
+    Ok(())
+}
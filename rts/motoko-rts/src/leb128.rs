@@ -81,7 +81,11 @@ pub unsafe fn sleb128_decode_checked(buf: *mut Buf) -> Option<i32> {
 
         // Overflow check ported from Wasm reference implementation:
         // https://github.com/WebAssembly/spec/blob/f9770eb75117cac0c878feaa5eaf4a4d9dda61f5/interpreter/binary/decode.ml#L89-L98
-        if shift == 28 && (byte & 0b0111_1000 != 0 && byte & 0b0111_1000 != 0b0111_1000) {
+        // The 5th byte also needs to be the last.
+        if shift == 28
+            && (byte & 0b1000_0000 != 0
+                || (byte & 0b0111_1000 != 0 && byte & 0b0111_1000 != 0b0111_1000))
+        {
             return None;
         }
 
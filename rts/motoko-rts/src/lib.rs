@@ -14,23 +14,29 @@ pub mod bitrel;
 #[cfg(feature = "ic")]
 mod blob_iter;
 pub mod buf;
+pub mod cbor;
 mod char;
+pub mod char_props;
 pub mod constants;
 pub mod continuation_table;
 #[cfg(feature = "ic")]
 mod float;
+pub mod float_conv;
 pub mod gc;
-#[cfg(feature = "ic")]
-mod idl;
+pub mod hash;
+pub mod idl;
 pub mod leb128;
+pub mod lz4;
 mod mem_utils;
 pub mod memory;
 pub mod principal_id;
+pub mod region;
 mod static_checks;
 pub mod stream;
 pub mod text;
 pub mod text_iter;
 mod tommath_bindings;
+pub mod transcode;
 pub mod types;
 pub mod utf8;
 mod visitor;
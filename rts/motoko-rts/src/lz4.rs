@@ -0,0 +1,199 @@
+//! LZ4 block compression, used to compress streams written to stable memory
+//!
+//! A block is a sequence of sequences, each consisting of a token byte, literals, and a match:
+//!
+//!      ┌───────┬──────────────┬──────────┬─────────┬──────────────┐
+//!      │ token │ literal len+ │ literals │ offset  │ match len+   │
+//!      └───────┴──────────────┴──────────┴─────────┴──────────────┘
+//!
+//! The high nibble of the token is the number of literals, the low nibble is the match length
+//! minus 4. A nibble of 15 is followed by bytes that are added to the length, up to and including
+//! the first byte that is not 255. The offset is a 16-bit little-endian distance back into the
+//! output. The last sequence only has literals.
+//!
+//! The compressor is the greedy single-probe hash table search of the reference implementation's
+//! fast mode. The decompressor checks all offsets and lengths, so corrupted input results in an
+//! error instead of memory accesses out of bounds.
+//!
+//! Reference: https://github.com/lz4/lz4/blob/dev/doc/lz4_Block_format.md
+
+/// Maximum size of the input of `compress`, so that positions fit in the hash table entries
+pub const MAX_INPUT_SIZE: usize = 1 << 16;
+
+const MIN_MATCH: usize = 4;
+
+/// The last match needs to start at least this many bytes before the end of the input
+const MF_LIMIT: usize = 12;
+
+/// The last bytes of the input are always literals
+const LAST_LITERALS: usize = 5;
+
+const HASH_LOG: u32 = 12;
+
+/// Maximum size of the output of `compress` for an input of the given size
+pub const fn compress_bound(size: usize) -> usize {
+    size + size / 255 + 16
+}
+
+/// Why `decompress` rejected its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecompressError {
+    /// The input ends in the middle of a sequence
+    InputTruncated,
+    /// A match refers to data before the start of the output
+    InvalidOffset,
+    /// The output does not fit in the buffer
+    OutputOverflow,
+}
+
+fn read_u32(bytes: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+}
+
+fn hash(sequence: u32) -> usize {
+    (sequence.wrapping_mul(2654435761) >> (32 - HASH_LOG)) as usize
+}
+
+struct Output<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Output<'a> {
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    /// Length bytes following a nibble of 15
+    fn push_length(&mut self, mut len: usize) {
+        while len >= 255 {
+            self.push(255);
+            len -= 255;
+        }
+        self.push(len as u8);
+    }
+
+    fn push_sequence(&mut self, literals: &[u8], match_: Option<(u16, usize)>) {
+        let literal_nibble = core::cmp::min(literals.len(), 15) as u8;
+        let match_nibble = match match_ {
+            None => 0,
+            Some((_, len)) => core::cmp::min(len - MIN_MATCH, 15) as u8,
+        };
+        self.push((literal_nibble << 4) | match_nibble);
+        if literals.len() >= 15 {
+            self.push_length(literals.len() - 15);
+        }
+        self.extend(literals);
+        if let Some((offset, len)) = match_ {
+            self.extend(&offset.to_le_bytes());
+            if len - MIN_MATCH >= 15 {
+                self.push_length(len - MIN_MATCH - 15);
+            }
+        }
+    }
+}
+
+/// Compresses `src` into `dst`, returning the size of the compressed data. `src` must be at most
+/// `MAX_INPUT_SIZE` bytes, and `dst` at least `compress_bound(src.len())` bytes.
+pub fn compress(src: &[u8], dst: &mut [u8]) -> usize {
+    assert!(src.len() <= MAX_INPUT_SIZE);
+    assert!(dst.len() >= compress_bound(src.len()));
+
+    let mut out = Output { bytes: dst, len: 0 };
+
+    // Last position seen for each hash of 4 bytes. Entries may belong to other sequences with the
+    // same hash (or be unused), so candidates are checked.
+    let mut table = [0u16; 1 << HASH_LOG];
+
+    let mut anchor = 0; // start of the pending literals
+    let mut i = 0;
+    while i + MF_LIMIT <= src.len() {
+        let sequence = read_u32(src, i);
+        let h = hash(sequence);
+        let candidate = usize::from(table[h]);
+        table[h] = i as u16;
+
+        if candidate < i && read_u32(src, candidate) == sequence {
+            let mut len = MIN_MATCH;
+            while i + len < src.len() - LAST_LITERALS && src[candidate + len] == src[i + len] {
+                len += 1;
+            }
+            out.push_sequence(&src[anchor..i], Some(((i - candidate) as u16, len)));
+            i += len;
+            anchor = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    out.push_sequence(&src[anchor..], None);
+    out.len
+}
+
+/// Reads a length following a nibble of 15
+fn read_length(src: &[u8], i: &mut usize, nibble: u8) -> Result<usize, DecompressError> {
+    let mut len = usize::from(nibble);
+    if nibble == 15 {
+        loop {
+            let byte = *src.get(*i).ok_or(DecompressError::InputTruncated)?;
+            *i += 1;
+            len += usize::from(byte);
+            if byte != 255 {
+                break;
+            }
+        }
+    }
+    Ok(len)
+}
+
+/// Decompresses `src` into `dst`, returning the size of the decompressed data
+pub fn decompress(src: &[u8], dst: &mut [u8]) -> Result<usize, DecompressError> {
+    let mut i = 0;
+    let mut out = 0;
+
+    loop {
+        let token = *src.get(i).ok_or(DecompressError::InputTruncated)?;
+        i += 1;
+
+        let literals = read_length(src, &mut i, token >> 4)?;
+        if src.len() - i < literals {
+            return Err(DecompressError::InputTruncated);
+        }
+        if dst.len() - out < literals {
+            return Err(DecompressError::OutputOverflow);
+        }
+        dst[out..out + literals].copy_from_slice(&src[i..i + literals]);
+        i += literals;
+        out += literals;
+
+        if i == src.len() {
+            // The last sequence has no match
+            return Ok(out);
+        }
+
+        if src.len() - i < 2 {
+            return Err(DecompressError::InputTruncated);
+        }
+        let offset = usize::from(u16::from_le_bytes([src[i], src[i + 1]]));
+        i += 2;
+        if offset == 0 || offset > out {
+            return Err(DecompressError::InvalidOffset);
+        }
+
+        let len = read_length(src, &mut i, token & 0xF)? + MIN_MATCH;
+        if dst.len() - out < len {
+            return Err(DecompressError::OutputOverflow);
+        }
+        // Byte by byte, as the match may overlap with its own output
+        for j in out..out + len {
+            dst[j] = dst[j - offset];
+        }
+        out += len;
+    }
+}
@@ -17,16 +17,18 @@ pub unsafe extern "C" fn compute_crc32(blob: Value) -> u32 {
     }
 
     let blob = blob.as_blob();
-    let len = blob.len();
+    let bytes = core::slice::from_raw_parts(blob.payload_const(), blob.len().as_usize());
 
-    let mut crc: u32 = !0;
+    !update_crc32(!0, bytes)
+}
 
-    for i in 0..len.as_u32() {
-        let octet = blob.get(i);
+/// Continues a CRC32 computation with more bytes. The CRC32 of a byte sequence is
+/// `!update_crc32(!0, bytes)`, or the same with `update_crc32` applied to consecutive chunks.
+pub fn update_crc32(mut crc: u32, bytes: &[u8]) -> u32 {
+    for octet in bytes {
         crc = (crc >> 8) ^ CRC_TABLE[usize::from((crc & 0xFF) as u8 ^ octet)];
     }
-
-    !crc
+    crc
 }
 
 static CRC_TABLE: [u32; 256] = [
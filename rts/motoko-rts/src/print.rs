@@ -78,6 +78,15 @@ impl<'a> WriteBuf<'a> {
     pub(crate) unsafe fn print(&self) {
         print_ptr(self.buf.as_ptr() as usize, self.offset as u32)
     }
+
+    /// The text written so far. A character cut off at the end of the buffer is dropped.
+    pub(crate) fn as_str(&self) -> &str {
+        let bytes = &self.buf[..self.offset];
+        match core::str::from_utf8(bytes) {
+            Ok(str) => str,
+            Err(err) => unsafe { core::str::from_utf8_unchecked(&bytes[..err.valid_up_to()]) },
+        }
+    }
 }
 
 impl<'a> fmt::Write for WriteBuf<'a> {
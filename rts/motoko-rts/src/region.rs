@@ -0,0 +1,289 @@
+//! Regions: independently growable areas of stable memory
+//!
+//! Stable memory is divided into blocks of `BLOCK_PAGES` pages. Block 0 holds the metadata, the
+//! other blocks belong to a region each. The pages of a region are the pages of its blocks, in
+//! the order in which the blocks were allocated, so growing a region moves neither its data nor
+//! other regions.
+//!
+//! Metadata (block 0):
+//!
+//!      ┌───────┬─────────┬──────────────┬─────────────┬─────────────────┬─────────────────┐
+//!      │ magic │ version │ region count │ block count │ block owners... │ region sizes... │
+//!      └───────┴─────────┴──────────────┴─────────────┴─────────────────┴─────────────────┘
+//!
+//! The first four fields are 32 bits. Block owners are `MAX_BLOCKS` 16-bit region ids
+//! (`NO_REGION` for block 0 and blocks not allocated by regions), region sizes are `MAX_REGIONS`
+//! 64-bit page counts. All little-endian (like Wasm memory). The metadata is written to stable
+//! memory on every change, so it survives upgrades, and `region_init` reads it back.
+//!
+//! Blocks are allocated with moc's logical grow of stable memory. The stable variables are
+//! serialised after the logical end of stable memory (see `Stream`), so they never overwrite
+//! regions. Pages grown otherwise are not allocated to regions; blocks start after them.
+//!
+//! Regions cannot be mixed with `ExperimentalStableMemory`, which accesses all of the logical
+//! stable memory, including the metadata: the generated code calls
+//! `region_check_stable_memory_access` before each of its accesses, which traps if regions are
+//! in use, and regions trap if `ExperimentalStableMemory` was used.
+
+use crate::rts_trap_with;
+
+pub const PAGE_SIZE: u64 = 64 * 1024;
+
+/// Number of pages allocated at a time
+pub const BLOCK_PAGES: u64 = 128;
+
+const BLOCK_SIZE: u64 = BLOCK_PAGES * PAGE_SIZE;
+
+/// Maximum number of blocks, including the metadata block (64 GiB of stable memory)
+pub const MAX_BLOCKS: u32 = 1 << 13;
+
+/// Maximum number of regions
+pub const MAX_REGIONS: u32 = 1 << 10;
+
+/// Owner of blocks not allocated by regions
+const NO_REGION: u16 = u16::MAX;
+
+pub const REGIONS_MAGIC: u32 = u32::from_le_bytes(*b"MORG");
+pub const REGIONS_VERSION: u32 = 1;
+
+const REGION_COUNT_OFFSET: u64 = 8;
+const BLOCK_COUNT_OFFSET: u64 = 12;
+const OWNERS_OFFSET: u64 = 16;
+const SIZES_OFFSET: u64 = OWNERS_OFFSET + 2 * MAX_BLOCKS as u64;
+
+extern "C" {
+    // generated by `moc`, simulated in the RTS tests
+    fn stable64_write_moc(to: u64, ptr: u64, n: u64);
+    fn stable64_read_moc(ptr: u64, from: u64, n: u64);
+    fn stable64_size_moc() -> u64;
+    fn stable64_grow_moc(pages: u64) -> u64;
+}
+
+// Cached metadata. `BLOCK_COUNT` is 0 until `region_init` is called, on the first use of regions.
+// Only the first `BLOCK_COUNT` owners and `REGION_COUNT` sizes are valid.
+static mut REGION_COUNT: u32 = 0;
+static mut BLOCK_COUNT: u32 = 0;
+static mut OWNERS: [u16; MAX_BLOCKS as usize] = [0; MAX_BLOCKS as usize];
+static mut SIZES: [u64; MAX_REGIONS as usize] = [0; MAX_REGIONS as usize];
+
+/// Whether `ExperimentalStableMemory` was used since the last (re)installation of the code
+static mut STABLE_MEMORY_ACCESSED: bool = false;
+
+unsafe fn write_u32(to: u64, value: u32) {
+    stable64_write_moc(to, &value as *const u32 as u64, 4);
+}
+
+unsafe fn read_u32(from: u64) -> u32 {
+    let mut value = 0u32;
+    stable64_read_moc(&mut value as *mut u32 as u64, from, 4);
+    value
+}
+
+/// Write the cached owners of blocks `from..to` to the metadata
+unsafe fn write_owners(from: u32, to: u32) {
+    stable64_write_moc(
+        OWNERS_OFFSET + 2 * from as u64,
+        OWNERS.as_ptr().add(from as usize) as u64,
+        2 * (to - from) as u64,
+    );
+}
+
+unsafe fn write_size(region: u32) {
+    stable64_write_moc(
+        SIZES_OFFSET + 8 * region as u64,
+        &SIZES[region as usize] as *const u64 as u64,
+        8,
+    );
+}
+
+/// Set up the regions: creates the metadata if stable memory is empty, otherwise reads it back
+/// (e.g. after an upgrade). Traps if stable memory is used, but not by regions.
+pub unsafe fn region_init() {
+    if STABLE_MEMORY_ACCESSED {
+        rts_trap_with("region_init: stable memory in use by ExperimentalStableMemory");
+    }
+    if stable64_size_moc() == 0 {
+        if stable64_grow_moc(BLOCK_PAGES) == u64::MAX {
+            rts_trap_with("region_init: out of stable memory");
+        }
+        REGION_COUNT = 0;
+        BLOCK_COUNT = 1;
+        OWNERS[0] = NO_REGION;
+        write_u32(0, REGIONS_MAGIC);
+        write_u32(4, REGIONS_VERSION);
+        write_u32(REGION_COUNT_OFFSET, REGION_COUNT);
+        write_u32(BLOCK_COUNT_OFFSET, BLOCK_COUNT);
+        write_owners(0, 1);
+        return;
+    }
+
+    if read_u32(0) != REGIONS_MAGIC {
+        rts_trap_with("region_init: stable memory in use, but not by regions");
+    }
+    if read_u32(4) > REGIONS_VERSION {
+        rts_trap_with("region_init: unsupported region metadata version");
+    }
+    let region_count = read_u32(REGION_COUNT_OFFSET);
+    let block_count = read_u32(BLOCK_COUNT_OFFSET);
+    // The counts size the reads into `OWNERS` and `SIZES`
+    if block_count == 0 || block_count > MAX_BLOCKS || region_count > MAX_REGIONS {
+        rts_trap_with("region_init: invalid region metadata");
+    }
+    if block_count as u64 * BLOCK_PAGES > stable64_size_moc() {
+        rts_trap_with("region_init: region metadata exceeds stable memory");
+    }
+    REGION_COUNT = region_count;
+    BLOCK_COUNT = block_count;
+    stable64_read_moc(
+        OWNERS.as_mut_ptr() as u64,
+        OWNERS_OFFSET,
+        2 * BLOCK_COUNT as u64,
+    );
+    stable64_read_moc(
+        SIZES.as_mut_ptr() as u64,
+        SIZES_OFFSET,
+        8 * REGION_COUNT as u64,
+    );
+}
+
+/// Called by the generated code before each access of `ExperimentalStableMemory`. Traps if
+/// stable memory is used by regions, now or before an upgrade.
+#[no_mangle]
+pub unsafe extern "C" fn region_check_stable_memory_access() {
+    if STABLE_MEMORY_ACCESSED {
+        return;
+    }
+    if BLOCK_COUNT != 0 || (stable64_size_moc() != 0 && read_u32(0) == REGIONS_MAGIC) {
+        rts_trap_with("ExperimentalStableMemory: stable memory in use by regions");
+    }
+    STABLE_MEMORY_ACCESSED = true;
+}
+
+unsafe fn init_if_needed() {
+    if BLOCK_COUNT == 0 {
+        region_init();
+    }
+}
+
+unsafe fn check_region(region: u32) {
+    init_if_needed();
+    if region >= REGION_COUNT {
+        rts_trap_with("region: invalid region");
+    }
+}
+
+/// Create an empty region, returning its id
+#[no_mangle]
+pub unsafe extern "C" fn region_new() -> u32 {
+    init_if_needed();
+    if REGION_COUNT == MAX_REGIONS {
+        rts_trap_with("region_new: too many regions");
+    }
+    let region = REGION_COUNT;
+    SIZES[region as usize] = 0;
+    write_size(region);
+    REGION_COUNT += 1;
+    write_u32(REGION_COUNT_OFFSET, REGION_COUNT);
+    region
+}
+
+/// Size of a region in pages
+#[no_mangle]
+pub unsafe extern "C" fn region_size(region: u32) -> u64 {
+    check_region(region);
+    SIZES[region as usize]
+}
+
+/// Add pages to a region, returning its old size in pages, or `u64::MAX` if stable memory cannot
+/// grow (like `ic0.stable64_grow`)
+#[no_mangle]
+pub unsafe extern "C" fn region_grow(region: u32, pages: u64) -> u64 {
+    check_region(region);
+    let old_size = SIZES[region as usize];
+    let new_size = match old_size.checked_add(pages) {
+        Some(new_size) if new_size <= MAX_BLOCKS as u64 * BLOCK_PAGES => new_size,
+        _ => return u64::MAX,
+    };
+
+    let extra_blocks = div_ceil(new_size, BLOCK_PAGES) - div_ceil(old_size, BLOCK_PAGES);
+    if extra_blocks > 0 {
+        // Blocks start after the pages grown directly
+        let stable_size = stable64_size_moc();
+        let first_block = div_ceil(stable_size, BLOCK_PAGES);
+        debug_assert!(first_block >= BLOCK_COUNT as u64);
+        let end_block = first_block + extra_blocks;
+        if end_block > MAX_BLOCKS as u64 {
+            return u64::MAX;
+        }
+        if stable64_grow_moc(end_block * BLOCK_PAGES - stable_size) == u64::MAX {
+            return u64::MAX;
+        }
+
+        let (first_block, end_block) = (first_block as u32, end_block as u32);
+        for owner in &mut OWNERS[BLOCK_COUNT as usize..first_block as usize] {
+            *owner = NO_REGION;
+        }
+        for owner in &mut OWNERS[first_block as usize..end_block as usize] {
+            *owner = region as u16;
+        }
+        write_owners(BLOCK_COUNT, end_block);
+        BLOCK_COUNT = end_block;
+        write_u32(BLOCK_COUNT_OFFSET, BLOCK_COUNT);
+    }
+
+    SIZES[region as usize] = new_size;
+    write_size(region);
+    old_size
+}
+
+fn div_ceil(n: u64, d: u64) -> u64 {
+    (n + d - 1) / d
+}
+
+/// Stable memory address of the `index`th block of a region
+unsafe fn block_address(region: u32, mut index: u64) -> u64 {
+    for block in 1..BLOCK_COUNT {
+        if OWNERS[block as usize] == region as u16 {
+            if index == 0 {
+                return block as u64 * BLOCK_SIZE;
+            }
+            index -= 1;
+        }
+    }
+    rts_trap_with("region: missing block")
+}
+
+/// Call `f` with the stable memory address, the offset into the accessed range, and the length
+/// of each part of the range that lies in one block
+unsafe fn access<F: FnMut(u64, u64, u64)>(region: u32, offset: u64, n: u64, mut f: F) {
+    check_region(region);
+    match offset.checked_add(n) {
+        Some(end) if end <= SIZES[region as usize] * PAGE_SIZE => {}
+        _ => rts_trap_with("region: access out of bounds"),
+    }
+
+    let mut done = 0;
+    while done < n {
+        let block_offset = (offset + done) % BLOCK_SIZE;
+        let len = core::cmp::min(n - done, BLOCK_SIZE - block_offset);
+        let address = block_address(region, (offset + done) / BLOCK_SIZE) + block_offset;
+        f(address, done, len);
+        done += len;
+    }
+}
+
+/// Copy `n` bytes at `offset` in a region to `ptr`
+#[no_mangle]
+pub unsafe extern "C" fn region_load(region: u32, offset: u64, ptr: *mut u8, n: u32) {
+    access(region, offset, n as u64, |address, done, len| {
+        stable64_read_moc(ptr.add(done as usize) as u64, address, len)
+    });
+}
+
+/// Copy `n` bytes at `ptr` to `offset` in a region
+#[no_mangle]
+pub unsafe extern "C" fn region_store(region: u32, offset: u64, ptr: *const u8, n: u32) {
+    access(region, offset, n as u64, |address, done, len| {
+        stable64_write_moc(address, ptr.add(done as usize) as u64, len)
+    });
+}
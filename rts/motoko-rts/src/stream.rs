@@ -10,13 +10,20 @@
 //! for bytes in transit, while bigger chunks will flush this staging area before being written
 //! directly to destination.
 //!
+//! The read side (`ReadStream`) works the same way in reverse: bytes are pulled from the source
+//! (stable memory) into a small cache blob on demand, and bigger reads bypass the cache. It is
+//! not used by the deserialiser in `compile.ml` yet, which still reads from a heap blob holding
+//! the whole (decompressed) image; see `stream_stable_take_blob`.
 //!
+//! Optionally, the output is framed with a header (`StreamHeader`), and compressed with LZ4 in
+//! chunks of at most `COMPRESSION_CHUNK_SIZE` bytes. Each compressed chunk is preceded by its
+//! compressed and uncompressed sizes as little-endian 32-bit words.
 
 // Layout of a stream node:
 //
-//      ┌────────────┬─────┬───────┬─────────┬─────────┬───────────┬────────┬──────────┐
-//      │ tag (blob) │ len │ ptr64 │ start64 │ limit64 │ outputter │ filled │ cache... │
-//      └────────────┴─────┴───┴───┴────┴────┴────┴────┴───────────┴────────┴──────────┘
+//      ┌────────────┬─────┬───────┬─────────┬─────────┬───────────┬────────┬───────┬───────┬───────────┬──────────┐
+//      │ tag (blob) │ len │ ptr64 │ start64 │ limit64 │ outputter │ filled │ crc32 │ flags │ written64 │ cache... │
+//      └────────────┴─────┴───┴───┴────┴────┴────┴────┴───────────┴────────┴───────┴───────┴─────┴─────┴──────────┘
 //
 // We reuse the opaque nature of blobs (to Motoko) and stick Rust-related information
 // into the leading bytes:
@@ -25,23 +32,94 @@
 // - `filled` and `cache` are the number of bytes consumed from the blob, and the
 //   staging area of the stream, respectively
 // - `outputter` is the function to be called when `len - filled` approaches zero.
-// - INVARIANT: keep `BlobStream.{ptr64_field, start64_field, filled_field}`,
+// - `crc32` is the running CRC32 of the bytes passed to the outputter so far, and `flags`
+//   describes the framing of the output (see `StreamHeader`)
+// - `written64` is the number of bytes passed to the outputter so far, before compression, so
+//   that `written64 + filled` is the position in the uncompressed output
+// - INVARIANT: keep `BlobStream.{ptr64_field, filled_field, written64_field}`,
 //              (from `compile.ml`) in sync with the layout!
 // - Note: `len` and `filled` are relative to the encompassing blob.
 
 use crate::bigint::{check, mp_get_u32, mp_isneg, mp_iszero};
+use crate::lz4;
 use crate::mem_utils::memcpy_bytes;
 use crate::memory::{alloc_blob, Memory};
+use crate::principal_id::update_crc32;
 use crate::rts_trap_with;
 use crate::tommath_bindings::{mp_div_2d, mp_int};
-use crate::types::{size_of, Blob, Bytes, Stream, Value, TAG_BLOB};
+use crate::types::{size_of, Blob, Bytes, ReadStream, Stream, Value, TAG_BLOB};
 
 use motoko_rts_macros::ic_mem_fn;
 
+use core::slice;
+
 const MAX_STREAM_SIZE: Bytes<u32> = Bytes((1 << 30) - 1);
-const INITIAL_STREAM_FILLED: Bytes<u32> = Bytes(32);
+const INITIAL_STREAM_FILLED: Bytes<u32> = Bytes(48);
 const STREAM_CHUNK_SIZE: Bytes<u32> = Bytes(128);
 
+/// Stream flag: the output starts with a `StreamHeader`
+pub const STREAM_FRAMED: u32 = 1;
+
+/// Stream flag: the data following the header is compressed
+pub const STREAM_COMPRESSED: u32 = 2;
+
+/// Maximum number of bytes compressed as one chunk. Readers of compressed streams need a cache of
+/// at least this size.
+pub const COMPRESSION_CHUNK_SIZE: Bytes<u32> = Bytes(4096);
+
+const COMPRESSED_CHUNK_HEADER_SIZE: usize = 8;
+
+/// Maximum size of a compressed chunk, including its header
+const MAX_COMPRESSED_CHUNK_SIZE: usize =
+    COMPRESSED_CHUNK_HEADER_SIZE + lz4::compress_bound(COMPRESSION_CHUNK_SIZE.0 as usize);
+
+/// Header of framed streams, written to the start of the destination by `Stream::shutdown`:
+///
+///      ┌───────┬─────────┬───────┬───────┬────────┬─────────┐
+///      │ magic │ version │ flags │ crc32 │ length │ data... │
+///      └───────┴─────────┴───────┴───────┴────────┴─────────┘
+///
+/// All fields are little-endian, `length` is 64 bits and the others are 32 bits. `length` is the
+/// number of bytes following the header, `crc32` the CRC32 of the data (before compression).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamHeader {
+    pub version: u32,
+    pub flags: u32,
+    pub crc32: u32,
+    pub length: u64,
+}
+
+pub const STREAM_HEADER_MAGIC: u32 = u32::from_le_bytes(*b"MOST");
+pub const STREAM_FORMAT_VERSION: u32 = 1;
+pub const STREAM_HEADER_SIZE: Bytes<u32> = Bytes(24);
+
+impl StreamHeader {
+    pub fn to_bytes(&self) -> [u8; STREAM_HEADER_SIZE.0 as usize] {
+        let mut bytes = [0u8; STREAM_HEADER_SIZE.0 as usize];
+        bytes[0..4].copy_from_slice(&STREAM_HEADER_MAGIC.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.version.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.flags.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.crc32.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.length.to_le_bytes());
+        bytes
+    }
+
+    /// `None` if the magic number does not match
+    pub fn from_bytes(bytes: &[u8; STREAM_HEADER_SIZE.0 as usize]) -> Option<StreamHeader> {
+        let word =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        if word(0) != STREAM_HEADER_MAGIC {
+            return None;
+        }
+        Some(StreamHeader {
+            version: word(4),
+            flags: word(8),
+            crc32: word(12),
+            length: u64::from(word(16)) | (u64::from(word(20)) << 32),
+        })
+    }
+}
+
 #[ic_mem_fn]
 pub unsafe fn alloc_stream<M: Memory>(mem: &mut M, size: Bytes<u32>) -> *mut Stream {
     debug_assert_eq!(
@@ -57,13 +135,16 @@ pub unsafe fn alloc_stream<M: Memory>(mem: &mut M, size: Bytes<u32>) -> *mut Str
     (*stream).limit64 = 0;
     (*stream).outputter = Stream::no_backing_store;
     (*stream).filled = INITIAL_STREAM_FILLED;
+    (*stream).crc32 = !0;
+    (*stream).flags = 0;
+    (*stream).written64 = 0;
     stream
 }
 
-#[allow(dead_code)]
 extern "C" {
-    // generated by `moc`
+    // generated by `moc`, simulated in the RTS tests
     fn stable64_write_moc(to: u64, ptr: u64, n: u64);
+    fn stable64_read_moc(ptr: u64, from: u64, n: u64);
 }
 
 impl Stream {
@@ -81,21 +162,42 @@ impl Stream {
     fn flush(self: *mut Self) {
         unsafe {
             if (*self).filled > INITIAL_STREAM_FILLED {
-                ((*self).outputter)(
-                    self,
-                    self.cache_addr(),
-                    (*self).filled - INITIAL_STREAM_FILLED,
-                );
+                self.output(self.cache_addr(), (*self).filled - INITIAL_STREAM_FILLED);
                 (*self).filled = INITIAL_STREAM_FILLED
             }
         }
     }
 
+    /// Pass bytes to the outputter, keeping track of their checksum
+    fn output(self: *mut Self, ptr: *const u8, n: Bytes<u32>) {
+        unsafe {
+            let bytes = slice::from_raw_parts(ptr, n.as_usize());
+            (*self).crc32 = update_crc32((*self).crc32, bytes);
+            (*self).written64 += n.as_u32() as u64;
+            if (*self).flags & STREAM_COMPRESSED != 0 {
+                self.output_compressed(bytes)
+            } else {
+                ((*self).outputter)(self, ptr, n)
+            }
+        }
+    }
+
+    /// Pass bytes to the outputter as compressed chunks
+    fn output_compressed(self: *mut Self, bytes: &[u8]) {
+        let mut buf = [0u8; MAX_COMPRESSED_CHUNK_SIZE];
+        for chunk in bytes.chunks(COMPRESSION_CHUNK_SIZE.as_usize()) {
+            let size = lz4::compress(chunk, &mut buf[COMPRESSED_CHUNK_HEADER_SIZE..]);
+            buf[0..4].copy_from_slice(&(size as u32).to_le_bytes());
+            buf[4..8].copy_from_slice(&(chunk.len() as u32).to_le_bytes());
+            let n = Bytes((COMPRESSED_CHUNK_HEADER_SIZE + size) as u32);
+            unsafe { ((*self).outputter)(self, buf.as_ptr(), n) }
+        }
+    }
+
     fn no_backing_store(self: *mut Self, _ptr: *const u8, _n: Bytes<u32>) {
         assert!(false)
     }
 
-    #[cfg(feature = "ic")]
     fn send_to_stable(self: *mut Self, ptr: *const u8, n: Bytes<u32>) {
         unsafe {
             let next_ptr64 = (*self).ptr64 + n.as_u32() as u64;
@@ -104,7 +206,6 @@ impl Stream {
         }
     }
 
-    #[cfg(feature = "ic")]
     /// Sets up the bottleneck routine to output towards a range of stable memory
     /// Note: assumes that the entire byte range is writable
     #[export_name = "stream_stable_dest"]
@@ -117,15 +218,53 @@ impl Stream {
         }
     }
 
-    /// Ingest a number of bytes into the stream.
+    /// Like `setup_stable_dest`, but the output is framed: space for a `StreamHeader` is left
+    /// at `start`, and `shutdown` fills it in
+    #[export_name = "stream_stable_dest_framed"]
+    pub fn setup_framed_stable_dest(self: *mut Self, start: u64, limit: u64) {
+        self.setup_stable_dest(start, limit);
+        self.start_framing()
+    }
+
+    /// Leave space for a `StreamHeader` at the start of the destination. Needs to be called before
+    /// any output. The space is cleared, so that a header of an earlier stream at the same place
+    /// does not stay valid while the stream is being written.
+    pub fn start_framing(self: *mut Self) {
+        unsafe {
+            debug_assert_eq!((*self).ptr64, (*self).start64);
+            let zeros = [0u8; STREAM_HEADER_SIZE.0 as usize];
+            ((*self).outputter)(self, zeros.as_ptr(), STREAM_HEADER_SIZE);
+            (*self).flags |= STREAM_FRAMED;
+        }
+    }
+
+    /// Like `setup_framed_stable_dest`, but the data following the header is compressed
+    #[export_name = "stream_stable_dest_compressed"]
+    pub fn setup_compressed_stable_dest(self: *mut Self, start: u64, limit: u64) {
+        self.setup_framed_stable_dest(start, limit);
+        self.start_compression()
+    }
+
+    /// Compress the data of a framed stream. Needs to be called before any output.
+    pub fn start_compression(self: *mut Self) {
+        unsafe {
+            debug_assert!((*self).flags & STREAM_FRAMED != 0);
+            (*self).flags |= STREAM_COMPRESSED;
+        }
+    }
+
+    /// Ingest a number of bytes into the stream. Bigger writes bypass the cache, unless the stream
+    /// is compressed (so that the compressed chunks are not smaller than the cache).
     #[export_name = "stream_write"]
     pub fn cache_bytes(self: *mut Self, ptr: *const u8, n: Bytes<u32>) {
         unsafe {
-            if (*self).limit64 != 0 && n > STREAM_CHUNK_SIZE
+            if (*self).limit64 != 0
+                && n > STREAM_CHUNK_SIZE
+                && (*self).flags & STREAM_COMPRESSED == 0
                 || (*self).filled + n > (*self).header.len
             {
                 self.flush();
-                ((*self).outputter)(self, ptr, n);
+                self.output(ptr, n);
             } else {
                 let dest = self
                     .as_blob_mut()
@@ -201,8 +340,459 @@ impl Stream {
 
     /// Shut down the stream by outputting all data. Lengths are
     /// adjusted correspondingly, and the stream remains intact.
+    /// For framed streams, the header is (re)written.
     #[export_name = "stream_shutdown"]
     pub unsafe fn shutdown(self: *mut Self) {
-        self.flush()
+        self.flush();
+
+        if (*self).flags & STREAM_FRAMED != 0 {
+            let end64 = (*self).ptr64;
+            let header = StreamHeader {
+                version: STREAM_FORMAT_VERSION,
+                flags: (*self).flags,
+                crc32: !(*self).crc32,
+                length: end64 - (*self).start64 - STREAM_HEADER_SIZE.as_u32() as u64,
+            };
+            // The outputter writes at `ptr64`, the header is not part of the checksum
+            (*self).ptr64 = (*self).start64;
+            ((*self).outputter)(self, header.to_bytes().as_ptr(), STREAM_HEADER_SIZE);
+            (*self).ptr64 = end64;
+        }
+    }
+}
+
+// Layout of a read stream node:
+//
+//      ┌────────────┬─────┬───────┬─────────┬─────────┬──────────┬──────┬────────┬───────┬──────────┐
+//      │ tag (blob) │ len │ ptr64 │ start64 │ limit64 │ inputter │ read │ filled │ flags │ cache... │
+//      └────────────┴─────┴───┴───┴────┴────┴────┴────┴──────────┴──────┴────────┴───────┴──────────┘
+//
+// Like in the write stream:
+// - `ptr64` and `limit64` are the next and past-end pointers into stable memory, `start64` is
+//   where reading started
+// - `inputter` is the function to be called to fill the cache when `read` reaches `filled`.
+//   It reads from `ptr64` and advances it.
+// - `read` and `filled` are the number of bytes consumed from the cache and the number of bytes in
+//   the cache, relative to the encompassing blob (so that the stream can be moved by the GC)
+// - `flags` are taken from the `StreamHeader` of framed streams. For compressed streams, the
+//   cache holds one decompressed chunk at a time.
+
+const INITIAL_READ_STREAM_FILLED: Bytes<u32> = Bytes(40);
+
+/// Why `ReadStream::read_header` rejected a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamHeaderError {
+    /// The stream does not start with a header, e.g. it was not written by a framed stream
+    NoHeader,
+    /// The stream was written by a newer format version
+    UnsupportedVersion,
+    /// The stream is shorter than the length in the header
+    Truncated,
+    /// The data does not match the checksum in the header
+    ChecksumMismatch,
+}
+
+#[ic_mem_fn]
+pub unsafe fn alloc_read_stream<M: Memory>(mem: &mut M, size: Bytes<u32>) -> *mut ReadStream {
+    debug_assert_eq!(
+        INITIAL_READ_STREAM_FILLED,
+        (size_of::<ReadStream>() - size_of::<Blob>()).to_bytes()
+    );
+    if size > MAX_STREAM_SIZE {
+        rts_trap_with("alloc_read_stream: Cache too large");
+    }
+    let stream = alloc_blob(mem, size + INITIAL_READ_STREAM_FILLED).as_read_stream();
+    (*stream).ptr64 = 0;
+    (*stream).start64 = 0;
+    (*stream).limit64 = 0;
+    (*stream).inputter = ReadStream::no_backing_store;
+    (*stream).read = INITIAL_READ_STREAM_FILLED;
+    (*stream).filled = INITIAL_READ_STREAM_FILLED;
+    (*stream).flags = 0;
+    stream
+}
+
+impl ReadStream {
+    #[inline]
+    pub unsafe fn cache_addr(self: *mut Self) -> *mut u8 {
+        self.add(1) as *mut u8 // skip closure header
+    }
+
+    #[inline]
+    pub unsafe fn as_blob_mut(self: *mut Self) -> *mut Blob {
+        self as *mut Blob
+    }
+
+    fn no_backing_store(self: *mut Self, _ptr: *mut u8, _n: Bytes<u32>) {
+        assert!(false)
+    }
+
+    fn fetch_from_stable(self: *mut Self, ptr: *mut u8, n: Bytes<u32>) {
+        unsafe {
+            stable64_read_moc(ptr as u64, (*self).ptr64, n.as_u32() as u64);
+            (*self).ptr64 += n.as_u32() as u64
+        }
     }
+
+    /// Sets up the bottleneck routine to input from a range of stable memory
+    /// Note: assumes that the entire byte range is readable
+    #[export_name = "read_stream_stable_source"]
+    pub fn setup_stable_source(self: *mut Self, start: u64, limit: u64) {
+        unsafe {
+            (*self).ptr64 = start;
+            (*self).start64 = start;
+            (*self).limit64 = limit;
+            (*self).inputter = Self::fetch_from_stable;
+        }
+    }
+
+    /// Like `setup_stable_source`, for stable memory written by a framed stream. Traps if the
+    /// header is not valid.
+    #[export_name = "read_stream_stable_source_framed"]
+    pub fn setup_framed_stable_source(self: *mut Self, start: u64, limit: u64) {
+        self.setup_stable_source(start, limit);
+        let error = match self.read_header() {
+            Ok(_) => return,
+            Err(StreamHeaderError::NoHeader) => "read_stream: no stream header",
+            Err(StreamHeaderError::UnsupportedVersion) => {
+                "read_stream: unsupported stream format version"
+            }
+            Err(StreamHeaderError::Truncated) => "read_stream: stream truncated",
+            Err(StreamHeaderError::ChecksumMismatch) => "read_stream: stream checksum mismatch",
+        };
+        unsafe { rts_trap_with(error) }
+    }
+
+    /// Reads and verifies the header of a framed stream, and restricts the stream to the data
+    /// following it. The data is read once to verify the checksum, so that corruption is detected
+    /// before decoding starts. Needs to be called before anything else is read.
+    pub fn read_header(self: *mut Self) -> Result<StreamHeader, StreamHeaderError> {
+        unsafe {
+            debug_assert_eq!((*self).read, (*self).filled);
+
+            if self.remaining() < STREAM_HEADER_SIZE.as_u32() as u64 {
+                return Err(StreamHeaderError::NoHeader);
+            }
+            let mut bytes = [0u8; STREAM_HEADER_SIZE.0 as usize];
+            self.read_bytes(bytes.as_mut_ptr(), STREAM_HEADER_SIZE);
+            let header = StreamHeader::from_bytes(&bytes).ok_or(StreamHeaderError::NoHeader)?;
+            if header.version > STREAM_FORMAT_VERSION {
+                return Err(StreamHeaderError::UnsupportedVersion);
+            }
+            if header.flags & STREAM_COMPRESSED != 0 && self.capacity() < COMPRESSION_CHUNK_SIZE {
+                rts_trap_with("read_stream: cache too small for compressed stream");
+            }
+
+            // Drop the cached bytes following the header, to read the data from the start
+            let data_start64 = (*self).ptr64 - ((*self).filled - (*self).read).as_u32() as u64;
+            (*self).read = (*self).filled;
+            (*self).ptr64 = data_start64;
+            if (*self).limit64 - data_start64 < header.length {
+                return Err(StreamHeaderError::Truncated);
+            }
+            (*self).limit64 = data_start64 + header.length;
+
+            (*self).flags = header.flags;
+
+            let mut crc = !0;
+            while (*self).ptr64 < (*self).limit64 {
+                self.try_refill()
+                    .map_err(|_| StreamHeaderError::ChecksumMismatch)?;
+                let n = (*self).filled - (*self).read;
+                crc = update_crc32(crc, slice::from_raw_parts(self.cache_addr(), n.as_usize()));
+                (*self).read = (*self).filled;
+            }
+            if !crc != header.crc32 {
+                return Err(StreamHeaderError::ChecksumMismatch);
+            }
+
+            (*self).ptr64 = data_start64;
+            Ok(header)
+        }
+    }
+
+    /// Number of bytes not yet read, in the cache and in the source. Bytes in the source of
+    /// compressed streams are counted as stored, i.e. after compression.
+    pub fn remaining(self: *mut Self) -> u64 {
+        unsafe { ((*self).filled - (*self).read).as_u32() as u64 + (*self).limit64 - (*self).ptr64 }
+    }
+
+    /// Number of bytes of the data of a compressed stream after decompression, from the sizes in
+    /// the chunk headers. Needs to be called right after `read_header`.
+    fn decompressed_length(self: *mut Self) -> Result<u64, &'static str> {
+        unsafe {
+            debug_assert_eq!((*self).read, (*self).filled);
+            let data_start64 = (*self).ptr64;
+            let mut length = 0;
+            let mut buf = [0u8; COMPRESSED_CHUNK_HEADER_SIZE];
+            while (*self).ptr64 < (*self).limit64 {
+                self.fetch(&mut buf)?;
+                let size = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as u64;
+                let uncompressed_size = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+                if (*self).limit64 - (*self).ptr64 < size {
+                    return Err("read_stream: invalid compressed chunk");
+                }
+                (*self).ptr64 += size;
+                length += uncompressed_size as u64;
+            }
+            (*self).ptr64 = data_start64;
+            Ok(length)
+        }
+    }
+
+    fn capacity(self: *mut Self) -> Bytes<u32> {
+        unsafe { (*self).header.len - INITIAL_READ_STREAM_FILLED }
+    }
+
+    /// Refill the (empty) cache from the source
+    fn refill(self: *mut Self) {
+        if let Err(error) = self.try_refill() {
+            unsafe { rts_trap_with(error) }
+        }
+    }
+
+    fn try_refill(self: *mut Self) -> Result<(), &'static str> {
+        unsafe {
+            debug_assert_eq!((*self).read, (*self).filled);
+            if (*self).flags & STREAM_COMPRESSED != 0 {
+                return self.try_refill_decompressed();
+            }
+            let capacity = self.capacity();
+            let available = (*self).limit64 - (*self).ptr64;
+            if available == 0 {
+                return Err("read_stream: read past end of stream");
+            }
+            let n = if available < capacity.as_u32() as u64 {
+                Bytes(available as u32)
+            } else {
+                capacity
+            };
+            ((*self).inputter)(self, self.cache_addr(), n);
+            (*self).read = INITIAL_READ_STREAM_FILLED;
+            (*self).filled = INITIAL_READ_STREAM_FILLED + n;
+            Ok(())
+        }
+    }
+
+    /// Fill the cache with the next chunk of a compressed stream
+    fn try_refill_decompressed(self: *mut Self) -> Result<(), &'static str> {
+        const INVALID_CHUNK: &str = "read_stream: invalid compressed chunk";
+        unsafe {
+            let mut buf = [0u8; MAX_COMPRESSED_CHUNK_SIZE];
+            self.fetch(&mut buf[..COMPRESSED_CHUNK_HEADER_SIZE])?;
+            let size = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+            let uncompressed_size = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+            if size > MAX_COMPRESSED_CHUNK_SIZE - COMPRESSED_CHUNK_HEADER_SIZE
+                || Bytes(uncompressed_size) > COMPRESSION_CHUNK_SIZE
+            {
+                return Err(INVALID_CHUNK);
+            }
+
+            let compressed = &mut buf[..size];
+            self.fetch(compressed)?;
+            let cache = slice::from_raw_parts_mut(self.cache_addr(), self.capacity().as_usize());
+            match lz4::decompress(compressed, cache) {
+                Ok(n) if n == uncompressed_size as usize => {
+                    (*self).read = INITIAL_READ_STREAM_FILLED;
+                    (*self).filled = INITIAL_READ_STREAM_FILLED + Bytes(uncompressed_size);
+                    Ok(())
+                }
+                _ => Err(INVALID_CHUNK),
+            }
+        }
+    }
+
+    /// Read bytes from the source, bypassing the cache
+    fn fetch(self: *mut Self, dest: &mut [u8]) -> Result<(), &'static str> {
+        unsafe {
+            if (*self).limit64 - (*self).ptr64 < dest.len() as u64 {
+                return Err("read_stream: read past end of stream");
+            }
+            ((*self).inputter)(self, dest.as_mut_ptr(), Bytes(dest.len() as u32));
+            Ok(())
+        }
+    }
+
+    /// Read a single byte
+    #[export_name = "read_stream_byte"]
+    pub fn read_byte(self: *mut Self) -> u8 {
+        unsafe {
+            if (*self).read >= (*self).filled {
+                self.refill()
+            }
+            let byte = self.as_blob_mut().get((*self).read.as_u32());
+            (*self).read += Bytes(1);
+            byte
+        }
+    }
+
+    /// Read a little-endian word
+    #[export_name = "read_stream_word"]
+    pub fn read_word(self: *mut Self) -> u32 {
+        let mut bytes = [0u8; 4];
+        for byte in bytes.iter_mut() {
+            *byte = self.read_byte();
+        }
+        u32::from_le_bytes(bytes)
+    }
+
+    /// Read a number of bytes. Bigger reads go directly to the destination, unless the stream is
+    /// compressed.
+    #[export_name = "read_stream_bytes"]
+    pub fn read_bytes(self: *mut Self, mut ptr: *mut u8, mut n: Bytes<u32>) {
+        unsafe {
+            loop {
+                let cached = core::cmp::min(n, (*self).filled - (*self).read);
+                let src = self
+                    .as_blob_mut()
+                    .payload_addr()
+                    .add((*self).read.as_usize());
+                memcpy_bytes(ptr as usize, src as usize, cached);
+                (*self).read += cached;
+                ptr = ptr.add(cached.as_usize());
+                n -= cached;
+
+                if n == Bytes(0) {
+                    return;
+                }
+
+                // Reads that do not fit into the cache bypass it
+                if (*self).flags & STREAM_COMPRESSED == 0
+                    && (n > STREAM_CHUNK_SIZE || n > self.capacity())
+                {
+                    let dest = slice::from_raw_parts_mut(ptr, n.as_usize());
+                    if let Err(error) = self.fetch(dest) {
+                        rts_trap_with(error)
+                    }
+                    return;
+                }
+
+                self.refill();
+            }
+        }
+    }
+
+    /// Skip a number of bytes
+    #[export_name = "read_stream_advance"]
+    pub fn advance(self: *mut Self, mut n: Bytes<u32>) {
+        unsafe {
+            loop {
+                let cached = core::cmp::min(n, (*self).filled - (*self).read);
+                (*self).read += cached;
+                n -= cached;
+
+                if n == Bytes(0) {
+                    return;
+                }
+
+                // Compressed data can only be skipped by decompressing it
+                if (*self).flags & STREAM_COMPRESSED != 0 {
+                    self.refill();
+                    continue;
+                }
+
+                let skipped = n.as_u32() as u64;
+                if (*self).limit64 - (*self).ptr64 < skipped {
+                    rts_trap_with("read_stream: advance past end of stream");
+                }
+                (*self).ptr64 += skipped;
+                return;
+            }
+        }
+    }
+
+    /// like `leb128_decode`, but from a stream
+    #[export_name = "read_stream_leb128"]
+    pub fn read_leb128(self: *mut Self) -> u32 {
+        let mut result = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.read_byte();
+
+            result |= ((byte & 0b0111_1111) as u32) << shift;
+
+            // The 5th byte needs to be the last, and it must contribute at most 4 bits, otherwise we
+            // have an overflow
+            if shift == 28 && (byte & 0b1111_0000) != 0 {
+                unsafe { rts_trap_with("read_stream_leb128: overflow") }
+            }
+
+            shift += 7;
+
+            if byte & 0b1000_0000 == 0 {
+                return result;
+            }
+        }
+    }
+
+    /// like `sleb128_decode`, but from a stream
+    #[export_name = "read_stream_sleb128"]
+    pub fn read_sleb128(self: *mut Self) -> i32 {
+        let mut result = 0;
+        let mut shift = 0;
+
+        let last_byte = loop {
+            let byte = self.read_byte();
+
+            result |= ((byte & 0b0111_1111) as i32) << shift;
+
+            // Overflow check as in `sleb128_decode_checked`, and the 5th byte needs to be the last
+            if shift == 28
+                && (byte & 0b1000_0000 != 0
+                    || (byte & 0b0111_1000 != 0 && byte & 0b0111_1000 != 0b0111_1000))
+            {
+                unsafe { rts_trap_with("read_stream_sleb128: overflow") }
+            }
+
+            shift += 7;
+
+            if byte & 0b1000_0000 == 0 {
+                break byte;
+            }
+        };
+
+        // Sign extend
+        if shift < 32 && last_byte & 0b0100_0000 != 0 {
+            result |= !0 << shift;
+        }
+
+        result
+    }
+}
+
+/// Cache size of the read stream in `stream_stable_take_blob`, as for the write side in `compile.ml`
+const STABLE_READ_CACHE_SIZE: Bytes<u32> = Bytes(0x8000);
+
+/// Reads the data of a framed stream written to `[start, limit)` of stable memory into a new blob,
+/// and clears that range. Traps if the header is not valid, so that a corrupted image is rejected
+/// before anything is decoded.
+#[ic_mem_fn]
+pub unsafe fn stream_stable_take_blob<M: Memory>(mem: &mut M, start: u64, limit: u64) -> Value {
+    let reader = alloc_read_stream(mem, STABLE_READ_CACHE_SIZE);
+    reader.setup_framed_stable_source(start, limit);
+    let length = if (*reader).flags & STREAM_COMPRESSED != 0 {
+        match reader.decompressed_length() {
+            Ok(length) => length,
+            Err(error) => rts_trap_with(error),
+        }
+    } else {
+        reader.remaining()
+    };
+    if length > u32::MAX as u64 {
+        rts_trap_with("stream_stable_take_blob: stream too large");
+    }
+    let length = Bytes(length as u32);
+    let blob = alloc_blob(mem, length);
+    reader.read_bytes(blob.as_blob_mut().payload_addr(), length);
+
+    let zeros = [0u8; 1024];
+    let mut ptr64 = start;
+    while ptr64 < limit {
+        let n = core::cmp::min(limit - ptr64, zeros.len() as u64);
+        stable64_write_moc(ptr64, zeros.as_ptr() as u64, n);
+        ptr64 += n;
+    }
+
+    blob
 }
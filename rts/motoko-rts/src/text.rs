@@ -41,7 +41,7 @@ const MAX_STR_SIZE: Bytes<u32> = Bytes((1 << 30) - 1);
 // Make this MAX_STR_SIZE to disable the use of ropes completely, e.g. for debugging
 const MIN_CONCAT_SIZE: Bytes<u32> = Bytes(9);
 
-unsafe fn alloc_text_blob<M: Memory>(mem: &mut M, size: Bytes<u32>) -> Value {
+pub(crate) unsafe fn alloc_text_blob<M: Memory>(mem: &mut M, size: Bytes<u32>) -> Value {
     if size > MAX_STR_SIZE {
         rts_trap_with("alloc_text_blob: Text too large");
     }
@@ -180,6 +180,26 @@ unsafe extern "C" fn stream_write_text(stream: *mut Stream, mut s: Value) {
     }
 }
 
+/// Calls `f` with the contents of the leaves of the text, from left to right
+pub(crate) unsafe fn for_each_leaf<F: FnMut(&str)>(mut s: Value, f: &mut F) {
+    loop {
+        let obj = s.as_obj();
+        if obj.tag() == TAG_BLOB {
+            let blob = obj.as_blob();
+            // Leaves of a text are texts themselves, so they are valid UTF-8 on their own
+            f(str::from_utf8_unchecked(slice::from_raw_parts(
+                blob.payload_const(),
+                blob.len().as_usize(),
+            )));
+            return;
+        } else {
+            let concat = obj.as_concat();
+            for_each_leaf(concat.text1(), f);
+            s = concat.text2();
+        }
+    }
+}
+
 // Straighten into contiguous memory, if needed (e.g. for system calls)
 #[ic_mem_fn]
 pub unsafe fn blob_of_text<M: Memory>(mem: &mut M, s: Value) -> Value {
@@ -0,0 +1,180 @@
+//! Transcoding between `Text` (UTF-8) and blobs in UTF-16 (little or big endian) or Latin-1
+//! (ISO-8859-1) encoding.
+//!
+//! Encoding walks the leaves of the text rope directly, so texts don't need to be straightened
+//! with `blob_of_text` first. All functions make two passes over their input: the first one
+//! validates it and computes the size of the result, the second one writes the result.
+//!
+//! Invalid input (unpaired surrogates or a trailing odd byte in UTF-16, characters above U+00FF
+//! when encoding to Latin-1) is handled according to a `mode` argument, which is one of the
+//! `TRANSCODE_*` constants below.
+
+use crate::memory::{alloc_blob, Memory};
+use crate::rts_trap_with;
+use crate::text::{alloc_text_blob, for_each_leaf};
+use crate::types::{Bytes, Value};
+
+use core::char::{decode_utf16, REPLACEMENT_CHARACTER};
+use core::slice;
+
+use motoko_rts_macros::ic_mem_fn;
+
+/// Trap on invalid input
+pub const TRANSCODE_TRAP: u32 = 0;
+
+/// Replace invalid input: with U+FFFD when decoding UTF-16, with `?` when encoding to Latin-1
+pub const TRANSCODE_REPLACE: u32 = 1;
+
+/// Latin-1 replacement for characters above U+00FF
+const LATIN1_REPLACEMENT: u8 = b'?';
+
+unsafe fn replace_invalid(mode: u32) -> bool {
+    match mode {
+        TRANSCODE_TRAP => false,
+        TRANSCODE_REPLACE => true,
+        _ => rts_trap_with("transcode: invalid mode"),
+    }
+}
+
+unsafe fn blob_bytes<'a>(blob: Value) -> &'a [u8] {
+    let blob = blob.as_blob();
+    slice::from_raw_parts(blob.payload_const(), blob.len().as_usize())
+}
+
+/// Allocates a text and fills it with the given characters, which need to take exactly `size`
+/// bytes in UTF-8
+unsafe fn text_of_chars<M: Memory, I: Iterator<Item = char>>(
+    mem: &mut M,
+    size: Bytes<u32>,
+    chars: I,
+) -> Value {
+    let text = alloc_text_blob(mem, size);
+    let mut dest = text.as_blob_mut().payload_addr();
+    let mut buf = [0u8; 4];
+    for c in chars {
+        for b in c.encode_utf8(&mut buf).as_bytes() {
+            *dest = *b;
+            dest = dest.add(1);
+        }
+    }
+    debug_assert_eq!(dest, text.as_blob_mut().payload_addr().add(size.as_usize()));
+    text
+}
+
+/// Encodes a text as UTF-16, without byte order mark
+#[ic_mem_fn]
+pub unsafe fn text_to_utf16<M: Memory>(mem: &mut M, text: Value, big_endian: bool) -> Value {
+    let mut n_units: u32 = 0;
+    for_each_leaf(text, &mut |s| {
+        n_units += s.chars().map(|c| c.len_utf16() as u32).sum::<u32>()
+    });
+
+    let blob = alloc_blob(mem, Bytes(n_units * 2));
+    let mut dest = blob.as_blob_mut().payload_addr();
+    for_each_leaf(text, &mut |s| {
+        for unit in s.encode_utf16() {
+            let bytes = if big_endian {
+                unit.to_be_bytes()
+            } else {
+                unit.to_le_bytes()
+            };
+            *dest = bytes[0];
+            *dest.add(1) = bytes[1];
+            dest = dest.add(2);
+        }
+    });
+
+    blob
+}
+
+/// Characters of a UTF-16 encoded byte sequence. Traps on invalid input unless `replace` is set.
+unsafe fn utf16_chars<'a>(
+    bytes: &'a [u8],
+    big_endian: bool,
+    replace: bool,
+) -> impl Iterator<Item = char> + 'a {
+    let units = bytes.chunks_exact(2).map(move |unit| {
+        if big_endian {
+            u16::from_be_bytes([unit[0], unit[1]])
+        } else {
+            u16::from_le_bytes([unit[0], unit[1]])
+        }
+    });
+
+    let odd_byte = if bytes.len() % 2 != 0 {
+        if !replace {
+            rts_trap_with("text_of_utf16: odd number of bytes");
+        }
+        Some(REPLACEMENT_CHARACTER)
+    } else {
+        None
+    };
+
+    decode_utf16(units)
+        .map(move |c| match c {
+            Ok(c) => c,
+            Err(_) if replace => REPLACEMENT_CHARACTER,
+            Err(_) => rts_trap_with("text_of_utf16: unpaired surrogate"),
+        })
+        .chain(odd_byte)
+}
+
+/// Decodes a UTF-16 encoded blob (without byte order mark) into a text
+#[ic_mem_fn]
+pub unsafe fn text_of_utf16<M: Memory>(
+    mem: &mut M,
+    blob: Value,
+    big_endian: bool,
+    mode: u32,
+) -> Value {
+    let replace = replace_invalid(mode);
+    let bytes = blob_bytes(blob);
+
+    let size: u32 = utf16_chars(bytes, big_endian, replace)
+        .map(|c| c.len_utf8() as u32)
+        .sum();
+
+    text_of_chars(mem, Bytes(size), utf16_chars(bytes, big_endian, replace))
+}
+
+/// Encodes a text as Latin-1. Characters above U+00FF trap or are replaced by `?`, depending on
+/// `mode`.
+#[ic_mem_fn]
+pub unsafe fn text_to_latin1<M: Memory>(mem: &mut M, text: Value, mode: u32) -> Value {
+    let replace = replace_invalid(mode);
+
+    let mut n_chars: u32 = 0;
+    for_each_leaf(text, &mut |s| {
+        for c in s.chars() {
+            if c as u32 > 0xFF && !replace {
+                rts_trap_with("text_to_latin1: character not representable in Latin-1");
+            }
+            n_chars += 1;
+        }
+    });
+
+    let blob = alloc_blob(mem, Bytes(n_chars));
+    let mut dest = blob.as_blob_mut().payload_addr();
+    for_each_leaf(text, &mut |s| {
+        for c in s.chars() {
+            *dest = if c as u32 > 0xFF {
+                LATIN1_REPLACEMENT
+            } else {
+                c as u8
+            };
+            dest = dest.add(1);
+        }
+    });
+
+    blob
+}
+
+/// Decodes a Latin-1 encoded blob into a text. All byte sequences are valid Latin-1.
+#[ic_mem_fn]
+pub unsafe fn text_of_latin1<M: Memory>(mem: &mut M, blob: Value) -> Value {
+    let bytes = blob_bytes(blob);
+
+    let size = bytes.len() as u32 + bytes.iter().filter(|b| **b >= 0x80).count() as u32;
+
+    text_of_chars(mem, Bytes(size), bytes.iter().map(|b| char::from(*b)))
+}
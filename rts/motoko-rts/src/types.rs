@@ -308,6 +308,14 @@ impl Value {
         self.get_ptr() as *mut Stream
     }
 
+    /// Get the pointer as `ReadStream`, which is also a glorified `Blob`.
+    /// In debug mode panics if the value is not a pointer or the
+    /// pointed object is not a `Blob`.
+    pub unsafe fn as_read_stream(self) -> *mut ReadStream {
+        debug_assert_eq!(self.tag(), TAG_BLOB);
+        self.get_ptr() as *mut ReadStream
+    }
+
     /// Get the pointer as `BigInt`. In debug mode panics if the value is not a pointer or the
     /// pointed object is not a `BigInt`.
     pub unsafe fn as_bigint(self) -> *mut BigInt {
@@ -315,6 +323,13 @@ impl Value {
         self.get_ptr() as *mut BigInt
     }
 
+    /// Get the pointer as `Bits64`. In debug mode panics if the value is not a pointer or the
+    /// pointed object is not a `Bits64`.
+    pub unsafe fn as_bits64(self) -> *mut Bits64 {
+        debug_assert_eq!(self.tag(), TAG_BITS64);
+        self.get_ptr() as *mut Bits64
+    }
+
     pub fn as_tiny(self) -> i32 {
         debug_assert!(self.is_scalar());
         self.0 as i32 >> 1
@@ -557,7 +572,22 @@ pub struct Stream {
     pub start64: u64,
     pub limit64: u64,
     pub outputter: fn(*mut Self, *const u8, Bytes<u32>) -> (),
-    pub filled: Bytes<u32>, // cache data follows ..
+    pub filled: Bytes<u32>,
+    pub crc32: u32,
+    pub flags: u32,
+    pub written64: u64, // cache data follows ..
+}
+
+#[repr(C)] // See the note at the beginning of this module
+pub struct ReadStream {
+    pub header: Blob,
+    pub ptr64: u64,
+    pub start64: u64,
+    pub limit64: u64,
+    pub inputter: fn(*mut Self, *mut u8, Bytes<u32>) -> (),
+    pub read: Bytes<u32>,
+    pub filled: Bytes<u32>,
+    pub flags: u32, // cache data follows ..
 }
 
 /// A forwarding pointer placed by the GC in place of an evacuated object.
@@ -661,6 +691,11 @@ impl Bits64 {
     pub fn bits(&self) -> u64 {
         (u64::from(self.bits_hi) << 32) | u64::from(self.bits_lo)
     }
+
+    pub fn set_bits(&mut self, bits: u64) {
+        self.bits_lo = bits as u32;
+        self.bits_hi = (bits >> 32) as u32;
+    }
 }
 
 #[repr(C)] // See the note at the beginning of this module
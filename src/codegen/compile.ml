@@ -888,6 +888,8 @@ module RTS = struct
     E.add_func_import env "rts" "idl_sub_buf_init" [I32Type; I32Type; I32Type] [];
     E.add_func_import env "rts" "idl_sub"
       [I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type] [I32Type];
+    E.add_func_import env "rts" "idl_sub_cached"
+      [I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type; I32Type] [I32Type];
     E.add_func_import env "rts" "leb128_decode" [I32Type] [I32Type];
     E.add_func_import env "rts" "sleb128_decode" [I32Type] [I32Type];
     E.add_func_import env "rts" "bigint_of_word32" [I32Type] [I32Type];
@@ -934,9 +936,18 @@ module RTS = struct
     E.add_func_import env "rts" "utf8_valid" [I32Type; I32Type] [I32Type];
     E.add_func_import env "rts" "utf8_validate" [I32Type; I32Type] [];
     E.add_func_import env "rts" "skip_leb128" [I32Type] [];
-    E.add_func_import env "rts" "skip_any" [I32Type; I32Type; I32Type; I32Type] [];
+    E.add_func_import env "rts" "skip_any" [I32Type; I32Type; I32Type] [];
+    E.add_func_import env "rts" "skip_any_set_budget" [I32Type] [];
+    E.add_func_import env "rts" "idl_set_decoding_quota" [I64Type] [];
+    E.add_func_import env "rts" "region_check_stable_memory_access" [] [];
+    E.add_func_import env "rts" "region_new" [] [I32Type];
+    E.add_func_import env "rts" "region_size" [I32Type] [I64Type];
+    E.add_func_import env "rts" "region_grow" [I32Type; I64Type] [I64Type];
+    E.add_func_import env "rts" "region_load" [I32Type; I64Type; I32Type; I32Type] [];
+    E.add_func_import env "rts" "region_store" [I32Type; I64Type; I32Type; I32Type] [];
     E.add_func_import env "rts" "find_field" [I32Type; I32Type; I32Type; I32Type; I32Type] [I32Type];
     E.add_func_import env "rts" "skip_fields" [I32Type; I32Type; I32Type; I32Type] [];
+    E.add_func_import env "rts" "idl_set_arg" [I32Type] [];
     E.add_func_import env "rts" "remember_continuation" [I32Type] [I32Type];
     E.add_func_import env "rts" "recall_continuation" [I32Type] [I32Type];
     E.add_func_import env "rts" "peek_future_continuation" [I32Type] [I32Type];
@@ -999,6 +1010,9 @@ module RTS = struct
     E.add_func_import env "rts" "stream_shutdown" [I32Type] [];
     E.add_func_import env "rts" "stream_reserve" [I32Type; I32Type] [I32Type];
     E.add_func_import env "rts" "stream_stable_dest" [I32Type; I64Type; I64Type] [];
+    E.add_func_import env "rts" "stream_stable_dest_framed" [I32Type; I64Type; I64Type] [];
+    E.add_func_import env "rts" "stream_stable_dest_compressed" [I32Type; I64Type; I64Type] [];
+    E.add_func_import env "rts" "stream_stable_take_blob" [I64Type; I64Type] [I32Type];
     E.add_func_import env "rts" "init_write_barrier" [] [];
     E.add_func_import env "rts" "write_barrier" [I32Type] [];
     ()
@@ -4547,8 +4561,9 @@ end (* Cycles *)
 
 module StableMem = struct
 
-  (* start from 1 to avoid accidental reads of 0 *)
-  let version = Int32.of_int 1
+  (* start from 1 to avoid accidental reads of 0;
+     version 2 frames the stable variables with a stream header *)
+  let version = Int32.of_int 2
 
   let register_globals env =
     (* size (in pages) *)
@@ -4559,6 +4574,11 @@ module StableMem = struct
   let set_mem_size env =
     G.i (GlobalSet (nr (E.get_global env "__stablemem_size")))
 
+  (* ExperimentalStableMemory cannot be mixed with the RTS' regions,
+     which trap if it was used (see `region.rs`) *)
+  let check_access env =
+    E.call_import env "rts" "region_check_stable_memory_access"
+
   (* stable memory bounds check *)
   let guard env =
     match E.mode env with
@@ -4566,6 +4586,7 @@ module StableMem = struct
       Func.share_code1 env "__stablemem_guard"
         ("offset", I64Type) []
         (fun env get_offset ->
+          check_access env ^^
           get_offset ^^
           compile_const_64 (Int64.of_int page_size_bits) ^^
           G.i (Binary (Wasm.Values.I64 I64Op.ShrU)) ^^
@@ -4582,6 +4603,7 @@ module StableMem = struct
         (("offset", I64Type), ("size", I32Type)) []
         (fun env get_offset get_size ->
           let (set_sum, get_sum) = new_local64 env "sum" in
+          check_access env ^^
           get_offset ^^
           get_size ^^ G.i (Convert (Wasm.Values.I64 I64Op.ExtendUI32)) ^^
           G.i (Binary (Wasm.Values.I64 I64Op.Add)) ^^
@@ -4641,7 +4663,7 @@ module StableMem = struct
             IC.system_call env "stable64_write"))
     | _ -> assert false
 
-  let _read_word32 env =
+  let read_word32 env =
     read env false "word32" I32Type 4l load_unskewed_ptr
   let write_word32 env =
     write env false "word32" I32Type 4l store_unskewed_ptr
@@ -4850,6 +4872,35 @@ module StableMem = struct
 
 end (* StableMemory *)
 
+module Region = struct
+  (* Regions of stable memory are implemented in the RTS (see `region.rs`),
+     which allocates the blocks of a region with `StableMem.logical_grow` *)
+
+  let load_blob env =
+    Func.share_code3 env "__region_load_blob"
+      (("region", I32Type), ("offset", I64Type), ("len", I32Type)) [I32Type]
+      (fun env get_region get_offset get_len ->
+        let (set_blob, get_blob) = new_local env "blob" in
+        get_len ^^ Blob.alloc env ^^ set_blob ^^
+        get_region ^^
+        get_offset ^^
+        get_blob ^^ Blob.payload_ptr_unskewed ^^
+        get_len ^^
+        E.call_import env "rts" "region_load" ^^
+        get_blob)
+
+  let store_blob env =
+    Func.share_code3 env "__region_store_blob"
+      (("region", I32Type), ("offset", I64Type), ("blob", I32Type)) []
+      (fun env get_region get_offset get_blob ->
+        get_region ^^
+        get_offset ^^
+        get_blob ^^ Blob.payload_ptr_unskewed ^^
+        get_blob ^^ Blob.len env ^^
+        E.call_import env "rts" "region_store")
+
+end (* Region *)
+
 module RTS_Exports = struct
   let system_exports env =
     let bigint_trap_fi = E.add_fun env "bigint_trap" (
@@ -4886,6 +4937,48 @@ module RTS_Exports = struct
     E.add_export env (nr {
       name = Lib.Utf8.decode "stable64_write_moc";
       edesc = nr (FuncExport (nr stable64_write_moc_fi))
+    });
+
+    let stable64_read_moc_fi =
+      if E.mode env = Flags.WASIMode then
+        E.add_fun env "stable64_read_moc" (
+            Func.of_body env ["to", I64Type; "from", I64Type; "len", I64Type] []
+              (fun env ->
+                E.trap_with env "stable64_read_moc is not supposed to be called in WASI"
+              )
+          )
+      else E.reuse_import env "ic0" "stable64_read" in
+    E.add_export env (nr {
+      name = Lib.Utf8.decode "stable64_read_moc";
+      edesc = nr (FuncExport (nr stable64_read_moc_fi))
+    });
+
+    (* The RTS' stable regions grow the logical stable memory, so that the
+       stable variables are serialised behind them *)
+    let stable64_size_moc_fi =
+      E.add_fun env "stable64_size_moc" (
+        Func.of_body env [] [I64Type] (fun env ->
+          if E.mode env = Flags.WASIMode
+          then E.trap_with env "stable64_size_moc is not supposed to be called in WASI"
+          else StableMem.get_mem_size env
+        )
+      ) in
+    E.add_export env (nr {
+      name = Lib.Utf8.decode "stable64_size_moc";
+      edesc = nr (FuncExport (nr stable64_size_moc_fi))
+    });
+
+    let stable64_grow_moc_fi =
+      E.add_fun env "stable64_grow_moc" (
+        Func.of_body env ["pages", I64Type] [I64Type] (fun env ->
+          if E.mode env = Flags.WASIMode
+          then E.trap_with env "stable64_grow_moc is not supposed to be called in WASI"
+          else G.i (LocalGet (nr 0l)) ^^ StableMem.logical_grow env
+        )
+      ) in
+    E.add_export env (nr {
+      name = Lib.Utf8.decode "stable64_grow_moc";
+      edesc = nr (FuncExport (nr stable64_grow_moc_fi))
     })
 
 end (* RTS_Exports *)
@@ -5681,7 +5774,7 @@ module MakeSerialization (Strm : Stream) = struct
         get_typtbl_size env ^^
         get_idltyp1 ^^
         get_idltyp2 ^^
-        E.call_import env "rts" "idl_sub")
+        E.call_import env "rts" "idl_sub_cached")
 
   (* The main deserialization function, generated once per type hash.
 
@@ -5769,7 +5862,7 @@ module MakeSerialization (Strm : Stream) = struct
       let go_can_recover = go' true in
 
       let skip get_typ =
-        get_data_buf ^^ get_typtbl ^^ get_typ ^^ compile_unboxed_const 0l ^^
+        get_data_buf ^^ get_typtbl ^^ get_typ ^^
         E.call_import env "rts" "skip_any"
       in
 
@@ -6408,6 +6501,7 @@ module MakeSerialization (Strm : Stream) = struct
       let (set_data_start, get_data_start) = new_local env "data_start" in
       let (set_refs_start, get_refs_start) = new_local env "refs_start" in
       let (set_arg_count, get_arg_count) = new_local env "arg_count" in
+      let (set_arg_index, get_arg_index) = new_local env "arg_index" in
       let (set_val, get_val) = new_local env "val" in
 
       get_blob ^^ Blob.len env ^^ set_data_size ^^
@@ -6442,7 +6536,7 @@ module MakeSerialization (Strm : Stream) = struct
         ReadBuf.set_end get_main_typs_buf (ReadBuf.get_end get_data_buf) ^^
         ReadBuf.read_leb128 env get_main_typs_buf ^^ set_arg_count ^^
 
-        G.concat_map (fun t ->
+        G.concat_mapi (fun i t ->
           let can_recover, default_or_trap = Type.(
             match normalize t with
             | Opt _ | Any ->
@@ -6459,6 +6553,9 @@ module MakeSerialization (Strm : Stream) = struct
           G.if1 I32Type
            (default_or_trap ("IDL error: too few arguments " ^ ts_name))
            (begin
+              (* for the location of decoding errors *)
+              compile_unboxed_const (Int32.of_int i) ^^
+              E.call_import env "rts" "idl_set_arg" ^^
               begin
                 (* set up invariant register arguments *)
                 get_rel_buf_opt ^^ Registers.set_rel_buf_opt env ^^
@@ -6491,13 +6588,15 @@ module MakeSerialization (Strm : Stream) = struct
         ) ts ^^
 
         (* Skip any extra arguments *)
+        compile_unboxed_const (Int32.of_int (List.length ts)) ^^ set_arg_index ^^
         compile_while env
          (get_arg_count ^^ compile_rel_const I32Op.GtU 0l)
          begin
+           get_arg_index ^^ E.call_import env "rts" "idl_set_arg" ^^
+           get_arg_index ^^ compile_add_const 1l ^^ set_arg_index ^^
            get_data_buf ^^
            get_typtbl_ptr ^^ load_unskewed_ptr ^^
            ReadBuf.read_sleb128 env get_main_typs_buf ^^
-           compile_unboxed_const 0l ^^
            E.call_import env "rts" "skip_any" ^^
            get_arg_count ^^ compile_sub_const 1l ^^ set_arg_count
          end ^^
@@ -6654,6 +6753,13 @@ should already be enforced by the static signature compatibility
 check.  We use the `null`-ness of the word buffer pointer to
 dynamically determine whether to omit or perform Candid subtype checks.
 
+Callers tend to send the same type table with every message, so
+generated code calls `idl_sub_cached`, which caches the verdicts of
+`idl_sub` across messages, keyed by the incoming type table (a copy
+of its bytes) and the pair of types. The cache lives in static RTS
+memory, so GC does not affect it. This relies on the static type
+table never changing.
+
 NB: Extending `idl_sub` to support extended, "stable" types (with mutable,
 invariant type constructors) would require extending the polarity argument
 from a Boolean to a three-valued argument to efficiently check equality for
@@ -6749,6 +6855,11 @@ module Stabilization = struct
 
   let extend64 code = code ^^ G.i (Convert (Wasm.Values.I64 I64Op.ExtendUI32))
 
+  (* The serialised stable variables are framed with a header that
+     carries their length and checksum (see `StreamHeader` in `stream.rs`) *)
+  let stream_header_size = 24l
+  let stream_header_magic = 0x54534f4dl (* "MOST" *)
+
   (* The below stream implementation is geared towards the
      tail section of stable memory, where the serialised
      stable variables go. As such a few intimate details of
@@ -6766,6 +6877,19 @@ module Stabilization = struct
       let (set_len, get_len) = new_local env "len" in
       get_data_size ^^
       compile_add_const (Int32.of_int (String.length header)) ^^
+      compile_add_const stream_header_size ^^
+      (if !Flags.stable_compression then
+         (* compressed chunks of at most 4KiB grow by at most 1/255 and
+            24 bytes, and only the chunks ending a flush of the cache (of
+            0x8000 bytes) are smaller, so the output grows by less than
+            1/64 and some slack (see `Stream::cache_bytes`) *)
+         let (set_size, get_size) = new_local env "size" in
+         set_size ^^
+         get_size ^^
+         get_size ^^ compile_divU_const 64l ^^
+         G.i (Binary (Wasm.Values.I32 I32Op.Add)) ^^
+         compile_add_const 64l
+       else G.nop) ^^
       set_len ^^
 
       let (set_dst, get_dst) = new_local64 env "dst" in
@@ -6782,7 +6906,10 @@ module Stabilization = struct
       get_dst ^^
       get_dst ^^ extend64 get_len ^^
       G.i (Binary (Wasm.Values.I64 I64Op.Add)) ^^
-      E.call_import env "rts" "stream_stable_dest"
+      E.call_import env "rts"
+        (if !Flags.stable_compression
+         then "stream_stable_dest_compressed"
+         else "stream_stable_dest_framed")
 
     let ptr64_field = Int32.add Blob.len_field 1l (* see invariant in `stream.rs` *)
 
@@ -6801,16 +6928,14 @@ module Stabilization = struct
     let finalize_buffer _ = G.nop (* everything is outputted already *)
 
     (* Returns a 32-bit unsigned int that is the number of bytes that would
-       have been written to stable memory if flushed. The difference
-       of two such numbers will always be an exact byte distance. *)
+       have been output if flushed, counted before compression. The difference
+       of two such numbers will always be an exact byte distance in the
+       (uncompressed) serialisation. *)
     let absolute_offset env get_token =
-      let start64_field = Int32.add ptr64_field 2l in (* see invariant in `stream.rs` *)
+      let written64_field = Int32.add Blob.len_field 11l in (* see invariant in `stream.rs` *)
       absolute_offset env get_token ^^
       get_token ^^
-      Heap.load_field64_unskewed ptr64_field ^^
-      get_token ^^
-      Heap.load_field64_unskewed start64_field ^^
-      G.i (Binary (Wasm.Values.I64 I64Op.Sub)) ^^
+      Heap.load_field64_unskewed written64_field ^^
       G.i (Convert (Wasm.Values.I32 I32Op.WrapI64)) ^^
       G.i (Binary (Wasm.Values.I32 I32Op.Add))
   end
@@ -6904,7 +7029,6 @@ module Stabilization = struct
         (* save version at M + (pagesize - 4) *)
         get_M ^^
           compile_add64_const (Int64.sub page_size64 4L) ^^
-        (* TODO bump version? *)
         compile_unboxed_const StableMem.version ^^
         StableMem.write_word32 env
 
@@ -6935,6 +7059,7 @@ module Stabilization = struct
           let (set_marker, get_marker) = new_local env "marker" in
           let (set_len, get_len) = new_local env "len" in
           let (set_offset, get_offset) = new_local64 env "offset" in
+          let (set_framed, get_framed) = new_local env "framed" in
           compile_const_64 0L ^^
           StableMem.read_and_clear_word32 env ^^
           set_marker ^^
@@ -6991,7 +7116,13 @@ module Stabilization = struct
               (* set offset *)
               get_N ^^
               compile_add64_const 4L ^^
-              set_offset
+              set_offset ^^
+
+              (* stable variables are framed since version 2 *)
+              get_version ^^
+              compile_unboxed_const 2l ^^
+              G.i (Compare (Wasm.Values.I32 I32Op.GeU)) ^^
+              set_framed
             end
             begin
               (* assert mem_size == 0 *)
@@ -7005,36 +7136,68 @@ module Stabilization = struct
 
               (* set offset *)
               compile_const_64 4L ^^
-              set_offset
+              set_offset ^^
+
+              (* without a footer, there is no version: framed images
+                 start with the stream header magic, older ones with "DIDL" *)
+              get_len ^^
+              compile_unboxed_const stream_header_size ^^
+              G.i (Compare (Wasm.Values.I32 I32Op.GeU)) ^^
+              G.if1 I32Type
+                begin
+                  get_offset ^^
+                  StableMem.read_word32 env ^^
+                  compile_eq_const stream_header_magic
+                end
+                (Bool.lit false) ^^
+              set_framed
             end ^^ (* if_ *)
 
           let (set_blob, get_blob) = new_local env "blob" in
-          (* read blob from stable memory *)
-          get_len ^^ Blob.alloc env ^^ set_blob ^^
-          extend64 (get_blob ^^ Blob.payload_ptr_unskewed) ^^
-          get_offset ^^
-          extend64 get_len ^^
-          IC.system_call env "stable64_read" ^^
-
           let (set_val, get_val) = new_local env "val" in
-          (* deserialize blob to val *)
-          get_blob ^^
-          Bool.lit false ^^ (* can't recover *)
-          Serialization.deserialize_from_blob true env [ty] ^^
-          set_val ^^
-
-          (* clear blob contents *)
-          get_blob ^^
-          Blob.clear env ^^
-
-          (* copy zeros from blob to stable memory *)
-          get_offset ^^
-          extend64 (get_blob ^^ Blob.payload_ptr_unskewed) ^^
-          extend64 (get_blob ^^ Blob.len env) ^^
-          IC.system_call env "stable64_write" ^^
+          get_framed ^^
+          G.if1 I32Type
+            begin
+              (* the RTS checks the header before anything is decoded,
+                 and clears the image in stable memory *)
+              get_offset ^^
+              get_offset ^^ extend64 get_len ^^
+              G.i (Binary (Wasm.Values.I64 I64Op.Add)) ^^
+              E.call_import env "rts" "stream_stable_take_blob" ^^
+              set_blob ^^
 
-          (* return val *)
-          get_val
+              (* deserialize blob to val *)
+              get_blob ^^
+              Bool.lit false ^^ (* can't recover *)
+              Serialization.deserialize_from_blob true env [ty]
+            end
+            begin
+              (* read blob from stable memory *)
+              get_len ^^ Blob.alloc env ^^ set_blob ^^
+              extend64 (get_blob ^^ Blob.payload_ptr_unskewed) ^^
+              get_offset ^^
+              extend64 get_len ^^
+              IC.system_call env "stable64_read" ^^
+
+              (* deserialize blob to val *)
+              get_blob ^^
+              Bool.lit false ^^ (* can't recover *)
+              Serialization.deserialize_from_blob true env [ty] ^^
+              set_val ^^
+
+              (* clear blob contents *)
+              get_blob ^^
+              Blob.clear env ^^
+
+              (* copy zeros from blob to stable memory *)
+              get_offset ^^
+              extend64 (get_blob ^^ Blob.payload_ptr_unskewed) ^^
+              extend64 (get_blob ^^ Blob.len env) ^^
+              IC.system_call env "stable64_write" ^^
+
+              (* return val *)
+              get_val
+            end
         end
     | _ -> assert false
 end
@@ -9651,12 +9814,41 @@ and compile_prim_invocation (env : E.t) ae p es at =
 
   | OtherPrim ("stableMemorySize"), [] ->
     SR.UnboxedWord64,
+    StableMem.check_access env ^^
     StableMem.get_mem_size env
   | OtherPrim ("stableMemoryGrow"), [e] ->
     SR.UnboxedWord64,
     compile_exp_as env ae SR.UnboxedWord64 e ^^
+    StableMem.check_access env ^^
     StableMem.logical_grow env
 
+  | OtherPrim ("regionNew"), [] ->
+    SR.UnboxedWord32,
+    E.call_import env "rts" "region_new"
+  | OtherPrim ("regionSize"), [e] ->
+    SR.UnboxedWord64,
+    compile_exp_as env ae SR.UnboxedWord32 e ^^
+    E.call_import env "rts" "region_size"
+  | OtherPrim ("regionGrow"), [e1; e2] ->
+    SR.UnboxedWord64,
+    compile_exp_as env ae SR.UnboxedWord32 e1 ^^
+    compile_exp_as env ae SR.UnboxedWord64 e2 ^^
+    E.call_import env "rts" "region_grow"
+  | OtherPrim ("regionLoadBlob"), [e1; e2; e3] ->
+    SR.Vanilla,
+    compile_exp_as env ae SR.UnboxedWord32 e1 ^^
+    compile_exp_as env ae SR.UnboxedWord64 e2 ^^
+    compile_exp_as env ae SR.Vanilla e3 ^^
+    Blob.lit env "Blob size out of bounds" ^^
+    BigNum.to_word32_with env ^^
+    Region.load_blob env
+  | OtherPrim ("regionStoreBlob"), [e1; e2; e3] ->
+    SR.unit,
+    compile_exp_as env ae SR.UnboxedWord32 e1 ^^
+    compile_exp_as env ae SR.UnboxedWord64 e2 ^^
+    compile_exp_as env ae SR.Vanilla e3 ^^
+    Region.store_blob env
+
   | OtherPrim ("stableVarQuery"), [] ->
     SR.UnboxedTuple 2,
     IC.get_self_reference env ^^
@@ -10623,6 +10815,18 @@ and conclude_module env set_serialization_globals start_fi_o =
       E.call_import env "rts" "init_write_barrier"
      else
       G.nop) ^^
+    (if !Flags.idl_skip_budget <> Flags.idl_skip_budget_default
+     then
+      compile_unboxed_const (Int32.of_int !Flags.idl_skip_budget) ^^
+      E.call_import env "rts" "skip_any_set_budget"
+     else
+      G.nop) ^^
+    (if !Flags.idl_decoding_quota <> Flags.idl_decoding_quota_default
+     then
+      compile_const_64 (Int64.of_int !Flags.idl_decoding_quota) ^^
+      E.call_import env "rts" "idl_set_decoding_quota"
+     else
+      G.nop) ^^
     match start_fi_o with
     | Some fi ->
       G.i (Call fi)
@@ -171,7 +171,19 @@ let argspec = [
 
   "--trap-on-call-error",
   Arg.Unit (fun () -> Flags.trap_on_call_error := true),
-  " Trap, don't throw an `Error`, when an IC call fails due to destination queue full or freezing threshold is crossed. Emulates behaviour of moc versions < 0.8.0."
+  " Trap, don't throw an `Error`, when an IC call fails due to destination queue full or freezing threshold is crossed. Emulates behaviour of moc versions < 0.8.0.";
+
+  "--stable-compression",
+  Arg.Unit (fun () -> Flags.stable_compression := true),
+  " compress stable variables (LZ4) when upgrading";
+
+  "--idl-skip-budget",
+  Arg.Set_int Flags.idl_skip_budget,
+  "<n>  set memory (in bytes) for skipping nested Candid values (default " ^ (Int.to_string Flags.idl_skip_budget_default) ^ ")";
+
+  "--idl-decoding-quota",
+  Arg.Set_int Flags.idl_decoding_quota,
+  "<n>  set work allowed for decoding one Candid message (default " ^ (Int.to_string Flags.idl_decoding_quota_default) ^ ")"
   ]
 
   @ Args.inclusion_args
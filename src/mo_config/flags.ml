@@ -47,3 +47,8 @@ let ocaml_js = ref false
 let rts_stack_pages_default = 32 (* 2MB *)
 let rts_stack_pages : int ref = ref rts_stack_pages_default
 let trap_on_call_error = ref false
+let stable_compression = ref false
+let idl_skip_budget_default = 1048576 (* 1MB *)
+let idl_skip_budget : int ref = ref idl_skip_budget_default
+let idl_decoding_quota_default = 4194304
+let idl_decoding_quota : int ref = ref idl_decoding_quota_default